@@ -33,20 +33,27 @@ fn generate_gl_bindings<W>(dest: &mut W) where W: Write {
                 "GL_APPLE_vertex_array_object".to_string(),
                 "GL_ARB_buffer_storage".to_string(),
                 "GL_ARB_compute_shader".to_string(),
+                "GL_ARB_copy_buffer".to_string(),
+                "GL_ARB_copy_image".to_string(),
                 "GL_ARB_debug_output".to_string(),
+                "GL_ARB_depth_clamp".to_string(),
                 "GL_ARB_depth_texture".to_string(),
                 "GL_ARB_direct_state_access".to_string(),
+                "GL_ARB_draw_elements_base_vertex".to_string(),
                 "GL_ARB_ES2_compatibility".to_string(),
                 "GL_ARB_ES3_compatibility".to_string(),
                 "GL_ARB_ES3_1_compatibility".to_string(),
                 "GL_ARB_framebuffer_sRGB".to_string(),
                 "GL_ARB_geometry_shader4".to_string(),
+                "GL_ARB_gl_spirv".to_string(),
+                "GL_ARB_instanced_arrays".to_string(),
                 "GL_ARB_invalidate_subdata".to_string(),
                 "GL_ARB_shader_objects".to_string(),
                 "GL_ARB_texture_float".to_string(),
                 "GL_ARB_texture_multisample".to_string(),
                 "GL_ARB_texture_rg".to_string(),
                 "GL_ARB_texture_rgb10_a2ui".to_string(),
+                "GL_ARB_timer_query".to_string(),
                 "GL_ARB_vertex_buffer_object".to_string(),
                 "GL_ARB_vertex_shader".to_string(),
                 "GL_ATI_meminfo".to_string(),
@@ -56,14 +63,17 @@ fn generate_gl_bindings<W>(dest: &mut W) where W: Write {
                 "GL_EXT_framebuffer_sRGB".to_string(),
                 "GL_EXT_gpu_shader4".to_string(),
                 "GL_EXT_packed_depth_stencil".to_string(),
+                "GL_EXT_texture_compression_s3tc".to_string(),
                 "GL_EXT_texture_filter_anisotropic".to_string(),
                 "GL_EXT_texture_integer".to_string(),
                 "GL_EXT_texture_sRGB".to_string(),
                 "GL_EXT_transform_feedback".to_string(),
+                "GL_KHR_blend_equation_advanced".to_string(),
                 "GL_KHR_robustness".to_string(),
+                "GL_NV_conditional_render".to_string(),
                 "GL_NVX_gpu_memory_info".to_string(),
             ],
-            version: "4.5".to_string(),
+            version: "4.6".to_string(),
             profile: "compatibility".to_string(),
         };
 
@@ -80,6 +90,7 @@ fn generate_gl_bindings<W>(dest: &mut W) where W: Write {
             extensions: vec![
                 "GL_EXT_disjoint_timer_query".to_string(),
                 "GL_KHR_debug".to_string(),
+                "GL_OES_fbo_render_mipmap".to_string(),
                 "GL_OES_texture_npot".to_string(),
                 "GL_OES_vertex_array_object".to_string(),
             ],
@@ -22,6 +22,7 @@ enum TextureDimensions {
     Texture1dArray,
     Texture2dArray,
     Texture2dMultisampleArray,
+    CubeMap,
 }
 
 impl TextureDimensions {
@@ -40,6 +41,13 @@ impl TextureDimensions {
             _ => false
         }
     }
+
+    fn is_cube(&self) -> bool {
+        match self {
+            &TextureDimensions::CubeMap => true,
+            _ => false
+        }
+    }
 }
 
 pub fn build_texture_file<W: Write>(mut dest: &mut W) {
@@ -102,6 +110,8 @@ pub fn build_texture_file<W: Write>(mut dest: &mut W) {
     build_texture(dest, TextureType::Depth, TextureDimensions::Texture2dMultisampleArray);
     build_texture(dest, TextureType::Stencil, TextureDimensions::Texture2dMultisampleArray);
     build_texture(dest, TextureType::DepthStencil, TextureDimensions::Texture2dMultisampleArray);
+    build_texture(dest, TextureType::Regular, TextureDimensions::CubeMap);
+    build_texture(dest, TextureType::Depth, TextureDimensions::CubeMap);
 }
 
 fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: TextureDimensions) {
@@ -127,6 +137,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             TextureDimensions::Texture1dArray => "Texture1dArray",
             TextureDimensions::Texture2dArray => "Texture2dArray",
             TextureDimensions::Texture2dMultisampleArray => "Texture2dMultisampleArray",
+            TextureDimensions::CubeMap => "CubeMap",
         };
 
         format!("{}{}", prefix, suffix)
@@ -135,7 +146,8 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     // the trait corresponding to the data source
     let data_source_trait = match dimensions {
         TextureDimensions::Texture1d | TextureDimensions::Texture1dArray => "Texture1dDataSource",
-        TextureDimensions::Texture2d | TextureDimensions::Texture2dArray => "Texture2dDataSource",
+        TextureDimensions::Texture2d | TextureDimensions::Texture2dArray |
+        TextureDimensions::CubeMap => "Texture2dDataSource",
         TextureDimensions::Texture3d => "Texture3dDataSource",
         TextureDimensions::Texture2dMultisample | TextureDimensions::Texture2dMultisampleArray => {
             "unreachable"
@@ -209,16 +221,18 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
         TextureDimensions::Texture1dArray => "width: u32, array_size: u32",
         TextureDimensions::Texture2dArray => "width: u32, height: u32, array_size: u32",
         TextureDimensions::Texture2dMultisampleArray => "width: u32, height: u32, array_size: u32, samples: u32",
+        TextureDimensions::CubeMap => "dimension: u32",
     };
 
     let dimensions_parameters_passing = match dimensions {
-        TextureDimensions::Texture1d => "width, None, None, None, None",
-        TextureDimensions::Texture2d => "width, Some(height), None, None, None",
-        TextureDimensions::Texture2dMultisample => "width, Some(height), None, None, Some(samples)",
-        TextureDimensions::Texture3d => "width, Some(height), Some(depth), None, None",
-        TextureDimensions::Texture1dArray => "width, None, None, Some(array_size), None",
-        TextureDimensions::Texture2dArray => "width, Some(height), None, Some(array_size), None",
-        TextureDimensions::Texture2dMultisampleArray => "width, Some(height), None, Some(array_size), Some(samples)",
+        TextureDimensions::Texture1d => "width, None, None, None, None, false",
+        TextureDimensions::Texture2d => "width, Some(height), None, None, None, false",
+        TextureDimensions::Texture2dMultisample => "width, Some(height), None, None, Some(samples), false",
+        TextureDimensions::Texture3d => "width, Some(height), Some(depth), None, None, false",
+        TextureDimensions::Texture1dArray => "width, None, None, Some(array_size), None, false",
+        TextureDimensions::Texture2dArray => "width, Some(height), None, Some(array_size), None, false",
+        TextureDimensions::Texture2dMultisampleArray => "width, Some(height), None, Some(array_size), Some(samples), false",
+        TextureDimensions::CubeMap => "dimension, Some(dimension), None, None, None, true",
     };
 
     // writing the struct with doc-comment
@@ -228,6 +242,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
         TextureDimensions::Texture2dMultisample | TextureDimensions::Texture3d => "A ",
         TextureDimensions::Texture1dArray | TextureDimensions::Texture2dArray |
         TextureDimensions::Texture2dMultisampleArray => "An array of ",
+        TextureDimensions::CubeMap => "A cube of six ",
     })).unwrap();
     if ty == TextureType::Compressed {
         (write!(dest, "compressed ")).unwrap();
@@ -235,7 +250,8 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     (write!(dest, "{}", match dimensions {
         TextureDimensions::Texture1d | TextureDimensions::Texture1dArray => "one-dimensional ",
         TextureDimensions::Texture2d | TextureDimensions::Texture2dArray |
-        TextureDimensions::Texture2dMultisample | TextureDimensions::Texture2dMultisampleArray => {
+        TextureDimensions::Texture2dMultisample | TextureDimensions::Texture2dMultisampleArray |
+        TextureDimensions::CubeMap => {
             "two-dimensional "
         },
         TextureDimensions::Texture3d => "three-dimensional ",
@@ -244,7 +260,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
         TextureDimensions::Texture1d | TextureDimensions::Texture2d |
         TextureDimensions::Texture2dMultisample | TextureDimensions::Texture3d => "texture ",
         TextureDimensions::Texture1dArray | TextureDimensions::Texture2dArray |
-        TextureDimensions::Texture2dMultisampleArray => "textures ",
+        TextureDimensions::Texture2dMultisampleArray | TextureDimensions::CubeMap => "textures ",
     })).unwrap();
     (write!(dest, "{}", match ty {
         TextureType::Regular | TextureType::Compressed => " containing floating-point data",
@@ -333,6 +349,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             TextureDimensions::Texture1dArray => "Texture1dArray",
             TextureDimensions::Texture2dArray => "Texture2dArray",
             TextureDimensions::Texture2dMultisampleArray => "Texture2dMultisampleArray",
+            TextureDimensions::CubeMap => "CubeMap",
         };
 
         match ty {
@@ -389,7 +406,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     (writeln!(dest, "impl {} {{", name)).unwrap();
 
     // writing the `new` function
-    if !dimensions.is_multisample() {
+    if !dimensions.is_multisample() && !dimensions.is_cube() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
             TextureDimensions::Texture3d => "T",
@@ -414,8 +431,48 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 cfg_attr = cfg_attribute)).unwrap();
     }
 
+    // writing the `try_new` function
+    if !dimensions.is_multisample() && !dimensions.is_cube() {
+        let param = match dimensions {
+            TextureDimensions::Texture1d | TextureDimensions::Texture2d |
+            TextureDimensions::Texture3d => "T",
+
+            TextureDimensions::Texture1dArray |
+            TextureDimensions::Texture2dArray => "Vec<T>",
+
+            _ => unreachable!()
+        };
+
+        (writeln!(dest, "
+                /// Builds a new texture by uploading data, or returns an error if the
+                /// allocation failed.
+                ///
+                /// Contrary to `new`, this function returns `TextureCreationError::OutOfMemory`
+                /// instead of panicking if there is not enough memory to hold the texture.
+                {cfg_attr}
+                pub fn try_new<'a, F, T>(facade: &F, data: {param})
+                                      -> Result<{name}, TextureCreationError>
+                                      where T: {data_source_trait}<'a>, F: Facade
+                {{
+                    let t = match {name}::new_impl(facade, data, None, true) {{
+                        Ok(t) => t,
+                        Err(TextureMaybeSupportedCreationError::CreationError(e)) => return Err(e),
+                        Err(TextureMaybeSupportedCreationError::NotSupported) => unreachable!()
+                    }};
+
+                    // the texture is destroyed when it is dropped
+                    if facade.get_context().check_out_of_memory() {{
+                        return Err(TextureCreationError::OutOfMemory);
+                    }}
+
+                    Ok(t)
+                }}
+            ", data_source_trait = data_source_trait, param = param, name = name,
+                cfg_attr = cfg_attribute)).unwrap();
+    }
+
     // writing the `new_if_supported` function
-    if cfg_attribute.len() >= 1 && !dimensions.is_multisample() {
+    if cfg_attribute.len() >= 1 && !dimensions.is_multisample() && !dimensions.is_cube() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
             TextureDimensions::Texture3d => "T",
@@ -436,14 +493,14 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                     match {name}::new_impl(facade, data, None, true) {{
                         Ok(t) => Some(t),
                         Err(TextureMaybeSupportedCreationError::NotSupported) => None,
-                        Err(TextureMaybeSupportedCreationError::CreationError(_)) => unreachable!()
+                        Err(TextureMaybeSupportedCreationError::CreationError(e)) => panic!(\"{{:?}}\", e)
                     }}
                 }}
             ", data_source_trait = data_source_trait, param = param, name = name)).unwrap();
     }
 
     // writing the `with_mipmaps` function
-    if !dimensions.is_multisample() {
+    if !dimensions.is_multisample() && !dimensions.is_cube() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
             TextureDimensions::Texture3d => "T",
@@ -470,7 +527,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     }
 
     // writing the `with_mipmaps_if_supported` function
-    if cfg_attribute.len() >= 1 && !dimensions.is_multisample() {
+    if cfg_attribute.len() >= 1 && !dimensions.is_multisample() && !dimensions.is_cube() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
             TextureDimensions::Texture3d => "T",
@@ -493,14 +550,14 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                     match {name}::new_impl(facade, data, None, mipmaps) {{
                         Ok(t) => Some(t),
                         Err(TextureMaybeSupportedCreationError::NotSupported) => None,
-                        Err(TextureMaybeSupportedCreationError::CreationError(_)) => unreachable!()
+                        Err(TextureMaybeSupportedCreationError::CreationError(e)) => panic!(\"{{:?}}\", e)
                     }}
                 }}
             ", data_source_trait = data_source_trait, param = param, name = name)).unwrap();
     }
 
     // writing the `with_format` function
-    if !dimensions.is_multisample() {
+    if !dimensions.is_multisample() && !dimensions.is_cube() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
             TextureDimensions::Texture3d => "T",
@@ -533,7 +590,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     }
 
     // writing the `with_format_if_supported` function
-    if cfg_attribute.len() >= 1 && !dimensions.is_multisample() {
+    if cfg_attribute.len() >= 1 && !dimensions.is_multisample() && !dimensions.is_cube() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
             TextureDimensions::Texture3d => "T",
@@ -560,8 +617,31 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                format = relevant_format, name = name)).unwrap();
     }
 
+    // writing the `with_compressed_data` function
+    if ty == TextureType::Compressed && dimensions == TextureDimensions::Texture2d {
+        (writeln!(dest, "
+                /// Builds a new texture from data that is already compressed in the given
+                /// format, for example the content of a DDS file.
+                ///
+                /// Returns `DataSizeMismatch` if the length of `data` doesn't match the format
+                /// and the dimensions, and `NotSupported` if the backend doesn't support the
+                /// format (S3TC formats require `GL_EXT_texture_compression_s3tc`).
+                ///
+                /// The texture can only be sampled and can't be used as an attachment.
+                pub fn with_compressed_data<F>(facade: &F, data: &[u8], width: u32, height: u32,
+                                               format: CompressedFormat)
+                                               -> Result<{name}, TextureMaybeSupportedCreationError>
+                                               where F: Facade
+                {{
+                    let t = try!(TextureImplementation::new_compressed_2d(facade, format, data,
+                                                                          width, height));
+                    Ok({name}(t))
+                }}
+            ", name = name)).unwrap();
+    }
+
     // writing the `new_impl` function
-    if !dimensions.is_multisample() {
+    if !dimensions.is_multisample() && !dimensions.is_cube() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
             TextureDimensions::Texture3d => "T",
@@ -625,7 +705,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
 
         // writing the constructor
         (write!(dest, "Ok({}(try!(TextureImplementation::new(facade, format, \
-                       Some((client_format, data)), mipmaps, {}", name, dimensions_parameters_passing)).unwrap();
+                       Some((client_format, data)), MipmapsOption::from(mipmaps), {}", name, dimensions_parameters_passing)).unwrap();
         (writeln!(dest, "))))")).unwrap();
 
         // end of "new" function block
@@ -646,7 +726,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             ", format = relevant_format, dim_params = dimensions_parameters_input, name = name)).unwrap();
 
         // writing the constructor
-        (write!(dest, "{}(TextureImplementation::new::<_, u8>(facade, format, None, true, {}).unwrap())", name, dimensions_parameters_passing)).unwrap();
+        (write!(dest, "{}(TextureImplementation::new::<_, u8>(facade, format, None, MipmapsOption::AutoGeneratedMipmaps, {}).unwrap())", name, dimensions_parameters_passing)).unwrap();
 
         // closing function
         (writeln!(dest, "}}")).unwrap();
@@ -668,7 +748,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 cfg_attr = cfg_attribute)).unwrap();
 
         // writing the constructor
-        (write!(dest, "{}(TextureImplementation::new::<_, u8>(facade, format, None, false, {}).unwrap())", name, dimensions_parameters_passing)).unwrap();
+        (write!(dest, "{}(TextureImplementation::new::<_, u8>(facade, format, None, MipmapsOption::NoMipmap, {}).unwrap())", name, dimensions_parameters_passing)).unwrap();
 
         // closing function
         (writeln!(dest, "}}")).unwrap();
@@ -688,12 +768,12 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             ", format = default_format, dim_params = dimensions_parameters_input, name = name)).unwrap();
 
         // writing the constructor
-        (write!(dest, "match TextureImplementation::new::<_, u8>(facade, format, None, false, {})", dimensions_parameters_passing)).unwrap();
+        (write!(dest, "match TextureImplementation::new::<_, u8>(facade, format, None, MipmapsOption::NoMipmap, {})", dimensions_parameters_passing)).unwrap();
         (writeln!(dest, "
             {{
                 Ok(t) => Some({}(t)),
                 Err(TextureMaybeSupportedCreationError::NotSupported) => None,
-                Err(TextureMaybeSupportedCreationError::CreationError(_)) => unreachable!()
+                Err(TextureMaybeSupportedCreationError::CreationError(e)) => panic!(\"{{:?}}\", e)
             }}", name)).unwrap();
 
         // closing function
@@ -718,7 +798,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 cfg_attr = cfg_attribute)).unwrap();
 
         // writing the constructor
-        (write!(dest, "let t = TextureImplementation::new::<_, u8>(facade, format, None, mipmaps, {});", dimensions_parameters_passing)).unwrap();
+        (write!(dest, "let t = TextureImplementation::new::<_, u8>(facade, format, None, MipmapsOption::from(mipmaps), {});", dimensions_parameters_passing)).unwrap();
         (writeln!(dest, "
             match t {{
                 Ok(t) => Ok({}(t)),
@@ -750,7 +830,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             ", format = relevant_format, dim_params = dimensions_parameters_input, name = name)).unwrap();
 
         // writing the constructor
-        (write!(dest, "TextureImplementation::new::<_, u8>(facade, format, None, mipmaps, {})", dimensions_parameters_passing)).unwrap();
+        (write!(dest, "TextureImplementation::new::<_, u8>(facade, format, None, MipmapsOption::from(mipmaps), {})", dimensions_parameters_passing)).unwrap();
         (writeln!(dest, ".map(|t| {}(t))", name)).unwrap();
 
         // closing function
@@ -774,7 +854,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 cfg_attr = cfg_attribute)).unwrap();
 
         // writing the constructor
-        (write!(dest, "{}(TextureImplementation::new::<_, u8>(facade, format, None, mipmaps, {})", name, dimensions_parameters_passing)).unwrap();
+        (write!(dest, "{}(TextureImplementation::new::<_, u8>(facade, format, None, MipmapsOption::from(mipmaps), {})", name, dimensions_parameters_passing)).unwrap();
         (writeln!(dest, ".unwrap())")).unwrap();
 
         // closing function
@@ -797,12 +877,12 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             ", format = default_format, dim_params = dimensions_parameters_input, name = name)).unwrap();
 
         // writing the constructor
-        (write!(dest, "match TextureImplementation::new::<_, u8>(facade, format, None, mipmaps, {})", dimensions_parameters_passing)).unwrap();
+        (write!(dest, "match TextureImplementation::new::<_, u8>(facade, format, None, MipmapsOption::from(mipmaps), {})", dimensions_parameters_passing)).unwrap();
         (writeln!(dest, "
             {{
                 Ok(t) => Some({}(t)),
                 Err(TextureMaybeSupportedCreationError::NotSupported) => None,
-                Err(TextureMaybeSupportedCreationError::CreationError(_)) => unreachable!()
+                Err(TextureMaybeSupportedCreationError::CreationError(e)) => panic!(\"{{:?}}\", e)
             }}", name)).unwrap();
 
         // closing function
@@ -810,6 +890,71 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     }
 
 
+    // writing the `empty_with_mipmap_levels` function
+    if ty != TextureType::Compressed && ty != TextureType::CompressedSrgb &&
+       !dimensions.is_multisample()
+    {
+        // opening function
+        (writeln!(dest, "
+                /// Creates an empty texture with exactly `levels` mipmap levels, including the
+                /// main level.
+                ///
+                /// Contrary to `empty_with_mipmaps`, the mipmaps are not generated. The texture
+                /// and all of its mipmaps will contain undefined data.
+                ///
+                /// Returns `Err(InvalidMipmapsCount)` if `levels` is `0` or is larger than the
+                /// number of levels allowed by the dimensions of the texture.
+                {cfg_attr}
+                pub fn empty_with_mipmap_levels<F>(facade: &F, levels: u32, {dim_params})
+                                                   -> Result<{name}, TextureCreationError>
+                                                   where F: Facade
+                {{
+                    let format = {format};
+            ", format = default_format, dim_params = dimensions_parameters_input, name = name,
+                cfg_attr = cfg_attribute)).unwrap();
+
+        // writing the constructor
+        (write!(dest, "let t = TextureImplementation::new::<_, u8>(facade, format, None, MipmapsOption::EmptyMipmaps(levels), {});", dimensions_parameters_passing)).unwrap();
+        (writeln!(dest, "
+            match t {{
+                Ok(t) => Ok({}(t)),
+                Err(TextureMaybeSupportedCreationError::CreationError(e)) => Err(e),
+                Err(TextureMaybeSupportedCreationError::NotSupported) => unreachable!()
+            }}", name)).unwrap();
+
+        // closing function
+        (writeln!(dest, "}}")).unwrap();
+    }
+
+    // writing the `empty_with_mipmap_levels_if_supported` function
+    if ty != TextureType::Compressed && ty != TextureType::CompressedSrgb &&
+       !dimensions.is_multisample()
+    {
+        // opening function
+        (writeln!(dest, "
+                /// Creates an empty texture with exactly `levels` mipmap levels, including the
+                /// main level.
+                ///
+                /// Contrary to `empty_with_mipmaps`, the mipmaps are not generated. The texture
+                /// and all of its mipmaps will contain undefined data.
+                pub fn empty_with_mipmap_levels_if_supported<F>(facade: &F, levels: u32,
+                                                                {dim_params})
+                                                                -> Result<{name},
+                                                                   TextureMaybeSupportedCreationError>
+                                                                where F: Facade
+                {{
+                    let format = {format};
+            ", format = default_format, dim_params = dimensions_parameters_input, name = name)).unwrap();
+
+        // writing the constructor
+        (write!(dest, "TextureImplementation::new::<_, u8>(facade, format, None, MipmapsOption::EmptyMipmaps(levels), {})", dimensions_parameters_passing)).unwrap();
+        (writeln!(dest, ".map(|t| {}(t))", name)).unwrap();
+
+        // closing function
+        (writeln!(dest, "}}")).unwrap();
+    }
+
+
     // writing the `as_surface` function
     if (dimensions == TextureDimensions::Texture2d ||
         dimensions == TextureDimensions::Texture2dMultisample) && ty == TextureType::Regular
@@ -842,6 +987,42 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             }}
         ")).unwrap();
 
+    // writing the `generate_mipmaps` function
+    if ty == TextureType::Regular || ty == TextureType::Srgb {
+        if dimensions != TextureDimensions::Texture2dMultisample &&
+           dimensions != TextureDimensions::Texture2dMultisampleArray
+        {
+            (write!(dest, "
+                    /// Regenerates the content of all the mipmap levels from the main level.
+                    ///
+                    /// This is useful after drawing on the texture or writing to it. The texture
+                    /// must have been created with mipmaps, otherwise this function returns
+                    /// `NoMipmaps`.
+                    pub fn generate_mipmaps(&self) -> Result<(), MipmapsGenerationError> {{
+                        self.0.generate_mipmaps()
+                    }}
+                ")).unwrap();
+        }
+    }
+
+    // writing the `sampled` function
+    match ty {
+        TextureType::Regular | TextureType::Compressed |
+        TextureType::Srgb | TextureType::CompressedSrgb |
+        TextureType::Integral | TextureType::Unsigned | TextureType::Depth => {
+            (write!(dest, "
+                    /// Builds a `Sampler` wrapping this texture, so that it can be passed as a
+                    /// uniform with custom sampling parameters.
+                    ///
+                    /// This is equivalent to `Sampler::new(&texture)`.
+                    pub fn sampled(&self) -> Sampler<{myname}> {{
+                        Sampler::new(self)
+                    }}
+                ", myname = name)).unwrap();
+        },
+        _ => ()
+    }
+
     // writing the `read` functions
     // TODO: implement for other types too
     if dimensions == TextureDimensions::Texture2d &&
@@ -858,6 +1039,45 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 }}
             "#)).unwrap();
 
+        (write!(dest, r#"
+                /// Reads the content of a mipmap level of the texture to RAM.
+                ///
+                /// The returned data has the dimensions of the mipmap level, which are the
+                /// dimensions of the texture divided by two for each level (and at least 1).
+                /// Returns `None` if the texture doesn't have this level, or if the backend
+                /// can't read it. OpenGL ES 2.0 can only read levels other than 0 if
+                /// `GL_OES_fbo_render_mipmap` is supported.
+                ///
+                /// This is mostly useful to check the result of generating mipmaps.
+                pub fn read_mipmap<P, T>(&self, level: u32) -> Option<T>
+                                         where T: Texture2dDataSink<Data = P>,
+                                               P: PixelValue + Clone
+                {{
+                    if self.0.is_mipmap_level_readable(level) {{
+                        Some(self.0.read(level))
+                    }} else {{
+                        None
+                    }}
+                }}
+            "#)).unwrap();
+
+        (write!(dest, r#"
+                /// Reads the content of the texture to RAM, in a buffer that you provide.
+                ///
+                /// Contrary to `read`, this function doesn't allocate any memory and fetches all
+                /// the pixels with a single call.
+                ///
+                /// The pixels are written row by row, starting with the bottom row. Each row
+                /// contains `get_width()` pixels and there is no padding between rows.
+                ///
+                /// ## Panic
+                ///
+                /// Panics if `dest` contains less than `get_width() * get_height()` elements.
+                pub fn read_into<P>(&self, dest: &mut [P]) where P: PixelValue {{
+                    self.0.read_into(0, dest)
+                }}
+            "#)).unwrap();
+
         (write!(dest, r#"
                 /// Reads the content of the texture into a buffer in video memory.
                 ///
@@ -873,6 +1093,39 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             "#)).unwrap();
     }
 
+    // writing the `read_layer` function
+    if dimensions == TextureDimensions::Texture2dArray && ty == TextureType::Regular {
+        (write!(dest, r#"
+                /// Reads the content of one layer of the texture to RAM.
+                ///
+                /// ## Panic
+                ///
+                /// Panics if `layer` is superior or equal to the number of layers.
+                pub fn read_layer<P, T>(&self, layer: u32) -> T
+                                        where T: Texture2dDataSink<Data = P>,
+                                              P: PixelValue + Clone
+                {{
+                    self.0.read_layer(layer)
+                }}
+            "#)).unwrap();
+    }
+
+    // writing the `read_depth_stencil` function
+    if dimensions == TextureDimensions::Texture2d && ty == TextureType::DepthStencil {
+        (write!(dest, r#"
+                /// Reads the content of the texture to RAM.
+                ///
+                /// Returns a list of rows of `(depth, stencil)` pairs, where the depth is between
+                /// `0.0` and `1.0`. Returns an error if the texture doesn't use the packed
+                /// `I24I8` format.
+                pub fn read_depth_stencil(&self)
+                                          -> Result<Vec<Vec<(f32, u8)>>, DepthStencilReadError>
+                {{
+                    self.0.read_depth_stencil()
+                }}
+            "#)).unwrap();
+    }
+
     // writing the `write` function
     // TODO: implement for other types too
     if dimensions == TextureDimensions::Texture2d &&
@@ -885,9 +1138,13 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 /// or right after this call. Prefer creating a whole new texture if you change a
                 /// huge part of it.
                 ///
+                /// Only the region described by `rect` is modified, which makes this function
+                /// suitable for updating a part of a texture atlas.
+                ///
                 /// ## Panic
                 ///
-                /// Panics if the the dimensions of `data` don't match the `Rect`.
+                /// Panics if the the dimensions of `data` don't match the `Rect`, or if the `Rect`
+                /// is outside of the texture.
                 pub fn write<'a, T>(&self, rect: Rect, data: T) where T: {data_source_trait}<'a> {{
                     let RawImage2d {{ data, width, height, format: client_format }} =
                                             data.into_raw();
@@ -901,6 +1158,54 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             "#, data_source_trait = data_source_trait)).unwrap();
     }
 
+    // writing the `copy_to` function
+    if dimensions == TextureDimensions::Texture2d && ty == TextureType::Regular {
+        (write!(dest, r#"
+                /// Copies the region described by `source_rect` to `target`, with the
+                /// bottom-left corner of the region ending at `dest`.
+                ///
+                /// The copy is done by the GPU with `glCopyImageSubData` if it is supported
+                /// (OpenGL 4.3 or `GL_ARB_copy_image`), and with a framebuffer blit otherwise.
+                /// `target` can be the same texture as long as the regions don't overlap.
+                ///
+                /// Returns `TextureCopyError::OutOfRange` if the source or destination region is
+                /// outside of its texture, and `TextureCopyError::Overlap` if `target` is the
+                /// same texture and the regions overlap.
+                pub fn copy_to(&self, target: &{name}, source_rect: Rect, dest: (u32, u32))
+                               -> Result<(), TextureCopyError>
+                {{
+                    self.0.copy_to(&target.0, &source_rect, dest)
+                }}
+            "#, name = name)).unwrap();
+    }
+
+    // writing the `write_face` function
+    if dimensions == TextureDimensions::CubeMap && ty == TextureType::Regular {
+        (write!(dest, r#"
+                /// Uploads some data in one face of the cubemap.
+                ///
+                /// Only the region described by `rect` is modified. The mipmaps are regenerated
+                /// if the texture has some.
+                ///
+                /// ## Panic
+                ///
+                /// Panics if the the dimensions of `data` don't match the `Rect`, or if the `Rect`
+                /// is outside of the face.
+                pub fn write_face<'a, T>(&self, face: CubeLayer, rect: Rect, data: T)
+                                         where T: {data_source_trait}<'a>
+                {{
+                    let RawImage2d {{ data, width, height, format: client_format }} =
+                                            data.into_raw();
+
+                    assert_eq!(width, rect.width);
+                    assert_eq!(height, rect.height);
+
+                    self.0.upload(rect.left, rect.bottom, face.get_layer_index() as u32,
+                                  (client_format, data), width, Some(height), None, 0, true);
+                }}
+            "#, data_source_trait = data_source_trait)).unwrap();
+    }
+
     // writing the `layer()` function
     if dimensions.is_array() {
         (write!(dest, r#"
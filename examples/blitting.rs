@@ -51,12 +51,12 @@ fn main() {
             };
 
             opengl_texture.as_surface().blit_whole_color_to(&dest_texture.as_surface(), &dest_rect,
-                                                            glium::uniforms::MagnifySamplerFilter::Linear);
+                                                            glium::uniforms::MagnifySamplerFilter::Linear).unwrap();
         }
 
         // drawing a frame
         let target = display.draw();
-        dest_texture.as_surface().fill(&target, glium::uniforms::MagnifySamplerFilter::Linear);
+        dest_texture.as_surface().fill(&target, glium::uniforms::MagnifySamplerFilter::Linear).unwrap();
         target.finish();
 
         // polling and handling the events received by the window
@@ -61,7 +61,10 @@ fn main() {
 
     //
     let mut camera = support::camera::CameraState::new();
-    
+
+    // pressing `Tab` switches between filled and wireframe rendering
+    let mut wireframe = false;
+
     // the main loop
     support::start_loop(|| {
         camera.update();
@@ -76,6 +79,11 @@ fn main() {
         let params = glium::DrawParameters {
             depth_test: glium::DepthTest::IfLess,
             depth_write: true,
+            polygon_mode: if wireframe {
+                glium::PolygonMode::Line
+            } else {
+                glium::PolygonMode::Fill
+            },
             .. std::default::Default::default()
         };
 
@@ -91,6 +99,11 @@ fn main() {
         for event in display.poll_events() {
             match event {
                 glutin::Event::Closed => return support::Action::Stop,
+                glutin::Event::KeyboardInput(glutin::ElementState::Pressed, _,
+                                             Some(glutin::VirtualKeyCode::Tab)) =>
+                {
+                    wireframe = !wireframe;
+                },
                 ev => camera.process_input(&ev),
             }
         }
@@ -10,11 +10,17 @@ use glutin;
 use DisplayBuild;
 use Frame;
 use GliumCreationError;
+use GlError;
+use ReadBuffer;
+use ToGlEnum;
+use ops;
+use pixel_buffer::PixelBuffer;
 use texture;
 
 use context;
 use backend;
 use backend::Context;
+use backend::ExtensionsList;
 use backend::Backend;
 use version::Version;
 
@@ -123,6 +129,23 @@ impl GlutinFacade {
         *self.context.get_version()
     }
 
+    /// Returns the list of extensions that glium knows about, and whether they are supported.
+    pub fn get_extensions(&self) -> &ExtensionsList {
+        self.context.get_extensions()
+    }
+
+    /// Returns true if the backend supports the extension with the given name.
+    ///
+    /// See `Context::is_extension_supported`.
+    pub fn is_extension_supported(&self, name: &str) -> bool {
+        self.context.is_extension_supported(name)
+    }
+
+    /// Returns the names of all the extensions supported by the backend.
+    pub fn get_extensions_names(&self) -> &[String] {
+        self.context.get_extensions_names()
+    }
+
     /// Returns the supported GLSL version.
     pub fn get_supported_glsl_version(&self) -> Version {
         self.context.get_supported_glsl_version()
@@ -149,6 +172,20 @@ impl GlutinFacade {
         self.context.get_max_anisotropy_support()
     }
 
+    /// Returns true if the backend can render to a texture of the given format.
+    ///
+    /// Returns `None` if the backend can't tell. See `Context::is_format_renderable`.
+    pub fn is_format_renderable(&self, format: texture::TextureFormat) -> Option<bool> {
+        self.context.is_format_renderable(format)
+    }
+
+    /// Returns true if the backend can apply linear filtering to a texture of the given format.
+    ///
+    /// Returns `None` if the backend can't tell. See `Context::is_format_filterable`.
+    pub fn is_format_filterable(&self, format: texture::TextureFormat) -> Option<bool> {
+        self.context.is_format_filterable(format)
+    }
+
     /// Returns the maximum dimensions of the viewport.
     ///
     /// Glium will panic if you request a larger viewport than this when drawing.
@@ -156,6 +193,45 @@ impl GlutinFacade {
         self.context.get_max_viewport_dimensions()
     }
 
+    /// Returns the maximum width and height of a 2D texture.
+    pub fn get_max_texture_size(&self) -> u32 {
+        self.context.get_max_texture_size()
+    }
+
+    /// Returns the maximum number of textures that can be used by the fragment shader.
+    pub fn get_max_texture_image_units(&self) -> u32 {
+        self.context.get_max_texture_image_units()
+    }
+
+    /// Returns the maximum number of textures that can be used by all the shaders of a
+    /// program combined.
+    pub fn get_max_combined_texture_image_units(&self) -> u32 {
+        self.context.get_max_combined_texture_image_units()
+    }
+
+    /// Returns the maximum number of vertex attributes that a program can use.
+    pub fn get_max_vertex_attributes(&self) -> u32 {
+        self.context.get_max_vertex_attributes()
+    }
+
+    /// Returns the maximum number of color attachments of a framebuffer.
+    pub fn get_max_color_attachments(&self) -> u32 {
+        self.context.get_max_color_attachments()
+    }
+
+    /// Returns the maximum number of samples of a multisample render buffer, or `None` if
+    /// multisample render buffers are not supported.
+    pub fn get_max_samples(&self) -> Option<u32> {
+        self.context.get_max_samples()
+    }
+
+    /// Returns true if the default framebuffer is in sRGB.
+    ///
+    /// See `Context::is_default_framebuffer_srgb`.
+    pub fn is_default_framebuffer_srgb(&self) -> bool {
+        self.context.is_default_framebuffer_srgb()
+    }
+
     /// Releases the shader compiler, indicating that no new programs will be created for a while.
     ///
     /// # Features
@@ -196,6 +272,43 @@ impl GlutinFacade {
         self.context.read_front_buffer()
     }
 
+    /// Reads the content of one of the buffers of the default framebuffer.
+    ///
+    /// See `Context::read_buffer`.
+    pub fn read_buffer<P, T>(&self, source: ReadBuffer) -> T          // TODO: remove Clone for P
+                             where P: texture::PixelValue + Clone + Send,
+                             T: texture::Texture2dDataSink<Data = P>
+    {
+        self.context.read_buffer(source)
+    }
+
+    /// Reads the content of the front buffer into a buffer in video memory.
+    ///
+    /// Contrary to `read_front_buffer`, this function doesn't wait for the GPU to finish
+    /// drawing. See the documentation of the `pixel_buffer` module for more infos.
+    pub fn read_front_buffer_to_pixel_buffer<P, T>(&self) -> PixelBuffer<T>          // TODO: remove Clone for P
+                                                   where P: texture::PixelValue + Clone + Send,
+                                                   T: texture::Texture2dDataSink<Data = P>
+    {
+        let (w, h) = self.get_framebuffer_dimensions();
+        let size = w as usize * h as usize *
+                   <T as texture::Texture2dDataSink>::get_preferred_formats()[0].get_size();
+
+        let mut pb = PixelBuffer::new_empty(self, size);
+        ops::read_from_default_fb_to_pb(ReadBuffer::Front.to_glenum(), &mut pb, &self.context);
+        pb
+    }
+
+    /// Reads the content of one of the buffers of the default framebuffer into a buffer that
+    /// you provide.
+    ///
+    /// See `Context::read_buffer_into`.
+    pub fn read_buffer_into<P>(&self, source: ReadBuffer, dest: &mut [P])
+                               where P: texture::PixelValue
+    {
+        self.context.read_buffer_into(source, dest)
+    }
+
     /// Execute an arbitrary closure with the OpenGL context active. Useful if another
     /// component needs to directly manipulate OpenGL state.
     ///
@@ -208,6 +321,22 @@ impl GlutinFacade {
         self.context.exec_in_context(action)
     }
 
+    /// Returns the oldest OpenGL error that is pending, or `None` if there is no error.
+    ///
+    /// The error is removed from the queue. Call this function multiple times in order to
+    /// retrieve all the pending errors.
+    pub fn get_error(&self) -> Option<GlError> {
+        self.context.get_error()
+    }
+
+    /// Returns the oldest message that glium has sent through the debug output, or `None` if
+    /// there is no message.
+    ///
+    /// See `Context::get_debug_message`.
+    pub fn get_debug_message(&self) -> Option<String> {
+        self.context.get_debug_message()
+    }
+
     /// Asserts that there are no OpenGL errors pending.
     ///
     /// This function should be used in tests.
@@ -225,6 +354,14 @@ impl GlutinFacade {
     pub fn synchronize(&self) {
         self.context.synchronize()
     }
+
+    /// Asks the backend to start executing all the previous commands, without waiting for
+    /// them to finish.
+    ///
+    /// See `Context::flush`.
+    pub fn flush(&self) {
+        self.context.flush()
+    }
 }
 
 impl DisplayBuild for glutin::WindowBuilder<'static> {
@@ -17,7 +17,7 @@ use std::ops::Deref;
 
 use libc;
 
-pub use context::Context;
+pub use context::{Context, ExtensionsList};
 
 pub mod glutin_backend;
 
@@ -6,6 +6,7 @@ use ContextExt;
 use gl;
 use libc;
 use std::{fmt, mem, ptr, slice};
+use std::error::Error;
 use std::sync::Mutex;
 use std::sync::mpsc::{channel, Sender, Receiver};
 use std::rc::Rc;
@@ -42,6 +43,55 @@ pub enum BufferCreationError {
     BufferTypeNotSupported,
 }
 
+impl fmt::Display for BufferCreationError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(formatter, "{}", self.description())
+    }
+}
+
+impl Error for BufferCreationError {
+    fn description(&self) -> &str {
+        match self {
+            &BufferCreationError::OutOfMemory => "Not enough memory to create the buffer",
+            &BufferCreationError::PersistentMappingNotSupported =>
+                "Persistent mapping is not supported",
+            &BufferCreationError::BufferTypeNotSupported =>
+                "This type of buffer is not supported",
+        }
+    }
+}
+
+/// Error that can happen when copying data between buffers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CopyError {
+    /// The backend doesn't support copying between buffers.
+    ///
+    /// Requires OpenGL 3.1, OpenGL ES 3.0 or `GL_ARB_copy_buffer`.
+    NotSupported,
+
+    /// The source or the destination range goes beyond the end of its buffer.
+    OutOfRange,
+
+    /// The source and the destination are the same buffer and the ranges overlap.
+    Overlap,
+}
+
+impl fmt::Display for CopyError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(formatter, "{}", self.description())
+    }
+}
+
+impl Error for CopyError {
+    fn description(&self) -> &str {
+        match self {
+            &CopyError::NotSupported => "Copying between buffers is not supported",
+            &CopyError::OutOfRange => "The range goes beyond the end of the buffer",
+            &CopyError::Overlap => "The source and destination ranges overlap",
+        }
+    }
+}
+
 /// Flags to specify how the buffer should behave.
 #[derive(Debug, Copy, Clone)]
 pub struct BufferFlags {
@@ -58,6 +108,11 @@ pub struct BufferFlags {
 
     /// Specifies how the buffer may be mapped.
     pub mapping: BufferFlagsMapping,
+
+    /// Hints that the content of the buffer is going to be rewritten frequently. Only has an
+    /// effect when the buffer is created with mutable storage, in which case `GL_DYNAMIC_DRAW`
+    /// is used instead of `GL_STATIC_DRAW`.
+    pub frequently_modified: bool,
 }
 
 impl BufferFlags {
@@ -67,6 +122,17 @@ impl BufferFlags {
             dynamic: true,
             client_storage: false,
             mapping: BufferFlagsMapping::ReadWrite(BufferFlagsPersistent::None),
+            frequently_modified: false,
+        }
+    }
+
+    /// Builds flags for a buffer whose content is going to be rewritten frequently.
+    pub fn dynamic_draw() -> BufferFlags {
+        BufferFlags {
+            dynamic: true,
+            client_storage: false,
+            mapping: BufferFlagsMapping::ReadWrite(BufferFlagsPersistent::None),
+            frequently_modified: true,
         }
     }
 
@@ -76,6 +142,7 @@ impl BufferFlags {
             dynamic: true,
             client_storage: false,
             mapping: BufferFlagsMapping::ReadWrite(BufferFlagsPersistent::PersistentCoherent),
+            frequently_modified: true,
         }
     }
 
@@ -155,6 +222,7 @@ pub enum BufferType {
     PixelPackBuffer,
     PixelUnpackBuffer,
     UniformBuffer,
+    ShaderStorageBuffer,
 }
 
 impl BufferType {
@@ -164,6 +232,7 @@ impl BufferType {
             BufferType::PixelPackBuffer => gl::PIXEL_PACK_BUFFER,
             BufferType::PixelUnpackBuffer => gl::PIXEL_UNPACK_BUFFER,
             BufferType::UniformBuffer => gl::UNIFORM_BUFFER,
+            BufferType::ShaderStorageBuffer => gl::SHADER_STORAGE_BUFFER,
         }
     }
 }
@@ -234,6 +303,32 @@ impl Buffer {
         self.persistent_mapping.is_some()
     }
 
+    /// Returns the pointer to the persistent mapping of the buffer, if it is persistent.
+    ///
+    /// Contrary to `map`, this doesn't wait for the fences of the buffer. It is the
+    /// responsibility of the caller to make sure that the range it writes to is not in use.
+    pub fn get_persistent_mapping(&self) -> Option<*mut libc::c_void> {
+        self.persistent_mapping
+    }
+
+    /// Removes the fences that have been registered with `add_fence` and returns them.
+    pub fn take_fences(&self) -> Vec<Receiver<sync::LinearSyncFence>> {
+        let mut fences = self.fences.lock().unwrap();
+        mem::replace(&mut *fences, Vec::new())
+    }
+
+    /// Tells the backend that the content of the buffer is no longer needed, so that
+    /// it can orphan the current storage instead of waiting for it to stop being used.
+    ///
+    /// Does nothing if `glInvalidateBufferData` is not supported.
+    pub fn invalidate(&self) {
+        let ctxt = self.context.make_current();
+
+        if ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.extensions.gl_arb_invalidate_subdata {
+            unsafe { ctxt.gl.InvalidateBufferData(self.id); }
+        }
+    }
+
     /// Changes the type of the buffer. Returns `Err` if this is forbidden.
     pub fn set_type(mut self, ty: BufferType) -> Result<Buffer, Buffer> {
         // FIXME: return Err for GLES2
@@ -245,11 +340,11 @@ impl Buffer {
     ///
     /// This function considers that the buffer is filled of elements of type `D`. The offset
     /// is a number of elements, not a number of bytes.
-    pub fn upload<D>(&self, offset: usize, data: Vec<D>)
+    pub fn upload<D>(&self, offset: usize, data: &[D])
                      where D: Copy + Send + 'static
     {
-        let offset = offset * get_elements_size(&data);
-        let buffer_size = get_elements_size(&data) * data.len();
+        let offset = offset * get_elements_size(data);
+        let buffer_size = get_elements_size(data) * data.len();
 
         assert!(offset <= self.get_total_size());
         assert!(offset + buffer_size <= self.get_total_size());
@@ -297,9 +392,83 @@ impl Buffer {
         }
     }
 
+    /// Copies `size` bytes from this buffer to `target` on the GPU.
+    ///
+    /// Contrary to the other methods of this struct, the offsets and the size are in bytes.
+    pub fn copy_to(&self, target: &Buffer, src_offset: usize, dest_offset: usize, size: usize)
+                   -> Result<(), CopyError>
+    {
+        if src_offset + size > self.get_total_size() ||
+           dest_offset + size > target.get_total_size()
+        {
+            return Err(CopyError::OutOfRange);
+        }
+
+        if self.id == target.id && src_offset < dest_offset + size &&
+           dest_offset < src_offset + size
+        {
+            return Err(CopyError::Overlap);
+        }
+
+        let mut ctxt = self.context.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 3, 1) ||
+             ctxt.version >= &Version(Api::GlEs, 3, 0) || ctxt.extensions.gl_arb_copy_buffer)
+        {
+            return Err(CopyError::NotSupported);
+        }
+
+        unsafe {
+            if ctxt.version >= &Version(Api::Gl, 4, 5) {
+                ctxt.gl.CopyNamedBufferSubData(self.id, target.id,
+                                               src_offset as gl::types::GLintptr,
+                                               dest_offset as gl::types::GLintptr,
+                                               size as gl::types::GLsizeiptr);
+
+            } else {
+                if ctxt.state.copy_read_buffer_binding != self.id {
+                    ctxt.gl.BindBuffer(gl::COPY_READ_BUFFER, self.id);
+                    ctxt.state.copy_read_buffer_binding = self.id;
+                }
+
+                if ctxt.state.copy_write_buffer_binding != target.id {
+                    ctxt.gl.BindBuffer(gl::COPY_WRITE_BUFFER, target.id);
+                    ctxt.state.copy_write_buffer_binding = target.id;
+                }
+
+                ctxt.gl.CopyBufferSubData(gl::COPY_READ_BUFFER, gl::COPY_WRITE_BUFFER,
+                                          src_offset as gl::types::GLintptr,
+                                          dest_offset as gl::types::GLintptr,
+                                          size as gl::types::GLsizeiptr);
+            }
+
+            // the persistent mappings must not be accessed before the copy is over
+            for fence in self.add_fence().into_iter().chain(target.add_fence().into_iter()) {
+                fence.send(sync::new_linear_sync_fence_if_supported(&mut ctxt).unwrap()).unwrap();
+            }
+        }
+
+        Ok(())
+    }
+
     /// Offset and size should be specified as number of elements
     pub fn map<'a, D>(&'a mut self, offset: usize, size: usize)
                       -> Mapping<'a, D> where D: Send + 'static
+    {
+        self.map_impl(offset, size, false)
+    }
+
+    /// Same as `map`, but the modified ranges must be flushed with `Mapping::flush_range`.
+    ///
+    /// Offset and size should be specified as number of elements
+    pub fn map_explicit_flush<'a, D>(&'a mut self, offset: usize, size: usize)
+                                     -> Mapping<'a, D> where D: Send + 'static
+    {
+        self.map_impl(offset, size, true)
+    }
+
+    fn map_impl<'a, D>(&'a mut self, offset: usize, size: usize, explicit_flush: bool)
+                       -> Mapping<'a, D> where D: Send + 'static
     {
         if offset > self.elements_count || (offset + size) > self.elements_count {
             panic!("Trying to map out of range of buffer");
@@ -314,24 +483,31 @@ impl Buffer {
                 }
             }
 
+            // the persistent mapping is coherent, so there is nothing to flush
             return Mapping {
                 buffer: self,
                 data: unsafe { (existing_mapping as *mut D).offset(offset as isize) },
                 len: size,
+                explicit_flush: false,
             };
         }
 
         let offset_bytes = offset * self.elements_size;
         let size_bytes = size * self.elements_size;
 
+        // FIXME: incorrect flags
+        let flags = if explicit_flush {
+            gl::MAP_READ_BIT | gl::MAP_WRITE_BIT | gl::MAP_FLUSH_EXPLICIT_BIT
+        } else {
+            gl::MAP_READ_BIT | gl::MAP_WRITE_BIT
+        };
+
         let ptr = unsafe {
             let mut ctxt = self.context.make_current();
 
-            // FIXME: incorrect flags
             if ctxt.version >= &Version(Api::Gl, 4, 5) {
                 ctxt.gl.MapNamedBufferRange(self.id, offset_bytes as gl::types::GLintptr,
-                                            size_bytes as gl::types::GLsizei,
-                                            gl::MAP_READ_BIT | gl::MAP_WRITE_BIT)
+                                            size_bytes as gl::types::GLsizei, flags)
 
             } else if ctxt.version >= &Version(Api::Gl, 3, 0) ||
                 ctxt.version >= &Version(Api::GlEs, 3, 0) ||
@@ -339,8 +515,7 @@ impl Buffer {
             {
                 let bind = bind_buffer(&mut ctxt, self.id, self.ty);
                 ctxt.gl.MapBufferRange(bind, offset_bytes as gl::types::GLintptr,
-                                       size_bytes as gl::types::GLsizeiptr,
-                                       gl::MAP_READ_BIT | gl::MAP_WRITE_BIT)
+                                       size_bytes as gl::types::GLsizeiptr, flags)
 
             } else {
                 unimplemented!();       // FIXME: 
@@ -351,6 +526,7 @@ impl Buffer {
             buffer: self,
             data: ptr as *mut D,
             len: size,
+            explicit_flush: explicit_flush,
         }
     }
 
@@ -450,6 +626,18 @@ impl Drop for Buffer {
             ctxt.state.uniform_buffer_binding = 0;
         }
 
+        if ctxt.state.shader_storage_buffer_binding == self.id {
+            ctxt.state.shader_storage_buffer_binding = 0;
+        }
+
+        if ctxt.state.copy_read_buffer_binding == self.id {
+            ctxt.state.copy_read_buffer_binding = 0;
+        }
+
+        if ctxt.state.copy_write_buffer_binding == self.id {
+            ctxt.state.copy_write_buffer_binding = 0;
+        }
+
         unsafe {
             if ctxt.version >= &Version(Api::Gl, 1, 5) ||
                 ctxt.version >= &Version(Api::GlEs, 2, 0)
@@ -476,6 +664,46 @@ pub struct Mapping<'b, D> {
     buffer: &'b mut Buffer,
     data: *mut D,
     len: usize,
+    explicit_flush: bool,
+}
+
+impl<'a, D> Mapping<'a, D> {
+    /// Indicates that a range of the mapping has been modified. Offset and size should be
+    /// specified as number of elements, relative to the start of the mapping.
+    ///
+    /// Only has an effect if the buffer was mapped with `map_explicit_flush`. Otherwise the
+    /// whole mapping is considered modified.
+    pub fn flush_range(&self, offset: usize, size: usize) {
+        assert!(offset + size <= self.len, "Trying to flush out of range of the mapping");
+
+        if !self.explicit_flush {
+            return;
+        }
+
+        let offset_bytes = offset * self.buffer.elements_size;
+        let size_bytes = size * self.buffer.elements_size;
+
+        let mut ctxt = self.buffer.context.make_current();
+
+        unsafe {
+            if ctxt.version >= &Version(Api::Gl, 4, 5) {
+                ctxt.gl.FlushMappedNamedBufferRange(self.buffer.id,
+                                                    offset_bytes as gl::types::GLintptr,
+                                                    size_bytes as gl::types::GLsizei);
+
+            } else if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+                ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+                ctxt.extensions.gl_arb_map_buffer_range
+            {
+                let bind = bind_buffer(&mut ctxt, self.buffer.id, self.buffer.ty);
+                ctxt.gl.FlushMappedBufferRange(bind, offset_bytes as gl::types::GLintptr,
+                                               size_bytes as gl::types::GLsizeiptr);
+
+            } else {
+                unreachable!();
+            }
+        }
+    }
 }
 
 impl<'a, D> Drop for Mapping<'a, D> {
@@ -628,6 +856,8 @@ unsafe fn create_buffer<D>(mut ctxt: &mut CommandContext, elements_size: usize,
         unreachable!();
     }
 
+    // `GL_OUT_OF_MEMORY` is only checked by the functions that return an error, see
+    // `ContextExt::check_out_of_memory`, but the driver can also silently create a smaller buffer
     if buffer_size != obtained_size as usize {
         if ctxt.version >= &Version(Api::Gl, 1, 5) ||
             ctxt.version >= &Version(Api::GlEs, 2, 0)
@@ -638,7 +868,7 @@ unsafe fn create_buffer<D>(mut ctxt: &mut CommandContext, elements_size: usize,
         } else {
             unreachable!();
         }
-        
+
         return Err(BufferCreationError::OutOfMemory);
     }
 
@@ -749,6 +979,22 @@ unsafe fn bind_buffer(mut ctxt: &mut CommandContext, id: gl::types::GLuint, ty:
 
             gl::UNIFORM_BUFFER
         },
+
+        BufferType::ShaderStorageBuffer => {
+            if ctxt.state.shader_storage_buffer_binding != id {
+                ctxt.state.shader_storage_buffer_binding = id;
+
+                if ctxt.version >= &Version(Api::Gl, 4, 3) ||
+                    ctxt.version >= &Version(Api::GlEs, 3, 1)
+                {
+                    ctxt.gl.BindBuffer(gl::SHADER_STORAGE_BUFFER, id);
+                } else {
+                    unreachable!();
+                }
+            }
+
+            gl::SHADER_STORAGE_BUFFER
+        },
     }
 }
 
@@ -798,5 +1044,9 @@ fn mutable_storage_flags(ctxt: &mut CommandContext, flags: BufferFlags)
 {
     // FIXME: do it properly
     // FIXME: detect persistent and return Err if not supported
-    Ok(gl::STATIC_DRAW)
+    if flags.frequently_modified {
+        Ok(gl::DYNAMIC_DRAW)
+    } else {
+        Ok(gl::STATIC_DRAW)
+    }
 }
@@ -11,6 +11,9 @@ pub struct Capabilities {
     /// An empty list means that the backend doesn't have a compiler.
     pub supported_glsl_versions: Vec<Version>,
 
+    /// True if the context is a core profile context.
+    pub core_profile: bool,
+
     /// Whether the context supports left and right buffers.
     pub stereo: bool,
 
@@ -23,11 +26,26 @@ pub struct Capabilities {
     /// Number of bits in the default framebuffer's stencil buffer
     pub stencil_bits: Option<u16>,
 
+    /// Number of samples of the default framebuffer. `None` if it is not multisampled.
+    pub samples: Option<u16>,
+
     /// Maximum number of textures that can be bound to a program.
     ///
     /// `glActiveTexture` must be between `GL_TEXTURE0` and `GL_TEXTURE0` + this value - 1.
     pub max_combined_texture_image_units: gl::types::GLint,
 
+    /// Maximum number of textures that can be accessed from the fragment shader.
+    pub max_texture_image_units: gl::types::GLint,
+
+    /// Maximum width and height of a 1D or 2D texture, and maximum width of a buffer texture.
+    pub max_texture_size: gl::types::GLint,
+
+    /// Maximum number of vertex attributes that a program can use.
+    pub max_vertex_attribs: gl::types::GLint,
+
+    /// Maximum number of color attachments of a framebuffer object.
+    pub max_color_attachments: gl::types::GLint,
+
     /// Maximum value for `GL_TEXTURE_MAX_ANISOTROPY_EXT​`.
     ///
     /// `None` if the extension is not supported by the hardware.
@@ -36,11 +54,23 @@ pub struct Capabilities {
     /// Maximum width and height of `glViewport`.
     pub max_viewport_dims: (gl::types::GLint, gl::types::GLint),
 
+    /// Minimum and maximum values accepted by `glLineWidth` for aliased lines.
+    pub line_width_range: (gl::types::GLfloat, gl::types::GLfloat),
+
     /// Maximum number of elements that can be passed with `glDrawBuffers`.
     pub max_draw_buffers: gl::types::GLint,
 
     /// Maximum number of vertices per patch. `None` if tessellation is not supported.
     pub max_patch_vertices: Option<gl::types::GLint>,
+
+    /// Maximum number of work groups that can be dispatched by a compute shader on each
+    /// dimension. `None` if compute shaders are not supported.
+    pub max_compute_work_group_count: Option<(gl::types::GLint, gl::types::GLint,
+                                              gl::types::GLint)>,
+
+    /// Maximum number of samples of a multisample render buffer. `None` if multisample render
+    /// buffers are not supported.
+    pub max_samples: Option<gl::types::GLint>,
 }
 
 /// Loads the capabilities.
@@ -54,6 +84,16 @@ pub fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &ExtensionsL
             get_supported_glsl(gl, version, extensions)
         },
 
+        core_profile: unsafe {
+            if version >= &Version(Api::Gl, 3, 2) {
+                let mut val = mem::uninitialized();
+                gl.GetIntegerv(gl::CONTEXT_PROFILE_MASK, &mut val);
+                (val as gl::types::GLenum & gl::CONTEXT_CORE_PROFILE_BIT) != 0
+            } else {
+                false
+            }
+        },
+
         stereo: unsafe {
             if version >= &Version(Api::Gl, 1, 0) {
                 let mut val: gl::types::GLboolean = mem::uninitialized();
@@ -116,12 +156,52 @@ pub fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &ExtensionsL
             }
         },
 
+        samples: unsafe {
+            let mut value = 0;
+            gl.GetIntegerv(gl::SAMPLES, &mut value);
+
+            match value {
+                0 | 1 => None,
+                v => Some(v as u16),
+            }
+        },
+
         max_combined_texture_image_units: unsafe {
             let mut val = 2;
             gl.GetIntegerv(gl::MAX_COMBINED_TEXTURE_IMAGE_UNITS, &mut val);
             val
         },
 
+        max_texture_image_units: unsafe {
+            let mut val = 2;
+            gl.GetIntegerv(gl::MAX_TEXTURE_IMAGE_UNITS, &mut val);
+            val
+        },
+
+        max_texture_size: unsafe {
+            let mut val = 64;
+            gl.GetIntegerv(gl::MAX_TEXTURE_SIZE, &mut val);
+            val
+        },
+
+        max_vertex_attribs: unsafe {
+            let mut val = 8;
+            gl.GetIntegerv(gl::MAX_VERTEX_ATTRIBS, &mut val);
+            val
+        },
+
+        max_color_attachments: unsafe {
+            if version >= &Version(Api::Gl, 3, 0) || version >= &Version(Api::GlEs, 3, 0) ||
+               extensions.gl_ext_framebuffer_object
+            {
+                let mut val = 1;
+                gl.GetIntegerv(gl::MAX_COLOR_ATTACHMENTS, &mut val);
+                val
+            } else {
+                1
+            }
+        },
+
         max_texture_max_anisotropy: if !extensions.gl_ext_texture_filter_anisotropic {
             None
 
@@ -139,6 +219,12 @@ pub fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &ExtensionsL
             (val[0], val[1])
         },
 
+        line_width_range: unsafe {
+            let mut val: [gl::types::GLfloat; 2] = [ 1.0, 1.0 ];
+            gl.GetFloatv(gl::ALIASED_LINE_WIDTH_RANGE, val.as_mut_ptr());
+            (val[0], val[1])
+        },
+
         max_draw_buffers: unsafe {
             if version >= &Version(Api::Gl, 2, 0) ||
                 version >= &Version(Api::GlEs, 3, 0)
@@ -163,6 +249,34 @@ pub fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &ExtensionsL
         } else {
             None
         },
+
+        max_compute_work_group_count: if version >= &Version(Api::Gl, 4, 3) ||
+            version >= &Version(Api::GlEs, 3, 1) || extensions.gl_arb_compute_shader
+        {
+            Some(unsafe {
+                let mut val: [gl::types::GLint; 3] = [ 0, 0, 0 ];
+                for (i, v) in val.iter_mut().enumerate() {
+                    gl.GetIntegeri_v(gl::MAX_COMPUTE_WORK_GROUP_COUNT, i as gl::types::GLuint, v);
+                }
+                (val[0], val[1], val[2])
+            })
+
+        } else {
+            None
+        },
+
+        max_samples: if version >= &Version(Api::Gl, 3, 0) ||
+            version >= &Version(Api::GlEs, 3, 0)
+        {
+            Some(unsafe {
+                let mut val = mem::uninitialized();
+                gl.GetIntegerv(gl::MAX_SAMPLES, &mut val);
+                val
+            })
+
+        } else {
+            None
+        },
     }
 }
 
@@ -10,12 +10,20 @@ pub struct ExtensionsList {
     pub gl_arb_buffer_storage: bool,
     /// GL_ARB_compute_shader
     pub gl_arb_compute_shader: bool,
+    /// GL_ARB_copy_buffer
+    pub gl_arb_copy_buffer: bool,
+    /// GL_ARB_copy_image
+    pub gl_arb_copy_image: bool,
     /// GL_ARB_debug_output
     pub gl_arb_debug_output: bool,
+    /// GL_ARB_depth_clamp
+    pub gl_arb_depth_clamp: bool,
     /// GL_ARB_depth_texture
     pub gl_arb_depth_texture: bool,
     /// GL_ARB_direct_state_access
     pub gl_arb_direct_state_access: bool,
+    /// GL_ARB_draw_elements_base_vertex
+    pub gl_arb_draw_elements_base_vertex: bool,
     /// GL_ARB_compatibility
     pub gl_arb_compatibility: bool,
     /// GL_ARB_ES2_compatibility
@@ -32,12 +40,18 @@ pub struct ExtensionsList {
     pub gl_arb_geometry_shader4: bool,
     /// GL_ARB_get_program_binary
     pub gl_arb_get_programy_binary: bool,
+    /// GL_ARB_gl_spirv
+    pub gl_arb_gl_spirv: bool,
     /// GL_ARB_instanced_arrays
     pub gl_arb_instanced_arrays: bool,
+    /// GL_ARB_internalformat_query2
+    pub gl_arb_internalformat_query2: bool,
     /// GL_ARB_invalidate_subdata
     pub gl_arb_invalidate_subdata: bool,
     /// GL_ARB_map_buffer_range
     pub gl_arb_map_buffer_range: bool,
+    /// GL_ARB_pixel_buffer_object
+    pub gl_arb_pixel_buffer_object: bool,
     /// GL_ARB_sampler_objects
     pub gl_arb_sampler_objects: bool,
     /// GL_ARB_shader_objects
@@ -58,6 +72,8 @@ pub struct ExtensionsList {
     pub gl_arb_texture_rgb10_a2ui: bool,
     /// GL_ARB_texture_storage
     pub gl_arb_texture_storage: bool,
+    /// GL_ARB_timer_query
+    pub gl_arb_timer_query: bool,
     /// GL_ARB_uniform_buffer_object
     pub gl_arb_uniform_buffer_object: bool,
     /// GL_ARB_vertex_array_object
@@ -84,6 +100,8 @@ pub struct ExtensionsList {
     pub gl_ext_gpu_shader4: bool,
     /// GL_EXT_packed_depth_stencil
     pub gl_ext_packed_depth_stencil: bool,
+    /// GL_EXT_texture_compression_s3tc
+    pub gl_ext_texture_compression_s3tc: bool,
     /// GL_EXT_texture_filter_anisotropic
     pub gl_ext_texture_filter_anisotropic: bool,
     /// GL_EXT_texture_integer
@@ -92,24 +110,33 @@ pub struct ExtensionsList {
     pub gl_ext_texture_srgb: bool,
     /// GL_EXT_transform_feedback
     pub gl_ext_transform_feedback: bool,
+    /// GL_KHR_blend_equation_advanced
+    pub gl_khr_blend_equation_advanced: bool,
     /// GL_KHR_debug
     pub gl_khr_debug: bool,
+    /// GL_NV_conditional_render
+    pub gl_nv_conditional_render: bool,
     /// GL_NVX_gpu_memory_info
     pub gl_nvx_gpu_memory_info: bool,
+    /// GL_OES_fbo_render_mipmap
+    pub gl_oes_fbo_render_mipmap: bool,
     /// GL_OES_vertex_array_object
     pub gl_oes_vertex_array_object: bool,
 }
 
-pub fn get_extensions(gl: &gl::Gl) -> ExtensionsList {
-    let strings = get_extensions_strings(gl);
-
+/// Builds the `ExtensionsList` from the names returned by `get_extensions_strings`.
+pub fn get_extensions(strings: &[String]) -> ExtensionsList {
     let mut extensions = ExtensionsList {
         gl_apple_vertex_array_object: false,
         gl_arb_buffer_storage: false,
         gl_arb_compute_shader: false,
+        gl_arb_copy_buffer: false,
+        gl_arb_copy_image: false,
         gl_arb_debug_output: false,
+        gl_arb_depth_clamp: false,
         gl_arb_depth_texture: false,
         gl_arb_direct_state_access: false,
+        gl_arb_draw_elements_base_vertex: false,
         gl_arb_compatibility: false,
         gl_arb_es2_compatibility: false,
         gl_arb_es3_compatibility: false,
@@ -118,9 +145,12 @@ pub fn get_extensions(gl: &gl::Gl) -> ExtensionsList {
         gl_arb_framebuffer_srgb: false,
         gl_arb_geometry_shader4: false,
         gl_arb_get_programy_binary: false,
+        gl_arb_gl_spirv: false,
         gl_arb_instanced_arrays: false,
+        gl_arb_internalformat_query2: false,
         gl_arb_invalidate_subdata: false,
         gl_arb_map_buffer_range: false,
+        gl_arb_pixel_buffer_object: false,
         gl_arb_sampler_objects: false,
         gl_arb_shader_objects: false,
         gl_arb_sync: false,
@@ -131,6 +161,7 @@ pub fn get_extensions(gl: &gl::Gl) -> ExtensionsList {
         gl_arb_texture_rg: false,
         gl_arb_texture_rgb10_a2ui: false,
         gl_arb_texture_storage: false,
+        gl_arb_timer_query: false,
         gl_arb_uniform_buffer_object: false,
         gl_arb_vertex_array_object: false,
         gl_arb_vertex_buffer_object: false,
@@ -144,23 +175,33 @@ pub fn get_extensions(gl: &gl::Gl) -> ExtensionsList {
         gl_ext_geometry_shader4: false,
         gl_ext_gpu_shader4: false,
         gl_ext_packed_depth_stencil: false,
+        gl_ext_texture_compression_s3tc: false,
         gl_ext_texture_filter_anisotropic: false,
         gl_ext_texture_integer: false,
         gl_ext_texture_srgb: false,
         gl_ext_transform_feedback: false,
+        gl_khr_blend_equation_advanced: false,
         gl_khr_debug: false,
+        gl_nv_conditional_render: false,
         gl_nvx_gpu_memory_info: false,
+        gl_oes_fbo_render_mipmap: false,
         gl_oes_vertex_array_object: false,
     };
 
-    for extension in strings.into_iter() {
+    for extension in strings.iter() {
         match &extension[..] {
             "GL_APPLE_vertex_array_object" => extensions.gl_apple_vertex_array_object = true,
             "GL_ARB_buffer_storage" => extensions.gl_arb_buffer_storage = true,
             "GL_ARB_compute_shader" => extensions.gl_arb_compute_shader = true,
+            "GL_ARB_copy_buffer" => extensions.gl_arb_copy_buffer = true,
+            "GL_ARB_copy_image" => extensions.gl_arb_copy_image = true,
             "GL_ARB_debug_output" => extensions.gl_arb_debug_output = true,
+            "GL_ARB_depth_clamp" => extensions.gl_arb_depth_clamp = true,
             "GL_ARB_depth_texture" => extensions.gl_arb_depth_texture = true,
             "GL_ARB_direct_state_access" => extensions.gl_arb_direct_state_access = true,
+            "GL_ARB_draw_elements_base_vertex" => {
+                extensions.gl_arb_draw_elements_base_vertex = true
+            },
             "GL_ARB_compatibility" => extensions.gl_arb_compatibility = true,
             "GL_ARB_ES2_compatibility" => extensions.gl_arb_es2_compatibility = true,
             "GL_ARB_ES3_compatibility" => extensions.gl_arb_es3_compatibility = true,
@@ -169,9 +210,12 @@ pub fn get_extensions(gl: &gl::Gl) -> ExtensionsList {
             "GL_ARB_framebuffer_sRGB" => extensions.gl_arb_framebuffer_srgb = true,
             "GL_ARB_geometry_shader4" => extensions.gl_arb_geometry_shader4 = true,
             "GL_ARB_get_program_binary" => extensions.gl_arb_get_programy_binary = true,
+            "GL_ARB_gl_spirv" => extensions.gl_arb_gl_spirv = true,
             "GL_ARB_instanced_arrays" => extensions.gl_arb_instanced_arrays = true,
+            "GL_ARB_internalformat_query2" => extensions.gl_arb_internalformat_query2 = true,
             "GL_ARB_invalidate_subdata" => extensions.gl_arb_invalidate_subdata = true,
             "GL_ARB_map_buffer_range" => extensions.gl_arb_map_buffer_range = true,
+            "GL_ARB_pixel_buffer_object" => extensions.gl_arb_pixel_buffer_object = true,
             "GL_ARB_sampler_objects" => extensions.gl_arb_sampler_objects = true,
             "GL_ARB_shader_objects" => extensions.gl_arb_shader_objects = true,
             "GL_ARB_sync" => extensions.gl_arb_sync = true,
@@ -182,6 +226,7 @@ pub fn get_extensions(gl: &gl::Gl) -> ExtensionsList {
             "GL_ARB_texture_rg" => extensions.gl_arb_texture_rg = true,
             "GL_ARB_texture_rgb10_a2ui" => extensions.gl_arb_texture_rgb10_a2ui = true,
             "GL_ARB_texture_storage" => extensions.gl_arb_texture_storage = true,
+            "GL_ARB_timer_query" => extensions.gl_arb_timer_query = true,
             "GL_ARB_uniform_buffer_object" => extensions.gl_arb_uniform_buffer_object = true,
             "GL_ARB_vertex_array_object" => extensions.gl_arb_vertex_array_object = true,
             "GL_ARB_vertex_buffer_object" => extensions.gl_arb_vertex_buffer_object = true,
@@ -195,12 +240,16 @@ pub fn get_extensions(gl: &gl::Gl) -> ExtensionsList {
             "GL_EXT_geometry_shader4" => extensions.gl_ext_geometry_shader4 = true,
             "GL_EXT_gpu_shader4" => extensions.gl_ext_gpu_shader4 = true,
             "GL_EXT_packed_depth_stencil" => extensions.gl_ext_packed_depth_stencil = true,
+            "GL_EXT_texture_compression_s3tc" => extensions.gl_ext_texture_compression_s3tc = true,
             "GL_EXT_texture_filter_anisotropic" => extensions.gl_ext_texture_filter_anisotropic = true,
             "GL_EXT_texture_integer" => extensions.gl_ext_texture_integer = true,
             "GL_EXT_texture_sRGB" => extensions.gl_ext_texture_srgb = true,
             "GL_EXT_transform_feedback" => extensions.gl_ext_transform_feedback = true,
+            "GL_KHR_blend_equation_advanced" => extensions.gl_khr_blend_equation_advanced = true,
             "GL_KHR_debug" => extensions.gl_khr_debug = true,
+            "GL_NV_conditional_render" => extensions.gl_nv_conditional_render = true,
             "GL_NVX_gpu_memory_info" => extensions.gl_nvx_gpu_memory_info = true,
+            "GL_OES_fbo_render_mipmap" => extensions.gl_oes_fbo_render_mipmap = true,
             "GL_OES_vertex_array_object" => extensions.gl_oes_vertex_array_object = true,
             _ => ()
         }
@@ -209,7 +258,8 @@ pub fn get_extensions(gl: &gl::Gl) -> ExtensionsList {
     extensions
 }
 
-fn get_extensions_strings(gl: &gl::Gl) -> Vec<String> {
+/// Returns the names of all the extensions supported by the backend.
+pub fn get_extensions_strings(gl: &gl::Gl) -> Vec<String> {
     unsafe {
         let list = gl.GetString(gl::EXTENSIONS);
 
@@ -11,6 +11,9 @@ use std::ffi::CStr;
 use std::rc::Rc;
 
 use GliumCreationError;
+use GlError;
+use ReadBuffer;
+use ToGlEnum;
 use ContextExt;
 use backend::Backend;
 use version;
@@ -18,9 +21,11 @@ use version::Api;
 use version::Version;
 
 use fbo;
+use image_format;
 use ops;
 use sampler_object;
 use texture;
+use texture::TextureFormat;
 use uniforms;
 use vertex_array_object;
 
@@ -39,6 +44,7 @@ pub struct Context {
     state: RefCell<GLState>,
     version: Version,
     extensions: ExtensionsList,
+    extensions_strings: Vec<String>,
     capabilities: Capabilities,
 
     backend: RefCell<Box<Backend>>,
@@ -46,6 +52,13 @@ pub struct Context {
 
     report_debug_output_errors: Cell<bool>,
 
+    // errors that have been popped by `check_out_of_memory` but not yet returned by `get_error`
+    pending_errors: RefCell<Vec<GlError>>,
+
+    // messages sent by glium through the debug output but not yet returned by
+    // `get_debug_message`
+    debug_messages: RefCell<Vec<String>>,
+
     // we maintain a list of FBOs
     // the option is here to destroy the container
     pub framebuffer_objects: Option<fbo::FramebuffersContainer>,
@@ -83,7 +96,8 @@ impl Context {
 
         let gl_state = RefCell::new(Default::default());
         let version = version::get_gl_version(&gl);
-        let extensions = extensions::get_extensions(&gl);
+        let extensions_strings = extensions::get_extensions_strings(&gl);
+        let extensions = extensions::get_extensions(&extensions_strings);
         let capabilities = capabilities::get_capabilities(&gl, &version, &extensions);
         let report_debug_output_errors = Cell::new(true);
 
@@ -105,8 +119,11 @@ impl Context {
             state: gl_state,
             version: version,
             extensions: extensions,
+            extensions_strings: extensions_strings,
             capabilities: capabilities,
             report_debug_output_errors: report_debug_output_errors,
+            pending_errors: RefCell::new(Vec::new()),
+            debug_messages: RefCell::new(Vec::new()),
             backend: RefCell::new(Box::new(backend)),
             check_current_context: check_current_context,
             framebuffer_objects: Some(fbo::FramebuffersContainer::new()),
@@ -178,11 +195,25 @@ impl Context {
         &self.version
     }
 
-    // TODO: make me private
+    /// Returns the list of extensions that glium knows about, and whether they are supported.
     pub fn get_extensions(&self) -> &ExtensionsList {
         &self.extensions
     }
 
+    /// Returns true if the backend supports the extension with the given name, for example
+    /// `"GL_ARB_bindless_texture"`.
+    ///
+    /// Contrary to `get_extensions`, this works with any extension, including the ones that
+    /// glium doesn't use.
+    pub fn is_extension_supported(&self, name: &str) -> bool {
+        self.extensions_strings.iter().any(|e| &e[..] == name)
+    }
+
+    /// Returns the names of all the extensions supported by the backend.
+    pub fn get_extensions_names(&self) -> &[String] {
+        &self.extensions_strings
+    }
+
     /// Returns the GLSL version guaranteed to be supported.
     pub fn get_supported_glsl_version(&self) -> Version {
         version::get_supported_glsl_version(self.get_version())
@@ -207,6 +238,95 @@ impl Context {
         (d.0 as u32, d.1 as u32)
     }
 
+    /// Returns the maximum width and height of a 2D texture.
+    pub fn get_max_texture_size(&self) -> u32 {
+        self.capabilities().max_texture_size as u32
+    }
+
+    /// Returns the maximum number of textures that can be used by the fragment shader.
+    pub fn get_max_texture_image_units(&self) -> u32 {
+        self.capabilities().max_texture_image_units as u32
+    }
+
+    /// Returns the maximum number of textures that can be used by all the shaders of a
+    /// program combined.
+    ///
+    /// Drawing returns `TooManyTextures` if a program uses more textures than this.
+    pub fn get_max_combined_texture_image_units(&self) -> u32 {
+        self.capabilities().max_combined_texture_image_units as u32
+    }
+
+    /// Returns the maximum number of vertex attributes that a program can use.
+    pub fn get_max_vertex_attributes(&self) -> u32 {
+        self.capabilities().max_vertex_attribs as u32
+    }
+
+    /// Returns the maximum number of color attachments of a framebuffer.
+    pub fn get_max_color_attachments(&self) -> u32 {
+        self.capabilities().max_color_attachments as u32
+    }
+
+    /// Returns the maximum number of samples of a multisample render buffer, or `None` if
+    /// multisample render buffers are not supported.
+    pub fn get_max_samples(&self) -> Option<u32> {
+        self.capabilities().max_samples.map(|v| v as u32)
+    }
+
+    /// Returns true if the default framebuffer is in sRGB.
+    ///
+    /// If this is the case, the colors written to it are converted from linear to sRGB
+    /// unless `DrawParameters::srgb` is `false`.
+    pub fn is_default_framebuffer_srgb(&self) -> bool {
+        self.capabilities().srgb
+    }
+
+    /// Returns true if the backend can render to a texture of the given format.
+    ///
+    /// Returns `None` if the backend doesn't support querying this information. This requires
+    /// OpenGL 4.3 or `GL_ARB_internalformat_query2`.
+    pub fn is_format_renderable(&self, format: TextureFormat) -> Option<bool> {
+        self.query_internal_format(format, gl::FRAMEBUFFER_RENDERABLE)
+    }
+
+    /// Returns true if the backend can apply linear filtering to a texture of the given format.
+    ///
+    /// Returns `None` if the backend doesn't support querying this information. This requires
+    /// OpenGL 4.3 or `GL_ARB_internalformat_query2`.
+    pub fn is_format_filterable(&self, format: TextureFormat) -> Option<bool> {
+        self.query_internal_format(format, gl::FILTER)
+    }
+
+    /// Queries the support of a 2D texture format for `pname` with `glGetInternalformativ`.
+    fn query_internal_format(&self, format: TextureFormat, pname: gl::types::GLenum)
+                             -> Option<bool>
+    {
+        {
+            let ctxt = self.make_current();
+
+            if !(ctxt.version >= &Version(Api::Gl, 4, 3)) &&
+               !ctxt.extensions.gl_arb_internalformat_query2
+            {
+                return None;
+            }
+        }
+
+        let request = image_format::TextureFormatRequest::Specific(format);
+        let internal_format = match image_format::format_request_to_glenum(self, None, request) {
+            Ok((unsized_format, sized_format)) => sized_format.unwrap_or(unsized_format),
+            Err(_) => return Some(false)
+        };
+
+        let ctxt = self.make_current();
+
+        unsafe {
+            let mut value = 0;
+            ctxt.gl.GetInternalformativ(gl::TEXTURE_2D, internal_format, pname, 1, &mut value);
+
+            Some(value as gl::types::GLenum == gl::FULL_SUPPORT ||
+                 value as gl::types::GLenum == gl::CAVEAT_SUPPORT)
+        }
+    }
+
     /// Releases the shader compiler, indicating that no new programs will be created for a while.
     ///
     /// This method is a no-op if it's not available in the implementation.
@@ -268,7 +388,62 @@ impl Context {
                                    where P: texture::PixelValue + Clone + Send,
                                    T: texture::Texture2dDataSink<Data = P>
     {
-        ops::read_from_default_fb(gl::FRONT_LEFT, &self)
+        self.read_buffer(ReadBuffer::Front)
+    }
+
+    /// Reads the content of one of the buffers of the default framebuffer.
+    ///
+    /// Reading the back buffer gives you the content of the frame that is being drawn,
+    /// while reading the front buffer gives you the content that is displayed on the screen.
+    ///
+    /// This function can return any type that implements `Texture2dData`.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # extern crate glium;
+    /// # extern crate glutin;
+    /// # fn main() {
+    /// # let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+    /// let pixels: Vec<Vec<(u8, u8, u8)>> = display.read_buffer(glium::ReadBuffer::Back);
+    /// # }
+    /// ```
+    pub fn read_buffer<P, T>(&self, source: ReadBuffer) -> T          // TODO: remove Clone for P
+                             where P: texture::PixelValue + Clone + Send,
+                             T: texture::Texture2dDataSink<Data = P>
+    {
+        ops::read_from_default_fb(source.to_glenum(), &self)
+    }
+
+    /// Reads the content of one of the buffers of the default framebuffer into a buffer that
+    /// you provide.
+    ///
+    /// Contrary to `read_buffer`, this function doesn't allocate any memory and fetches all
+    /// the pixels with a single call.
+    ///
+    /// The pixels are written row by row, starting with the bottom row. Each row contains
+    /// as many pixels as the width of the framebuffer and there is no padding between rows.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `dest` contains less pixels than the framebuffer.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # extern crate glium;
+    /// # extern crate glutin;
+    /// # fn main() {
+    /// # let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+    /// let (width, height) = display.get_framebuffer_dimensions();
+    /// let mut pixels = vec![(0u8, 0u8, 0u8, 0u8); width as usize * height as usize];
+    /// display.read_buffer_into(glium::ReadBuffer::Front, &mut pixels);
+    /// # }
+    /// ```
+    pub fn read_buffer_into<P>(&self, source: ReadBuffer, dest: &mut [P])
+                               where P: texture::PixelValue
+    {
+        ops::read_from_default_fb_into(source.to_glenum(), dest, &self)
     }
 
     /// Execute an arbitrary closure with the OpenGL context active. Useful if another
@@ -284,14 +459,46 @@ impl Context {
         action()
     }
 
+    /// Returns the oldest OpenGL error that is pending, or `None` if there is no error.
+    ///
+    /// The error is removed from the queue. Call this function multiple times in order to
+    /// retrieve all the pending errors.
+    pub fn get_error(&self) -> Option<GlError> {
+        {
+            let mut pending_errors = self.pending_errors.borrow_mut();
+            if !pending_errors.is_empty() {
+                return Some(pending_errors.remove(0));
+            }
+        }
+
+        let mut ctxt = self.make_current();
+        ::get_gl_error(&mut ctxt)
+    }
+
+    /// Returns the oldest message that glium has sent through the debug output, or `None` if
+    /// there is no message.
+    ///
+    /// Glium sends a message when it silently falls back to a different behavior than the one
+    /// that was requested, for example when anisotropic filtering is not supported. Messages
+    /// are only recorded if the debug output is supported by the backend.
+    ///
+    /// The message is removed from the queue. Call this function multiple times in order to
+    /// retrieve all the pending messages.
+    pub fn get_debug_message(&self) -> Option<String> {
+        let mut debug_messages = self.debug_messages.borrow_mut();
+        if debug_messages.is_empty() {
+            None
+        } else {
+            Some(debug_messages.remove(0))
+        }
+    }
+
     /// Asserts that there are no OpenGL errors pending.
     ///
     /// This function should be used in tests.
     pub fn assert_no_error(&self) {
-        let mut ctxt = self.make_current();
-
-        match ::get_gl_error(&mut ctxt) {
-            Some(msg) => panic!("{}", msg),
+        match self.get_error() {
+            Some(err) => panic!("{}", err),
             None => ()
         };
     }
@@ -307,6 +514,17 @@ impl Context {
         let ctxt = self.make_current();
         unsafe { ctxt.gl.Finish(); }
     }
+
+    /// Asks the backend to start executing all the previous commands, without waiting for
+    /// them to finish.
+    ///
+    /// This is useful before waiting with a timeout on a `SyncFence` from another place
+    /// than the one that created it, or to make sure that the commands are submitted before
+    /// doing some long work on the CPU.
+    pub fn flush(&self) {
+        let ctxt = self.make_current();
+        unsafe { ctxt.gl.Flush(); }
+    }
 }
 
 impl ContextExt for Context {
@@ -314,6 +532,21 @@ impl ContextExt for Context {
         self.report_debug_output_errors.set(value);
     }
 
+    fn check_out_of_memory(&self) -> bool {
+        let mut ctxt = self.make_current();
+        let mut out_of_memory = false;
+
+        while let Some(error) = ::get_gl_error(&mut ctxt) {
+            if error == GlError::OutOfMemory {
+                out_of_memory = true;
+            } else {
+                self.pending_errors.borrow_mut().push(error);
+            }
+        }
+
+        out_of_memory
+    }
+
     fn make_current<'a>(&'a self) -> CommandContext<'a, 'a> {
         if self.check_current_context {
             let backend = self.backend.borrow();
@@ -429,6 +662,7 @@ fn check_gl_compatibility(ctxt: &mut CommandContext) -> Result<(), GliumCreation
     }
 
     if cfg!(feature = "gl_program_binary") && !(ctxt.version >= &Version(Api::Gl, 4, 1)) &&
+        !(ctxt.version >= &Version(Api::GlEs, 3, 0)) &&
         !ctxt.extensions.gl_arb_get_programy_binary
     {
         result.push("OpenGL implementation doesn't support program binary");
@@ -500,6 +734,17 @@ fn init_debug_callback(context: &Rc<Context>) {
         let user_param = user_param as *const Context;
         let user_param: &Context = unsafe { mem::transmute(user_param) };
 
+        // messages inserted by `insert_debug_message` are stored so that the user can
+        // retrieve them with `get_debug_message`
+        if source == gl::DEBUG_SOURCE_THIRD_PARTY {
+            let message = unsafe {
+                String::from_utf8(CStr::from_ptr(message).to_bytes().to_vec()).unwrap()
+            };
+
+            user_param.debug_messages.borrow_mut().push(message);
+            return;
+        }
+
         if (severity == gl::DEBUG_SEVERITY_HIGH || severity == gl::DEBUG_SEVERITY_MEDIUM) && 
            (ty == gl::DEBUG_TYPE_ERROR || ty == gl::DEBUG_TYPE_UNDEFINED_BEHAVIOR ||
             ty == gl::DEBUG_TYPE_PORTABILITY || ty == gl::DEBUG_TYPE_DEPRECATED_BEHAVIOR)
@@ -568,3 +813,34 @@ fn init_debug_callback(context: &Rc<Context>) {
         }
     }
 }
+
+/// Sends a low-severity message through the debug output, if it is enabled.
+///
+/// This is used to report situations where glium silently falls back to a different behavior
+/// than the one requested by the user. The message can then be retrieved with
+/// `Context::get_debug_message`.
+pub fn insert_debug_message(ctxt: &mut CommandContext, message: &str) {
+    if ctxt.state.enabled_debug_output != Some(true) {
+        return;
+    }
+
+    let len = message.len() as gl::types::GLsizei;
+    let ptr = message.as_ptr() as *const gl::types::GLchar;
+
+    unsafe {
+        if ctxt.version >= &Version(Api::Gl, 4, 5) ||
+            (ctxt.version >= &Version(Api::Gl, 1, 0) && ctxt.extensions.gl_khr_debug)
+        {
+            ctxt.gl.DebugMessageInsert(gl::DEBUG_SOURCE_THIRD_PARTY, gl::DEBUG_TYPE_OTHER, 0,
+                                       gl::DEBUG_SEVERITY_LOW, len, ptr);
+
+        } else if ctxt.version >= &Version(Api::GlEs, 2, 0) && ctxt.extensions.gl_khr_debug {
+            ctxt.gl.DebugMessageInsertKHR(gl::DEBUG_SOURCE_THIRD_PARTY, gl::DEBUG_TYPE_OTHER, 0,
+                                          gl::DEBUG_SEVERITY_LOW, len, ptr);
+
+        } else if ctxt.extensions.gl_arb_debug_output {
+            ctxt.gl.DebugMessageInsertARB(gl::DEBUG_SOURCE_THIRD_PARTY, gl::DEBUG_TYPE_OTHER, 0,
+                                          gl::DEBUG_SEVERITY_LOW, len, ptr);
+        }
+    }
+}
@@ -19,6 +19,9 @@ pub struct GLState {
     /// Whether GL_DEBUG_OUTPUT_SYNCHRONOUS is enabled
     pub enabled_debug_output_synchronous: bool,
 
+    /// Whether GL_DEPTH_CLAMP is enabled
+    pub enabled_depth_clamp: bool,
+
     /// Whether GL_DEPTH_TEST is enabled
     pub enabled_depth_test: bool,
 
@@ -28,12 +31,24 @@ pub struct GLState {
     /// Whether GL_FRAMEBUFFER_SRGB is enabled
     pub enabled_framebuffer_srgb: bool,
 
+    /// Whether GL_LINE_SMOOTH is enabled
+    pub enabled_line_smooth: bool,
+
     /// Whether GL_MULTISAMPLE is enabled
     pub enabled_multisample: bool,
 
     /// Whether GL_POLYGON_OFFSET_FILL is enabled
     pub enabled_polygon_offset_fill: bool,
 
+    /// Whether GL_POLYGON_OFFSET_LINE is enabled
+    pub enabled_polygon_offset_line: bool,
+
+    /// Whether GL_POLYGON_OFFSET_POINT is enabled
+    pub enabled_polygon_offset_point: bool,
+
+    /// Whether GL_POLYGON_SMOOTH is enabled
+    pub enabled_polygon_smooth: bool,
+
     /// Whether GL_RASTERIZER_DISCARD is enabled
     pub enabled_rasterizer_discard: bool,
 
@@ -43,6 +58,18 @@ pub struct GLState {
     /// Whether GL_SAMPLE_COVERAGE is enabled
     pub enabled_sample_coverage: bool,
 
+    /// Whether GL_SAMPLE_MASK is enabled
+    pub enabled_sample_mask: bool,
+
+    /// Whether GL_PRIMITIVE_RESTART is enabled
+    pub enabled_primitive_restart: bool,
+
+    /// Whether GL_PRIMITIVE_RESTART_FIXED_INDEX is enabled
+    pub enabled_primitive_restart_fixed_index: bool,
+
+    /// Whether GL_PROGRAM_POINT_SIZE is enabled
+    pub enabled_program_point_size: bool,
+
     /// Whether GL_SCISSOR_TEST is enabled
     pub enabled_scissor_test: bool,
 
@@ -55,6 +82,9 @@ pub struct GLState {
     // The latest value passed to `glBindVertexArray`.
     pub vertex_array: gl::types::GLuint,
 
+    // List of the locations passed to `glEnableVertexAttribArray` while no VAO is bound.
+    pub enabled_vertex_attrib_arrays: Vec<gl::types::GLuint>,
+
     // The latest value passed to `glClearColor`.
     pub clear_color: (gl::types::GLclampf, gl::types::GLclampf,
                       gl::types::GLclampf, gl::types::GLclampf),
@@ -77,6 +107,15 @@ pub struct GLState {
     /// The latest buffer bound to `GL_UNIFORM_BUFFER`.
     pub uniform_buffer_binding: gl::types::GLuint,
 
+    /// The latest buffer bound to `GL_SHADER_STORAGE_BUFFER`.
+    pub shader_storage_buffer_binding: gl::types::GLuint,
+
+    /// The latest buffer bound to `GL_COPY_READ_BUFFER`.
+    pub copy_read_buffer_binding: gl::types::GLuint,
+
+    /// The latest buffer bound to `GL_COPY_WRITE_BUFFER`.
+    pub copy_write_buffer_binding: gl::types::GLuint,
+
     /// The latest buffer bound to `GL_READ_FRAMEBUFFER`.
     pub read_framebuffer: gl::types::GLuint,
 
@@ -96,6 +135,9 @@ pub struct GLState {
     /// The latest values passed to `glBlendFunc`.
     pub blend_func: (gl::types::GLenum, gl::types::GLenum),
 
+    /// The latest values passed to `glColorMask`.
+    pub color_mask: (bool, bool, bool, bool),
+
     /// The latest value passed to `glDepthFunc`.
     pub depth_func: gl::types::GLenum,
 
@@ -137,12 +179,27 @@ pub struct GLState {
     /// The latest value passed to `glPointSize`.
     pub point_size: gl::types::GLfloat,
 
+    /// The latest value passed to `glPrimitiveRestartIndex`.
+    pub primitive_restart_index: gl::types::GLuint,
+
     /// The latest value passed to `glCullFace`.
     pub cull_face: gl::types::GLenum,
 
+    /// The latest values passed to `glPolygonOffset`.
+    pub polygon_offset: (gl::types::GLfloat, gl::types::GLfloat),
+
     /// The latest value passed to `glPolygonMode`.
     pub polygon_mode: gl::types::GLenum,
 
+    /// The latest value passed to `glSampleMaski` with index 0.
+    pub sample_mask: gl::types::GLbitfield,
+
+    /// The latest value passed to `glHint` with `GL_LINE_SMOOTH_HINT`.
+    pub line_smooth_hint: gl::types::GLenum,
+
+    /// The latest value passed to `glHint` with `GL_POLYGON_SMOOTH_HINT`.
+    pub polygon_smooth_hint: gl::types::GLenum,
+
     /// The latest value passed to `glPixelStore` with `GL_UNPACK_ALIGNMENT`.
     pub pixel_store_unpack_alignment: gl::types::GLint,
 
@@ -154,6 +211,17 @@ pub struct GLState {
 
     /// The latest value passed to `glActiveTexture`.
     pub active_texture: gl::types::GLenum,
+
+    /// For each texture unit, the bind point and id of the latest texture bound to it, and
+    /// the id of the sampler bound to it. Units that have never been used by a draw command
+    /// are missing from the list.
+    pub texture_units: Vec<(gl::types::GLenum, gl::types::GLuint, gl::types::GLuint)>,
+
+    /// The id of the query currently active for `GL_TIME_ELAPSED`, or 0 if there is none.
+    pub time_elapsed_query: gl::types::GLuint,
+
+    /// The id of the query currently active for `GL_SAMPLES_PASSED`, or 0 if there is none.
+    pub samples_passed_query: gl::types::GLuint,
 }
 
 impl Default for GLState {
@@ -163,19 +231,29 @@ impl Default for GLState {
             enabled_cull_face: false,
             enabled_debug_output: None,
             enabled_debug_output_synchronous: false,
+            enabled_depth_clamp: false,
             enabled_depth_test: false,
             enabled_dither: false,
             enabled_framebuffer_srgb: false,
+            enabled_line_smooth: false,
             enabled_multisample: true,
             enabled_polygon_offset_fill: false,
+            enabled_polygon_offset_line: false,
+            enabled_polygon_offset_point: false,
+            enabled_polygon_smooth: false,
             enabled_rasterizer_discard: false,
             enabled_sample_alpha_to_coverage: false,
             enabled_sample_coverage: false,
+            enabled_sample_mask: false,
+            enabled_primitive_restart: false,
+            enabled_primitive_restart_fixed_index: false,
+            enabled_program_point_size: false,
             enabled_scissor_test: false,
             enabled_stencil_test: false,
 
             program: Handle::Id(0),
             vertex_array: 0,
+            enabled_vertex_attrib_arrays: Vec::new(),
             clear_color: (0.0, 0.0, 0.0, 0.0),
             clear_depth: 1.0,
             clear_stencil: 0,
@@ -183,11 +261,15 @@ impl Default for GLState {
             pixel_pack_buffer_binding: 0,
             pixel_unpack_buffer_binding: 0,
             uniform_buffer_binding: 0,
+            shader_storage_buffer_binding: 0,
+            copy_read_buffer_binding: 0,
+            copy_write_buffer_binding: 0,
             read_framebuffer: 0,
             draw_framebuffer: 0,
             default_framebuffer_read: None,
             renderbuffer: 0,
             depth_func: gl::LESS,
+            color_mask: (true, true, true, true),
             depth_mask: true,
             depth_range: (0.0, 1.0),
             stencil_func_front: (gl::ALWAYS, 0, 0xffffffff),
@@ -202,12 +284,20 @@ impl Default for GLState {
             scissor: None,
             line_width: 1.0,
             point_size: 1.0,
+            primitive_restart_index: 0,
             cull_face: gl::BACK,
             polygon_mode: gl::FILL,
+            polygon_offset: (0.0, 0.0),
+            sample_mask: 0xffffffff,
+            line_smooth_hint: gl::DONT_CARE,
+            polygon_smooth_hint: gl::DONT_CARE,
             pixel_store_unpack_alignment: 4,
             pixel_store_pack_alignment: 4,
             patch_patch_vertices: 3,
             active_texture: gl::TEXTURE0,
+            texture_units: Vec::new(),
+            time_elapsed_query: 0,
+            samples_passed_query: 0,
         }
     }
 }
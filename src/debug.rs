@@ -6,9 +6,12 @@
 use backend::Facade;
 use context::Context;
 use ContextExt;
+use GlObject;
+use QueryExt;
 use version::Api;
 use version::Version;
 use gl;
+use std::fmt;
 use std::rc::Rc;
 use std::mem;
 
@@ -116,7 +119,7 @@ impl TimestampQuery {
     pub fn new<F>(facade: &F) -> Option<TimestampQuery> where F: Facade {
         let ctxt = facade.get_context().make_current();
 
-        let id = if ctxt.version >= &Version(Api::Gl, 3, 2) {    // TODO: extension
+        let id = if ctxt.version >= &Version(Api::Gl, 3, 3) || ctxt.extensions.gl_arb_timer_query {
             unsafe {
                 let mut id = mem::uninitialized();
                 ctxt.gl.GenQueries(1, &mut id);
@@ -155,7 +158,7 @@ impl TimestampQuery {
 
         let ctxt = self.context.make_current();
 
-        if ctxt.version >= &Version(Api::Gl, 3, 2) {    // TODO: extension
+        if ctxt.version >= &Version(Api::Gl, 3, 3) || ctxt.extensions.gl_arb_timer_query {
             unsafe {
                 let mut value = mem::uninitialized();
                 ctxt.gl.GetQueryObjectiv(self.id, gl::QUERY_RESULT_AVAILABLE, &mut value);
@@ -182,7 +185,7 @@ impl TimestampQuery {
 
         let ctxt = self.context.make_current();
 
-        if ctxt.version >= &Version(Api::Gl, 3, 2) {    // TODO: extension
+        if ctxt.version >= &Version(Api::Gl, 3, 3) || ctxt.extensions.gl_arb_timer_query {
             unsafe {
                 let mut value = mem::uninitialized();
                 ctxt.gl.GetQueryObjectui64v(self.id, gl::QUERY_RESULT, &mut value);
@@ -203,3 +206,398 @@ impl TimestampQuery {
         }
     }
 }
+
+/// Measures the time it takes for the backend to execute some commands.
+///
+/// Contrary to measuring the time on the CPU, this only includes the time that the GPU
+/// spent executing the commands between `begin` and `end`, and not the time they spent
+/// waiting in the commands queue.
+///
+/// Only one `TimeElapsedQuery` can be active at a time.
+///
+/// ## Example
+///
+/// ```no_run
+/// # let display: glium::Display = unsafe { std::mem::uninitialized() };
+/// if let Some(mut query) = glium::debug::TimeElapsedQuery::new_if_supported(&display) {
+///     query.begin();
+///     // draw some stuff here
+///     query.end();
+///
+///     // later, for example during the next frame
+///     if let Some(elapsed) = query.get() {
+///         println!("Time it took to draw stuff: {} ns", elapsed);
+///     }
+/// }
+/// ```
+///
+pub struct TimeElapsedQuery {
+    context: Rc<Context>,
+    id: gl::types::GLuint,
+    // true if `end` has been called at least once
+    ended: bool,
+}
+
+impl TimeElapsedQuery {
+    /// Creates a new `TimeElapsedQuery`. Returns `None` if the backend doesn't support it.
+    ///
+    /// Time elapsed queries require OpenGL 3.3, `GL_ARB_timer_query` or
+    /// `GL_EXT_disjoint_timer_query`.
+    pub fn new_if_supported<F>(facade: &F) -> Option<TimeElapsedQuery> where F: Facade {
+        let ctxt = facade.get_context().make_current();
+
+        let id = if ctxt.version >= &Version(Api::Gl, 3, 3) || ctxt.extensions.gl_arb_timer_query {
+            unsafe {
+                let mut id = mem::uninitialized();
+                ctxt.gl.GenQueries(1, &mut id);
+                Some(id)
+            }
+
+        } else if ctxt.extensions.gl_ext_disjoint_timer_query {
+            unsafe {
+                let mut id = mem::uninitialized();
+                ctxt.gl.GenQueriesEXT(1, &mut id);
+                Some(id)
+            }
+
+        } else {
+            None
+        };
+
+        id.map(|q| TimeElapsedQuery {
+            context: facade.get_context().clone(),
+            id: q,
+            ended: false,
+        })
+    }
+
+    /// Starts measuring the time. The measured time includes all the commands that are
+    /// executed until `end` is called.
+    ///
+    /// Calling `begin` again after the query has ended discards the previous result.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if a `TimeElapsedQuery` is already active.
+    ///
+    pub fn begin(&mut self) {
+        let mut ctxt = self.context.make_current();
+
+        if ctxt.state.time_elapsed_query != 0 {
+            panic!("A TimeElapsedQuery is already active");
+        }
+
+        if ctxt.version >= &Version(Api::Gl, 3, 3) || ctxt.extensions.gl_arb_timer_query {
+            unsafe { ctxt.gl.BeginQuery(gl::TIME_ELAPSED, self.id); }
+        } else if ctxt.extensions.gl_ext_disjoint_timer_query {
+            unsafe { ctxt.gl.BeginQueryEXT(gl::TIME_ELAPSED_EXT, self.id); }
+        } else {
+            unreachable!();
+        }
+
+        ctxt.state.time_elapsed_query = self.id;
+        self.ended = false;
+    }
+
+    /// Stops measuring the time.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if this query is not active.
+    ///
+    pub fn end(&mut self) {
+        let mut ctxt = self.context.make_current();
+
+        if ctxt.state.time_elapsed_query != self.id {
+            panic!("The TimeElapsedQuery is not active");
+        }
+
+        if ctxt.version >= &Version(Api::Gl, 3, 3) || ctxt.extensions.gl_arb_timer_query {
+            unsafe { ctxt.gl.EndQuery(gl::TIME_ELAPSED); }
+        } else if ctxt.extensions.gl_ext_disjoint_timer_query {
+            unsafe { ctxt.gl.EndQueryEXT(gl::TIME_ELAPSED_EXT); }
+        } else {
+            unreachable!();
+        }
+
+        ctxt.state.time_elapsed_query = 0;
+        self.ended = true;
+    }
+
+    /// Returns the number of nanoseconds that have elapsed between `begin` and `end`,
+    /// or `None` if the result is not available yet.
+    ///
+    /// It takes some time to retreive the value, during which you can execute other
+    /// functions. This function never blocks.
+    pub fn get(&self) -> Option<u64> {
+        if !self.ended {
+            return None;
+        }
+
+        let ctxt = self.context.make_current();
+
+        let available = if ctxt.version >= &Version(Api::Gl, 3, 3) ||
+                           ctxt.extensions.gl_arb_timer_query
+        {
+            unsafe {
+                let mut value = mem::uninitialized();
+                ctxt.gl.GetQueryObjectiv(self.id, gl::QUERY_RESULT_AVAILABLE, &mut value);
+                value != 0
+            }
+
+        } else if ctxt.extensions.gl_ext_disjoint_timer_query {
+            unsafe {
+                let mut value = mem::uninitialized();
+                ctxt.gl.GetQueryObjectivEXT(self.id, gl::QUERY_RESULT_AVAILABLE_EXT, &mut value);
+                value != 0
+            }
+
+        } else {
+            unreachable!();
+        };
+
+        if available {
+            Some(self.get_blocking())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of nanoseconds that have elapsed between `begin` and `end`.
+    /// Blocks until the result is available.
+    ///
+    /// This function doesn't block if `get` returns `Some`.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `end` has never been called.
+    ///
+    pub fn get_blocking(&self) -> u64 {
+        assert!(self.ended, "The TimeElapsedQuery has never been ended");
+
+        let ctxt = self.context.make_current();
+
+        if ctxt.version >= &Version(Api::Gl, 3, 3) || ctxt.extensions.gl_arb_timer_query {
+            unsafe {
+                let mut value = mem::uninitialized();
+                ctxt.gl.GetQueryObjectui64v(self.id, gl::QUERY_RESULT, &mut value);
+                value
+            }
+
+        } else if ctxt.extensions.gl_ext_disjoint_timer_query {
+            unsafe {
+                let mut value = mem::uninitialized();
+                ctxt.gl.GetQueryObjectui64vEXT(self.id, gl::QUERY_RESULT_EXT, &mut value);
+                value
+            }
+
+        } else {
+            unreachable!();
+        }
+    }
+}
+
+impl Drop for TimeElapsedQuery {
+    fn drop(&mut self) {
+        let mut ctxt = self.context.make_current();
+
+        if ctxt.version >= &Version(Api::Gl, 3, 3) || ctxt.extensions.gl_arb_timer_query {
+            unsafe {
+                if ctxt.state.time_elapsed_query == self.id {
+                    ctxt.gl.EndQuery(gl::TIME_ELAPSED);
+                }
+                ctxt.gl.DeleteQueries(1, [self.id].as_ptr());
+            }
+
+        } else if ctxt.extensions.gl_ext_disjoint_timer_query {
+            unsafe {
+                if ctxt.state.time_elapsed_query == self.id {
+                    ctxt.gl.EndQueryEXT(gl::TIME_ELAPSED_EXT);
+                }
+                ctxt.gl.DeleteQueriesEXT(1, [self.id].as_ptr());
+            }
+
+        } else {
+            unreachable!();
+        }
+
+        if ctxt.state.time_elapsed_query == self.id {
+            ctxt.state.time_elapsed_query = 0;
+        }
+    }
+}
+
+/// Counts the number of samples that pass the depth and stencil tests.
+///
+/// All the draw commands that are executed between `begin` and `end` are counted. This is
+/// usually used for occlusion culling, in combination with `DrawParameters::condition`.
+///
+/// Only one `SamplesPassedQuery` can be active at a time.
+///
+/// ## Example
+///
+/// ```no_run
+/// # let display: glium::Display = unsafe { std::mem::uninitialized() };
+/// let mut query = glium::debug::SamplesPassedQuery::new_if_supported(&display).unwrap();
+///
+/// query.begin();
+/// // draw the bounding box of an object here
+/// query.end();
+///
+/// let params = glium::DrawParameters {
+///     condition: Some(&query),
+///     .. Default::default()
+/// };
+///
+/// // drawing the object with `params` does nothing if its bounding box is hidden
+/// ```
+///
+pub struct SamplesPassedQuery {
+    context: Rc<Context>,
+    id: gl::types::GLuint,
+    // true if `end` has been called at least once
+    ended: bool,
+}
+
+impl SamplesPassedQuery {
+    /// Creates a new `SamplesPassedQuery`. Returns `None` if the backend doesn't support it.
+    ///
+    /// Samples passed queries require OpenGL 1.5.
+    pub fn new_if_supported<F>(facade: &F) -> Option<SamplesPassedQuery> where F: Facade {
+        let ctxt = facade.get_context().make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 1, 5)) {
+            return None;
+        }
+
+        let id = unsafe {
+            let mut id = mem::uninitialized();
+            ctxt.gl.GenQueries(1, &mut id);
+            id
+        };
+
+        Some(SamplesPassedQuery {
+            context: facade.get_context().clone(),
+            id: id,
+            ended: false,
+        })
+    }
+
+    /// Starts counting the samples. All the draw commands until `end` is called are counted.
+    ///
+    /// Calling `begin` again after the query has ended discards the previous result.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if a `SamplesPassedQuery` is already active.
+    ///
+    pub fn begin(&mut self) {
+        let mut ctxt = self.context.make_current();
+
+        if ctxt.state.samples_passed_query != 0 {
+            panic!("A SamplesPassedQuery is already active");
+        }
+
+        unsafe { ctxt.gl.BeginQuery(gl::SAMPLES_PASSED, self.id); }
+        ctxt.state.samples_passed_query = self.id;
+        self.ended = false;
+    }
+
+    /// Stops counting the samples.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if this query is not active.
+    ///
+    pub fn end(&mut self) {
+        let mut ctxt = self.context.make_current();
+
+        if ctxt.state.samples_passed_query != self.id {
+            panic!("The SamplesPassedQuery is not active");
+        }
+
+        unsafe { ctxt.gl.EndQuery(gl::SAMPLES_PASSED); }
+        ctxt.state.samples_passed_query = 0;
+        self.ended = true;
+    }
+
+    /// Returns the number of samples that passed, or `None` if the result is not available
+    /// yet. This function never blocks.
+    pub fn get(&self) -> Option<u32> {
+        if !self.ended {
+            return None;
+        }
+
+        let ctxt = self.context.make_current();
+
+        let available = unsafe {
+            let mut value = mem::uninitialized();
+            ctxt.gl.GetQueryObjectiv(self.id, gl::QUERY_RESULT_AVAILABLE, &mut value);
+            value != 0
+        };
+
+        if available {
+            Some(self.get_blocking())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of samples that passed. Blocks until the result is available.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `end` has never been called.
+    ///
+    pub fn get_blocking(&self) -> u32 {
+        assert!(self.ended, "The SamplesPassedQuery has never been ended");
+
+        let ctxt = self.context.make_current();
+
+        unsafe {
+            let mut value = mem::uninitialized();
+            ctxt.gl.GetQueryObjectuiv(self.id, gl::QUERY_RESULT, &mut value);
+            value
+        }
+    }
+}
+
+impl GlObject for SamplesPassedQuery {
+    type Id = gl::types::GLuint;
+    fn get_id(&self) -> gl::types::GLuint {
+        self.id
+    }
+}
+
+impl fmt::Debug for SamplesPassedQuery {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "SamplesPassedQuery {{ id: {} }}", self.id)
+    }
+}
+
+impl QueryExt for SamplesPassedQuery {
+    fn is_ended(&self) -> bool {
+        self.ended
+    }
+}
+
+impl PartialEq for SamplesPassedQuery {
+    fn eq(&self, other: &SamplesPassedQuery) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Drop for SamplesPassedQuery {
+    fn drop(&mut self) {
+        let mut ctxt = self.context.make_current();
+
+        unsafe {
+            if ctxt.state.samples_passed_query == self.id {
+                ctxt.gl.EndQuery(gl::SAMPLES_PASSED);
+                ctxt.state.samples_passed_query = 0;
+            }
+
+            ctxt.gl.DeleteQueries(1, [self.id].as_ptr());
+        }
+    }
+}
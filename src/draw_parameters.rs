@@ -2,8 +2,12 @@ use gl;
 use context::Context;
 use version::Version;
 use version::Api;
+use debug::SamplesPassedQuery;
 
+use ContextExt;
 use DrawError;
+use GlObject;
+use QueryExt;
 use Rect;
 use ToGlEnum;
 
@@ -82,6 +86,75 @@ pub enum BlendingFunction {
         /// The factor to apply to the destination pixel.
         destination: LinearBlendingFactor,
     },
+
+    /// Uses one of the advanced blending equations of `GL_KHR_blend_equation_advanced`.
+    ///
+    /// The fragment shader must declare that it supports the equation with a layout
+    /// qualifier, for example `layout(blend_support_multiply) out;`, otherwise the result
+    /// is undefined.
+    ///
+    /// Only one color output is allowed when using advanced blending.
+    Advanced(AdvancedBlendingEquation),
+}
+
+/// Advanced blending equations, as found in image editing programs.
+///
+/// Requires the `GL_KHR_blend_equation_advanced` extension. All the equations work on
+/// premultiplied colors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdvancedBlendingEquation {
+    /// The source and destination colors are multiplied.
+    Multiply,
+    /// The inverse of the source and destination colors are multiplied, then inverted.
+    Screen,
+    /// `Multiply` or `Screen` depending on the destination color.
+    Overlay,
+    /// The minimum of the source and destination colors.
+    Darken,
+    /// The maximum of the source and destination colors.
+    Lighten,
+    /// Brightens the destination color to reflect the source color.
+    ColorDodge,
+    /// Darkens the destination color to reflect the source color.
+    ColorBurn,
+    /// `Multiply` or `Screen` depending on the source color.
+    HardLight,
+    /// Darkens or lightens depending on the source color.
+    SoftLight,
+    /// The absolute value of the difference between the source and destination colors.
+    Difference,
+    /// Similar to `Difference`, but with a lower contrast.
+    Exclusion,
+    /// Hue of the source color with the saturation and luminosity of the destination.
+    HslHue,
+    /// Saturation of the source color with the hue and luminosity of the destination.
+    HslSaturation,
+    /// Hue and saturation of the source color with the luminosity of the destination.
+    HslColor,
+    /// Luminosity of the source color with the hue and saturation of the destination.
+    HslLuminosity,
+}
+
+impl ToGlEnum for AdvancedBlendingEquation {
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            AdvancedBlendingEquation::Multiply => gl::MULTIPLY_KHR,
+            AdvancedBlendingEquation::Screen => gl::SCREEN_KHR,
+            AdvancedBlendingEquation::Overlay => gl::OVERLAY_KHR,
+            AdvancedBlendingEquation::Darken => gl::DARKEN_KHR,
+            AdvancedBlendingEquation::Lighten => gl::LIGHTEN_KHR,
+            AdvancedBlendingEquation::ColorDodge => gl::COLORDODGE_KHR,
+            AdvancedBlendingEquation::ColorBurn => gl::COLORBURN_KHR,
+            AdvancedBlendingEquation::HardLight => gl::HARDLIGHT_KHR,
+            AdvancedBlendingEquation::SoftLight => gl::SOFTLIGHT_KHR,
+            AdvancedBlendingEquation::Difference => gl::DIFFERENCE_KHR,
+            AdvancedBlendingEquation::Exclusion => gl::EXCLUSION_KHR,
+            AdvancedBlendingEquation::HslHue => gl::HSL_HUE_KHR,
+            AdvancedBlendingEquation::HslSaturation => gl::HSL_SATURATION_KHR,
+            AdvancedBlendingEquation::HslColor => gl::HSL_COLOR_KHR,
+            AdvancedBlendingEquation::HslLuminosity => gl::HSL_LUMINOSITY_KHR,
+        }
+    }
 }
 
 /// Indicates which value to multiply each component with.
@@ -185,6 +258,16 @@ impl ToGlEnum for LinearBlendingFactor {
 /// By doing so you can use backface culling to discard all the triangles that are not
 /// facing the screen, and increase your framerate.
 ///
+/// # Winding and projections
+///
+/// The winding is determined after the vertices have been projected, in window coordinates.
+/// A projection or a model matrix that mirrors the scene, for example a matrix with a
+/// negative determinant or a projection designed for the other handedness, reverses the
+/// winding of every triangle.
+///
+/// If your models look inside-out once culling is enabled, switch between
+/// `CullClockWise` and `CullCounterClockWise` instead of modifying the models.
+///
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BackfaceCullingMode {
     /// All triangles are always drawn.
@@ -226,7 +309,8 @@ pub enum DepthTest {
 
     /// Always replace the target pixel.
     ///
-    /// This is the default mode.
+    /// This is the default mode. If `depth_write` is `true`, the depth value of the source
+    /// is still written to the depth buffer.
     Overwrite,
 
     /// Replace if the z-value of the source is equal to the destination.
@@ -320,6 +404,16 @@ pub enum StencilTest {
     IfNotEqual { mask: u32 },
 }
 
+impl StencilTest {
+    /// Returns true if the test requires a stencil buffer to be used.
+    pub fn requires_stencil_buffer(&self) -> bool {
+        match *self {
+            StencilTest::AlwaysPass => false,
+            _ => true,
+        }
+    }
+}
+
 /// Specificies which operation the GPU will do depending on the result of the stencil test.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum StencilOperation {
@@ -413,6 +507,57 @@ impl ToGlEnum for PolygonMode {
     }
 }
 
+/// Specifies a hint for the smoothing.
+///
+/// Note that this is just a hint and the driver may disregard it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Smooth {
+    /// The most efficient option should be chosen.
+    Fastest,
+
+    /// The most correct, or highest quality, option should be chosen.
+    Nicest,
+
+    /// No preference.
+    DontCare,
+}
+
+impl ToGlEnum for Smooth {
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            Smooth::Fastest => gl::FASTEST,
+            Smooth::Nicest => gl::NICEST,
+            Smooth::DontCare => gl::DONT_CARE,
+        }
+    }
+}
+
+/// Depth offset applied to the primitives, with `glPolygonOffset`.
+///
+/// The offset added to the depth of each fragment is `factor * DZ + units * r`, where `DZ`
+/// is the depth slope of the polygon and `r` the smallest value that produces a resolvable
+/// difference in the depth buffer. Positive values move the primitives away from the camera.
+///
+/// The offset is only applied to the primitives that are rasterized in one of the enabled
+/// modes (see `PolygonMode`). By default nothing is enabled.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PolygonOffset {
+    /// Multiplied by the depth slope of the polygon.
+    pub factor: f32,
+
+    /// Multiplied by the smallest resolvable difference in the depth buffer.
+    pub units: f32,
+
+    /// Whether the offset applies to polygons rasterized as `PolygonMode::Fill`.
+    pub fill: bool,
+
+    /// Whether the offset applies to polygons rasterized as `PolygonMode::Line`.
+    pub line: bool,
+
+    /// Whether the offset applies to polygons rasterized as `PolygonMode::Point`.
+    pub point: bool,
+}
+
 /// Represents the parameters to use when drawing.
 ///
 /// Example:
@@ -426,7 +571,7 @@ impl ToGlEnum for PolygonMode {
 /// ```
 ///
 #[derive(Clone, Copy, Debug, PartialEq)]
-pub struct DrawParameters {
+pub struct DrawParameters<'a> {
     /// The function that the GPU will use to determine whether to write over an existing pixel
     /// on the target. Don't forget to set `depth_write` appropriately if you use a depth test.
     ///
@@ -458,6 +603,25 @@ pub struct DrawParameters {
     /// It is possible for the "near" value to be greater than the "far" value.
     pub depth_range: (f32, f32),
 
+    /// If `true`, the primitives are not clipped by the near and far planes. Instead the
+    /// depth values that are out of range are clamped to the depth range. The default value
+    /// is `false`.
+    ///
+    /// This is useful for example when rendering shadow casters that are behind the light's
+    /// near plane, as they would otherwise be clipped.
+    ///
+    /// Drawing will return `DepthClampNotSupported` if the backend doesn't support OpenGL 3.2
+    /// or `GL_ARB_depth_clamp`.
+    pub depth_clamp: bool,
+
+    /// Whether the red, green, blue and alpha components are written to the color
+    /// attachments. The default value is `(true, true, true, true)`.
+    ///
+    /// Disabling all the components is useful for depth pre-passes or passes that only
+    /// write to the stencil buffer. The mask applies to all the color attachments of the
+    /// target.
+    pub color_mask: (bool, bool, bool, bool),
+
     /// A comparaison against the existing value in the stencil buffer.
     ///
     /// Only relevant for faces that are clockwise on the target surface. Other faces, points and
@@ -565,19 +729,32 @@ pub struct DrawParameters {
     /// The function that the GPU will use to merge the existing pixel with the pixel that is
     /// being written.
     ///
-    /// `None` means "don't care" (usually when you know that the alpha is always 1).
+    /// `None` disables blending, which is the same as `Some(BlendingFunction::AlwaysReplace)`.
     pub blending_function: Option<BlendingFunction>,
 
     /// Width in pixels of the lines to draw when drawing lines.
     ///
+    /// The value is clamped to the `line_width_range` of the context's capabilities.
+    ///
     /// `None` means "don't care". Use this when you don't draw lines.
     pub line_width: Option<f32>,
 
-    /// Diameter in pixels of the points to draw when drawing points. 
+    /// Diameter in pixels of the points to draw when drawing points.
     ///
     /// `None` means "don't care". Use this when you don't draw points.
+    ///
+    /// Ignored if `program_point_size` is `true`. OpenGL ES doesn't support this parameter
+    /// and always uses `gl_PointSize`.
     pub point_size: Option<f32>,
 
+    /// If `true`, the size of the points is determined by the value written to `gl_PointSize`
+    /// in the vertex, tessellation or geometry shader. The default value is `false`.
+    ///
+    /// Drawing will return `ProgramPointSizeNotSupported` if the backend doesn't support
+    /// OpenGL 3.2, `GL_ARB_geometry_shader4` or `GL_EXT_geometry_shader4`. OpenGL ES always
+    /// uses `gl_PointSize`, so this parameter has no effect there.
+    pub program_point_size: bool,
+
     /// Whether or not the GPU should filter out some faces.
     ///
     /// After the vertex shader stage, the GPU will try to remove the faces that aren't facing
@@ -589,20 +766,85 @@ pub struct DrawParameters {
     /// How to render polygons. The default value is `Fill`.
     ///
     /// See the documentation of `PolygonMode` for more infos.
+    ///
+    /// OpenGL ES only supports `Fill`. Drawing will return `PolygonModeNotSupported` if you
+    /// use another mode in this situation.
     pub polygon_mode: PolygonMode,
 
+    /// Offsets the depth of the polygons. Useful to avoid z-fighting with decals that are
+    /// coplanar with the surface below them, or to reduce shadow acne when rendering shadow
+    /// maps.
+    ///
+    /// OpenGL ES only supports offsetting filled polygons. Drawing will return
+    /// `PolygonOffsetNotSupported` if `line` or `point` is enabled there.
+    pub polygon_offset: PolygonOffset,
+
+    /// If specified, enables `GL_LINE_SMOOTH` and `GL_POLYGON_SMOOTH` with the given hint.
+    /// Default value is `None`.
+    ///
+    /// Smoothing works by modifying the alpha value of the pixels at the edges of lines and
+    /// polygons, so you must enable blending (for example with
+    /// `Addition { source: SourceAlpha, destination: OneMinusSourceAlpha }`) in order to see
+    /// its effect.
+    ///
+    /// Smoothing is not available in core profile contexts and in OpenGL ES. Drawing will
+    /// return `SmoothingNotSupported` in this situation.
+    pub smooth: Option<Smooth>,
+
     /// Whether multisample antialiasing (MSAA) should be used. Default value is `true`.
     ///
     /// Note that you will need to set the appropriate option when creating the window.
     /// The recommended way to do is to leave this to `true`, and adjust the option when
     /// creating the window.
+    ///
+    /// This has no effect if the target is not multisampled, which is the case of the default
+    /// framebuffer created without multisampling or of non-multisample textures.
     pub multisampling: bool,
 
+    /// If `true`, the alpha value returned by the fragment shader is used to compute which
+    /// samples of each pixel are written. Default value is `false`.
+    ///
+    /// An alpha of `0.0` writes no sample and an alpha of `1.0` writes all of them. This gives
+    /// order-independent smooth edges to alpha-tested geometry like foliage, without
+    /// requiring blending.
+    ///
+    /// Like `sample_mask`, this has no effect unless the target is multisampled and
+    /// `multisampling` is `true`.
+    pub alpha_to_coverage: bool,
+
+    /// If specified, enables `GL_SAMPLE_MASK` and uses this value as the sample mask. Default
+    /// value is `None`.
+    ///
+    /// Bit `n` of the mask corresponds to sample `n` of each pixel. Samples whose bit is `0`
+    /// are not written. This has no effect unless the target is multisampled and
+    /// `multisampling` is `true`.
+    ///
+    /// The mask is combined with the coverage computed from alpha-to-coverage with a logical
+    /// "and", so a sample is only written if both allow it.
+    ///
+    /// Drawing will return `SampleMaskNotSupported` if the backend doesn't support OpenGL 3.2
+    /// or `GL_ARB_texture_multisample`.
+    pub sample_mask: Option<u32>,
+
     /// Whether dithering is activated. Default value is `true`.
     ///
     /// Dithering will smoothen the transition between colors in your color buffer.
     pub dithering: bool,
 
+    /// Whether the colors returned by the fragment shader are considered to be linear and
+    /// converted to sRGB when written to an sRGB attachment. The default value is `true`.
+    ///
+    /// This enables `GL_FRAMEBUFFER_SRGB`. The conversion only happens for attachments that
+    /// are in sRGB, like a `SrgbTexture2d` or the default framebuffer if
+    /// `is_default_framebuffer_srgb` returns true. Other attachments are not affected.
+    ///
+    /// If your fragment shader already outputs sRGB values, set this to `false` when drawing
+    /// to an sRGB attachment, otherwise the colors are encoded twice and look too bright.
+    ///
+    /// This has no effect if the backend supports neither OpenGL 3.0,
+    /// `GL_ARB_framebuffer_sRGB` nor `GL_EXT_framebuffer_sRGB`.
+    pub srgb: bool,
+
     /// The viewport to use when drawing.
     ///
     /// The X and Y positions of your vertices are mapped to the viewport so that `(-1, -1)`
@@ -631,14 +873,56 @@ pub struct DrawParameters {
     /// This parameter may seem pointless, but it can be useful when you use transform
     /// feedback or if you just use your shaders to write to a buffer.
     pub draw_primitives: bool,
+
+    /// Value added to each index before fetching the vertex when drawing with indices. The
+    /// default value is `0`.
+    ///
+    /// This allows you to store multiple meshes in the same vertex buffer and to draw them
+    /// with indices that are relative to the first vertex of each mesh. This parameter is
+    /// ignored when drawing with `NoIndices`.
+    ///
+    /// Drawing will return `BaseVertexNotSupported` if the value is not `0` and the backend
+    /// doesn't support this feature.
+    pub base_vertex: i32,
+
+    /// If specified, each time the GPU encounters this index it ends the current strip or fan
+    /// and starts a new one. The default value is `None`.
+    ///
+    /// The value must fit in the type of the indices. Drawing returns
+    /// `InvalidPrimitiveRestartIndex` if that is not the case, and
+    /// `PrimitiveRestartNotSupported` if the backend doesn't support this value. See the
+    /// documentation of the `index` module for more infos.
+    pub primitive_restart_index: Option<u32>,
+
+    /// If `true`, the vertex formats of the vertices sources are checked against the
+    /// attributes of the program before drawing. The default value is `true`.
+    ///
+    /// Drawing returns `AttributeMissing` if the program uses an attribute that none of the
+    /// vertices sources provide, and `AttributeTypeMismatch` if the type of an attribute
+    /// doesn't match. Disabling the check saves a little CPU time, but a mismatch then
+    /// produces undefined results instead of an error.
+    pub validate_attributes: bool,
+
+    /// If set, the draw command is only executed if the query reported that at least one
+    /// sample passed the depth and stencil tests. The default value is `None`.
+    ///
+    /// The backend waits for the result of the query, but this happens entirely on the
+    /// GPU and doesn't block the CPU. This allows you to draw a cheap bounding volume
+    /// with a `SamplesPassedQuery` active, then skip drawing the real object if its
+    /// bounding volume was entirely hidden.
+    ///
+    /// Requires OpenGL 3.0 or `GL_NV_conditional_render`.
+    pub condition: Option<&'a SamplesPassedQuery>,
 }
 
-impl Default for DrawParameters {
-    fn default() -> DrawParameters {
+impl<'a> Default for DrawParameters<'a> {
+    fn default() -> DrawParameters<'a> {
         DrawParameters {
             depth_test: DepthTest::Overwrite,
             depth_write: false,
             depth_range: (0.0, 1.0),
+            depth_clamp: false,
+            color_mask: (true, true, true, true),
             stencil_test_clockwise: StencilTest::AlwaysPass,
             stencil_reference_value_clockwise: 0,
             stencil_write_mask_clockwise: 0xffffffff,
@@ -654,17 +938,39 @@ impl Default for DrawParameters {
             blending_function: Some(BlendingFunction::AlwaysReplace),
             line_width: None,
             point_size: None,
+            program_point_size: false,
             backface_culling: BackfaceCullingMode::CullingDisabled,
             polygon_mode: PolygonMode::Fill,
+            polygon_offset: Default::default(),
+            smooth: None,
             multisampling: true,
+            alpha_to_coverage: false,
+            sample_mask: None,
             dithering: true,
+            srgb: true,
             viewport: None,
             scissor: None,
             draw_primitives: true,
+            base_vertex: 0,
+            primitive_restart_index: None,
+            validate_attributes: true,
+            condition: None,
         }
     }
 }
 
+/// Returns true if the parameters read from or write to the stencil buffer.
+pub fn requires_stencil_buffer(params: &DrawParameters) -> bool {
+    params.stencil_test_clockwise.requires_stencil_buffer() ||
+    params.stencil_test_counter_clockwise.requires_stencil_buffer() ||
+    params.stencil_fail_operation_clockwise != StencilOperation::Keep ||
+    params.stencil_pass_depth_fail_operation_clockwise != StencilOperation::Keep ||
+    params.stencil_depth_pass_operation_clockwise != StencilOperation::Keep ||
+    params.stencil_fail_operation_counter_clockwise != StencilOperation::Keep ||
+    params.stencil_pass_depth_fail_operation_counter_clockwise != StencilOperation::Keep ||
+    params.stencil_depth_pass_operation_counter_clockwise != StencilOperation::Keep
+}
+
 /// Checks parameters and panics if something is wrong.
 pub fn validate(context: &Context, params: &DrawParameters) -> Result<(), DrawError> {
     if params.depth_range.0 < 0.0 || params.depth_range.0 > 1.0 ||
@@ -673,11 +979,78 @@ pub fn validate(context: &Context, params: &DrawParameters) -> Result<(), DrawEr
         return Err(DrawError::InvalidDepthRange);
     }
 
+    if params.depth_clamp && !(context.get_version() >= &Version(Api::Gl, 3, 2)) &&
+       !context.get_extensions().gl_arb_depth_clamp
+    {
+        return Err(DrawError::DepthClampNotSupported);
+    }
+
+    if params.program_point_size && context.get_version().0 == Api::Gl &&
+       !(context.get_version() >= &Version(Api::Gl, 3, 2)) &&
+       !context.get_extensions().gl_arb_geometry_shader4 &&
+       !context.get_extensions().gl_ext_geometry_shader4
+    {
+        return Err(DrawError::ProgramPointSizeNotSupported);
+    }
+
     if !params.draw_primitives && context.get_version() < &Version(Api::Gl, 3, 0) &&
         !context.get_extensions().gl_ext_transform_feedback
     {
         return Err(DrawError::TransformFeedbackNotSupported);
     }
 
+    if params.sample_mask.is_some() && context.get_version() < &Version(Api::Gl, 3, 2) &&
+        !context.get_extensions().gl_arb_texture_multisample
+    {
+        return Err(DrawError::SampleMaskNotSupported);
+    }
+
+    if params.smooth.is_some() && (context.capabilities().core_profile ||
+                                   context.get_version().0 == Api::GlEs)
+    {
+        return Err(DrawError::SmoothingNotSupported);
+    }
+
+    if params.polygon_mode != PolygonMode::Fill && context.get_version().0 == Api::GlEs {
+        return Err(DrawError::PolygonModeNotSupported);
+    }
+
+    if (params.polygon_offset.line || params.polygon_offset.point) &&
+       context.get_version().0 == Api::GlEs
+    {
+        return Err(DrawError::PolygonOffsetNotSupported);
+    }
+
+    if params.base_vertex != 0 && !(context.get_version() >= &Version(Api::Gl, 3, 2)) &&
+       !(context.get_version() >= &Version(Api::GlEs, 3, 2)) &&
+       !context.get_extensions().gl_arb_draw_elements_base_vertex
+    {
+        return Err(DrawError::BaseVertexNotSupported);
+    }
+
+    if let Some(BlendingFunction::Advanced(_)) = params.blending_function {
+        if !context.get_extensions().gl_khr_blend_equation_advanced {
+            return Err(DrawError::AdvancedBlendingNotSupported);
+        }
+    }
+
+    if params.condition.is_some() && !(context.get_version() >= &Version(Api::Gl, 3, 0)) &&
+       !context.get_extensions().gl_nv_conditional_render
+    {
+        return Err(DrawError::ConditionalRenderingNotSupported);
+    }
+
+    if let Some(query) = params.condition {
+        let ctxt = context.make_current();
+
+        if ctxt.state.samples_passed_query == query.get_id() {
+            return Err(DrawError::ConditionQueryActive);
+        }
+
+        if !query.is_ended() {
+            return Err(DrawError::ConditionQueryNeverBegun);
+        }
+    }
+
     Ok(())
 }
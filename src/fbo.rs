@@ -57,7 +57,7 @@ use std::cell::RefCell;
 use GlObject;
 
 use gl;
-use context::CommandContext;
+use context::{Context, CommandContext};
 use version::Version;
 use version::Api;
 
@@ -82,7 +82,13 @@ pub enum Attachment {
         bind_point: gl::types::GLenum,      // must be GL_TEXTURE_3D, GL_TEXTURE_2D_ARRAY, etc.
         id: gl::types::GLuint,
         level: u32,
-        layer: u32,
+        layer: u32,     // ignored for non-layered textures, face index for cubemaps
+    },
+    /// All the layers of a 3D, array or cubemap texture, for layered rendering.
+    LayeredTexture {
+        bind_point: gl::types::GLenum,
+        id: gl::types::GLuint,
+        level: u32,
     },
     RenderBuffer(gl::types::GLuint),
 }
@@ -115,6 +121,7 @@ impl FramebuffersContainer {
         self.purge_if(|a| {
             match a {
                 &Attachment::Texture { id, .. } if id == texture => true,
+                &Attachment::LayeredTexture { id, .. } if id == texture => true,
                 _ => false 
             }
         }, ctxt);
@@ -303,7 +310,8 @@ impl FrameBufferObject {
                                     raw_attachments.as_ptr());
 
             } else if ctxt.version >= &Version(Api::GlEs, 2, 0) {
-                assert_eq!(raw_attachments, &[gl::COLOR_ATTACHMENT0]);
+                assert!(raw_attachments.len() == 0 ||
+                        raw_attachments == &[gl::COLOR_ATTACHMENT0]);
 
             } else {
                 unimplemented!();       // FIXME: use an extension
@@ -416,18 +424,57 @@ pub fn bind_framebuffer(ctxt: &mut CommandContext, fbo_id: gl::types::GLuint,
 unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
                  id: gl::types::GLuint, attachment: Attachment)
 {
+    // the faces of a cubemap are attached with the 2D functions, `layer` being the face index
+    if let Attachment::Texture { bind_point: gl::TEXTURE_CUBE_MAP, id: tex_id, level, layer } =
+                                                                                    attachment
+    {
+        let face = gl::TEXTURE_CUBE_MAP_POSITIVE_X + layer;
+
+        if ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.extensions.gl_arb_direct_state_access {
+            ctxt.gl.NamedFramebufferTextureLayer(id, slot, tex_id, level as gl::types::GLint,
+                                                 layer as gl::types::GLint);
+
+        } else if ctxt.extensions.gl_ext_direct_state_access {
+            ctxt.gl.NamedFramebufferTexture2DEXT(id, slot, face, tex_id,
+                                                 level as gl::types::GLint);
+
+        } else if ctxt.version >= &Version(Api::Gl, 3, 0) {
+            bind_framebuffer(ctxt, id, true, false);
+            ctxt.gl.FramebufferTexture2D(gl::DRAW_FRAMEBUFFER, slot, face, tex_id,
+                                         level as gl::types::GLint);
+
+        } else if ctxt.version >= &Version(Api::GlEs, 2, 0) {
+            bind_framebuffer(ctxt, id, true, true);
+            ctxt.gl.FramebufferTexture2D(gl::FRAMEBUFFER, slot, face, tex_id,
+                                         level as gl::types::GLint);
+
+        } else if ctxt.extensions.gl_ext_framebuffer_object {
+            bind_framebuffer(ctxt, id, true, true);
+            ctxt.gl.FramebufferTexture2DEXT(gl::FRAMEBUFFER_EXT, slot, face, tex_id,
+                                            level as gl::types::GLint);
+
+        } else {
+            unreachable!();
+        }
+
+        return;
+    }
+
     if ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.extensions.gl_arb_direct_state_access {
         match attachment {
-            Attachment::Texture { id: tex_id, level, layer, .. } => {
-                if layer == 0 {
-                    ctxt.gl.NamedFramebufferTexture(id, slot, tex_id,
-                                                    level as gl::types::GLint);
-                } else {
+            Attachment::Texture { bind_point, id: tex_id, level, layer } => {
+                if is_layered_bind_point(bind_point) {
                     ctxt.gl.NamedFramebufferTextureLayer(id, slot, tex_id,
                                                          level as gl::types::GLint,
                                                          layer as gl::types::GLint);
+                } else {
+                    ctxt.gl.NamedFramebufferTexture(id, slot, tex_id,
+                                                    level as gl::types::GLint);
                 }
             },
+            Attachment::LayeredTexture { id: tex_id, level, .. } => {
+                ctxt.gl.NamedFramebufferTexture(id, slot, tex_id, level as gl::types::GLint);
+            },
             Attachment::RenderBuffer(buf_id) => {
                 ctxt.gl.NamedFramebufferRenderbuffer(id, slot, gl::RENDERBUFFER,
                                                      buf_id);
@@ -438,16 +485,19 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
               ctxt.extensions.gl_ext_geometry_shader4
     {
         match attachment {
-            Attachment::Texture { id: tex_id, level, layer, .. } => {
-                if layer == 0 {
-                    ctxt.gl.NamedFramebufferTextureEXT(id, slot, tex_id,
-                                                       level as gl::types::GLint);
-                } else {
+            Attachment::Texture { bind_point, id: tex_id, level, layer } => {
+                if is_layered_bind_point(bind_point) {
                     ctxt.gl.NamedFramebufferTextureLayerEXT(id, slot, tex_id,
                                                             level as gl::types::GLint,
                                                             layer as gl::types::GLint);
+                } else {
+                    ctxt.gl.NamedFramebufferTextureEXT(id, slot, tex_id,
+                                                       level as gl::types::GLint);
                 }
             },
+            Attachment::LayeredTexture { id: tex_id, level, .. } => {
+                ctxt.gl.NamedFramebufferTextureEXT(id, slot, tex_id, level as gl::types::GLint);
+            },
             Attachment::RenderBuffer(buf_id) => {
                 ctxt.gl.NamedFramebufferRenderbufferEXT(id, slot, gl::RENDERBUFFER,
                                                         buf_id);
@@ -458,17 +508,21 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
         bind_framebuffer(ctxt, id, true, false);
 
         match attachment {
-            Attachment::Texture { id: tex_id, level, layer, .. } => {
-                if layer == 0 {
-                    ctxt.gl.FramebufferTexture(gl::DRAW_FRAMEBUFFER,
-                                               slot, tex_id, level as gl::types::GLint);
-                } else {
+            Attachment::Texture { bind_point, id: tex_id, level, layer } => {
+                if is_layered_bind_point(bind_point) {
                     ctxt.gl.FramebufferTextureLayer(gl::DRAW_FRAMEBUFFER,
                                                     slot, tex_id,
                                                     level as gl::types::GLint,
                                                     layer as gl::types::GLint);
+                } else {
+                    ctxt.gl.FramebufferTexture(gl::DRAW_FRAMEBUFFER,
+                                               slot, tex_id, level as gl::types::GLint);
                 }
             },
+            Attachment::LayeredTexture { id: tex_id, level, .. } => {
+                ctxt.gl.FramebufferTexture(gl::DRAW_FRAMEBUFFER, slot, tex_id,
+                                           level as gl::types::GLint);
+            },
             Attachment::RenderBuffer(buf_id) => {
                 ctxt.gl.FramebufferRenderbuffer(gl::DRAW_FRAMEBUFFER, slot,
                                                 gl::RENDERBUFFER, buf_id);
@@ -487,13 +541,14 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
                                                      slot, bind_point, tex_id,
                                                      level as gl::types::GLint);
                     },
-                    gl::TEXTURE_2D | gl::TEXTURE_2D_MULTISAMPLE | gl::TEXTURE_1D_ARRAY => {
+                    gl::TEXTURE_2D | gl::TEXTURE_2D_MULTISAMPLE => {
                         assert!(layer == 0);
                         ctxt.gl.FramebufferTexture2D(gl::DRAW_FRAMEBUFFER,
                                                      slot, bind_point, tex_id,
                                                      level as gl::types::GLint);
                     },
-                    gl::TEXTURE_3D | gl::TEXTURE_2D_ARRAY | gl::TEXTURE_2D_MULTISAMPLE_ARRAY => {
+                    gl::TEXTURE_3D | gl::TEXTURE_1D_ARRAY | gl::TEXTURE_2D_ARRAY |
+                    gl::TEXTURE_2D_MULTISAMPLE_ARRAY => {
                         ctxt.gl.FramebufferTextureLayer(gl::DRAW_FRAMEBUFFER,
                                                         slot, tex_id,
                                                         level as gl::types::GLint,
@@ -502,6 +557,10 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
                     _ => unreachable!()
                 }
             },
+            Attachment::LayeredTexture { .. } => {
+                // checked by `is_layered_attachment_supported` when the framebuffer is built
+                unreachable!();
+            },
             Attachment::RenderBuffer(buf_id) => {
                 ctxt.gl.FramebufferRenderbuffer(gl::DRAW_FRAMEBUFFER, slot,
                                                 gl::RENDERBUFFER, buf_id);
@@ -523,6 +582,10 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
                     _ => unreachable!()
                 }
             },
+            Attachment::LayeredTexture { .. } => {
+                // checked by `is_layered_attachment_supported` when the framebuffer is built
+                unreachable!();
+            },
             Attachment::RenderBuffer(buf_id) => {
                 ctxt.gl.FramebufferRenderbuffer(gl::DRAW_FRAMEBUFFER, slot,
                                                 gl::RENDERBUFFER, buf_id);
@@ -556,6 +619,10 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
                     _ => unreachable!()
                 }
             },
+            Attachment::LayeredTexture { .. } => {
+                // checked by `is_layered_attachment_supported` when the framebuffer is built
+                unreachable!();
+            },
             Attachment::RenderBuffer(buf_id) => {
                 ctxt.gl.FramebufferRenderbufferEXT(gl::DRAW_FRAMEBUFFER, slot,
                                                    gl::RENDERBUFFER, buf_id);
@@ -566,3 +633,25 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
         unreachable!();
     }
 }
+
+/// Returns true if the backend can attach all the layers of a texture at once.
+///
+/// This must stay in sync with the branches of `attach` that handle
+/// `Attachment::LayeredTexture`.
+pub fn is_layered_attachment_supported(context: &Context) -> bool {
+    let version = context.get_version();
+    let extensions = context.get_extensions();
+
+    version >= &Version(Api::Gl, 3, 2) || extensions.gl_arb_direct_state_access ||
+    (extensions.gl_ext_direct_state_access && extensions.gl_ext_geometry_shader4)
+}
+
+/// Returns true if the texture type has multiple layers and needs `glFramebufferTextureLayer`
+/// to attach a single one of them.
+fn is_layered_bind_point(bind_point: gl::types::GLenum) -> bool {
+    match bind_point {
+        gl::TEXTURE_1D_ARRAY | gl::TEXTURE_2D_ARRAY | gl::TEXTURE_2D_MULTISAMPLE_ARRAY |
+        gl::TEXTURE_3D => true,
+        _ => false,
+    }
+}
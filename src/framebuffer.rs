@@ -11,6 +11,18 @@ let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture)
 // framebuffer.draw(...);    // draws over `texture`
 ```
 
+A single face of a cubemap can be attached by building the attachment manually.
+
+```no_run
+# let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+# let cubemap: glium::texture::CubeMap = unsafe { ::std::mem::uninitialized() };
+use glium::framebuffer::ColorAttachment;
+use glium::texture::CubeLayer;
+
+let face = ColorAttachment::CubeMap(cubemap.main_level(), CubeLayer::PositiveX);
+let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &face);
+```
+
 If, however, your shader wants to write to multiple color buffers at once, you must use
 a `MultiOutputFrameBuffer`.
 
@@ -33,14 +45,24 @@ let framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display, outp
 //     }
 ```
 
-**Note**: depth-stencil attachments are not yet implemented.
+Depth, stencil and depth-stencil buffers can be attached with the `with_*` constructors.
+
+```no_run
+# let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+# let texture: glium::texture::Texture2d = unsafe { ::std::mem::uninitialized() };
+# let depthstencil: glium::texture::DepthStencilTexture2d = unsafe { ::std::mem::uninitialized() };
+let framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_stencil_buffer(&display,
+                                                                   &texture, &depthstencil);
+```
 
 */
+use std::error::Error;
+use std::fmt;
 use std::marker::PhantomData;
 use std::rc::Rc;
 
-use texture::Texture;
-use texture::Texture2d;
+use texture::{Texture, PixelValue, Texture2dDataSink};
+use texture::{Texture2d, Texture2dArray, Texture3d, CubeMap};
 use texture::{Texture1dMipmap, SrgbTexture1dMipmap, DepthTexture1dMipmap, StencilTexture1dMipmap, DepthStencilTexture1dMipmap};
 use texture::{Texture2dMipmap, SrgbTexture2dMipmap, DepthTexture2dMipmap, StencilTexture2dMipmap, DepthStencilTexture2dMipmap};
 use texture::{Texture2dMultisampleMipmap, SrgbTexture2dMultisampleMipmap, DepthTexture2dMultisampleMipmap, StencilTexture2dMultisampleMipmap, DepthStencilTexture2dMultisampleMipmap};
@@ -48,14 +70,19 @@ use texture::{Texture3dMipmap, SrgbTexture3dMipmap, DepthTexture3dMipmap, Stenci
 use texture::{Texture1dArrayMipmap, SrgbTexture1dArrayMipmap, DepthTexture1dArrayMipmap, StencilTexture1dArrayMipmap, DepthStencilTexture1dArrayMipmap};
 use texture::{Texture2dArrayMipmap, SrgbTexture2dArrayMipmap, DepthTexture2dArrayMipmap, StencilTexture2dArrayMipmap, DepthStencilTexture2dArrayMipmap};
 use texture::{Texture2dMultisampleArrayMipmap, SrgbTexture2dMultisampleArrayMipmap, DepthTexture2dMultisampleArrayMipmap, StencilTexture2dMultisampleArrayMipmap, DepthStencilTexture2dMultisampleArrayMipmap};
+use texture::{CubeMapMipmap, DepthCubeMapMipmap, CubeLayer};
 
 use backend::Facade;
 use context::Context;
+use ContextExt;
+use version::Api;
+use version::Version;
 
 use fbo::FramebufferAttachments;
 use FboAttachments;
 use Rect;
 use BlitTarget;
+use BlitError;
 use ToGlEnum;
 use ops;
 use uniforms;
@@ -78,10 +105,37 @@ pub struct SimpleFrameBuffer<'a> {
 impl<'a> SimpleFrameBuffer<'a> {
     /// Creates a `SimpleFrameBuffer` with a single color attachment and no depth
     /// nor stencil buffer.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the backend doesn't support the attachment. See `try_new`.
     pub fn new<F, C>(facade: &F, color: &'a C) -> SimpleFrameBuffer<'a>
                   where C: ToColorAttachment, F: Facade
     {
-        SimpleFrameBuffer::new_impl(facade, color.to_color_attachment(), None, None, None)
+        SimpleFrameBuffer::new_impl(facade, Some(color.to_color_attachment()), None, None, None)
+            .unwrap()
+    }
+
+    /// Creates a `SimpleFrameBuffer` with a single color attachment and no depth
+    /// nor stencil buffer.
+    ///
+    /// Contrary to `new`, returns an error if the backend doesn't support the attachment,
+    /// for example a `ColorAttachment::Layered` on a context that can't do layered rendering.
+    pub fn try_new<F, C>(facade: &F, color: &'a C) -> Result<SimpleFrameBuffer<'a>, ValidationError>
+                         where C: ToColorAttachment, F: Facade
+    {
+        SimpleFrameBuffer::new_impl(facade, Some(color.to_color_attachment()), None, None, None)
+    }
+
+    /// Creates a `SimpleFrameBuffer` with a depth buffer but no color attachment.
+    ///
+    /// This is typically used to render shadow maps, where only the depth of the scene is
+    /// needed.
+    pub fn depth_only<F, D>(facade: &F, depth: &'a D) -> SimpleFrameBuffer<'a>
+                            where D: ToDepthAttachment, F: Facade
+    {
+        SimpleFrameBuffer::new_impl(facade, None, Some(depth.to_depth_attachment()), None, None)
+            .unwrap()
     }
 
     /// Creates a `SimpleFrameBuffer` with a single color attachment and a depth
@@ -90,8 +144,8 @@ impl<'a> SimpleFrameBuffer<'a> {
                                       -> SimpleFrameBuffer<'a>
                                       where C: ToColorAttachment, D: ToDepthAttachment, F: Facade
     {
-        SimpleFrameBuffer::new_impl(facade, color.to_color_attachment(),
-                                    Some(depth.to_depth_attachment()), None, None)
+        SimpleFrameBuffer::new_impl(facade, Some(color.to_color_attachment()),
+                                    Some(depth.to_depth_attachment()), None, None).unwrap()
     }
 
     /// Creates a `SimpleFrameBuffer` with a single color attachment, a depth
@@ -102,9 +156,9 @@ impl<'a> SimpleFrameBuffer<'a> {
                                                            D: ToDepthAttachment,
                                                            S: ToStencilAttachment, F: Facade
     {
-        SimpleFrameBuffer::new_impl(facade, color.to_color_attachment(),
+        SimpleFrameBuffer::new_impl(facade, Some(color.to_color_attachment()),
                                     Some(depth.to_depth_attachment()),
-                                    Some(stencil.to_stencil_attachment()), None)
+                                    Some(stencil.to_stencil_attachment()), None).unwrap()
     }
 
     /// Creates a `SimpleFrameBuffer` with a single color attachment and a stencil
@@ -114,8 +168,8 @@ impl<'a> SimpleFrameBuffer<'a> {
                                         where C: ToColorAttachment, S: ToStencilAttachment,
                                               F: Facade
     {
-        SimpleFrameBuffer::new_impl(facade, color.to_color_attachment(), None,
-                                    Some(stencil.to_stencil_attachment()), None)
+        SimpleFrameBuffer::new_impl(facade, Some(color.to_color_attachment()), None,
+                                    Some(stencil.to_stencil_attachment()), None).unwrap()
     }
 
     /// Creates a `SimpleFrameBuffer` with a single color attachment and a depth-stencil buffer.
@@ -124,117 +178,542 @@ impl<'a> SimpleFrameBuffer<'a> {
                                               where C: ToColorAttachment,
                                                     D: ToDepthStencilAttachment, F: Facade
     {
-        SimpleFrameBuffer::new_impl(facade, color.to_color_attachment(), None, None,
-                                    Some(depthstencil.to_depth_stencil_attachment()))
+        SimpleFrameBuffer::new_impl(facade, Some(color.to_color_attachment()), None, None,
+                                    Some(depthstencil.to_depth_stencil_attachment())).unwrap()
     }
 
 
-    fn new_impl<F>(facade: &F, color: ColorAttachment, depth: Option<DepthAttachment>,
+    fn new_impl<F>(facade: &F, color: Option<ColorAttachment>, depth: Option<DepthAttachment>,
                    stencil: Option<StencilAttachment>, depthstencil: Option<DepthStencilAttachment>)
-                   -> SimpleFrameBuffer<'a> where F: Facade
+                   -> Result<SimpleFrameBuffer<'a>, ValidationError> where F: Facade
     {
-        // TODO: remove this
-        if depthstencil.is_some() {
-            unimplemented!();
-        }
-
-        let (dimensions, color_attachment) = match color {
-            ColorAttachment::Texture2d(tex) => {
-                let dimensions = (tex.get_texture().get_width(), tex.get_texture().get_height().unwrap());
-                let id = fbo::Attachment::Texture { id: tex.get_texture().get_id(), bind_point: gl::TEXTURE_2D, level: 0, layer: 0 };
-                (dimensions, id)
+        // the dimensions are `None` if there is no color attachment
+        let (mut dimensions, color_attachment) = match color {
+            Some(color) => {
+                let (dimensions, attachment) = SimpleFrameBuffer::color_attachment_to_fbo(color);
+                try!(check_attachment_support(facade.get_context(), &attachment));
+                (Some(dimensions), Some(attachment))
             },
-
-            ColorAttachment::Texture2dMultisample(tex) => {
-                let dimensions = (tex.get_texture().get_width(), tex.get_texture().get_height().unwrap());
-                let id = fbo::Attachment::Texture { id: tex.get_texture().get_id(), bind_point: gl::TEXTURE_2D_MULTISAMPLE, level: 0, layer: 0 };
-                (dimensions, id)
-            },
-
-            ColorAttachment::RenderBuffer(buffer) => {
-                let dimensions = buffer.get_dimensions();
-                let id = fbo::Attachment::RenderBuffer(buffer.get_id());
-                (dimensions, id)
-            },
-
-            _ => unimplemented!()
+            None => (None, None)
         };
 
         let (depth, depth_bits) = if let Some(depth) = depth {
-            match depth {
-                DepthAttachment::Texture2d(tex) => {
-                    if (tex.get_texture().get_width(), tex.get_texture().get_height().unwrap()) != dimensions {
-                        panic!("The depth attachment must have the same dimensions \
-                                as the color attachment");
-                    }
-
-                    (Some(fbo::Attachment::Texture { id: tex.get_texture().get_id(), bind_point: gl::TEXTURE_2D, level: 0, layer: 0 }), Some(32))      // FIXME: wrong number
-                },
+            let (depth_dimensions, attachment) = depth_attachment_to_fbo(depth);
 
-                DepthAttachment::RenderBuffer(buffer) => {
-                    // TODO: dimensions
+            if dimensions.is_some() && dimensions != Some(depth_dimensions) {
+                panic!("The depth attachment must have the same dimensions \
+                        as the color attachment");
+            }
 
-                    (Some(fbo::Attachment::RenderBuffer(buffer.get_id())), Some(32))      // FIXME: wrong number
-                },
+            dimensions = Some(depth_dimensions);
 
-                _ => unimplemented!()
-            }
+            let (bits, _) = get_attachment_depth_stencil_bits(facade.get_context(), &attachment);
+            (Some(attachment), Some(bits))
 
         } else {
             (None, None)
         };
 
         let (stencil, stencil_bits) = if let Some(stencil) = stencil {
-            match stencil {
-                StencilAttachment::Texture2d(tex) => {
-                    if (tex.get_texture().get_width(), tex.get_texture().get_height().unwrap()) != dimensions {
-                        panic!("The stencil attachment must have the same dimensions \
-                                as the color attachment");
-                    }
-
-                    (Some(fbo::Attachment::Texture { id: tex.get_texture().get_id(), bind_point: gl::TEXTURE_2D, level: 0, layer: 0 }), Some(8))       // FIXME: wrong number
-                },
+            let (stencil_dimensions, attachment) = stencil_attachment_to_fbo(stencil);
 
-                StencilAttachment::RenderBuffer(buffer) => {
-                    // TODO: dimensions
+            if dimensions.is_some() && dimensions != Some(stencil_dimensions) {
+                panic!("The stencil attachment must have the same dimensions \
+                        as the color attachment");
+            }
 
-                    (Some(fbo::Attachment::RenderBuffer(buffer.get_id())), Some(8))
-                },
+            dimensions = Some(stencil_dimensions);
+
+            let (_, bits) = get_attachment_depth_stencil_bits(facade.get_context(), &attachment);
+            (Some(attachment), Some(bits))
+
+        } else {
+            (None, None)
+        };
 
-                _ => unimplemented!()
+        let (depthstencil, depthstencil_bits) = if let Some(depthstencil) = depthstencil {
+            let (depthstencil_dimensions, attachment) =
+                                            depth_stencil_attachment_to_fbo(depthstencil);
+
+            if dimensions.is_some() && dimensions != Some(depthstencil_dimensions) {
+                panic!("The depth-stencil attachment must have the same dimensions \
+                        as the color attachment");
             }
 
+            dimensions = Some(depthstencil_dimensions);
+
+            let bits = get_attachment_depth_stencil_bits(facade.get_context(), &attachment);
+            (Some(attachment), Some(bits))
+
         } else {
             (None, None)
         };
 
-        SimpleFrameBuffer {
+        let (depth_bits, stencil_bits) = match depthstencil_bits {
+            Some((depth_bits, stencil_bits)) => (Some(depth_bits), Some(stencil_bits)),
+            None => (depth_bits, stencil_bits)
+        };
+
+        Ok(SimpleFrameBuffer {
             context: facade.get_context().clone(),
             attachments: FramebufferAttachments {
-                colors: vec![(0, color_attachment)],
-                depth_stencil: if let (Some(depth), Some(stencil)) = (depth, stencil) {
+                colors: color_attachment.into_iter().map(|a| (0, a)).collect(),
+                depth_stencil: if let Some(depthstencil) = depthstencil {
+                    fbo::FramebufferDepthStencilAttachments::DepthStencilAttachment(depthstencil)
+                } else if let (Some(depth), Some(stencil)) = (depth, stencil) {
                     fbo::FramebufferDepthStencilAttachments::DepthAndStencilAttachments(depth, stencil)
                 } else if let Some(depth) = depth {
                     fbo::FramebufferDepthStencilAttachments::DepthAttachment(depth)
                 } else if let Some(stencil) = stencil {
-                    fbo::FramebufferDepthStencilAttachments::DepthAttachment(stencil)
+                    fbo::FramebufferDepthStencilAttachments::StencilAttachment(stencil)
                 } else {
                     fbo::FramebufferDepthStencilAttachments::None
                 },
             },
             marker: PhantomData,
-            dimensions: dimensions,
+            dimensions: dimensions.unwrap(),
             depth_buffer_bits: depth_bits,
             stencil_buffer_bits: stencil_bits,
+        })
+    }
+
+    /// Replaces the color attachment of the framebuffer.
+    ///
+    /// The depth and stencil attachments are kept. This is cheaper than building a new
+    /// `SimpleFrameBuffer`, for example when you alternate between several textures.
+    ///
+    /// Returns an error and leaves the framebuffer untouched if the backend doesn't support
+    /// the new attachment, or if the framebuffer has a depth or stencil attachment and the new
+    /// color attachment doesn't have the same dimensions as the previous one.
+    pub fn set_color_attachment<C>(&mut self, color: &'a C) -> Result<(), ValidationError>
+                                   where C: ToColorAttachment
+    {
+        let (dimensions, color_attachment) =
+            SimpleFrameBuffer::color_attachment_to_fbo(color.to_color_attachment());
+
+        try!(check_attachment_support(&self.context, &color_attachment));
+
+        match self.attachments.depth_stencil {
+            fbo::FramebufferDepthStencilAttachments::None => (),
+            _ => {
+                if dimensions != self.dimensions {
+                    return Err(ValidationError::DimensionsMismatch);
+                }
+            }
+        }
+
+        self.attachments.colors = vec![(0, color_attachment)];
+        self.dimensions = dimensions;
+        Ok(())
+    }
+
+    /// Turns a `ColorAttachment` into its dimensions and an `fbo::Attachment`.
+    fn color_attachment_to_fbo(color: ColorAttachment) -> ((u32, u32), fbo::Attachment) {
+        match color {
+            ColorAttachment::Texture2d(tex) => {
+                let dimensions = (tex.get_texture().get_width(), tex.get_texture().get_height().unwrap());
+                let id = fbo::Attachment::Texture { id: tex.get_texture().get_id(), bind_point: gl::TEXTURE_2D, level: 0, layer: 0 };
+                (dimensions, id)
+            },
+
+            ColorAttachment::Texture2dMultisample(tex) => {
+                let dimensions = (tex.get_texture().get_width(), tex.get_texture().get_height().unwrap());
+                let id = fbo::Attachment::Texture { id: tex.get_texture().get_id(), bind_point: gl::TEXTURE_2D_MULTISAMPLE, level: 0, layer: 0 };
+                (dimensions, id)
+            },
+
+            ColorAttachment::Texture3d(tex, layer) => {
+                assert!(layer < tex.get_texture().get_depth().unwrap());
+                let dimensions = (tex.get_texture().get_width(), tex.get_texture().get_height().unwrap());
+                let id = fbo::Attachment::Texture { id: tex.get_texture().get_id(), bind_point: gl::TEXTURE_3D, level: tex.get_level(), layer: layer };
+                (dimensions, id)
+            },
+
+            ColorAttachment::SrgbTexture3d(tex, layer) => {
+                assert!(layer < tex.get_texture().get_depth().unwrap());
+                let dimensions = (tex.get_texture().get_width(), tex.get_texture().get_height().unwrap());
+                let id = fbo::Attachment::Texture { id: tex.get_texture().get_id(), bind_point: gl::TEXTURE_3D, level: tex.get_level(), layer: layer };
+                (dimensions, id)
+            },
+
+            ColorAttachment::Texture2dArray(tex) => {
+                let dimensions = (tex.get_texture().get_width(), tex.get_texture().get_height().unwrap());
+                let id = fbo::Attachment::Texture { id: tex.get_texture().get_id(), bind_point: gl::TEXTURE_2D_ARRAY, level: tex.get_level(), layer: tex.get_layer() };
+                (dimensions, id)
+            },
+
+            ColorAttachment::SrgbTexture2dArray(tex) => {
+                let dimensions = (tex.get_texture().get_width(), tex.get_texture().get_height().unwrap());
+                let id = fbo::Attachment::Texture { id: tex.get_texture().get_id(), bind_point: gl::TEXTURE_2D_ARRAY, level: tex.get_level(), layer: tex.get_layer() };
+                (dimensions, id)
+            },
+
+            ColorAttachment::CubeMap(tex, face) => {
+                let dimensions = (tex.get_texture().get_width(), tex.get_texture().get_height().unwrap());
+                let id = cube_face_attachment(tex.get_texture().get_id(), tex.get_level(), face);
+                (dimensions, id)
+            },
+
+            ColorAttachment::Layered(LayeredColorAttachment::Texture2dArray(tex)) => {
+                let dimensions = (tex.get_width(), tex.get_height().unwrap());
+                let id = fbo::Attachment::LayeredTexture { id: tex.get_id(), bind_point: gl::TEXTURE_2D_ARRAY, level: 0 };
+                (dimensions, id)
+            },
+
+            ColorAttachment::Layered(LayeredColorAttachment::Texture3d(tex)) => {
+                let dimensions = (tex.get_width(), tex.get_height().unwrap());
+                let id = fbo::Attachment::LayeredTexture { id: tex.get_id(), bind_point: gl::TEXTURE_3D, level: 0 };
+                (dimensions, id)
+            },
+
+            ColorAttachment::Layered(LayeredColorAttachment::CubeMap(tex)) => {
+                let dimensions = (tex.get_width(), tex.get_height().unwrap());
+                let id = fbo::Attachment::LayeredTexture { id: tex.get_id(), bind_point: gl::TEXTURE_CUBE_MAP, level: 0 };
+                (dimensions, id)
+            },
+
+            ColorAttachment::RenderBuffer(buffer) => {
+                let dimensions = buffer.get_dimensions();
+                let id = fbo::Attachment::RenderBuffer(buffer.get_id());
+                (dimensions, id)
+            },
+
+            ColorAttachment::Texture1d(tex) => {
+                texture_attachment(tex.get_texture(), gl::TEXTURE_1D, tex.get_level(), 0)
+            },
+
+            ColorAttachment::SrgbTexture1d(tex) => {
+                texture_attachment(tex.get_texture(), gl::TEXTURE_1D, tex.get_level(), 0)
+            },
+
+            ColorAttachment::SrgbTexture2d(tex) => {
+                texture_attachment(tex.get_texture(), gl::TEXTURE_2D, tex.get_level(), 0)
+            },
+
+            ColorAttachment::SrgbTexture2dMultisample(tex) => {
+                texture_attachment(tex.get_texture(), gl::TEXTURE_2D_MULTISAMPLE, 0, 0)
+            },
+
+            ColorAttachment::Texture1dArray(tex) => {
+                texture_attachment(tex.get_texture(), gl::TEXTURE_1D_ARRAY, tex.get_level(),
+                                   tex.get_layer())
+            },
+
+            ColorAttachment::SrgbTexture1dArray(tex) => {
+                texture_attachment(tex.get_texture(), gl::TEXTURE_1D_ARRAY, tex.get_level(),
+                                   tex.get_layer())
+            },
+
+            // the mipmaps of multisample arrays don't select a layer, so the first one is used
+            ColorAttachment::Texture2dMultisampleArray(tex) => {
+                texture_attachment(tex.get_texture(), gl::TEXTURE_2D_MULTISAMPLE_ARRAY, 0, 0)
+            },
+
+            ColorAttachment::SrgbTexture2dMultisampleArray(tex) => {
+                texture_attachment(tex.get_texture(), gl::TEXTURE_2D_MULTISAMPLE_ARRAY, 0, 0)
+            },
         }
     }
 }
 
+/// Error that can happen when building a framebuffer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    /// A layered attachment was passed, but the backend doesn't support layered rendering.
+    LayeredRenderingNotSupported,
+
+    /// The attachments don't have the same dimensions.
+    DimensionsMismatch,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(formatter, "{}", self.description())
+    }
+}
+
+impl Error for ValidationError {
+    fn description(&self) -> &str {
+        match self {
+            &ValidationError::LayeredRenderingNotSupported => {
+                "Layered attachments are not supported by the backend"
+            },
+            &ValidationError::DimensionsMismatch => {
+                "The attachments don't have the same dimensions"
+            },
+        }
+    }
+}
+
+/// Checks whether the backend supports an attachment.
+fn check_attachment_support(context: &Rc<Context>, attachment: &fbo::Attachment)
+                            -> Result<(), ValidationError>
+{
+    match attachment {
+        &fbo::Attachment::LayeredTexture { .. } => {
+            if !fbo::is_layered_attachment_supported(context) {
+                return Err(ValidationError::LayeredRenderingNotSupported);
+            }
+        },
+        _ => ()
+    }
+
+    Ok(())
+}
+
+/// Builds the `fbo::Attachment` corresponding to a face of a cubemap.
+fn cube_face_attachment(id: gl::types::GLuint, level: u32, face: CubeLayer) -> fbo::Attachment {
+    fbo::Attachment::Texture {
+        id: id,
+        bind_point: gl::TEXTURE_CUBE_MAP,
+        level: level,
+        layer: face.get_layer_index() as u32,
+    }
+}
+
+/// Returns the width and height of the main level of a texture, `1` being used as the height
+/// of one-dimensional textures.
+fn texture_dimensions<T>(texture: &T) -> (u32, u32) where T: Texture {
+    (texture.get_width(), texture.get_height().unwrap_or(1))
+}
+
+/// Builds the `fbo::Attachment` of a single layer and level of a texture.
+fn texture_attachment<T>(texture: &T, bind_point: gl::types::GLenum, level: u32, layer: u32)
+                         -> ((u32, u32), fbo::Attachment) where T: Texture + GlObject<Id = gl::types::GLuint>
+{
+    let attachment = fbo::Attachment::Texture {
+        id: texture.get_id(),
+        bind_point: bind_point,
+        level: level,
+        layer: layer,
+    };
+
+    (texture_dimensions(texture), attachment)
+}
+
+/// Turns a `DepthAttachment` into its dimensions and an `fbo::Attachment`.
+fn depth_attachment_to_fbo(depth: DepthAttachment) -> ((u32, u32), fbo::Attachment) {
+    match depth {
+        DepthAttachment::Texture1d(tex) => {
+            texture_attachment(tex.get_texture(), gl::TEXTURE_1D, tex.get_level(), 0)
+        },
+        DepthAttachment::Texture2d(tex) => {
+            texture_attachment(tex.get_texture(), gl::TEXTURE_2D, tex.get_level(), 0)
+        },
+        DepthAttachment::Texture2dMultisample(tex) => {
+            texture_attachment(tex.get_texture(), gl::TEXTURE_2D_MULTISAMPLE, 0, 0)
+        },
+        DepthAttachment::Texture3d(tex, layer) => {
+            assert!(layer < tex.get_texture().get_depth().unwrap());
+            texture_attachment(tex.get_texture(), gl::TEXTURE_3D, tex.get_level(), layer)
+        },
+        DepthAttachment::Texture1dArray(tex) => {
+            texture_attachment(tex.get_texture(), gl::TEXTURE_1D_ARRAY, tex.get_level(),
+                               tex.get_layer())
+        },
+        DepthAttachment::Texture2dArray(tex) => {
+            texture_attachment(tex.get_texture(), gl::TEXTURE_2D_ARRAY, tex.get_level(),
+                               tex.get_layer())
+        },
+        DepthAttachment::Texture2dMultisampleArray(tex) => {
+            // the mipmaps of multisample arrays don't select a layer, so the first one is used
+            texture_attachment(tex.get_texture(), gl::TEXTURE_2D_MULTISAMPLE_ARRAY, 0, 0)
+        },
+        DepthAttachment::CubeMap(tex, face) => {
+            let dimensions = texture_dimensions(tex.get_texture());
+            (dimensions, cube_face_attachment(tex.get_texture().get_id(), tex.get_level(), face))
+        },
+        DepthAttachment::RenderBuffer(buffer) => {
+            (buffer.get_dimensions(), fbo::Attachment::RenderBuffer(buffer.get_id()))
+        },
+    }
+}
+
+/// Turns a `StencilAttachment` into its dimensions and an `fbo::Attachment`.
+fn stencil_attachment_to_fbo(stencil: StencilAttachment) -> ((u32, u32), fbo::Attachment) {
+    match stencil {
+        StencilAttachment::Texture1d(tex) => {
+            texture_attachment(tex.get_texture(), gl::TEXTURE_1D, tex.get_level(), 0)
+        },
+        StencilAttachment::Texture2d(tex) => {
+            texture_attachment(tex.get_texture(), gl::TEXTURE_2D, tex.get_level(), 0)
+        },
+        StencilAttachment::Texture2dMultisample(tex) => {
+            texture_attachment(tex.get_texture(), gl::TEXTURE_2D_MULTISAMPLE, 0, 0)
+        },
+        StencilAttachment::Texture3d(tex, layer) => {
+            assert!(layer < tex.get_texture().get_depth().unwrap());
+            texture_attachment(tex.get_texture(), gl::TEXTURE_3D, tex.get_level(), layer)
+        },
+        StencilAttachment::Texture1dArray(tex) => {
+            texture_attachment(tex.get_texture(), gl::TEXTURE_1D_ARRAY, tex.get_level(),
+                               tex.get_layer())
+        },
+        StencilAttachment::Texture2dArray(tex) => {
+            texture_attachment(tex.get_texture(), gl::TEXTURE_2D_ARRAY, tex.get_level(),
+                               tex.get_layer())
+        },
+        StencilAttachment::Texture2dMultisampleArray(tex) => {
+            // the mipmaps of multisample arrays don't select a layer, so the first one is used
+            texture_attachment(tex.get_texture(), gl::TEXTURE_2D_MULTISAMPLE_ARRAY, 0, 0)
+        },
+        StencilAttachment::RenderBuffer(buffer) => {
+            (buffer.get_dimensions(), fbo::Attachment::RenderBuffer(buffer.get_id()))
+        },
+    }
+}
+
+/// Turns a `DepthStencilAttachment` into its dimensions and an `fbo::Attachment`.
+fn depth_stencil_attachment_to_fbo(depthstencil: DepthStencilAttachment)
+                                   -> ((u32, u32), fbo::Attachment)
+{
+    match depthstencil {
+        DepthStencilAttachment::Texture1d(tex) => {
+            texture_attachment(tex.get_texture(), gl::TEXTURE_1D, tex.get_level(), 0)
+        },
+        DepthStencilAttachment::Texture2d(tex) => {
+            texture_attachment(tex.get_texture(), gl::TEXTURE_2D, tex.get_level(), 0)
+        },
+        DepthStencilAttachment::Texture2dMultisample(tex) => {
+            texture_attachment(tex.get_texture(), gl::TEXTURE_2D_MULTISAMPLE, 0, 0)
+        },
+        DepthStencilAttachment::Texture3d(tex, layer) => {
+            assert!(layer < tex.get_texture().get_depth().unwrap());
+            texture_attachment(tex.get_texture(), gl::TEXTURE_3D, tex.get_level(), layer)
+        },
+        DepthStencilAttachment::Texture1dArray(tex) => {
+            texture_attachment(tex.get_texture(), gl::TEXTURE_1D_ARRAY, tex.get_level(),
+                               tex.get_layer())
+        },
+        DepthStencilAttachment::Texture2dArray(tex) => {
+            texture_attachment(tex.get_texture(), gl::TEXTURE_2D_ARRAY, tex.get_level(),
+                               tex.get_layer())
+        },
+        DepthStencilAttachment::Texture2dMultisampleArray(tex) => {
+            // the mipmaps of multisample arrays don't select a layer, so the first one is used
+            texture_attachment(tex.get_texture(), gl::TEXTURE_2D_MULTISAMPLE_ARRAY, 0, 0)
+        },
+        DepthStencilAttachment::RenderBuffer(buffer) => {
+            (buffer.get_dimensions(), fbo::Attachment::RenderBuffer(buffer.get_id()))
+        },
+    }
+}
+
+/// Queries the number of depth and stencil bits of an attachment.
+fn get_attachment_depth_stencil_bits(context: &Rc<Context>, attachment: &fbo::Attachment)
+                                     -> (u16, u16)
+{
+    match attachment {
+        &fbo::Attachment::Texture { id, bind_point, .. } |
+        &fbo::Attachment::LayeredTexture { id, bind_point, .. } => {
+            get_texture_depth_stencil_bits(context, id, bind_point)
+        },
+        &fbo::Attachment::RenderBuffer(id) => get_renderbuffer_depth_stencil_bits(context, id),
+    }
+}
+
+/// Queries the number of depth and stencil bits of a render buffer.
+fn get_renderbuffer_depth_stencil_bits(context: &Rc<Context>, id: gl::types::GLuint)
+                                       -> (u16, u16)
+{
+    let mut ctxt = context.make_current();
+
+    unsafe {
+        let mut depth_bits: gl::types::GLint = 0;
+        let mut stencil_bits: gl::types::GLint = 0;
+
+        if ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.extensions.gl_arb_direct_state_access {
+            ctxt.gl.GetNamedRenderbufferParameteriv(id, gl::RENDERBUFFER_DEPTH_SIZE,
+                                                    &mut depth_bits);
+            ctxt.gl.GetNamedRenderbufferParameteriv(id, gl::RENDERBUFFER_STENCIL_SIZE,
+                                                    &mut stencil_bits);
+
+        } else if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+                  ctxt.version >= &Version(Api::GlEs, 2, 0)
+        {
+            if ctxt.state.renderbuffer != id {
+                ctxt.gl.BindRenderbuffer(gl::RENDERBUFFER, id);
+                ctxt.state.renderbuffer = id;
+            }
+
+            ctxt.gl.GetRenderbufferParameteriv(gl::RENDERBUFFER, gl::RENDERBUFFER_DEPTH_SIZE,
+                                               &mut depth_bits);
+            ctxt.gl.GetRenderbufferParameteriv(gl::RENDERBUFFER, gl::RENDERBUFFER_STENCIL_SIZE,
+                                               &mut stencil_bits);
+
+        } else if ctxt.extensions.gl_ext_framebuffer_object {
+            if ctxt.state.renderbuffer != id {
+                ctxt.gl.BindRenderbufferEXT(gl::RENDERBUFFER_EXT, id);
+                ctxt.state.renderbuffer = id;
+            }
+
+            ctxt.gl.GetRenderbufferParameterivEXT(gl::RENDERBUFFER_EXT,
+                                                  gl::RENDERBUFFER_DEPTH_SIZE_EXT,
+                                                  &mut depth_bits);
+            ctxt.gl.GetRenderbufferParameterivEXT(gl::RENDERBUFFER_EXT,
+                                                  gl::RENDERBUFFER_STENCIL_SIZE_EXT,
+                                                  &mut stencil_bits);
+
+        } else {
+            unreachable!();
+        }
+
+        (depth_bits as u16, stencil_bits as u16)
+    }
+}
+
+/// Queries the number of depth and stencil bits of the main level of a texture.
+fn get_texture_depth_stencil_bits(context: &Rc<Context>, id: gl::types::GLuint,
+                                  bind_point: gl::types::GLenum) -> (u16, u16)
+{
+    let mut ctxt = context.make_current();
+
+    unsafe {
+        let mut depth_bits: gl::types::GLint = 0;
+        let mut stencil_bits: gl::types::GLint = 0;
+
+        if ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.extensions.gl_arb_direct_state_access {
+            ctxt.gl.GetTextureLevelParameteriv(id, 0, gl::TEXTURE_DEPTH_SIZE, &mut depth_bits);
+            ctxt.gl.GetTextureLevelParameteriv(id, 0, gl::TEXTURE_STENCIL_SIZE,
+                                               &mut stencil_bits);
+
+        } else if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+                  ctxt.version >= &Version(Api::GlEs, 3, 1)
+        {
+            ctxt.gl.BindTexture(bind_point, id);
+
+            // keeping track of the texture that is now bound to the active unit
+            let unit = (ctxt.state.active_texture - gl::TEXTURE0) as usize;
+            if let Some(entry) = ctxt.state.texture_units.get_mut(unit) {
+                entry.0 = bind_point;
+                entry.1 = id;
+            }
+
+            // the level parameters of a cubemap are queried on one of its faces
+            let target = if bind_point == gl::TEXTURE_CUBE_MAP {
+                gl::TEXTURE_CUBE_MAP_POSITIVE_X
+            } else {
+                bind_point
+            };
+
+            ctxt.gl.GetTexLevelParameteriv(target, 0, gl::TEXTURE_DEPTH_SIZE, &mut depth_bits);
+            ctxt.gl.GetTexLevelParameteriv(target, 0, gl::TEXTURE_STENCIL_SIZE,
+                                           &mut stencil_bits);
+
+        } else {
+            // the only packed format available in this situation
+            return (24, 8);
+        }
+
+        (depth_bits as u16, stencil_bits as u16)
+    }
+}
+
 impl<'a> Surface for SimpleFrameBuffer<'a> {
-    fn clear(&mut self, color: Option<(f32, f32, f32, f32)>, depth: Option<f32>,
-             stencil: Option<i32>)
+    fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>,
+             depth: Option<f32>, stencil: Option<i32>)
     {
-        ops::clear(&self.context, Some(&self.attachments), color, depth, stencil);
+        ops::clear(&self.context, Some(&self.attachments), rect, color, depth, stencil);
     }
 
     fn get_dimensions(&self) -> (u32, u32) {
@@ -262,6 +741,12 @@ impl<'a> Surface for SimpleFrameBuffer<'a> {
             return Err(DrawError::NoDepthBuffer);
         }
 
+        if !self.has_stencil_buffer() &&
+            ::draw_parameters::requires_stencil_buffer(draw_parameters)
+        {
+            return Err(DrawError::NoStencilBuffer);
+        }
+
         if let Some(viewport) = draw_parameters.viewport {
             if viewport.width > self.context.capabilities().max_viewport_dims.0
                     as u32
@@ -280,13 +765,14 @@ impl<'a> Surface for SimpleFrameBuffer<'a> {
     }
 
     fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
-                     filter: uniforms::MagnifySamplerFilter) where S: Surface
+                     filter: uniforms::MagnifySamplerFilter)
+                     -> Result<(), BlitError> where S: Surface
     {
         target.blit_from_simple_framebuffer(self, source_rect, target_rect, filter)
     }
 
     fn blit_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget,
-                       filter: uniforms::MagnifySamplerFilter)
+                       filter: uniforms::MagnifySamplerFilter) -> Result<(), BlitError>
     {
         ops::blit(&self.context, None, self.get_attachments(),
                   gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
@@ -295,6 +781,7 @@ impl<'a> Surface for SimpleFrameBuffer<'a> {
     fn blit_from_simple_framebuffer(&self, source: &SimpleFrameBuffer,
                                     source_rect: &Rect, target_rect: &BlitTarget,
                                     filter: uniforms::MagnifySamplerFilter)
+                                    -> Result<(), BlitError>
     {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
                   gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
@@ -303,10 +790,22 @@ impl<'a> Surface for SimpleFrameBuffer<'a> {
     fn blit_from_multioutput_framebuffer(&self, source: &MultiOutputFrameBuffer,
                                          source_rect: &Rect, target_rect: &BlitTarget,
                                          filter: uniforms::MagnifySamplerFilter)
+                                         -> Result<(), BlitError>
     {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
                   gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
     }
+
+    fn read_rect<P, T>(&self, rect: &Rect) -> T
+                       where P: PixelValue + Clone + Send, T: Texture2dDataSink<Data = P>
+    {
+        ops::read_rect(Some(&self.attachments), self.get_dimensions(), rect, &self.context)
+    }
+
+    fn read_rect_into<P>(&self, rect: &Rect, dest: &mut [P]) where P: PixelValue {
+        ops::read_rect_into(Some(&self.attachments), self.get_dimensions(), rect, dest,
+                            &self.context)
+    }
 }
 
 impl<'a> FboAttachments for SimpleFrameBuffer<'a> {
@@ -320,11 +819,14 @@ pub struct MultiOutputFrameBuffer<'a> {
     context: Rc<Context>,
     marker: PhantomData<&'a ()>,
     dimensions: (u32, u32),
-    color_attachments: Vec<(String, gl::types::GLuint)>,
+    color_attachments: Vec<(String, &'a Texture2d)>,
     depth_attachment: Option<fbo::Attachment>,
     depth_buffer_bits: Option<u16>,
     stencil_attachment: Option<fbo::Attachment>,
     stencil_buffer_bits: Option<u16>,
+    /// The attachments in the order of `color_attachments`, for operations that don't
+    /// depend on a program.
+    attachments_any: FramebufferAttachments,
 }
 
 impl<'a> MultiOutputFrameBuffer<'a> {
@@ -358,12 +860,41 @@ impl<'a> MultiOutputFrameBuffer<'a> {
                                          None::<&render_buffer::StencilRenderBuffer>)
     }
 
+    /// Creates a `MultiOutputFrameBuffer` with a stencil buffer.
+    ///
+    /// # Panic
+    ///
+    /// Panics if all attachments don't have the same dimensions.
+    pub fn with_stencil_buffer<F, S>(facade: &F, color_attachments: &[(&str, &'a Texture2d)],
+                                     stencil: &'a S) -> MultiOutputFrameBuffer<'a>
+                                     where S: ToStencilAttachment, F: Facade
+    {
+        use render_buffer;
+
+        MultiOutputFrameBuffer::new_impl(facade, color_attachments,
+                                         None::<&render_buffer::DepthRenderBuffer>, Some(stencil))
+    }
+
+    /// Creates a `MultiOutputFrameBuffer` with a depth buffer and a stencil buffer.
+    ///
+    /// # Panic
+    ///
+    /// Panics if all attachments don't have the same dimensions.
+    pub fn with_depth_and_stencil_buffer<F, D, S>(facade: &F,
+                                                  color_attachments: &[(&str, &'a Texture2d)],
+                                                  depth: &'a D, stencil: &'a S)
+                                                  -> MultiOutputFrameBuffer<'a>
+                                                  where D: ToDepthAttachment,
+                                                        S: ToStencilAttachment, F: Facade
+    {
+        MultiOutputFrameBuffer::new_impl(facade, color_attachments, Some(depth), Some(stencil))
+    }
+
     fn new_impl<F, D, S>(facade: &F, color_attachments: &[(&str, &'a Texture2d)],
                          depth: Option<&'a D>, stencil: Option<&'a S>)
-                         -> MultiOutputFrameBuffer<'a> where D: ToDepthAttachment, F: Facade
+                         -> MultiOutputFrameBuffer<'a>
+                         where D: ToDepthAttachment, S: ToStencilAttachment, F: Facade
     {
-        assert!(stencil.is_none());     // not implemented yet
-
         let mut attachments = Vec::new();
         let mut dimensions = None;
 
@@ -378,7 +909,7 @@ impl<'a> MultiOutputFrameBuffer<'a> {
             }
 
             dimensions = Some(tex_dims);
-            attachments.push((name.to_string(), texture.get_id()));
+            attachments.push((name.to_string(), texture));
         }
 
         let dimensions = match dimensions {
@@ -388,60 +919,126 @@ impl<'a> MultiOutputFrameBuffer<'a> {
         };
 
         let (depth, depth_bits) = if let Some(depth) = depth {
-            match depth.to_depth_attachment() {
-                DepthAttachment::Texture2d(tex) => {
-                    if (tex.get_texture().get_width(), tex.get_texture().get_height().unwrap()) != dimensions {
-                        panic!("The depth attachment must have the same dimensions \
-                                as the color attachment");
-                    }
-
-                    (Some(fbo::Attachment::Texture { id: tex.get_texture().get_id(), bind_point: gl::TEXTURE_2D, level: 0, layer: 0 }), Some(32))      // FIXME: wrong number
-                },
+            let (depth_dimensions, attachment) = depth_attachment_to_fbo(depth.to_depth_attachment());
 
-                DepthAttachment::RenderBuffer(buffer) => {
-                    // TODO: dimensions
+            if depth_dimensions != dimensions {
+                panic!("The depth attachment must have the same dimensions \
+                        as the color attachment");
+            }
 
-                    (Some(fbo::Attachment::RenderBuffer(buffer.get_id())), Some(32))      // FIXME: wrong number
-                },
+            let (bits, _) = get_attachment_depth_stencil_bits(facade.get_context(), &attachment);
+            (Some(attachment), Some(bits))
+
+        } else {
+            (None, None)
+        };
+
+        let (stencil, stencil_bits) = if let Some(stencil) = stencil {
+            let (stencil_dimensions, attachment) =
+                                        stencil_attachment_to_fbo(stencil.to_stencil_attachment());
 
-                _ => unimplemented!()
+            if stencil_dimensions != dimensions {
+                panic!("The stencil attachment must have the same dimensions \
+                        as the color attachment");
             }
 
+            let (_, bits) = get_attachment_depth_stencil_bits(facade.get_context(), &attachment);
+            (Some(attachment), Some(bits))
+
         } else {
             (None, None)
         };
 
-        MultiOutputFrameBuffer {
+        let mut framebuffer = MultiOutputFrameBuffer {
             context: facade.get_context().clone(),
             marker: PhantomData,
             dimensions: dimensions,
             color_attachments: attachments,
             depth_attachment: depth,
             depth_buffer_bits: depth_bits,
-            stencil_attachment: None,
-            stencil_buffer_bits: None,
-        }
+            stencil_attachment: stencil,
+            stencil_buffer_bits: stencil_bits,
+            attachments_any: FramebufferAttachments {
+                colors: Vec::new(),
+                depth_stencil: fbo::FramebufferDepthStencilAttachments::None,
+            },
+        };
+
+        framebuffer.attachments_any = framebuffer.build_attachments_any();
+        framebuffer
+    }
+
+    /// Reads the content of the color attachment at the given index, in the order in which
+    /// the attachments were passed when building the framebuffer.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `index` is out of range.
+    pub fn read_attachment<P, T>(&self, index: u32) -> T
+                                 where P: PixelValue + Clone + Send,
+                                       T: Texture2dDataSink<Data = P>
+    {
+        let &(_, texture) = self.color_attachments.get(index as usize)
+                                .expect("Color attachment index out of range");
+
+        let attachment = fbo::Attachment::Texture {
+            id: texture.get_id(),
+            bind_point: gl::TEXTURE_2D,
+            level: 0,
+            layer: 0,
+        };
+
+        ops::read_attachment(&attachment, self.dimensions, &self.context)
+    }
+
+    /// Copies a rectangle of the color attachment at the given index to a rectangle of
+    /// `target`.
+    ///
+    /// `blit_color` always copies from the first attachment.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `index` is out of range.
+    pub fn blit_attachment_color<S>(&self, index: u32, source_rect: &Rect, target: &S,
+                                    target_rect: &BlitTarget,
+                                    filter: uniforms::MagnifySamplerFilter)
+                                    -> Result<(), BlitError> where S: Surface
+    {
+        let &(_, texture) = self.color_attachments.get(index as usize)
+                                .expect("Color attachment index out of range");
+
+        let source = SimpleFrameBuffer::new(&self.context, texture);
+        source.blit_color(source_rect, target, target_rect, filter)
     }
 
-    fn build_attachments(&self, program: &Program) -> FramebufferAttachments {
+    fn build_attachments(&self, program: &Program) -> Result<FramebufferAttachments, DrawError> {
         let mut colors = Vec::new();
 
         for &(ref name, texture) in self.color_attachments.iter() {
             let location = match program.get_frag_data_location(&name) {
                 Some(l) => l,
-                None => panic!("The fragment output `{}` was not found in the program", name)
+                None => return Err(DrawError::FragmentShaderOutputNotFound(name.clone())),
             };
 
-            colors.push((location, fbo::Attachment::Texture { id: texture, bind_point: gl::TEXTURE_2D, level: 0, layer: 0 }));
+            colors.push((location, fbo::Attachment::Texture { id: texture.get_id(), bind_point: gl::TEXTURE_2D, level: 0, layer: 0 }));
         }
 
-        FramebufferAttachments {
+        Ok(FramebufferAttachments {
             colors: colors,
-            depth_stencil: if let Some(depth) = self.depth_attachment {
-                fbo::FramebufferDepthStencilAttachments::DepthAttachment(depth)
-            } else {
-                fbo::FramebufferDepthStencilAttachments::None
+            depth_stencil: self.build_depth_stencil_attachments(),
+        })
+    }
+
+    fn build_depth_stencil_attachments(&self) -> fbo::FramebufferDepthStencilAttachments {
+        match (self.depth_attachment, self.stencil_attachment) {
+            (Some(depth), Some(stencil)) => {
+                fbo::FramebufferDepthStencilAttachments::DepthAndStencilAttachments(depth, stencil)
             },
+            (Some(depth), None) => fbo::FramebufferDepthStencilAttachments::DepthAttachment(depth),
+            (None, Some(stencil)) => {
+                fbo::FramebufferDepthStencilAttachments::StencilAttachment(stencil)
+            },
+            (None, None) => fbo::FramebufferDepthStencilAttachments::None,
         }
     }
 
@@ -449,26 +1046,26 @@ impl<'a> MultiOutputFrameBuffer<'a> {
         let mut colors = Vec::new();
 
         for (id, &(ref name, texture)) in self.color_attachments.iter().enumerate() {
-            colors.push((id as u32, fbo::Attachment::Texture { id: texture, bind_point: gl::TEXTURE_2D, level: 0, layer: 0 }));
+            colors.push((id as u32, fbo::Attachment::Texture { id: texture.get_id(), bind_point: gl::TEXTURE_2D, level: 0, layer: 0 }));
         }
 
         FramebufferAttachments {
             colors: colors,
-            depth_stencil: if let Some(depth) = self.depth_attachment {
-                fbo::FramebufferDepthStencilAttachments::DepthAttachment(depth)
-            } else {
-                fbo::FramebufferDepthStencilAttachments::None
-            },
+            depth_stencil: self.build_depth_stencil_attachments(),
         }
     }
 }
 
 impl<'a> Surface for MultiOutputFrameBuffer<'a> {
-    fn clear(&mut self, color: Option<(f32, f32, f32, f32)>, depth: Option<f32>,
-             stencil: Option<i32>)
+    fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>,
+             depth: Option<f32>, stencil: Option<i32>)
     {
-        ops::clear(&self.context, Some(&self.build_attachments_any()),
-                   color, depth, stencil);
+        ops::clear(&self.context, Some(&self.attachments_any), rect, color, depth, stencil);
+    }
+
+    fn clear_color_buffer(&mut self, index: u32, color: (f32, f32, f32, f32)) {
+        assert!((index as usize) < self.color_attachments.len());
+        ops::clear_color_buffer(&self.context, &self.attachments_any, index, color);
     }
 
     fn get_dimensions(&self) -> (u32, u32) {
@@ -496,6 +1093,12 @@ impl<'a> Surface for MultiOutputFrameBuffer<'a> {
             return Err(DrawError::NoDepthBuffer);
         }
 
+        if !self.has_stencil_buffer() &&
+            ::draw_parameters::requires_stencil_buffer(draw_parameters)
+        {
+            return Err(DrawError::NoStencilBuffer);
+        }
+
         if let Some(viewport) = draw_parameters.viewport {
             if viewport.width > self.context.capabilities().max_viewport_dims.0
                     as u32
@@ -509,18 +1112,21 @@ impl<'a> Surface for MultiOutputFrameBuffer<'a> {
             }
         }
 
-        ops::draw(&self.context, Some(&self.build_attachments(program)), vb,
+        let attachments = try!(self.build_attachments(program));
+
+        ops::draw(&self.context, Some(&attachments), vb,
                   ib.to_indices_source(), program, uniforms, draw_parameters, self.dimensions)
     }
 
     fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
-                     filter: uniforms::MagnifySamplerFilter) where S: Surface
+                     filter: uniforms::MagnifySamplerFilter)
+                     -> Result<(), BlitError> where S: Surface
     {
         target.blit_from_multioutput_framebuffer(self, source_rect, target_rect, filter)
     }
 
     fn blit_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget,
-                       filter: uniforms::MagnifySamplerFilter)
+                       filter: uniforms::MagnifySamplerFilter) -> Result<(), BlitError>
     {
         ops::blit(&self.context, None, self.get_attachments(),
                   gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
@@ -529,6 +1135,7 @@ impl<'a> Surface for MultiOutputFrameBuffer<'a> {
     fn blit_from_simple_framebuffer(&self, source: &SimpleFrameBuffer,
                                     source_rect: &Rect, target_rect: &BlitTarget,
                                     filter: uniforms::MagnifySamplerFilter)
+                                    -> Result<(), BlitError>
     {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
                   gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
@@ -537,15 +1144,27 @@ impl<'a> Surface for MultiOutputFrameBuffer<'a> {
     fn blit_from_multioutput_framebuffer(&self, source: &MultiOutputFrameBuffer,
                                          source_rect: &Rect, target_rect: &BlitTarget,
                                          filter: uniforms::MagnifySamplerFilter)
+                                         -> Result<(), BlitError>
     {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
                   gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
     }
+
+    fn read_rect<P, T>(&self, rect: &Rect) -> T
+                       where P: PixelValue + Clone + Send, T: Texture2dDataSink<Data = P>
+    {
+        ops::read_rect(Some(&self.attachments_any), self.get_dimensions(), rect, &self.context)
+    }
+
+    fn read_rect_into<P>(&self, rect: &Rect, dest: &mut [P]) where P: PixelValue {
+        ops::read_rect_into(Some(&self.attachments_any), self.get_dimensions(), rect, dest,
+                            &self.context)
+    }
 }
 
 impl<'a> FboAttachments for MultiOutputFrameBuffer<'a> {
     fn get_attachments(&self) -> Option<&FramebufferAttachments> {
-        unimplemented!();
+        Some(&self.attachments_any)
     }
 }
 
@@ -580,16 +1199,40 @@ pub enum ColorAttachment<'a> {
     Texture2dMultisampleArray(Texture2dMultisampleArrayMipmap<'a>),
     /// A texture.
     SrgbTexture2dMultisampleArray(SrgbTexture2dMultisampleArrayMipmap<'a>),
+    /// A face of a cubemap.
+    CubeMap(CubeMapMipmap<'a>, CubeLayer),
+    /// All the layers of a texture at once.
+    ///
+    /// The geometry shader chooses the layer of each primitive by writing to `gl_Layer`.
+    /// Requires OpenGL 3.2.
+    Layered(LayeredColorAttachment<'a>),
     /// A render buffer.
     RenderBuffer(&'a ::render_buffer::RenderBuffer),
 }
 
+/// Describes a texture whose layers are all attached at once, for layered rendering.
+#[derive(Copy, Clone)]
+pub enum LayeredColorAttachment<'a> {
+    /// All the layers of the main level of a 2D array texture.
+    Texture2dArray(&'a Texture2dArray),
+    /// All the slices of the main level of a 3D texture.
+    Texture3d(&'a Texture3d),
+    /// The six faces of the main level of a cubemap.
+    CubeMap(&'a CubeMap),
+}
+
 /// Trait for objects that can be used as color attachments.
 pub trait ToColorAttachment {
     /// Builds the `ColorAttachment`.
     fn to_color_attachment(&self) -> ColorAttachment;
 }
 
+impl<'a> ToColorAttachment for ColorAttachment<'a> {
+    fn to_color_attachment(&self) -> ColorAttachment {
+        *self
+    }
+}
+
 /// Describes an attachment for a depth buffer.
 #[derive(Copy, Clone)]
 pub enum DepthAttachment<'a> {
@@ -607,6 +1250,8 @@ pub enum DepthAttachment<'a> {
     Texture2dArray(DepthTexture2dArrayMipmap<'a>),
     /// A texture.
     Texture2dMultisampleArray(DepthTexture2dMultisampleArrayMipmap<'a>),
+    /// A face of a cubemap.
+    CubeMap(DepthCubeMapMipmap<'a>, CubeLayer),
     /// A render buffer.
     RenderBuffer(&'a ::render_buffer::DepthRenderBuffer),
 }
@@ -617,6 +1262,12 @@ pub trait ToDepthAttachment {
     fn to_depth_attachment(&self) -> DepthAttachment;
 }
 
+impl<'a> ToDepthAttachment for DepthAttachment<'a> {
+    fn to_depth_attachment(&self) -> DepthAttachment {
+        *self
+    }
+}
+
 /// Describes an attachment for a stencil buffer.
 #[derive(Copy, Clone)]
 pub enum StencilAttachment<'a> {
@@ -405,6 +405,15 @@ pub enum CompressedFormat {
     RGTCFormatUU,
     /// Red/green compressed texture with two signed components.
     RGTCFormatII,
+
+    /// S3TC DXT1 without alpha, also known as BC1.
+    S3tcDxt1NoAlpha,
+    /// S3TC DXT1 with one bit of alpha, also known as BC1.
+    S3tcDxt1Alpha,
+    /// S3TC DXT3, also known as BC2.
+    S3tcDxt3Alpha,
+    /// S3TC DXT5, also known as BC3.
+    S3tcDxt5Alpha,
 }
 
 impl CompressedFormat {
@@ -412,6 +421,24 @@ impl CompressedFormat {
     pub fn to_texture_format(self) -> TextureFormat {
         TextureFormat::CompressedFormat(self)
     }
+
+    /// Returns the number of bytes that a 4x4 block of texels takes in this format.
+    pub fn get_block_size(&self) -> usize {
+        match *self {
+            CompressedFormat::RGTCFormatU | CompressedFormat::RGTCFormatI |
+            CompressedFormat::S3tcDxt1NoAlpha | CompressedFormat::S3tcDxt1Alpha => 8,
+            CompressedFormat::RGTCFormatUU | CompressedFormat::RGTCFormatII |
+            CompressedFormat::S3tcDxt3Alpha | CompressedFormat::S3tcDxt5Alpha => 16,
+        }
+    }
+
+    /// Returns the number of bytes of compressed data that a texture of the given dimensions
+    /// takes in this format.
+    pub fn get_data_size(&self, width: u32, height: u32) -> usize {
+        let blocks_x = (width as usize + 3) / 4;
+        let blocks_y = (height as usize + 3) / 4;
+        blocks_x * blocks_y * self.get_block_size()
+    }
 }
 
 /// List of compressed pixel formats in the sRGB color space.
@@ -894,6 +921,38 @@ pub fn format_request_to_glenum(context: &Context, client: Option<ClientFormat>,
             }
         },
 
+        TextureFormatRequest::Specific(TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt1NoAlpha)) => {
+            if extensions.gl_ext_texture_compression_s3tc {
+                (gl::COMPRESSED_RGB_S3TC_DXT1_EXT, Some(gl::COMPRESSED_RGB_S3TC_DXT1_EXT))
+            } else {
+                return Err(FormatNotSupportedError);
+            }
+        },
+
+        TextureFormatRequest::Specific(TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt1Alpha)) => {
+            if extensions.gl_ext_texture_compression_s3tc {
+                (gl::COMPRESSED_RGBA_S3TC_DXT1_EXT, Some(gl::COMPRESSED_RGBA_S3TC_DXT1_EXT))
+            } else {
+                return Err(FormatNotSupportedError);
+            }
+        },
+
+        TextureFormatRequest::Specific(TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt3Alpha)) => {
+            if extensions.gl_ext_texture_compression_s3tc {
+                (gl::COMPRESSED_RGBA_S3TC_DXT3_EXT, Some(gl::COMPRESSED_RGBA_S3TC_DXT3_EXT))
+            } else {
+                return Err(FormatNotSupportedError);
+            }
+        },
+
+        TextureFormatRequest::Specific(TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt5Alpha)) => {
+            if extensions.gl_ext_texture_compression_s3tc {
+                (gl::COMPRESSED_RGBA_S3TC_DXT5_EXT, Some(gl::COMPRESSED_RGBA_S3TC_DXT5_EXT))
+            } else {
+                return Err(FormatNotSupportedError);
+            }
+        },
+
         /*******************************************************************/
         /*                             SRGB                                */
         /*******************************************************************/
@@ -1328,8 +1387,29 @@ pub fn format_request_to_glenum(context: &Context, client: Option<ClientFormat>,
                                             UncompressedIntFormat::I8)));
         },
 
-        TextureFormatRequest::Specific(TextureFormat::StencilFormat(_)) => {
-            unimplemented!();
+        TextureFormatRequest::Specific(TextureFormat::StencilFormat(StencilFormat::I8)) => {
+            if version >= &Version(Api::Gl, 3, 0) || version >= &Version(Api::GlEs, 2, 0) ||
+               extensions.gl_ext_framebuffer_object
+            {
+                (gl::STENCIL_INDEX8, Some(gl::STENCIL_INDEX8))
+            } else {
+                return Err(FormatNotSupportedError);
+            }
+        },
+
+        TextureFormatRequest::Specific(TextureFormat::StencilFormat(format)) => {
+            if version >= &Version(Api::Gl, 3, 0) || extensions.gl_ext_framebuffer_object {
+                let value = match format {
+                    StencilFormat::I1 => gl::STENCIL_INDEX1,
+                    StencilFormat::I4 => gl::STENCIL_INDEX4,
+                    StencilFormat::I16 => gl::STENCIL_INDEX16,
+                    StencilFormat::I8 => unreachable!(),
+                };
+
+                (value, Some(value))
+            } else {
+                return Err(FormatNotSupportedError);
+            }
         },
 
         /*******************************************************************/
@@ -1,4 +1,4 @@
-use buffer::{Buffer, BufferFlags, BufferType};
+use buffer::{Buffer, BufferFlags, BufferType, CopyError};
 use gl;
 use BufferExt;
 use GlObject;
@@ -14,6 +14,8 @@ use index::Index;
 use index::IndexType;
 use index::PrimitiveType;
 
+use vertex::SliceError;
+
 use std::mem;
 use std::ops::Range;
 use std::sync::mpsc::Sender;
@@ -26,6 +28,11 @@ pub struct IndexBuffer {
     primitives: PrimitiveType,
 }
 
+/// Represents a slice of an `IndexBuffer`.
+///
+/// When multiple meshes are packed in the same vertex and index buffers, you can draw one of
+/// them by passing a slice of the vertex buffer alongside a slice of the index buffer. The
+/// indices are then relative to the start of the vertex buffer slice.
 pub struct IndexBufferSlice<'a> {
     buffer: &'a IndexBuffer,
     offset: usize,  // in number of elements
@@ -52,6 +59,63 @@ impl IndexBuffer {
         }
     }
 
+    /// Builds an empty index buffer that can hold `len` indices of the given type.
+    ///
+    /// The buffer is created with the `GL_DYNAMIC_DRAW` hint, as it is meant to be filled
+    /// and updated with `write`. Its content is undefined until you write to it.
+    pub fn empty<F>(facade: &F, data_type: IndexType, prim: PrimitiveType, len: usize)
+                    -> IndexBuffer where F: Facade
+    {
+        IndexBuffer {
+            buffer: Buffer::new_empty(facade, BufferType::ArrayBuffer, data_type.get_size(),
+                                      len, BufferFlags::dynamic_draw()).unwrap(),    // FIXME: ElementArrayBuffer
+            data_type: data_type,
+            primitives: prim,
+        }
+    }
+
+    /// Returns the number of indices in the buffer.
+    pub fn len(&self) -> usize {
+        self.buffer.get_elements_count()
+    }
+
+    /// Replaces some indices of the buffer, starting at index `offset`.
+    ///
+    /// Returns `Err(SliceError::OutOfRange)` if the data doesn't fit in the buffer.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the type of the indices doesn't match the type of the buffer.
+    pub fn write<T>(&self, offset: usize, data: &[T]) -> Result<(), SliceError> where T: Index {
+        assert!(<T as Index>::get_type() == self.data_type,
+                "The type of the indices doesn't match the type of the buffer");
+
+        if offset + data.len() > self.len() {
+            return Err(SliceError::OutOfRange { end: offset + data.len(), len: self.len() });
+        }
+
+        self.buffer.upload(offset, data);
+        Ok(())
+    }
+
+    /// Copies `len` indices starting at `src_offset` to `target`, starting at `dest_offset`.
+    ///
+    /// The copy is done by the GPU with `glCopyBufferSubData`. `target` can be the same buffer
+    /// as long as the ranges don't overlap.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the type of the indices of `target` doesn't match the type of this buffer.
+    pub fn copy_to(&self, target: &IndexBuffer, src_offset: usize, dest_offset: usize,
+                   len: usize) -> Result<(), CopyError>
+    {
+        assert!(target.data_type == self.data_type,
+                "The type of the indices doesn't match the type of the buffer");
+
+        let size = self.data_type.get_size();
+        self.buffer.copy_to(&target.buffer, src_offset * size, dest_offset * size, len * size)
+    }
+
     /// Returns the type of primitives associated with this index buffer.
     pub fn get_primitives_type(&self) -> PrimitiveType {
         self.primitives
@@ -62,20 +126,18 @@ impl IndexBuffer {
         self.data_type
     }
 
+    /// Accesses a slice of the buffer.
+    ///
     /// Returns `None` if out of range.
     pub fn slice(&self, Range { start, end }: Range<usize>) -> Option<IndexBufferSlice> {
-        let len = end - start;
-
-        if start > self.buffer.get_elements_count() ||
-            start + len > self.buffer.get_elements_count()
-        {
+        if end < start || end > self.buffer.get_elements_count() {
             return None;
         }
 
         Some(IndexBufferSlice {
             buffer: self,
             offset: start,
-            len: len,
+            len: end - start,
         })
     }
 }
@@ -101,6 +101,29 @@ impl<T> ToIndicesSource for LineStrip<T> where T: Index + Send + Copy {
     }
 }
 
+/// A list of lines connected together, where the last vertex is connected to the first one,
+/// stored in RAM.
+pub struct LineLoop<T>(pub Vec<T>);
+
+impl<T> IntoIndexBuffer for LineLoop<T> where T: Index + Send + Copy {
+    fn into_index_buffer<F>(self, facade: &F) -> IndexBuffer where F: Facade {
+        IndexBuffer::from_raw(facade, self.0, PrimitiveType::LineLoop)
+    }
+}
+
+impl<T> ToIndicesSource for LineLoop<T> where T: Index + Send + Copy {
+    type Data = T;
+
+    fn to_indices_source(&self) -> IndicesSource<T> {
+        IndicesSource::Buffer {
+            pointer: &self.0,
+            primitives: PrimitiveType::LineLoop,
+            offset: 0,
+            length: self.0.len(),
+        }
+    }
+}
+
 /// A list of lines connected together, with adjacency information, stored in RAM.
 ///
 /// # Panic
@@ -2,13 +2,14 @@
 In order to draw, you need to provide a source of indices which is used to link the vertices
 together into *primitives*.
 
-There are eleven types of primitives, each one with a corresponding struct:
+There are twelve types of primitives, each one with a corresponding struct:
 
  - `PointsList`
  - `LinesList`
  - `LinesListAdjacency`
  - `LineStrip`
  - `LineStripAdjacency`
+ - `LineLoop`
  - `TrianglesList`
  - `TrianglesListAdjacency`
  - `TriangleStrip`
@@ -31,6 +32,16 @@ When you draw something, a draw command is sent to the GPU and the execution con
 after. But if you pass indices in RAM, the execution has to block until the GPU has finished
 drawing in order to make sure that the indices are not free'd.
 
+## Primitive restart
+
+Strips and fans can be split in multiple parts by inserting a special index, and by setting
+the `primitive_restart_index` member of the draw parameters to this value. Each time the GPU
+encounters this index, it starts a new strip or fan instead of emitting a primitive.
+
+The most portable value is the maximum value of the index type, which is `0xff` for `u8`,
+`0xffff` for `u16` and `0xffffffff` for `u32`. Other values are only supported with
+OpenGL 3.1 or superior.
+
 */
 use gl;
 use ToGlEnum;
@@ -41,6 +52,7 @@ use backend::Facade;
 
 pub use self::buffer::IndexBuffer;
 pub use self::local::{PointsList, LinesList, LinesListAdjacency, LineStrip, LineStripAdjacency};
+pub use self::local::LineLoop;
 pub use self::local::{TrianglesList, TrianglesListAdjacency, TriangleStrip, TriangleStripAdjacency};
 pub use self::local::{TriangleFan, Patches};
 
@@ -98,6 +110,15 @@ impl<'a, T> IndicesSource<'a, T> where T: Index {
             &IndicesSource::NoIndices { primitives } => primitives,
         }
     }
+
+    /// Returns the type of the indices, or `None` if there is no index.
+    pub fn get_indices_type(&self) -> Option<IndexType> {
+        match self {
+            &IndicesSource::IndexBuffer { ref buffer, .. } => Some(buffer.get_indices_type()),
+            &IndicesSource::Buffer { .. } => Some(<T as Index>::get_type()),
+            &IndicesSource::NoIndices { .. } => None,
+        }
+    }
 }
 
 /// List of available primitives.
@@ -114,6 +135,8 @@ pub enum PrimitiveType {
     ///
     LineStripAdjacency,
     ///
+    LineLoop,
+    ///
     TrianglesList,
     ///
     TrianglesListAdjacency,
@@ -138,6 +161,7 @@ impl ToGlEnum for PrimitiveType {
             &PrimitiveType::LinesListAdjacency => gl::LINES_ADJACENCY,
             &PrimitiveType::LineStrip => gl::LINE_STRIP,
             &PrimitiveType::LineStripAdjacency => gl::LINE_STRIP_ADJACENCY,
+            &PrimitiveType::LineLoop => gl::LINE_LOOP,
             &PrimitiveType::TrianglesList => gl::TRIANGLES,
             &PrimitiveType::TrianglesListAdjacency => gl::TRIANGLES_ADJACENCY,
             &PrimitiveType::TriangleStrip => gl::TRIANGLE_STRIP,
@@ -173,7 +173,9 @@ extern crate libc;
 #[cfg(feature = "nalgebra")]
 extern crate nalgebra;
 
+pub use buffer::{BufferCreationError, CopyError};
 pub use draw_parameters::{BlendingFunction, LinearBlendingFactor, BackfaceCullingMode};
+pub use draw_parameters::{AdvancedBlendingEquation, PolygonOffset, Smooth};
 pub use draw_parameters::{DepthTest, PolygonMode, DrawParameters, StencilTest, StencilOperation};
 pub use index::IndexBuffer;
 pub use vertex::{VertexBuffer, Vertex, VertexFormat};
@@ -262,6 +264,27 @@ trait ContextExt {
 
     /// Start executing OpenGL commands by checking the current context.
     fn make_current<'a>(&'a self) -> context::CommandContext<'a, 'a>;
+
+    /// Pops all the pending OpenGL errors and returns true if one of them is
+    /// `GL_OUT_OF_MEMORY`. The other errors are kept and returned later by `get_error`.
+    fn check_out_of_memory(&self) -> bool;
+}
+
+/// Internal trait for queries.
+trait QueryExt {
+    /// Returns true if `end` has been called on the query since it was last begun.
+    fn is_ended(&self) -> bool;
+}
+
+/// Internal trait for programs.
+trait ProgramExt {
+    /// Sets the value of a sampler uniform to the given texture unit, unless the uniform
+    /// already has this value. The program must be the current program.
+    fn set_sampler_uniform(&self, ctxt: &mut context::CommandContext,
+                           location: gl::types::GLint, texture_unit: gl::types::GLint);
+
+    /// Returns true if the program contains a compute shader.
+    fn is_compute(&self) -> bool;
 }
 
 /// Area of a surface in pixels.
@@ -284,6 +307,9 @@ pub struct Rect {
 /// Area of a surface in pixels. Similar to a `Rect` except that dimensions can be negative.
 ///
 /// In the OpenGL ecosystem, the (0,0) coordinate is at the bottom-left hand corner of the images.
+///
+/// A negative width or height mirrors the image during the blit. For example, to flip an
+/// image of height `h` vertically, use `bottom: h` and `height: -h`.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct BlitTarget {
     /// Number of pixels between the left border of the surface and the left border of
@@ -298,6 +324,29 @@ pub struct BlitTarget {
     pub height: i32,
 }
 
+/// Buffer of the default framebuffer to read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadBuffer {
+    /// The buffer that is currently displayed on the screen.
+    ///
+    /// Contains the image that was presented by the last call to `Frame::finish`.
+    Front,
+
+    /// The buffer that is being drawn upon.
+    ///
+    /// Contains the image that will be presented by the next call to `Frame::finish`.
+    Back,
+}
+
+impl ToGlEnum for ReadBuffer {
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            ReadBuffer::Front => gl::FRONT_LEFT,
+            ReadBuffer::Back => gl::BACK_LEFT,
+        }
+    }
+}
+
 /// Object that can be drawn upon.
 ///
 /// # What does the GPU do when you draw?
@@ -474,42 +523,85 @@ pub struct BlitTarget {
 ///
 pub trait Surface: Sized {
     /// Clears some attachments of the target.
-    fn clear(&mut self, color: Option<(f32, f32, f32, f32)>, depth: Option<f32>,
-             stencil: Option<i32>);
+    ///
+    /// If `rect` is `Some`, only the pixels inside this rectangle are cleared and the rest of
+    /// the surface is left untouched. Otherwise the whole surface is cleared.
+    ///
+    /// If the depth attachment contains floating-point values, the depth value is written
+    /// as-is when the backend supports it (OpenGL 3.0 or OpenGL ES 3.0) instead of being
+    /// clamped to `[0, 1]`.
+    fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>,
+             depth: Option<f32>, stencil: Option<i32>);
 
     /// Clears the color attachment of the target.
     fn clear_color(&mut self, red: f32, green: f32, blue: f32, alpha: f32) {
-        self.clear(Some((red, green, blue, alpha)), None, None);
+        self.clear(None, Some((red, green, blue, alpha)), None, None);
+    }
+
+    /// Clears a single color attachment of the target, leaving the other color attachments
+    /// and the depth and stencil buffers untouched.
+    ///
+    /// `index` is the position of the attachment in the list of color attachments of the
+    /// target. Surfaces that have only one color attachment only accept `0`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `index` is out of range.
+    fn clear_color_buffer(&mut self, index: u32, color: (f32, f32, f32, f32)) {
+        assert!(index == 0, "The surface only has one color attachment");
+        self.clear(None, Some(color), None, None);
+    }
+
+    /// Clears the depth buffer of the target, leaving the color attachments and the stencil
+    /// buffer untouched.
+    ///
+    /// This is the depth counterpart of `clear_color_buffer`, and is equivalent to
+    /// `clear_depth`.
+    fn clear_depth_buffer(&mut self, value: f32) {
+        self.clear(None, None, Some(value), None);
+    }
+
+    /// Clears the stencil buffer of the target, leaving the color attachments and the depth
+    /// buffer untouched.
+    ///
+    /// All the bits of the stencil buffer are written, whatever the stencil write masks of the
+    /// previous draw calls. This is equivalent to `clear_stencil`.
+    fn clear_stencil_buffer(&mut self, value: i32) {
+        self.clear(None, None, None, Some(value));
     }
 
     /// Clears the depth attachment of the target.
+    ///
+    /// The color and stencil attachments are left untouched.
     fn clear_depth(&mut self, value: f32) {
-        self.clear(None, Some(value), None);
+        self.clear(None, None, Some(value), None);
     }
 
     /// Clears the stencil attachment of the target.
+    ///
+    /// The color and depth attachments are left untouched.
     fn clear_stencil(&mut self, value: i32) {
-        self.clear(None, None, Some(value));
+        self.clear(None, None, None, Some(value));
     }
 
     /// Clears the color and depth attachments of the target.
     fn clear_color_and_depth(&mut self, color: (f32, f32, f32, f32), depth: f32) {
-        self.clear(Some(color), Some(depth), None);
+        self.clear(None, Some(color), Some(depth), None);
     }
 
     /// Clears the color and stencil attachments of the target.
     fn clear_color_and_stencil(&mut self, color: (f32, f32, f32, f32), stencil: i32) {
-        self.clear(Some(color), None, Some(stencil));
+        self.clear(None, Some(color), None, Some(stencil));
     }
 
     /// Clears the depth and stencil attachments of the target.
     fn clear_depth_and_stencil(&mut self, depth: f32, stencil: i32) {
-        self.clear(None, Some(depth), Some(stencil));
+        self.clear(None, None, Some(depth), Some(stencil));
     }
 
     /// Clears the color, depth and stencil attachments of the target.
     fn clear_all(&mut self, color: (f32, f32, f32, f32), depth: f32, stencil: i32) {
-        self.clear(Some(color), Some(depth), Some(stencil));
+        self.clear(None, Some(color), Some(depth), Some(stencil));
     }
 
     /// Returns the dimensions in pixels of the target.
@@ -554,19 +646,40 @@ pub trait Surface: Sized {
         V: vertex::MultiVerticesSource<'b>, I: index::ToIndicesSource,
         U: uniforms::Uniforms;
 
+    /// Draws `instances` instances of the same geometry.
+    ///
+    /// This is a shortcut for calling `draw` with an `EmptyInstanceAttributes` marker in
+    /// addition to the vertex source. The per-instance data is usually read by the vertex
+    /// shader from a uniform buffer with `gl_InstanceID`.
+    ///
+    /// Returns `Err(DrawError::InstancingNotSupported)` if the backend doesn't support
+    /// instancing.
+    fn draw_instanced<'a, 'b, V, I, U>(&mut self, vertex_buffer: V, index_buffer: &I,
+                                       program: &Program, uniforms: U, instances: usize,
+                                       draw_parameters: &DrawParameters)
+                                       -> Result<(), DrawError>
+                                       where V: vertex::IntoVerticesSource<'b>,
+                                       I: index::ToIndicesSource, U: uniforms::Uniforms
+    {
+        let vertices = (vertex_buffer, vertex::EmptyInstanceAttributes { len: instances });
+        self.draw(vertices, index_buffer, program, uniforms, draw_parameters)
+    }
+
     /// Blits from the default framebuffer.
     fn blit_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget,
-                       filter: uniforms::MagnifySamplerFilter);
+                       filter: uniforms::MagnifySamplerFilter) -> Result<(), BlitError>;
 
     /// Blits from a simple framebuffer.
     fn blit_from_simple_framebuffer(&self, source: &framebuffer::SimpleFrameBuffer,
                                     source_rect: &Rect, target_rect: &BlitTarget,
-                                    filter: uniforms::MagnifySamplerFilter);
+                                    filter: uniforms::MagnifySamplerFilter)
+                                    -> Result<(), BlitError>;
 
     /// Blits from a multi-output framebuffer.
     fn blit_from_multioutput_framebuffer(&self, source: &framebuffer::MultiOutputFrameBuffer,
                                          source_rect: &Rect, target_rect: &BlitTarget,
-                                         filter: uniforms::MagnifySamplerFilter);
+                                         filter: uniforms::MagnifySamplerFilter)
+                                         -> Result<(), BlitError>;
 
     /// Copies a rectangle of pixels from this surface to another surface.
     ///
@@ -580,14 +693,23 @@ pub trait Surface: Sized {
     ///
     /// Note that there is no alpha blending, depth/stencil checking, etc. This function just
     /// copies pixels.
+    ///
+    /// Blitting from a multisample surface to a single-sample one resolves the samples, which
+    /// is the usual way to display something that was rendered with antialiasing. OpenGL
+    /// doesn't support resizing in this situation, and an error is returned if the source and
+    /// target areas don't have the same dimensions.
     #[unstable = "The name will likely change"]
     fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
-                     filter: uniforms::MagnifySamplerFilter) where S: Surface;
+                     filter: uniforms::MagnifySamplerFilter)
+                     -> Result<(), BlitError> where S: Surface;
 
     /// Copies the entire surface to a target surface. See `blit_color`.
+    ///
+    /// With a `target_rect` of the same dimensions as the surface, this resolves a multisample
+    /// surface into a single-sample one.
     #[unstable = "The name will likely change"]
     fn blit_whole_color_to<S>(&self, target: &S, target_rect: &BlitTarget,
-        filter: uniforms::MagnifySamplerFilter) where S: Surface
+        filter: uniforms::MagnifySamplerFilter) -> Result<(), BlitError> where S: Surface
     {
         let src_dim = self.get_dimensions();
         let src_rect = Rect { left: 0, bottom: 0, width: src_dim.0 as u32, height: src_dim.1 as u32 };
@@ -596,13 +718,35 @@ pub trait Surface: Sized {
 
     /// Copies the entire surface to the entire target. See `blit_color`.
     #[unstable = "The name will likely change"]
-    fn fill<S>(&self, target: &S, filter: uniforms::MagnifySamplerFilter) where S: Surface {
+    fn fill<S>(&self, target: &S, filter: uniforms::MagnifySamplerFilter)
+               -> Result<(), BlitError> where S: Surface
+    {
         let src_dim = self.get_dimensions();
         let src_rect = Rect { left: 0, bottom: 0, width: src_dim.0 as u32, height: src_dim.1 as u32 };
         let target_dim = target.get_dimensions();
         let target_rect = BlitTarget { left: 0, bottom: 0, width: target_dim.0 as i32, height: target_dim.1 as i32 };
         self.blit_color(&src_rect, target, &target_rect, filter)
     }
+
+    /// Reads the pixels of a rectangle of the first color attachment of the surface.
+    ///
+    /// This is cheaper than reading the whole surface when you only need a few pixels, for
+    /// example when picking the object under the cursor.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `rect` is outside of the surface.
+    fn read_rect<P, T>(&self, rect: &Rect) -> T
+                       where P: texture::PixelValue + Clone + Send,
+                             T: texture::Texture2dDataSink<Data = P>;
+
+    /// Same as `read_rect`, but writes the pixels in `dest`, row by row starting with the
+    /// bottom row.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `rect` is outside of the surface, or if `dest` is too small.
+    fn read_rect_into<P>(&self, rect: &Rect, dest: &mut [P]) where P: texture::PixelValue;
 }
 
 /// Private trait for framebuffer-like objects that provide attachments.
@@ -617,15 +761,31 @@ pub enum DrawError {
     /// A depth function has been requested but no depth buffer is available.
     NoDepthBuffer,
 
+    /// A stencil test or a stencil operation has been requested but no stencil buffer is
+    /// available.
+    NoStencilBuffer,
+
     /// The type of a vertex attribute in the vertices source doesn't match what the
     /// program requires.
-    AttributeTypeMismatch,
+    AttributeTypeMismatch {
+        /// Name of the attribute.
+        name: String,
+    },
 
     /// One of the attributes required by the program is missing from the vertex format.
     ///
     /// Note that it is perfectly valid to have an attribute in the vertex format that is
     /// not used by the program.
-    AttributeMissing,
+    AttributeMissing {
+        /// Name of the attribute.
+        name: String,
+    },
+
+    /// An attribute is provided by more than one of the vertices sources.
+    AttributeDefinedMultipleTimes {
+        /// Name of the attribute.
+        name: String,
+    },
 
     /// The viewport's dimensions are not supported by the backend.
     ViewportTooLarge,
@@ -639,6 +799,8 @@ pub enum DrawError {
         name: String,
         /// The expected type.
         expected: uniforms::UniformType,
+        /// The type of the value that you passed.
+        got: uniforms::UniformType,
     },
 
     /// Tried to bind a uniform buffer to a single uniform value.
@@ -653,10 +815,19 @@ pub enum DrawError {
         name: String,
     },
 
+    /// Tried to bind a uniform buffer to a name that doesn't correspond to any active uniform
+    /// block of the program.
+    UniformBlockNotFound {
+        /// Name of the block you are trying to bind.
+        name: String,
+    },
+
     /// The layout of the content of the uniform buffer does not match the layout of the block.
     UniformBlockLayoutMismatch {
         /// Name of the block you are trying to bind.
         name: String,
+        /// The reason of the mismatch.
+        err: uniforms::UniformBlockLayoutError,
     },
 
     /// The number of vertices per patch that has been requested is not supported.
@@ -679,6 +850,101 @@ pub enum DrawError {
 
     /// You requested not to draw primitives, but this is not supported by the backend.
     TransformFeedbackNotSupported,
+
+    /// Trying to draw multiple instances, but instancing is not supported by the backend.
+    InstancingNotSupported,
+
+    /// The program uses more textures than the backend can bind at once.
+    TooManyTextures {
+        /// Number of textures that the draw call would bind.
+        requested: usize,
+        /// Value of `GL_MAX_COMBINED_TEXTURE_IMAGE_UNITS`.
+        max: usize,
+    },
+
+    /// Trying to use an advanced blending equation, but `GL_KHR_blend_equation_advanced`
+    /// is not supported by the backend.
+    AdvancedBlendingNotSupported,
+
+    /// Trying to use a base vertex different from `0`, but this is not supported by the backend.
+    ///
+    /// Base vertices require OpenGL 3.2, OpenGL ES 3.2 or `GL_ARB_draw_elements_base_vertex`.
+    BaseVertexNotSupported,
+
+    /// The primitive restart index doesn't fit in the type of the indices.
+    InvalidPrimitiveRestartIndex,
+
+    /// Trying to use a primitive restart index, but this is not supported by the backend.
+    ///
+    /// Any index requires OpenGL 3.1. OpenGL ES 3.0 only supports the maximum value of the
+    /// index type.
+    PrimitiveRestartNotSupported,
+
+    /// Trying to use a sample mask, but this is not supported by the backend.
+    SampleMaskNotSupported,
+
+    /// Trying to use line or polygon smoothing, but this is not supported by the backend.
+    ///
+    /// Smoothing is not available in core profile contexts and in OpenGL ES.
+    SmoothingNotSupported,
+
+    /// Trying to use a polygon mode other than `Fill`, but this is not supported by the backend.
+    ///
+    /// OpenGL ES doesn't support `glPolygonMode`.
+    PolygonModeNotSupported,
+
+    /// Trying to offset lines or points, but this is not supported by the backend.
+    ///
+    /// OpenGL ES only supports `GL_POLYGON_OFFSET_FILL`.
+    PolygonOffsetNotSupported,
+
+    /// One of the vertex attributes uses a type that is not supported by the backend.
+    ///
+    /// Packed attribute types require OpenGL 3.3 or OpenGL ES 3.0.
+    AttributeTypeNotSupported,
+
+    /// The number of work groups requested when dispatching a compute shader is zero, or
+    /// exceeds `GL_MAX_COMPUTE_WORK_GROUP_COUNT` on one of the dimensions.
+    WorkGroupCountNotSupported,
+
+    /// Trying to dispatch a program that doesn't contain a compute shader.
+    NotAComputeProgram,
+
+    /// Trying to dispatch a compute program, but compute shaders are not supported by the
+    /// backend.
+    ///
+    /// Compute shaders require OpenGL 4.3, OpenGL ES 3.1 or `GL_ARB_compute_shader`.
+    ComputeShaderNotSupported,
+
+    /// One of the color attachments of a `MultiOutputFrameBuffer` is bound to a fragment
+    /// shader output that doesn't exist in the program.
+    ///
+    /// Contains the name of the output.
+    FragmentShaderOutputNotFound(String),
+
+    /// Trying to use depth clamping, but this is not supported by the backend.
+    ///
+    /// Depth clamping requires OpenGL 3.2 or `GL_ARB_depth_clamp`.
+    DepthClampNotSupported,
+
+    /// Trying to let the shaders set the size of the points, but this is not supported by
+    /// the backend.
+    ///
+    /// This requires OpenGL 3.2, `GL_ARB_geometry_shader4` or `GL_EXT_geometry_shader4`.
+    ProgramPointSizeNotSupported,
+
+    /// Trying to use conditional rendering, but this is not supported by the backend.
+    ///
+    /// Conditional rendering requires OpenGL 3.0 or `GL_NV_conditional_render`.
+    ConditionalRenderingNotSupported,
+
+    /// The query passed as `DrawParameters::condition` is still active. Call `end` on it
+    /// before drawing.
+    ConditionQueryActive,
+
+    /// The query passed as `DrawParameters::condition` has never been begun and ended, so
+    /// it doesn't have any result.
+    ConditionQueryNeverBegun,
 }
 
 impl std::fmt::Display for DrawError {
@@ -686,18 +952,28 @@ impl std::fmt::Display for DrawError {
         match self {
             &DrawError::NoDepthBuffer => write!(fmt, "A depth function has been requested but no \
                                                       depth buffer is available."),
-            &DrawError::AttributeTypeMismatch => write!(fmt, "The type of a vertex attribute in \
-                                                              the vertices source doesn't match \
-                                                              what the program requires."),
-            &DrawError::AttributeMissing => write!(fmt, "One of the attributes required by the \
-                                                         program is missing from the vertex \
-                                                         format."),
+            &DrawError::NoStencilBuffer => write!(fmt, "A stencil test or operation has been \
+                                                        requested but no stencil buffer is \
+                                                        available."),
+            &DrawError::AttributeTypeMismatch { ref name } => {
+                write!(fmt, "The type of the vertex attribute `{}` in the vertices source \
+                             doesn't match what the program requires.", name)
+            },
+            &DrawError::AttributeMissing { ref name } => {
+                write!(fmt, "The attribute `{}` required by the program is missing from the \
+                             vertex format.", name)
+            },
+            &DrawError::AttributeDefinedMultipleTimes { ref name } => {
+                write!(fmt, "The attribute `{}` is provided by more than one of the vertices \
+                             sources.", name)
+            },
             &DrawError::ViewportTooLarge => write!(fmt, "The viewport's dimensions are not \
                                                          supported by the backend."),
             &DrawError::InvalidDepthRange => write!(fmt, "The depth range is outside of the \
                                                           `(0, 1)` range."),
-            &DrawError::UniformTypeMismatch { ref name, ref expected } => {
-                write!(fmt, "The type of a uniform doesn't match what the program requires.")
+            &DrawError::UniformTypeMismatch { ref name, ref expected, ref got } => {
+                write!(fmt, "The type of the uniform `{}` doesn't match what the program \
+                             requires: expected {:?}, got {:?}.", name, expected, got)
             },
             &DrawError::UniformBufferToValue { ref name } => write!(fmt, "Tried to bind a uniform \
                                                                           buffer to a single \
@@ -705,9 +981,12 @@ impl std::fmt::Display for DrawError {
             &DrawError::UniformValueToBlock { ref name } => {
                 write!(fmt, "Tried to bind a single uniform value to a uniform block.")
             },
-            &DrawError::UniformBlockLayoutMismatch { ref name } => {
+            &DrawError::UniformBlockNotFound { ref name } => {
+                write!(fmt, "The program doesn't have any active uniform block named `{}`.", name)
+            },
+            &DrawError::UniformBlockLayoutMismatch { ref name, ref err } => {
                 write!(fmt, "The layout of the content of the uniform buffer does not match \
-                             the layout of the block.")
+                             the layout of the block `{}`: {}", name, err)
             },
             &DrawError::UnsupportedVerticesPerPatch => write!(fmt, "The number of vertices per \
                                                                     patch that has been requested \
@@ -729,6 +1008,144 @@ impl std::fmt::Display for DrawError {
             &DrawError::TransformFeedbackNotSupported => write!(fmt, "Requested not to draw \
                                                                       primitves, but this is not \
                                                                       supported by the backend."),
+            &DrawError::InstancingNotSupported => write!(fmt, "Trying to draw multiple instances, \
+                                                               but instancing is not supported \
+                                                               by the backend."),
+            &DrawError::TooManyTextures { requested, max } => {
+                write!(fmt, "Trying to bind {} textures, but the backend only supports {} \
+                             texture units (GL_MAX_COMBINED_TEXTURE_IMAGE_UNITS).", requested, max)
+            },
+            &DrawError::AdvancedBlendingNotSupported => write!(fmt, "Trying to use an advanced \
+                                                                     blending equation, but this \
+                                                                     is not supported by the \
+                                                                     backend."),
+            &DrawError::BaseVertexNotSupported => write!(fmt, "Trying to use a base vertex, but \
+                                                               this is not supported by the \
+                                                               backend."),
+            &DrawError::InvalidPrimitiveRestartIndex => write!(fmt, "The primitive restart index \
+                                                                     doesn't fit in the type of \
+                                                                     the indices."),
+            &DrawError::PrimitiveRestartNotSupported => write!(fmt, "Trying to use a primitive \
+                                                                     restart index, but this is \
+                                                                     not supported by the \
+                                                                     backend."),
+            &DrawError::SampleMaskNotSupported => write!(fmt, "Trying to use a sample mask, but \
+                                                               this is not supported by the \
+                                                               backend."),
+            &DrawError::SmoothingNotSupported => write!(fmt, "Trying to use smoothing, but this \
+                                                              is not supported by the \
+                                                              backend."),
+            &DrawError::PolygonModeNotSupported => write!(fmt, "Trying to use a polygon mode \
+                                                                other than `Fill`, but this is \
+                                                                not supported by the backend."),
+            &DrawError::PolygonOffsetNotSupported => write!(fmt, "Trying to offset lines or \
+                                                                  points, but this is not \
+                                                                  supported by the backend."),
+            &DrawError::AttributeTypeNotSupported => write!(fmt, "The type of a vertex attribute \
+                                                                  is not supported by the \
+                                                                  backend."),
+            &DrawError::WorkGroupCountNotSupported => write!(fmt, "The number of work groups \
+                                                                   to dispatch is not \
+                                                                   supported by the \
+                                                                   backend."),
+            &DrawError::NotAComputeProgram => write!(fmt, "The program doesn't contain a \
+                                                           compute shader."),
+            &DrawError::ComputeShaderNotSupported => write!(fmt, "Compute shaders are not \
+                                                                  supported by the \
+                                                                  backend."),
+            &DrawError::FragmentShaderOutputNotFound(ref name) => {
+                write!(fmt, "The program doesn't have any fragment shader output named `{}`.",
+                       name)
+            },
+            &DrawError::DepthClampNotSupported => write!(fmt, "Trying to use depth clamping, \
+                                                               but this is not supported by \
+                                                               the backend."),
+            &DrawError::ProgramPointSizeNotSupported => write!(fmt, "Trying to let the shaders \
+                                                                     set the size of the \
+                                                                     points, but this is not \
+                                                                     supported by the \
+                                                                     backend."),
+            &DrawError::ConditionalRenderingNotSupported => write!(fmt, "Trying to use \
+                                                                         conditional rendering, \
+                                                                         but this is not \
+                                                                         supported by the \
+                                                                         backend."),
+            &DrawError::ConditionQueryActive => write!(fmt, "The query used as the drawing \
+                                                             condition is still active."),
+            &DrawError::ConditionQueryNeverBegun => write!(fmt, "The query used as the drawing \
+                                                                 condition has never been \
+                                                                 begun and ended."),
+        }
+    }
+}
+
+/// Error that can happen while blitting.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BlitError {
+    /// The source or the target is multisampled, and the source and target areas don't have
+    /// the same dimensions.
+    MultisampleDimensionsMismatch,
+}
+
+impl std::fmt::Display for BlitError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            &BlitError::MultisampleDimensionsMismatch => write!(fmt, "Blitting from or to a \
+                                                                      multisample surface \
+                                                                      requires the source and \
+                                                                      target areas to have the \
+                                                                      same dimensions."),
+        }
+    }
+}
+
+/// Error reported by OpenGL through `glGetError`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GlError {
+    /// An unacceptable value was specified for an enumerated argument.
+    InvalidEnum,
+
+    /// A numeric argument is out of range.
+    InvalidValue,
+
+    /// The specified operation is not allowed in the current state.
+    InvalidOperation,
+
+    /// The framebuffer object is not complete.
+    InvalidFramebufferOperation,
+
+    /// There is not enough memory left to execute the command.
+    OutOfMemory,
+
+    /// An attempt has been made to perform an operation that would cause an internal stack
+    /// to underflow.
+    StackUnderflow,
+
+    /// An attempt has been made to perform an operation that would cause an internal stack
+    /// to overflow.
+    StackOverflow,
+
+    /// The context has been lost, for example because of a graphics card reset.
+    ContextLost,
+
+    /// `glGetError` returned a value that glium doesn't know about.
+    Unknown(gl::types::GLenum),
+}
+
+impl std::fmt::Display for GlError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            &GlError::InvalidEnum => write!(fmt, "GL_INVALID_ENUM"),
+            &GlError::InvalidValue => write!(fmt, "GL_INVALID_VALUE"),
+            &GlError::InvalidOperation => write!(fmt, "GL_INVALID_OPERATION"),
+            &GlError::InvalidFramebufferOperation => {
+                write!(fmt, "GL_INVALID_FRAMEBUFFER_OPERATION")
+            },
+            &GlError::OutOfMemory => write!(fmt, "GL_OUT_OF_MEMORY"),
+            &GlError::StackUnderflow => write!(fmt, "GL_STACK_UNDERFLOW"),
+            &GlError::StackOverflow => write!(fmt, "GL_STACK_OVERFLOW"),
+            &GlError::ContextLost => write!(fmt, "GL_CONTEXT_LOST"),
+            &GlError::Unknown(value) => write!(fmt, "Unknown glGetError return value: {}", value),
         }
     }
 }
@@ -751,16 +1168,31 @@ impl Frame {
         }
     }
 
+    /// Copies a rectangle of the frame's color buffer to another surface.
+    ///
+    /// This is the same as `blit_color`, and can be used to capture what has been drawn on
+    /// the frame into a texture, for example for post-processing. Only the color buffer is
+    /// copied.
+    ///
+    /// As everywhere in OpenGL, the `(0, 0)` coordinate of both rectangles is the bottom-left
+    /// corner of the surfaces.
+    pub fn blit_to<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
+                      filter: uniforms::MagnifySamplerFilter)
+                      -> Result<(), BlitError> where S: Surface
+    {
+        self.blit_color(source_rect, target, target_rect, filter)
+    }
+
     /// Stop drawing, and swap the buffers.
     pub fn finish(self) {
     }
 }
 
 impl Surface for Frame {
-    fn clear(&mut self, color: Option<(f32, f32, f32, f32)>, depth: Option<f32>,
-             stencil: Option<i32>)
+    fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>,
+             depth: Option<f32>, stencil: Option<i32>)
     {
-        ops::clear(&self.context, None, color, depth, stencil);
+        ops::clear(&self.context, None, rect, color, depth, stencil);
     }
 
     fn get_dimensions(&self) -> (u32, u32) {
@@ -789,6 +1221,12 @@ impl Surface for Frame {
             return Err(DrawError::NoDepthBuffer);
         }
 
+        if !self.has_stencil_buffer() &&
+            ::draw_parameters::requires_stencil_buffer(draw_parameters)
+        {
+            return Err(DrawError::NoStencilBuffer);
+        }
+
         if let Some(viewport) = draw_parameters.viewport {
             if viewport.width > self.context.capabilities().max_viewport_dims.0
                     as u32
@@ -807,13 +1245,14 @@ impl Surface for Frame {
     }
 
     fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
-                     filter: uniforms::MagnifySamplerFilter) where S: Surface
+                     filter: uniforms::MagnifySamplerFilter)
+                     -> Result<(), BlitError> where S: Surface
     {
         target.blit_from_frame(source_rect, target_rect, filter)
     }
 
     fn blit_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget,
-                       filter: uniforms::MagnifySamplerFilter)
+                       filter: uniforms::MagnifySamplerFilter) -> Result<(), BlitError>
     {
         ops::blit(&self.context, None, self.get_attachments(),
                   gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
@@ -822,6 +1261,7 @@ impl Surface for Frame {
     fn blit_from_simple_framebuffer(&self, source: &framebuffer::SimpleFrameBuffer,
                                     source_rect: &Rect, target_rect: &BlitTarget,
                                     filter: uniforms::MagnifySamplerFilter)
+                                    -> Result<(), BlitError>
     {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
                   gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
@@ -830,10 +1270,22 @@ impl Surface for Frame {
     fn blit_from_multioutput_framebuffer(&self, source: &framebuffer::MultiOutputFrameBuffer,
                                          source_rect: &Rect, target_rect: &BlitTarget,
                                          filter: uniforms::MagnifySamplerFilter)
+                                         -> Result<(), BlitError>
     {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
                   gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
     }
+
+    fn read_rect<P, T>(&self, rect: &Rect) -> T
+                       where P: texture::PixelValue + Clone + Send,
+                             T: texture::Texture2dDataSink<Data = P>
+    {
+        ops::read_rect(None, self.get_dimensions(), rect, &self.context)
+    }
+
+    fn read_rect_into<P>(&self, rect: &Rect, dest: &mut [P]) where P: texture::PixelValue {
+        ops::read_rect_into(None, self.get_dimensions(), rect, dest, &self.context)
+    }
 }
 
 impl FboAttachments for Frame {
@@ -909,17 +1361,17 @@ impl std::convert::From<glutin::CreationError> for GliumCreationError {
 }
 
 #[allow(dead_code)]
-fn get_gl_error(ctxt: &mut context::CommandContext) -> Option<&'static str> {
+fn get_gl_error(ctxt: &mut context::CommandContext) -> Option<GlError> {
     match unsafe { ctxt.gl.GetError() } {
         gl::NO_ERROR => None,
-        gl::INVALID_ENUM => Some("GL_INVALID_ENUM"),
-        gl::INVALID_VALUE => Some("GL_INVALID_VALUE"),
-        gl::INVALID_OPERATION => Some("GL_INVALID_OPERATION"),
-        gl::INVALID_FRAMEBUFFER_OPERATION => Some("GL_INVALID_FRAMEBUFFER_OPERATION"),
-        gl::OUT_OF_MEMORY => Some("GL_OUT_OF_MEMORY"),
-        gl::STACK_UNDERFLOW => Some("GL_STACK_UNDERFLOW"),
-        gl::STACK_OVERFLOW => Some("GL_STACK_OVERFLOW"),
-        gl::CONTEXT_LOST => Some("GL_CONTEXT_LOST"),
-        _ => Some("Unknown glGetError return value")
+        gl::INVALID_ENUM => Some(GlError::InvalidEnum),
+        gl::INVALID_VALUE => Some(GlError::InvalidValue),
+        gl::INVALID_OPERATION => Some(GlError::InvalidOperation),
+        gl::INVALID_FRAMEBUFFER_OPERATION => Some(GlError::InvalidFramebufferOperation),
+        gl::OUT_OF_MEMORY => Some(GlError::OutOfMemory),
+        gl::STACK_UNDERFLOW => Some(GlError::StackUnderflow),
+        gl::STACK_OVERFLOW => Some(GlError::StackOverflow),
+        gl::CONTEXT_LOST => Some(GlError::ContextLost),
+        value => Some(GlError::Unknown(value)),
     }
 }
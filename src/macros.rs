@@ -59,9 +59,28 @@ macro_rules! uniform {
 /// # }
 /// ```
 ///
+/// Each field can be followed by `normalize(true)` to indicate that its integer values must
+/// be normalized when the shader reads them as floating-point. In this case all the fields
+/// must be followed by `normalize(...)`.
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate glium;
+/// # fn main() {
+/// #[derive(Copy, Clone)]
+/// struct Vertex {
+///     position: [f32; 3],
+///     color: [u8; 4],
+/// }
+///
+/// // the shader sees `color` as a `vec4` whose components are between `0.0` and `1.0`
+/// implement_vertex!(Vertex, position normalize(false), color normalize(true));
+/// # }
+/// ```
+///
 #[macro_export]
 macro_rules! implement_vertex {
-    ($struct_name:ident, $($field_name:ident),+) => (
+    ($struct_name:ident, $($field_name:ident normalize($normalize:expr)),+) => (
         impl $crate::vertex::Vertex for $struct_name {
             fn build_bindings() -> $crate::vertex::VertexFormat {
                 use std::borrow::Cow;
@@ -85,6 +104,7 @@ macro_rules! implement_vertex {
                                 let dummy: &$struct_name = unsafe { ::std::mem::transmute(0usize) };
                                 attr_type_of_val(&dummy.$field_name)
                             },
+                            $normalize,
                         )
                     ),+
                 ]
@@ -92,8 +112,107 @@ macro_rules! implement_vertex {
         }
     );
 
+    ($struct_name:ident, $($field_name:ident normalize($normalize:expr)),+,) => (
+        implement_vertex!($struct_name, $($field_name normalize($normalize)),+);
+    );
+
+    ($struct_name:ident, $($field_name:ident),+) => (
+        implement_vertex!($struct_name, $($field_name normalize(false)),+);
+    );
+
+    ($struct_name:ident, $($field_name:ident),+,) => (
+        implement_vertex!($struct_name, $($field_name normalize(false)),+);
+    );
+}
+
+/// Implements the `glium::uniforms::UniformBlock` trait for the given type.
+///
+/// The parameters must be the name of the struct and the names of its fields. The names of the
+/// fields must match the names of the members of the block.
+///
+/// When drawing, the offset of each field is compared with the offset of the corresponding
+/// member of the block, and `UniformBlockLayoutError::OffsetMismatch` is returned if they
+/// differ. Remember that blocks usually follow the `std140` layout, which means that for
+/// example a `vec3` is aligned like a `vec4`.
+///
+/// ## Example
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate glium;
+/// # fn main() {
+/// #[derive(Copy, Clone)]
+/// struct Light {
+///     position: [f32; 3],
+///     _padding: f32,
+///     color: [f32; 3],
+/// }
+///
+/// implement_uniform_block!(Light, position, color);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! implement_uniform_block {
+    ($struct_name:ident, $($field_name:ident),+) => (
+        impl $crate::uniforms::UniformBlock for $struct_name {
+            fn matches(block: &$crate::program::UniformBlock)
+                       -> Result<(), $crate::uniforms::UniformBlockLayoutError>
+            {
+                use $crate::uniforms::UniformBlockLayoutError;
+
+                let fields = [
+                    $(
+                        (
+                            stringify!($field_name),
+                            {
+                                let dummy: &$struct_name = unsafe { ::std::mem::transmute(0usize) };
+                                let dummy_field = &dummy.$field_name;
+                                let dummy_field: usize = unsafe { ::std::mem::transmute(dummy_field) };
+                                dummy_field
+                            }
+                        )
+                    ),+
+                ];
+
+                for member in block.members.iter() {
+                    // arrays are reported as `name[0]`
+                    let name = if member.name.ends_with("[0]") {
+                        &member.name[.. member.name.len() - 3]
+                    } else {
+                        &member.name[..]
+                    };
+
+                    match fields.iter().find(|&&(field, _)| field == name) {
+                        None => return Err(UniformBlockLayoutError::MissingField {
+                            field: name.to_string(),
+                        }),
+                        Some(&(_, offset)) if offset != member.offset => {
+                            return Err(UniformBlockLayoutError::OffsetMismatch {
+                                field: name.to_string(),
+                                expected_offset: member.offset,
+                                actual_offset: offset,
+                            });
+                        },
+                        _ => ()
+                    }
+                }
+
+                let type_size = ::std::mem::size_of::<$struct_name>();
+                if type_size < block.size {
+                    return Err(UniformBlockLayoutError::SizeMismatch {
+                        block_size: block.size,
+                        type_size: type_size,
+                    });
+                }
+
+                Ok(())
+            }
+        }
+    );
+
     ($struct_name:ident, $($field_name:ident),+,) => (
-        implement_vertex!($struct_name, $($field_name),+);
+        implement_uniform_block!($struct_name, $($field_name),+);
     );
 }
 
@@ -208,6 +327,7 @@ macro_rules! program {
                 geometry_shader: _geometry_shader,
                 fragment_shader: _fragment_shader,
                 transform_feedback_varyings: None,
+                frag_data_locations: Vec::new(),
             };
 
             $crate::program::Program::new($context, input)
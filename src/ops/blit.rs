@@ -1,10 +1,11 @@
+use BlitError;
 use BlitTarget;
 use Rect;
 
-use context::Context;
+use context::{Context, CommandContext};
 use ContextExt;
 
-use fbo::FramebufferAttachments;
+use fbo::{self, FramebufferAttachments};
 
 use gl;
 use version::Version;
@@ -13,10 +14,21 @@ use version::Api;
 pub fn blit(context: &Context, source: Option<&FramebufferAttachments>,
             target: Option<&FramebufferAttachments>, mask: gl::types::GLbitfield,
             src_rect: &Rect, target_rect: &BlitTarget, filter: gl::types::GLenum)
+            -> Result<(), BlitError>
 {
     unsafe {
         let mut ctxt = context.make_current();
 
+        // when blitting from or to a multisample framebuffer, OpenGL resolves or replicates the
+        // samples but requires the source and target areas to have exactly the same size
+        if is_multisample(&mut ctxt, source) || is_multisample(&mut ctxt, target) {
+            if src_rect.width as i32 != target_rect.width ||
+               src_rect.height as i32 != target_rect.height
+            {
+                return Err(BlitError::MultisampleDimensionsMismatch);
+            }
+        }
+
         // FIXME: we don't draw on it
         let source = context.framebuffer_objects.as_ref().unwrap()
                             .get_framebuffer_for_drawing(source, &mut ctxt);
@@ -29,62 +41,94 @@ pub fn blit(context: &Context, source: Option<&FramebufferAttachments>,
             ctxt.state.enabled_scissor_test = false;
         }
 
+        let src_x0 = src_rect.left as gl::types::GLint;
+        let src_y0 = src_rect.bottom as gl::types::GLint;
+        let src_x1 = (src_rect.left + src_rect.width) as gl::types::GLint;
+        let src_y1 = (src_rect.bottom + src_rect.height) as gl::types::GLint;
+
+        // the dimensions of the target can be negative, in which case `x1 < x0` or `y1 < y0`
+        // and OpenGL mirrors the image
+        let dst_x0 = target_rect.left as gl::types::GLint;
+        let dst_y0 = target_rect.bottom as gl::types::GLint;
+        let dst_x1 = (target_rect.left as i32 + target_rect.width) as gl::types::GLint;
+        let dst_y1 = (target_rect.bottom as i32 + target_rect.height) as gl::types::GLint;
+
         // trying to do a named blit if possible
         if ctxt.version >= &Version(Api::Gl, 4, 5) {
-            ctxt.gl.BlitNamedFramebuffer(source, target,
-                src_rect.left as gl::types::GLint,
-                src_rect.bottom as gl::types::GLint,
-                (src_rect.left + src_rect.width) as gl::types::GLint,
-                (src_rect.bottom + src_rect.height) as gl::types::GLint,
-                target_rect.left as gl::types::GLint, target_rect.bottom as gl::types::GLint,
-                (target_rect.left as i32 + target_rect.width) as gl::types::GLint,
-                (target_rect.bottom as i32 + target_rect.height) as gl::types::GLint, mask, filter);
-
-            return;
+            ctxt.gl.BlitNamedFramebuffer(source, target, src_x0, src_y0, src_x1, src_y1,
+                                         dst_x0, dst_y0, dst_x1, dst_y1, mask, filter);
+
+            return Ok(());
         }
 
-        // binding source framebuffer
-        if ctxt.state.read_framebuffer != source {
-            if ctxt.version >= &Version(Api::Gl, 3, 0) {
-                ctxt.gl.BindFramebuffer(gl::READ_FRAMEBUFFER, source);
-                ctxt.state.read_framebuffer = source;
+        // binding the framebuffers and doing the blit
+        if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.version >= &Version(Api::GlEs, 3, 0) {
+            fbo::bind_framebuffer(&mut ctxt, source, false, true);
+            fbo::bind_framebuffer(&mut ctxt, target, true, false);
 
-            } else {
+            ctxt.gl.BlitFramebuffer(src_x0, src_y0, src_x1, src_y1,
+                                    dst_x0, dst_y0, dst_x1, dst_y1, mask, filter);
+
+        } else {
+            // `bind_framebuffer` would bind both to `GL_FRAMEBUFFER_EXT` here, so the read and
+            // draw targets of `EXT_framebuffer_blit` are bound explicitly
+            if ctxt.state.read_framebuffer != source {
                 ctxt.gl.BindFramebufferEXT(gl::READ_FRAMEBUFFER_EXT, source);
                 ctxt.state.read_framebuffer = source;
             }
-        }
 
-        // binding target framebuffer
-        if ctxt.state.draw_framebuffer != target {
-            if ctxt.version >= &Version(Api::Gl, 3, 0) {
-                ctxt.gl.BindFramebuffer(gl::DRAW_FRAMEBUFFER, target);
-                ctxt.state.draw_framebuffer = target;
-
-            } else {
+            if ctxt.state.draw_framebuffer != target {
                 ctxt.gl.BindFramebufferEXT(gl::DRAW_FRAMEBUFFER_EXT, target);
                 ctxt.state.draw_framebuffer = target;
             }
+
+            ctxt.gl.BlitFramebufferEXT(src_x0, src_y0, src_x1, src_y1,
+                                       dst_x0, dst_y0, dst_x1, dst_y1, mask, filter);
         }
+    }
 
-        // doing the blit
-        if ctxt.version >= &Version(Api::Gl, 3, 0) {
-            ctxt.gl.BlitFramebuffer(src_rect.left as gl::types::GLint,
-                src_rect.bottom as gl::types::GLint,
-                (src_rect.left + src_rect.width) as gl::types::GLint,
-                (src_rect.bottom + src_rect.height) as gl::types::GLint,
-                target_rect.left as gl::types::GLint, target_rect.bottom as gl::types::GLint,
-                (target_rect.left as i32 + target_rect.width) as gl::types::GLint,
-                (target_rect.bottom as i32 + target_rect.height) as gl::types::GLint, mask, filter);
+    Ok(())
+}
 
-        } else {
-            ctxt.gl.BlitFramebufferEXT(src_rect.left as gl::types::GLint,
-                src_rect.bottom as gl::types::GLint,
-                (src_rect.left + src_rect.width) as gl::types::GLint,
-                (src_rect.bottom + src_rect.height) as gl::types::GLint,
-                target_rect.left as gl::types::GLint, target_rect.bottom as gl::types::GLint,
-                (target_rect.left as i32 + target_rect.width) as gl::types::GLint,
-                (target_rect.bottom as i32 + target_rect.height) as gl::types::GLint, mask, filter);
+/// Returns true if the framebuffer with these attachments has multiple samples per pixel.
+unsafe fn is_multisample(ctxt: &mut CommandContext, attachments: Option<&FramebufferAttachments>)
+                         -> bool
+{
+    let attachments = match attachments {
+        Some(a) => a,
+        None => return ctxt.capabilities.samples.is_some(),
+    };
+
+    attachments.colors.iter().any(|&(_, ref attachment)| {
+        match attachment {
+            &fbo::Attachment::Texture { bind_point: gl::TEXTURE_2D_MULTISAMPLE, .. } => true,
+            &fbo::Attachment::Texture { bind_point: gl::TEXTURE_2D_MULTISAMPLE_ARRAY, .. } => true,
+            &fbo::Attachment::RenderBuffer(id) => get_renderbuffer_samples(ctxt, id) > 1,
+            _ => false,
         }
+    })
+}
+
+/// Queries the number of samples of a render buffer.
+unsafe fn get_renderbuffer_samples(ctxt: &mut CommandContext, id: gl::types::GLuint)
+                                   -> gl::types::GLint
+{
+    let mut value = 0;
+
+    if ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.extensions.gl_arb_direct_state_access {
+        ctxt.gl.GetNamedRenderbufferParameteriv(id, gl::RENDERBUFFER_SAMPLES, &mut value);
+
+    } else if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+              ctxt.version >= &Version(Api::GlEs, 3, 0)
+    {
+        if ctxt.state.renderbuffer != id {
+            ctxt.gl.BindRenderbuffer(gl::RENDERBUFFER, id);
+            ctxt.state.renderbuffer = id;
+        }
+
+        ctxt.gl.GetRenderbufferParameteriv(gl::RENDERBUFFER, gl::RENDERBUFFER_SAMPLES,
+                                           &mut value);
     }
+
+    value
 }
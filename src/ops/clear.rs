@@ -4,6 +4,7 @@ use context::Context;
 use ContextExt;
 
 use Surface;
+use Rect;
 
 use Api;
 use version::Version;
@@ -11,7 +12,8 @@ use gl;
 
 
 pub fn clear(context: &Context, framebuffer: Option<&FramebufferAttachments>,
-             color: Option<(f32, f32, f32, f32)>, depth: Option<f32>, stencil: Option<i32>)
+             rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>, depth: Option<f32>,
+             stencil: Option<i32>)
 {
     unsafe {
         let mut ctxt = context.make_current();
@@ -26,9 +28,25 @@ pub fn clear(context: &Context, framebuffer: Option<&FramebufferAttachments>,
             ctxt.state.enabled_rasterizer_discard = false;
         }
 
-        if ctxt.state.enabled_scissor_test {
-            ctxt.gl.Disable(gl::SCISSOR_TEST);
-            ctxt.state.enabled_scissor_test = false;
+        if let Some(rect) = rect {
+            let rect = (rect.left as gl::types::GLint, rect.bottom as gl::types::GLint,
+                        rect.width as gl::types::GLsizei, rect.height as gl::types::GLsizei);
+
+            if ctxt.state.scissor != Some(rect) {
+                ctxt.gl.Scissor(rect.0, rect.1, rect.2, rect.3);
+                ctxt.state.scissor = Some(rect);
+            }
+
+            if !ctxt.state.enabled_scissor_test {
+                ctxt.gl.Enable(gl::SCISSOR_TEST);
+                ctxt.state.enabled_scissor_test = true;
+            }
+
+        } else {
+            if ctxt.state.enabled_scissor_test {
+                ctxt.gl.Disable(gl::SCISSOR_TEST);
+                ctxt.state.enabled_scissor_test = false;
+            }
         }
 
         let mut flags = 0;
@@ -39,6 +57,11 @@ pub fn clear(context: &Context, framebuffer: Option<&FramebufferAttachments>,
 
             flags |= gl::COLOR_BUFFER_BIT;
 
+            if ctxt.state.color_mask != (true, true, true, true) {
+                ctxt.gl.ColorMask(gl::TRUE, gl::TRUE, gl::TRUE, gl::TRUE);
+                ctxt.state.color_mask = (true, true, true, true);
+            }
+
             if ctxt.state.clear_color != color {
                 ctxt.gl.ClearColor(color.0, color.1, color.2, color.3);
                 ctxt.state.clear_color = color;
@@ -48,37 +71,113 @@ pub fn clear(context: &Context, framebuffer: Option<&FramebufferAttachments>,
         if let Some(depth) = depth {
             let depth = depth as gl::types::GLclampf;
 
-            flags |= gl::DEPTH_BUFFER_BIT;
-
-            if ctxt.state.clear_depth != depth {
-                if ctxt.version >= &Version(Api::Gl, 1, 0) {
-                    ctxt.gl.ClearDepth(depth as gl::types::GLclampd);
-                } else if ctxt.version >= &Version(Api::GlEs, 2, 0) {
-                    ctxt.gl.ClearDepthf(depth);
-                } else {
-                    unreachable!();
-                }
-
-                ctxt.state.clear_depth = depth;
-            }
-
             if !ctxt.state.depth_mask {
                 ctxt.gl.DepthMask(gl::TRUE);
                 ctxt.state.depth_mask = true;
             }
+
+            // `glClearBufferfv` doesn't clamp the value if the depth buffer contains floats,
+            // contrary to `glClearDepth`
+            if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+               ctxt.version >= &Version(Api::GlEs, 3, 0)
+            {
+                ctxt.gl.ClearBufferfv(gl::DEPTH, 0, &depth);
+
+            } else {
+                flags |= gl::DEPTH_BUFFER_BIT;
+
+                if ctxt.state.clear_depth != depth {
+                    if ctxt.version >= &Version(Api::Gl, 1, 0) {
+                        ctxt.gl.ClearDepth(depth as gl::types::GLclampd);
+                    } else if ctxt.version >= &Version(Api::GlEs, 2, 0) {
+                        ctxt.gl.ClearDepthf(depth);
+                    } else {
+                        unreachable!();
+                    }
+
+                    ctxt.state.clear_depth = depth;
+                }
+            }
         }
 
         if let Some(stencil) = stencil {
             let stencil = stencil as gl::types::GLint;
 
-            flags |= gl::STENCIL_BUFFER_BIT;
+            // the stencil write mask of the last draw call would also apply to the clear
+            if ctxt.state.stencil_mask_front != 0xffffffff ||
+               ctxt.state.stencil_mask_back != 0xffffffff
+            {
+                ctxt.gl.StencilMask(0xffffffff);
+                ctxt.state.stencil_mask_front = 0xffffffff;
+                ctxt.state.stencil_mask_back = 0xffffffff;
+            }
+
+            if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+               ctxt.version >= &Version(Api::GlEs, 3, 0)
+            {
+                ctxt.gl.ClearBufferiv(gl::STENCIL, 0, &stencil);
+
+            } else {
+                flags |= gl::STENCIL_BUFFER_BIT;
 
-            if ctxt.state.clear_stencil != stencil {
-                ctxt.gl.ClearStencil(stencil);
-                ctxt.state.clear_stencil = stencil;
+                if ctxt.state.clear_stencil != stencil {
+                    ctxt.gl.ClearStencil(stencil);
+                    ctxt.state.clear_stencil = stencil;
+                }
             }
         }
 
-        ctxt.gl.Clear(flags);
+        if flags != 0 {
+            ctxt.gl.Clear(flags);
+        }
+    }
+}
+
+/// Clears a single color attachment of a framebuffer, leaving the other attachments and the
+/// depth and stencil buffers untouched.
+///
+/// `index` is the position of the attachment in `framebuffer.colors`.
+pub fn clear_color_buffer(context: &Context, framebuffer: &FramebufferAttachments, index: u32,
+                          color: (f32, f32, f32, f32))
+{
+    // without `glClearBuffer`, we clear a framebuffer that only contains this attachment
+    if !(context.get_version() >= &Version(Api::Gl, 3, 0)) &&
+       !(context.get_version() >= &Version(Api::GlEs, 3, 0))
+    {
+        let attachment = framebuffer.colors[index as usize].1;
+        let attachments = FramebufferAttachments {
+            colors: vec![(0, attachment)],
+            depth_stencil: fbo::FramebufferDepthStencilAttachments::None,
+        };
+
+        clear(context, Some(&attachments), None, Some(color), None, None);
+        return;
+    }
+
+    unsafe {
+        let mut ctxt = context.make_current();
+
+        let fbo_id = context.framebuffer_objects.as_ref().unwrap()
+                            .get_framebuffer_for_drawing(Some(framebuffer), &mut ctxt);
+
+        fbo::bind_framebuffer(&mut ctxt, fbo_id, true, false);
+
+        if ctxt.state.enabled_rasterizer_discard {
+            ctxt.gl.Disable(gl::RASTERIZER_DISCARD);
+            ctxt.state.enabled_rasterizer_discard = false;
+        }
+
+        if ctxt.state.enabled_scissor_test {
+            ctxt.gl.Disable(gl::SCISSOR_TEST);
+            ctxt.state.enabled_scissor_test = false;
+        }
+
+        if ctxt.state.color_mask != (true, true, true, true) {
+            ctxt.gl.ColorMask(gl::TRUE, gl::TRUE, gl::TRUE, gl::TRUE);
+            ctxt.state.color_mask = (true, true, true, true);
+        }
+
+        let color = [color.0, color.1, color.2, color.3];
+        ctxt.gl.ClearBufferfv(gl::COLOR, index as gl::types::GLint, color.as_ptr());
     }
 }
@@ -0,0 +1,63 @@
+use DrawError;
+use Handle;
+use Program;
+use GlObject;
+
+use context::Context;
+use ContextExt;
+use ProgramExt;
+
+use ops::draw::bind_uniforms;
+use uniforms::Uniforms;
+use sync;
+use gl;
+
+/// Dispatches a compute program with the given number of work groups.
+pub fn dispatch_compute<U>(context: &Context, program: &Program, uniforms: U,
+                           x: u32, y: u32, z: u32) -> Result<(), DrawError>
+                           where U: Uniforms
+{
+    if !program.is_compute() {
+        return Err(DrawError::NotAComputeProgram);
+    }
+
+    let max = match context.capabilities().max_compute_work_group_count {
+        Some(max) => max,
+        None => return Err(DrawError::ComputeShaderNotSupported)
+    };
+
+    if x == 0 || y == 0 || z == 0 || x > max.0 as u32 || y > max.1 as u32 || z > max.2 as u32 {
+        return Err(DrawError::WorkGroupCountNotSupported);
+    }
+
+    let mut ctxt = context.make_current();
+
+    // binding the program
+    unsafe {
+        let program_id = program.get_id();
+        if ctxt.state.program != program_id {
+            match program_id {
+                Handle::Id(id) => ctxt.gl.UseProgram(id),
+                Handle::Handle(id) => ctxt.gl.UseProgramObjectARB(id),
+            }
+            ctxt.state.program = program_id;
+        }
+    }
+
+    let fences = try!(bind_uniforms(context, &mut ctxt, program, uniforms));
+
+    unsafe {
+        ctxt.gl.DispatchCompute(x, y, z);
+
+        // making the writes of the shader visible to the commands that follow, for example
+        // when mapping a shader storage buffer
+        ctxt.gl.MemoryBarrier(gl::ALL_BARRIER_BITS);
+
+        // fulfilling the fences
+        for fence in fences.into_iter() {
+            fence.send(sync::new_linear_sync_fence_if_supported(&mut ctxt).unwrap()).unwrap();
+        }
+    }
+
+    Ok(())
+}
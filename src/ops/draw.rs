@@ -1,3 +1,4 @@
+use std::ffi;
 use std::ptr;
 use std::sync::mpsc::Sender;
 use std::collections::HashMap;
@@ -14,17 +15,18 @@ use fbo::{self, FramebufferAttachments};
 use sync;
 use uniforms::{Uniforms, UniformValue, SamplerBehavior};
 use sampler_object::SamplerObject;
-use {Program, GlObject, ToGlEnum};
+use {Program, ProgramExt, GlObject, ToGlEnum};
 use index::{self, IndicesSource};
 use vertex::{MultiVerticesSource, VerticesSource};
 
 use draw_parameters::DrawParameters;
 use draw_parameters::{BlendingFunction, BackfaceCullingMode};
-use draw_parameters::{DepthTest, PolygonMode};
+use draw_parameters::{DepthTest, PolygonMode, PolygonOffset, Smooth};
 use draw_parameters::{StencilTest};
 use Rect;
 
 use program;
+use vertex_array_object;
 use libc;
 use {gl, context, draw_parameters};
 use version::Version;
@@ -69,13 +71,22 @@ pub fn draw<'a, I, U, V>(context: &Context, framebuffer: Option<&FramebufferAtta
         let mut instances_count: Option<usize> = None;
         for src in vertex_buffers.iter() {
             match src {
-                &VerticesSource::VertexBuffer(ref buffer, _, _, true) => {
+                &VerticesSource::VertexBuffer(_, _, len, true) => {
                     if let Some(curr) = instances_count {
-                        if curr != buffer.len() {
+                        if curr != len {
+                            return Err(DrawError::InstancesCountMismatch);
+                        }
+                    } else {
+                        instances_count = Some(len);
+                    }
+                },
+                &VerticesSource::Marker { len, per_instance: true } => {
+                    if let Some(curr) = instances_count {
+                        if curr != len {
                             return Err(DrawError::InstancesCountMismatch);
                         }
                     } else {
-                        instances_count = Some(buffer.len());
+                        instances_count = Some(len);
                     }
                 },
                 _ => ()
@@ -84,6 +95,48 @@ pub fn draw<'a, I, U, V>(context: &Context, framebuffer: Option<&FramebufferAtta
         instances_count
     };
 
+    if instances_count.is_some() && context.get_version() < &Version(Api::Gl, 3, 1) &&
+       !context.get_extensions().gl_arb_instanced_arrays
+    {
+        return Err(DrawError::InstancingNotSupported);
+    }
+
+    // per-instance buffers additionally require `glVertexAttribDivisor`
+    if vertex_buffers.iter().any(|src| match src {
+        &VerticesSource::VertexBuffer(_, _, _, true) => true,
+        _ => false
+    }) {
+        if context.get_version() < &Version(Api::Gl, 3, 3) &&
+           context.get_version() < &Version(Api::GlEs, 3, 0) &&
+           !context.get_extensions().gl_arb_instanced_arrays
+        {
+            return Err(DrawError::InstancingNotSupported);
+        }
+    }
+
+    // packed attribute types require OpenGL 3.3 or OpenGL ES 3.0
+    if !(context.get_version() >= &Version(Api::Gl, 3, 3)) &&
+       !(context.get_version() >= &Version(Api::GlEs, 3, 0))
+    {
+        for src in vertex_buffers.iter() {
+            if let &VerticesSource::VertexBuffer(ref buffer, _, _, _) = src {
+                if buffer.get_bindings().iter().any(|&(_, _, ty, _)| ty.is_packed()) {
+                    return Err(DrawError::AttributeTypeNotSupported);
+                }
+            }
+        }
+    }
+
+    // checking the vertex formats against the attributes of the program
+    if draw_parameters.validate_attributes {
+        let formats = vertex_buffers.iter().filter_map(|src| match src {
+            &VerticesSource::VertexBuffer(ref buffer, _, _, _) => Some(buffer.get_bindings()),
+            &VerticesSource::Marker { .. } => None
+        }).collect::<Vec<_>>();
+
+        try!(vertex_array_object::check_attributes(program, &formats));
+    }
+
     // handling tessellation
     let vertices_per_patch = match indices.get_primitives_type() {
         index::PrimitiveType::Patches { vertices_per_patch } => {
@@ -114,6 +167,32 @@ pub fn draw<'a, I, U, V>(context: &Context, framebuffer: Option<&FramebufferAtta
         },
     };
 
+    // handling primitive restart
+    let primitive_restart = match (draw_parameters.primitive_restart_index,
+                                   indices.get_indices_type())
+    {
+        (Some(restart_index), Some(indices_type)) => {
+            let max = match indices_type {
+                index::IndexType::U8 => 0xff,
+                index::IndexType::U16 => 0xffff,
+                index::IndexType::U32 => 0xffffffff,
+            };
+
+            if restart_index > max {
+                return Err(DrawError::InvalidPrimitiveRestartIndex);
+            }
+
+            if context.get_version() >= &Version(Api::Gl, 3, 1) {
+                Some(PrimitiveRestart::Index(restart_index))
+            } else if context.get_version() >= &Version(Api::GlEs, 3, 0) && restart_index == max {
+                Some(PrimitiveRestart::FixedIndex)
+            } else {
+                return Err(DrawError::PrimitiveRestartNotSupported);
+            }
+        },
+        _ => None
+    };
+
     // sending the command
     let mut ctxt = context.make_current();
 
@@ -127,8 +206,13 @@ pub fn draw<'a, I, U, V>(context: &Context, framebuffer: Option<&FramebufferAtta
 
         let mut binder = context.vertex_array_objects.start(&mut ctxt, program, ib_id);
 
-        for &VerticesSource::VertexBuffer(ref buffer, offset, _, per_instance) in &vertex_buffers {
-            binder = binder.add(buffer, offset, if per_instance { Some(1) } else { None });
+        for src in &vertex_buffers {
+            match src {
+                &VerticesSource::VertexBuffer(ref buffer, offset, _, per_instance) => {
+                    binder = binder.add(buffer, offset, if per_instance { Some(1) } else { None });
+                },
+                &VerticesSource::Marker { .. } => ()
+            }
         }
 
         binder.bind();
@@ -156,58 +240,7 @@ pub fn draw<'a, I, U, V>(context: &Context, framebuffer: Option<&FramebufferAtta
     // building the list of uniforms binders and the fences that must be fulfilled
     // TODO: panic if uniforms of the program are not found in the parameter
     let fences = {
-        let mut active_texture = 0;
-        let mut active_buffer_binding = 0;
-
-        let mut fences = Vec::new();
-
-        let mut visiting_result = Ok(());
-        uniforms.visit_values(|name, value| {
-            if visiting_result.is_err() { return; }
-
-            if let Some(uniform) = program.get_uniform(name) {
-                assert!(uniform.size.is_none(), "Uniform arrays not supported yet");
-
-                if !value.is_usable_with(&uniform.ty) {
-                    visiting_result = Err(DrawError::UniformTypeMismatch {
-                        name: name.to_string(),
-                        expected: uniform.ty,
-                    });
-                    return;
-                }
-
-                match bind_uniform(&mut ctxt, &mut context.samplers.borrow_mut(),
-                                   value, uniform.location,
-                                   &mut active_texture, name)
-                {
-                    Ok(_) => (),
-                    Err(e) => {
-                        visiting_result = Err(e);
-                        return;
-                    }
-                };
-
-            } else if let Some(block) = program.get_uniform_blocks().get(name) {
-                let fence = match bind_uniform_block(&mut ctxt, value, block,
-                                                     program.get_id(),
-                                                    &mut active_buffer_binding, name)
-                {
-                    Ok(f) => f,
-                    Err(e) => {
-                        visiting_result = Err(e);
-                        return;
-                    }
-                };
-
-                if let Some(fence) = fence {
-                    fences.push(fence);
-                }
-            }
-        });
-
-        if let Err(e) = visiting_result {
-            return Err(e);
-        }
+        let mut fences = try!(bind_uniforms(context, &mut ctxt, program, uniforms));
 
         // adding the vertex buffer and index buffer to the list of fences
         for vertex_buffer in vertex_buffers.iter_mut() {
@@ -216,7 +249,8 @@ pub fn draw<'a, I, U, V>(context: &Context, framebuffer: Option<&FramebufferAtta
                     if let Some(fence) = buffer.add_fence() {
                         fences.push(fence);
                     }
-                }
+                },
+                &mut VerticesSource::Marker { .. } => ()
             };
         }
         match &mut indices {
@@ -236,75 +270,70 @@ pub fn draw<'a, I, U, V>(context: &Context, framebuffer: Option<&FramebufferAtta
         sync_depth(&mut ctxt, draw_parameters.depth_test, draw_parameters.depth_write,
                    draw_parameters.depth_range);
         sync_stencil(&mut ctxt, &draw_parameters);
+        sync_color_mask(&mut ctxt, draw_parameters.color_mask);
+        sync_depth_clamp(&mut ctxt, draw_parameters.depth_clamp);
         sync_blending(&mut ctxt, draw_parameters.blending_function);
         sync_line_width(&mut ctxt, draw_parameters.line_width);
-        sync_point_size(&mut ctxt, draw_parameters.point_size);
+        sync_point_size(&mut ctxt, draw_parameters.point_size,
+                        draw_parameters.program_point_size);
         sync_polygon_mode(&mut ctxt, draw_parameters.backface_culling, draw_parameters.polygon_mode);
+        sync_polygon_offset(&mut ctxt, draw_parameters.polygon_offset);
+        sync_smooth(&mut ctxt, draw_parameters.smooth);
         sync_multisampling(&mut ctxt, draw_parameters.multisampling);
+        sync_alpha_to_coverage(&mut ctxt, draw_parameters.alpha_to_coverage);
+        sync_sample_mask(&mut ctxt, draw_parameters.sample_mask);
         sync_dithering(&mut ctxt, draw_parameters.dithering);
         sync_viewport_scissor(&mut ctxt, draw_parameters.viewport, draw_parameters.scissor,
                               dimensions);
         sync_rasterizer_discard(&mut ctxt, draw_parameters.draw_primitives);
         sync_vertices_per_patch(&mut ctxt, vertices_per_patch);
+        sync_primitive_restart(&mut ctxt, primitive_restart);
+        sync_framebuffer_srgb(&mut ctxt, draw_parameters.srgb && !program.has_srgb_output());
+    }
 
-        if !program.has_srgb_output() {
-            if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.extensions.gl_arb_framebuffer_srgb ||
-               ctxt.extensions.gl_ext_framebuffer_srgb
-            {
-                if !ctxt.state.enabled_framebuffer_srgb {
-                    ctxt.gl.Enable(gl::FRAMEBUFFER_SRGB);
-                    ctxt.state.enabled_framebuffer_srgb = true;
-                }
-            }
+    if let &IndicesSource::NoIndices { .. } = &indices {
+        if vertices_count.is_none() {
+            return Err(DrawError::VerticesSourcesLengthMismatch);
         }
     }
 
     // drawing
     {
+        if let Some(query) = draw_parameters.condition {
+            unsafe {
+                if ctxt.version >= &Version(Api::Gl, 3, 0) {
+                    ctxt.gl.BeginConditionalRender(query.get_id(), gl::QUERY_WAIT);
+                } else if ctxt.extensions.gl_nv_conditional_render {
+                    ctxt.gl.BeginConditionalRenderNV(query.get_id(), gl::QUERY_WAIT_NV);
+                } else {
+                    unreachable!();
+                }
+            }
+        }
+
         match &indices {
             &IndicesSource::IndexBuffer { ref buffer, offset, length, .. } => {
                 let ptr: *const u8 = ptr::null_mut();
                 let ptr = unsafe { ptr.offset((offset * buffer.get_indices_type().get_size()) as isize) };
 
-                unsafe {
-                    if let Some(instances_count) = instances_count {
-                        ctxt.gl.DrawElementsInstanced(buffer.get_primitives_type().to_glenum(),
-                                                      length as gl::types::GLsizei,
-                                                      buffer.get_indices_type().to_glenum(),
-                                                      ptr as *const libc::c_void,
-                                                      instances_count as gl::types::GLsizei);
-                    } else {
-                        ctxt.gl.DrawElements(buffer.get_primitives_type().to_glenum(),
-                                             length as gl::types::GLsizei,
-                                             buffer.get_indices_type().to_glenum(),
-                                             ptr as *const libc::c_void);
-                    }
-                }
+                draw_elements(&mut ctxt, buffer.get_primitives_type().to_glenum(),
+                              length as gl::types::GLsizei,
+                              buffer.get_indices_type().to_glenum(),
+                              ptr as *const libc::c_void, instances_count,
+                              draw_parameters.base_vertex);
             },
 
             &IndicesSource::Buffer { ref pointer, primitives, offset, length } => {
                 assert!(offset == 0);       // not yet implemented
 
-                unsafe {
-                    if let Some(instances_count) = instances_count {
-                        ctxt.gl.DrawElementsInstanced(primitives.to_glenum(),
-                                                      length as gl::types::GLsizei,
-                                                      <I as index::Index>::get_type().to_glenum(),
-                                                      pointer.as_ptr() as *const gl::types::GLvoid,
-                                                      instances_count as gl::types::GLsizei);
-                    } else {
-                        ctxt.gl.DrawElements(primitives.to_glenum(), length as gl::types::GLsizei,
-                                             <I as index::Index>::get_type().to_glenum(),
-                                             pointer.as_ptr() as *const gl::types::GLvoid);
-                    }
-                }
+                draw_elements(&mut ctxt, primitives.to_glenum(), length as gl::types::GLsizei,
+                              <I as index::Index>::get_type().to_glenum(),
+                              pointer.as_ptr() as *const libc::c_void, instances_count,
+                              draw_parameters.base_vertex);
             },
 
             &IndicesSource::NoIndices { primitives } => {
-                let vertices_count = match vertices_count {
-                    Some(c) => c,
-                    None => return Err(DrawError::VerticesSourcesLengthMismatch)
-                };
+                let vertices_count = vertices_count.unwrap();
 
                 unsafe {
                     if let Some(instances_count) = instances_count {
@@ -318,6 +347,18 @@ pub fn draw<'a, I, U, V>(context: &Context, framebuffer: Option<&FramebufferAtta
                 }
             },
         };
+
+        if draw_parameters.condition.is_some() {
+            unsafe {
+                if ctxt.version >= &Version(Api::Gl, 3, 0) {
+                    ctxt.gl.EndConditionalRender();
+                } else if ctxt.extensions.gl_nv_conditional_render {
+                    ctxt.gl.EndConditionalRenderNV();
+                } else {
+                    unreachable!();
+                }
+            }
+        }
     };
 
     unsafe {
@@ -330,6 +371,118 @@ pub fn draw<'a, I, U, V>(context: &Context, framebuffer: Option<&FramebufferAtta
     Ok(())
 }
 
+/// Binds the uniforms of a program and returns the fences that must be fulfilled once the
+/// command that uses them has been submitted.
+pub fn bind_uniforms<U>(context: &Context, ctxt: &mut context::CommandContext, program: &Program,
+                        uniforms: U) -> Result<Vec<Sender<sync::LinearSyncFence>>, DrawError>
+                        where U: Uniforms
+{
+    let mut active_texture = 0;
+    let mut active_buffer_binding = 0;
+    let mut active_storage_binding = 0;
+
+    // textures beyond the number of texture units are counted but not bound, so that
+    // the error reports how many were requested
+    let max_textures = ctxt.capabilities.max_combined_texture_image_units as usize;
+    let mut textures_count = 0;
+
+    let mut fences = Vec::new();
+
+    let mut visiting_result = Ok(());
+    uniforms.visit_values(|name, value| {
+        if visiting_result.is_err() { return; }
+
+        if let Some(uniform) = program.get_uniform(name) {
+            // buffers can only be bound to blocks
+            let got = match value.get_type() {
+                Some(ty) => ty,
+                None => {
+                    visiting_result = Err(DrawError::UniformBufferToValue {
+                        name: name.to_string(),
+                    });
+                    return;
+                }
+            };
+
+            // passing multiple values to a uniform that isn't an array is an error
+            let is_array_mismatch = uniform.size.is_none() &&
+                                    value.get_array_len().map(|l| l > 1).unwrap_or(false);
+
+            if !value.is_usable_with(&uniform.ty) || is_array_mismatch {
+                visiting_result = Err(DrawError::UniformTypeMismatch {
+                    name: name.to_string(),
+                    expected: uniform.ty,
+                    got: got,
+                });
+                return;
+            }
+
+            if is_texture(value) {
+                textures_count += 1;
+                if textures_count > max_textures {
+                    return;
+                }
+            }
+
+            match bind_uniform(ctxt, &mut context.samplers.borrow_mut(),
+                               program, value, uniform.location,
+                               &mut active_texture, name)
+            {
+                Ok(_) => (),
+                Err(e) => {
+                    visiting_result = Err(e);
+                    return;
+                }
+            };
+
+        } else if let Some(block) = program.get_uniform_blocks().get(name) {
+            let fence = match bind_uniform_block(ctxt, value, block,
+                                                 program.get_id(),
+                                                 &mut active_buffer_binding, name)
+            {
+                Ok(f) => f,
+                Err(e) => {
+                    visiting_result = Err(e);
+                    return;
+                }
+            };
+
+            if let Some(fence) = fence {
+                fences.push(fence);
+            }
+
+        } else if let &UniformValue::ShaderStorageBlock(_) = value {
+            let fence = match bind_shader_storage_block(ctxt, value, program.get_id(),
+                                                        &mut active_storage_binding, name)
+            {
+                Ok(f) => f,
+                Err(e) => {
+                    visiting_result = Err(e);
+                    return;
+                }
+            };
+
+            if let Some(fence) = fence {
+                fences.push(fence);
+            }
+
+        } else if let &UniformValue::Block(_, _) = value {
+            visiting_result = Err(DrawError::UniformBlockNotFound { name: name.to_string() });
+        }
+    });
+
+    if let Err(e) = visiting_result {
+        return Err(e);
+    }
+
+    if textures_count > max_textures {
+        return Err(DrawError::TooManyTextures { requested: textures_count,
+                                               max: max_textures });
+    }
+
+    Ok(fences)
+}
+
 fn bind_uniform_block(ctxt: &mut context::CommandContext, value: &UniformValue,
                       block: &program::UniformBlock,
                       program: Handle, current_bind_point: &mut gl::types::GLuint, name: &str)
@@ -337,8 +490,11 @@ fn bind_uniform_block(ctxt: &mut context::CommandContext, value: &UniformValue,
 {
     match value {
         &UniformValue::Block(ref buffer, ref layout) => {
-            if !layout(block) {
-                return Err(DrawError::UniformBlockLayoutMismatch { name: name.to_string() });
+            if let Err(err) = layout(block) {
+                return Err(DrawError::UniformBlockLayoutMismatch {
+                    name: name.to_string(),
+                    err: err,
+                });
             }
 
             let bind_point = *current_bind_point;
@@ -356,6 +512,7 @@ fn bind_uniform_block(ctxt: &mut context::CommandContext, value: &UniformValue,
             unsafe {
                 ctxt.gl.BindBufferBase(gl::UNIFORM_BUFFER, bind_point as gl::types::GLuint,
                                        buffer);
+                ctxt.state.uniform_buffer_binding = buffer;
                 ctxt.gl.UniformBlockBinding(program, binding,
                                             bind_point as gl::types::GLuint);
             }
@@ -368,9 +525,48 @@ fn bind_uniform_block(ctxt: &mut context::CommandContext, value: &UniformValue,
     }
 }
 
+fn bind_shader_storage_block(ctxt: &mut context::CommandContext, value: &UniformValue,
+                             program: Handle, current_bind_point: &mut gl::types::GLuint,
+                             name: &str)
+                             -> Result<Option<Sender<sync::LinearSyncFence>>, DrawError>
+{
+    let buffer = match value {
+        &UniformValue::ShaderStorageBlock(buffer) => buffer,
+        _ => unreachable!()
+    };
+
+    let program = match program {
+        Handle::Id(id) => id,
+        _ => unreachable!()
+    };
+
+    let index = unsafe {
+        let name = ffi::CString::new(name.as_bytes()).unwrap();
+        ctxt.gl.GetProgramResourceIndex(program, gl::SHADER_STORAGE_BLOCK, name.as_ptr())
+    };
+
+    if index == gl::INVALID_INDEX {
+        return Err(DrawError::UniformBlockNotFound { name: name.to_string() });
+    }
+
+    let bind_point = *current_bind_point;
+    *current_bind_point += 1;
+
+    let fence = buffer.add_fence();
+
+    unsafe {
+        ctxt.gl.BindBufferBase(gl::SHADER_STORAGE_BUFFER, bind_point, buffer.get_id());
+        // `glBindBufferBase` also changes the generic binding point
+        ctxt.state.shader_storage_buffer_binding = buffer.get_id();
+        ctxt.gl.ShaderStorageBlockBinding(program, index, bind_point);
+    }
+
+    Ok(fence)
+}
+
 fn bind_uniform(ctxt: &mut context::CommandContext,
                 samplers: &mut HashMap<SamplerBehavior, SamplerObject>,
-                value: &UniformValue, location: gl::types::GLint,
+                program: &Program, value: &UniformValue, location: gl::types::GLint,
                 active_texture: &mut gl::types::GLenum, name: &str)
                 -> Result<(), DrawError>
 {
@@ -390,7 +586,7 @@ fn bind_uniform(ctxt: &mut context::CommandContext,
     );
 
     match *value {
-        UniformValue::Block(_, _) => {
+        UniformValue::Block(_, _) | UniformValue::ShaderStorageBlock(_) => {
             Err(DrawError::UniformBufferToValue {
                 name: name.to_string(),
             })
@@ -445,192 +641,255 @@ fn bind_uniform(ctxt: &mut context::CommandContext,
             uniform!(ctxt, Uniform4fv, Uniform4fvARB, location, 1, val.as_ptr() as *const f32);
             Ok(())
         },
+        UniformValue::SignedIntArray(val) => {
+            uniform!(ctxt, Uniform1iv, Uniform1ivARB, location,
+                     val.len() as gl::types::GLsizei, val.as_ptr());
+            Ok(())
+        },
+        UniformValue::FloatArray(val) => {
+            uniform!(ctxt, Uniform1fv, Uniform1fvARB, location,
+                     val.len() as gl::types::GLsizei, val.as_ptr());
+            Ok(())
+        },
+        UniformValue::Mat2Array(val) => {
+            uniform!(ctxt, UniformMatrix2fv, UniformMatrix2fvARB, location,
+                     val.len() as gl::types::GLsizei, gl::FALSE, val.as_ptr() as *const f32);
+            Ok(())
+        },
+        UniformValue::Mat3Array(val) => {
+            uniform!(ctxt, UniformMatrix3fv, UniformMatrix3fvARB, location,
+                     val.len() as gl::types::GLsizei, gl::FALSE, val.as_ptr() as *const f32);
+            Ok(())
+        },
+        UniformValue::Mat4Array(val) => {
+            uniform!(ctxt, UniformMatrix4fv, UniformMatrix4fvARB, location,
+                     val.len() as gl::types::GLsizei, gl::FALSE, val.as_ptr() as *const f32);
+            Ok(())
+        },
+        UniformValue::Vec2Array(val) => {
+            uniform!(ctxt, Uniform2fv, Uniform2fvARB, location,
+                     val.len() as gl::types::GLsizei, val.as_ptr() as *const f32);
+            Ok(())
+        },
+        UniformValue::Vec3Array(val) => {
+            uniform!(ctxt, Uniform3fv, Uniform3fvARB, location,
+                     val.len() as gl::types::GLsizei, val.as_ptr() as *const f32);
+            Ok(())
+        },
+        UniformValue::Vec4Array(val) => {
+            uniform!(ctxt, Uniform4fv, Uniform4fvARB, location,
+                     val.len() as gl::types::GLsizei, val.as_ptr() as *const f32);
+            Ok(())
+        },
         UniformValue::Texture1d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_1D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_1D)
         },
         UniformValue::CompressedTexture1d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_1D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_1D)
         },
         UniformValue::SrgbTexture1d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_1D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_1D)
         },
         UniformValue::CompressedSrgbTexture1d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_1D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_1D)
         },
         UniformValue::IntegralTexture1d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_1D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_1D)
         },
         UniformValue::UnsignedTexture1d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_1D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_1D)
         },
         UniformValue::DepthTexture1d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_1D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_1D)
         },
         UniformValue::Texture2d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D)
         },
         UniformValue::CompressedTexture2d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D)
         },
         UniformValue::SrgbTexture2d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D)
         },
         UniformValue::CompressedSrgbTexture2d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D)
         },
         UniformValue::IntegralTexture2d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D)
         },
         UniformValue::UnsignedTexture2d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D)
         },
         UniformValue::DepthTexture2d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D)
         },
         UniformValue::Texture2dMultisample(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE)
         },
         UniformValue::SrgbTexture2dMultisample(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE)
         },
         UniformValue::IntegralTexture2dMultisample(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE)
         },
         UniformValue::UnsignedTexture2dMultisample(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE)
         },
         UniformValue::DepthTexture2dMultisample(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE)
         },
         UniformValue::Texture3d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_3D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_3D)
         },
         UniformValue::CompressedTexture3d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_3D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_3D)
         },
         UniformValue::SrgbTexture3d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_3D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_3D)
         },
         UniformValue::CompressedSrgbTexture3d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_3D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_3D)
         },
         UniformValue::IntegralTexture3d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_3D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_3D)
         },
         UniformValue::UnsignedTexture3d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_3D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_3D)
         },
         UniformValue::DepthTexture3d(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_3D)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_3D)
         },
         UniformValue::Texture1dArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_1D_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_1D_ARRAY)
         },
         UniformValue::CompressedTexture1dArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_1D_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_1D_ARRAY)
         },
         UniformValue::SrgbTexture1dArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_1D_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_1D_ARRAY)
         },
         UniformValue::CompressedSrgbTexture1dArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_1D_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_1D_ARRAY)
         },
         UniformValue::IntegralTexture1dArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_1D_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_1D_ARRAY)
         },
         UniformValue::UnsignedTexture1dArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_1D_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_1D_ARRAY)
         },
         UniformValue::DepthTexture1dArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_1D_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_1D_ARRAY)
         },
         UniformValue::Texture2dArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D_ARRAY)
         },
         UniformValue::CompressedTexture2dArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D_ARRAY)
         },
         UniformValue::SrgbTexture2dArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D_ARRAY)
         },
         UniformValue::CompressedSrgbTexture2dArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D_ARRAY)
         },
         UniformValue::IntegralTexture2dArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D_ARRAY)
         },
         UniformValue::UnsignedTexture2dArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D_ARRAY)
         },
         UniformValue::DepthTexture2dArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D_ARRAY)
         },
         UniformValue::Texture2dMultisampleArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE_ARRAY)
         },
         UniformValue::SrgbTexture2dMultisampleArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE_ARRAY)
         },
         UniformValue::IntegralTexture2dMultisampleArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE_ARRAY)
         },
         UniformValue::UnsignedTexture2dMultisampleArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE_ARRAY)
         },
         UniformValue::DepthTexture2dMultisampleArray(texture, sampler) => {
             let texture = texture.get_id();
-            bind_texture_uniform(ctxt, samplers, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE_ARRAY)
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_2D_MULTISAMPLE_ARRAY)
+        },
+        UniformValue::CubeMap(texture, sampler) => {
+            let texture = texture.get_id();
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_CUBE_MAP)
+        },
+        UniformValue::DepthCubeMap(texture, sampler) => {
+            let texture = texture.get_id();
+            bind_texture_uniform(ctxt, samplers, program, texture, sampler, location, active_texture, gl::TEXTURE_CUBE_MAP)
         },
     }
 }
 
+/// Returns true if binding this uniform value consumes a texture unit.
+fn is_texture(value: &UniformValue) -> bool {
+    match *value {
+        UniformValue::Block(_, _) | UniformValue::ShaderStorageBlock(_) |
+        UniformValue::SignedInt(_) | UniformValue::UnsignedInt(_) |
+        UniformValue::Float(_) | UniformValue::Mat2(_) | UniformValue::Mat3(_) |
+        UniformValue::Mat4(_) | UniformValue::Vec2(_) | UniformValue::Vec3(_) |
+        UniformValue::Vec4(_) | UniformValue::SignedIntArray(_) | UniformValue::FloatArray(_) |
+        UniformValue::Mat2Array(_) | UniformValue::Mat3Array(_) | UniformValue::Mat4Array(_) |
+        UniformValue::Vec2Array(_) | UniformValue::Vec3Array(_) |
+        UniformValue::Vec4Array(_) => false,
+        _ => true,
+    }
+}
+
 fn bind_texture_uniform(ctxt: &mut context::CommandContext,
                         samplers: &mut HashMap<SamplerBehavior, SamplerObject>,
-                        texture: gl::types::GLuint,
+                        program: &Program, texture: gl::types::GLuint,
                         sampler: Option<SamplerBehavior>, location: gl::types::GLint,
                         active_texture: &mut gl::types::GLenum,
                         bind_point: gl::types::GLenum)
@@ -648,43 +907,102 @@ fn bind_texture_uniform(ctxt: &mut context::CommandContext,
     let current_texture = *active_texture;
     *active_texture += 1;
 
-    unsafe {
-        // TODO: what if it's not supported?
-        let active_tex_enum = current_texture + gl::TEXTURE0;
-        if ctxt.state.active_texture != active_tex_enum {
-            ctxt.gl.ActiveTexture(current_texture + gl::TEXTURE0);
-            ctxt.state.active_texture = active_tex_enum;
-        }
+    let unit = current_texture as usize;
+    while ctxt.state.texture_units.len() <= unit {
+        ctxt.state.texture_units.push((0, 0, 0));
+    }
 
-        ctxt.gl.BindTexture(bind_point, texture);
+    unsafe {
+        // the texture only needs to be bound if the unit doesn't already contain it
+        if ctxt.state.texture_units[unit].0 != bind_point ||
+           ctxt.state.texture_units[unit].1 != texture
+        {
+            // TODO: what if it's not supported?
+            let active_tex_enum = current_texture + gl::TEXTURE0;
+            if ctxt.state.active_texture != active_tex_enum {
+                ctxt.gl.ActiveTexture(current_texture + gl::TEXTURE0);
+                ctxt.state.active_texture = active_tex_enum;
+            }
 
-        if ctxt.version >= &Version(Api::Gl, 1, 5) {
-            ctxt.gl.Uniform1i(location, current_texture as gl::types::GLint);
-        } else {
-            assert!(ctxt.extensions.gl_arb_shader_objects);
-            ctxt.gl.Uniform1iARB(location, current_texture as gl::types::GLint);
+            ctxt.gl.BindTexture(bind_point, texture);
+            ctxt.state.texture_units[unit].0 = bind_point;
+            ctxt.state.texture_units[unit].1 = texture;
         }
+    }
+
+    // even if the texture is the same, the uniform must be updated if it was previously
+    // pointing to another unit
+    program.set_sampler_uniform(ctxt, location, current_texture as gl::types::GLint);
 
+    unsafe {
         if let Some(sampler) = sampler {
             assert!(ctxt.version >= &Version(Api::Gl, 3, 3) ||
+                    ctxt.version >= &Version(Api::GlEs, 3, 0) ||
                     ctxt.extensions.gl_arb_sampler_objects);
-            ctxt.gl.BindSampler(current_texture, sampler);
+            if ctxt.state.texture_units[unit].2 != sampler {
+                ctxt.gl.BindSampler(current_texture, sampler);
+                ctxt.state.texture_units[unit].2 = sampler;
+            }
         } else if ctxt.version >= &Version(Api::Gl, 3, 3) ||
+            ctxt.version >= &Version(Api::GlEs, 3, 0) ||
             ctxt.extensions.gl_arb_sampler_objects
         {
-            ctxt.gl.BindSampler(current_texture, 0);
+            if ctxt.state.texture_units[unit].2 != 0 {
+                ctxt.gl.BindSampler(current_texture, 0);
+                ctxt.state.texture_units[unit].2 = 0;
+            }
         }
     }
 
     Ok(())
 }
 
+/// How primitive restart must be configured.
+#[derive(Copy, Clone)]
+enum PrimitiveRestart {
+    /// Uses `GL_PRIMITIVE_RESTART` and `glPrimitiveRestartIndex`.
+    Index(u32),
+    /// Uses `GL_PRIMITIVE_RESTART_FIXED_INDEX`, where the index is the maximum value of the
+    /// indices type.
+    FixedIndex,
+}
+
+/// Calls the right variant of `glDrawElements` depending on the number of instances and
+/// on the base vertex.
+fn draw_elements(ctxt: &mut context::CommandContext, primitives: gl::types::GLenum,
+                 length: gl::types::GLsizei, indices_type: gl::types::GLenum,
+                 ptr: *const libc::c_void, instances_count: Option<usize>, base_vertex: i32)
+{
+    unsafe {
+        match (instances_count, base_vertex) {
+            (Some(instances_count), 0) => {
+                ctxt.gl.DrawElementsInstanced(primitives, length, indices_type, ptr,
+                                              instances_count as gl::types::GLsizei);
+            },
+            (Some(instances_count), base_vertex) => {
+                ctxt.gl.DrawElementsInstancedBaseVertex(primitives, length, indices_type, ptr,
+                                                        instances_count as gl::types::GLsizei,
+                                                        base_vertex);
+            },
+            (None, 0) => {
+                ctxt.gl.DrawElements(primitives, length, indices_type, ptr);
+            },
+            (None, base_vertex) => {
+                ctxt.gl.DrawElementsBaseVertex(primitives, length, indices_type, ptr,
+                                               base_vertex);
+            },
+        }
+    }
+}
+
 fn sync_depth(ctxt: &mut context::CommandContext, depth_test: DepthTest, depth_write: bool,
               depth_range: (f32, f32))
 {
     // depth test
+    // note that disabling the depth test also disables writing to the depth buffer, so we
+    // can only do this if `depth_write` is false
     match depth_test {
-        DepthTest::Overwrite => unsafe {
+        DepthTest::Overwrite if !depth_write => unsafe {
             if ctxt.state.enabled_depth_test {
                 ctxt.gl.Disable(gl::DEPTH_TEST);
                 ctxt.state.enabled_depth_test = false;
@@ -751,7 +1069,7 @@ fn sync_stencil(ctxt: &mut context::CommandContext, params: &DrawParameters) {
     }
 
     if ctxt.state.stencil_func_front != (test_ccw, params.stencil_reference_value_counter_clockwise, read_mask_ccw) {
-        unsafe { ctxt.gl.StencilFuncSeparate(gl::FRONT, test_cw, params.stencil_reference_value_clockwise, read_mask_cw) };
+        unsafe { ctxt.gl.StencilFuncSeparate(gl::FRONT, test_ccw, params.stencil_reference_value_counter_clockwise, read_mask_ccw) };
         ctxt.state.stencil_func_front = (test_ccw, params.stencil_reference_value_counter_clockwise, read_mask_ccw);
     }
 
@@ -760,9 +1078,9 @@ fn sync_stencil(ctxt: &mut context::CommandContext, params: &DrawParameters) {
         ctxt.state.stencil_mask_back = params.stencil_write_mask_clockwise;
     }
 
-    if ctxt.state.stencil_mask_front != params.stencil_write_mask_clockwise {
-        unsafe { ctxt.gl.StencilMaskSeparate(gl::FRONT, params.stencil_write_mask_clockwise) };
-        ctxt.state.stencil_mask_front = params.stencil_write_mask_clockwise;
+    if ctxt.state.stencil_mask_front != params.stencil_write_mask_counter_clockwise {
+        unsafe { ctxt.gl.StencilMaskSeparate(gl::FRONT, params.stencil_write_mask_counter_clockwise) };
+        ctxt.state.stencil_mask_front = params.stencil_write_mask_counter_clockwise;
     }
 
     let op_back = (params.stencil_fail_operation_clockwise.to_glenum(),
@@ -781,8 +1099,11 @@ fn sync_stencil(ctxt: &mut context::CommandContext, params: &DrawParameters) {
         ctxt.state.stencil_op_front = op_front;
     }
 
+    // the stencil buffer is only written when the test is enabled, so it must also be enabled
+    // when only the operations differ from the default
     let enable_stencil = test_cw != gl::ALWAYS || test_ccw != gl::ALWAYS ||
-                         op_back.0 != gl::KEEP || op_front.0 != gl::KEEP;
+                         op_back != (gl::KEEP, gl::KEEP, gl::KEEP) ||
+                         op_front != (gl::KEEP, gl::KEEP, gl::KEEP);
     if ctxt.state.enabled_stencil_test != enable_stencil {
         if enable_stencil {
             unsafe { ctxt.gl.Enable(gl::STENCIL_TEST) };
@@ -794,9 +1115,36 @@ fn sync_stencil(ctxt: &mut context::CommandContext, params: &DrawParameters) {
     }
 }
 
+fn sync_depth_clamp(ctxt: &mut context::CommandContext, depth_clamp: bool) {
+    if depth_clamp != ctxt.state.enabled_depth_clamp {
+        unsafe {
+            if depth_clamp {
+                ctxt.gl.Enable(gl::DEPTH_CLAMP);
+            } else {
+                ctxt.gl.Disable(gl::DEPTH_CLAMP);
+            }
+        }
+
+        ctxt.state.enabled_depth_clamp = depth_clamp;
+    }
+}
+
+fn sync_color_mask(ctxt: &mut context::CommandContext, mask: (bool, bool, bool, bool)) {
+    if ctxt.state.color_mask != mask {
+        unsafe {
+            ctxt.gl.ColorMask(if mask.0 { gl::TRUE } else { gl::FALSE },
+                              if mask.1 { gl::TRUE } else { gl::FALSE },
+                              if mask.2 { gl::TRUE } else { gl::FALSE },
+                              if mask.3 { gl::TRUE } else { gl::FALSE });
+        }
+
+        ctxt.state.color_mask = mask;
+    }
+}
+
 fn sync_blending(ctxt: &mut context::CommandContext, blending_function: Option<BlendingFunction>) {
     let blend_factors = match blending_function {
-        Some(BlendingFunction::AlwaysReplace) => unsafe {
+        None | Some(BlendingFunction::AlwaysReplace) => unsafe {
             if ctxt.state.enabled_blend {
                 ctxt.gl.Disable(gl::BLEND);
                 ctxt.state.enabled_blend = false;
@@ -858,7 +1206,22 @@ fn sync_blending(ctxt: &mut context::CommandContext, blending_function: Option<B
             }
             Some((source, destination))
         },
-        _ => None
+        Some(BlendingFunction::Advanced(equation)) => unsafe {
+            let equation = equation.to_glenum();
+            if ctxt.state.blend_equation != equation {
+                ctxt.gl.BlendEquation(equation);
+                ctxt.state.blend_equation = equation;
+            }
+            if !ctxt.state.enabled_blend {
+                ctxt.gl.Enable(gl::BLEND);
+                ctxt.state.enabled_blend = true;
+            }
+
+            // without `GL_KHR_blend_equation_advanced_coherent`, the result of drawing over
+            // pixels written by a previous draw is undefined unless a barrier is inserted
+            ctxt.gl.BlendBarrierKHR();
+            None
+        },
     };
     if let Some((source, destination)) = blend_factors {
         let source = source.to_glenum();
@@ -873,6 +1236,15 @@ fn sync_blending(ctxt: &mut context::CommandContext, blending_function: Option<B
 
 fn sync_line_width(ctxt: &mut context::CommandContext, line_width: Option<f32>) {
     if let Some(line_width) = line_width {
+        let (min, max) = ctxt.capabilities.line_width_range;
+        let line_width = if line_width < min {
+            min
+        } else if line_width > max {
+            max
+        } else {
+            line_width
+        };
+
         if ctxt.state.line_width != line_width {
             unsafe {
                 ctxt.gl.LineWidth(line_width);
@@ -882,9 +1254,29 @@ fn sync_line_width(ctxt: &mut context::CommandContext, line_width: Option<f32>)
     }
 }
 
-fn sync_point_size(ctxt: &mut context::CommandContext, point_size: Option<f32>) {
+fn sync_point_size(ctxt: &mut context::CommandContext, point_size: Option<f32>,
+                   program_point_size: bool)
+{
+    // OpenGL ES always uses the value of `gl_PointSize`
+    if ctxt.version.0 == Api::GlEs {
+        return;
+    }
+
+    // `validate` ensures that `GL_PROGRAM_POINT_SIZE` is supported when it is requested
+    if ctxt.state.enabled_program_point_size != program_point_size {
+        unsafe {
+            if program_point_size {
+                ctxt.gl.Enable(gl::PROGRAM_POINT_SIZE);
+            } else {
+                ctxt.gl.Disable(gl::PROGRAM_POINT_SIZE);
+            }
+        }
+
+        ctxt.state.enabled_program_point_size = program_point_size;
+    }
+
     if let Some(point_size) = point_size {
-        if ctxt.state.point_size != point_size {
+        if !program_point_size && ctxt.state.point_size != point_size {
             unsafe {
                 ctxt.gl.PointSize(point_size);
                 ctxt.state.point_size = point_size;
@@ -929,6 +1321,12 @@ fn sync_polygon_mode(ctxt: &mut context::CommandContext, backface_culling: Backf
     }
 
     // polygon mode
+    // note: `glPolygonMode` doesn't exist in OpenGL ES, and `validate` ensures that only `Fill`
+    //  is used there
+    if ctxt.version.0 == Api::GlEs {
+        return;
+    }
+
     unsafe {
         let polygon_mode = polygon_mode.to_glenum();
         if ctxt.state.polygon_mode != polygon_mode {
@@ -938,6 +1336,83 @@ fn sync_polygon_mode(ctxt: &mut context::CommandContext, backface_culling: Backf
     }
 }
 
+fn sync_polygon_offset(ctxt: &mut context::CommandContext, offset: PolygonOffset) {
+    let (factor, units) = (offset.factor, offset.units);
+
+    if (offset.fill || offset.line || offset.point) && ctxt.state.polygon_offset != (factor, units) {
+        unsafe { ctxt.gl.PolygonOffset(factor, units); }
+        ctxt.state.polygon_offset = (factor, units);
+    }
+
+    unsafe {
+        if offset.fill != ctxt.state.enabled_polygon_offset_fill {
+            if offset.fill {
+                ctxt.gl.Enable(gl::POLYGON_OFFSET_FILL);
+            } else {
+                ctxt.gl.Disable(gl::POLYGON_OFFSET_FILL);
+            }
+            ctxt.state.enabled_polygon_offset_fill = offset.fill;
+        }
+
+        if offset.line != ctxt.state.enabled_polygon_offset_line {
+            if offset.line {
+                ctxt.gl.Enable(gl::POLYGON_OFFSET_LINE);
+            } else {
+                ctxt.gl.Disable(gl::POLYGON_OFFSET_LINE);
+            }
+            ctxt.state.enabled_polygon_offset_line = offset.line;
+        }
+
+        if offset.point != ctxt.state.enabled_polygon_offset_point {
+            if offset.point {
+                ctxt.gl.Enable(gl::POLYGON_OFFSET_POINT);
+            } else {
+                ctxt.gl.Disable(gl::POLYGON_OFFSET_POINT);
+            }
+            ctxt.state.enabled_polygon_offset_point = offset.point;
+        }
+    }
+}
+
+fn sync_smooth(ctxt: &mut context::CommandContext, smooth: Option<Smooth>) {
+    unsafe {
+        if let Some(smooth) = smooth {
+            let hint = smooth.to_glenum();
+
+            if !ctxt.state.enabled_line_smooth {
+                ctxt.gl.Enable(gl::LINE_SMOOTH);
+                ctxt.state.enabled_line_smooth = true;
+            }
+
+            if !ctxt.state.enabled_polygon_smooth {
+                ctxt.gl.Enable(gl::POLYGON_SMOOTH);
+                ctxt.state.enabled_polygon_smooth = true;
+            }
+
+            if ctxt.state.line_smooth_hint != hint {
+                ctxt.gl.Hint(gl::LINE_SMOOTH_HINT, hint);
+                ctxt.state.line_smooth_hint = hint;
+            }
+
+            if ctxt.state.polygon_smooth_hint != hint {
+                ctxt.gl.Hint(gl::POLYGON_SMOOTH_HINT, hint);
+                ctxt.state.polygon_smooth_hint = hint;
+            }
+
+        } else {
+            if ctxt.state.enabled_line_smooth {
+                ctxt.gl.Disable(gl::LINE_SMOOTH);
+                ctxt.state.enabled_line_smooth = false;
+            }
+
+            if ctxt.state.enabled_polygon_smooth {
+                ctxt.gl.Disable(gl::POLYGON_SMOOTH);
+                ctxt.state.enabled_polygon_smooth = false;
+            }
+        }
+    }
+}
+
 fn sync_multisampling(ctxt: &mut context::CommandContext, multisampling: bool) {
     if ctxt.state.enabled_multisample != multisampling {
         unsafe {
@@ -952,6 +1427,40 @@ fn sync_multisampling(ctxt: &mut context::CommandContext, multisampling: bool) {
     }
 }
 
+fn sync_alpha_to_coverage(ctxt: &mut context::CommandContext, alpha_to_coverage: bool) {
+    if ctxt.state.enabled_sample_alpha_to_coverage != alpha_to_coverage {
+        unsafe {
+            if alpha_to_coverage {
+                ctxt.gl.Enable(gl::SAMPLE_ALPHA_TO_COVERAGE);
+                ctxt.state.enabled_sample_alpha_to_coverage = true;
+            } else {
+                ctxt.gl.Disable(gl::SAMPLE_ALPHA_TO_COVERAGE);
+                ctxt.state.enabled_sample_alpha_to_coverage = false;
+            }
+        }
+    }
+}
+
+fn sync_sample_mask(ctxt: &mut context::CommandContext, sample_mask: Option<u32>) {
+    unsafe {
+        if let Some(mask) = sample_mask {
+            if !ctxt.state.enabled_sample_mask {
+                ctxt.gl.Enable(gl::SAMPLE_MASK);
+                ctxt.state.enabled_sample_mask = true;
+            }
+
+            if ctxt.state.sample_mask != mask {
+                ctxt.gl.SampleMaski(0, mask);
+                ctxt.state.sample_mask = mask;
+            }
+
+        } else if ctxt.state.enabled_sample_mask {
+            ctxt.gl.Disable(gl::SAMPLE_MASK);
+            ctxt.state.enabled_sample_mask = false;
+        }
+    }
+}
+
 fn sync_dithering(ctxt: &mut context::CommandContext, dithering: bool) {
     if ctxt.state.enabled_dither != dithering {
         unsafe {
@@ -1053,6 +1562,24 @@ fn sync_rasterizer_discard(ctxt: &mut context::CommandContext, draw_primitives:
     }
 }
 
+fn sync_framebuffer_srgb(ctxt: &mut context::CommandContext, enable: bool) {
+    if !(ctxt.version >= &Version(Api::Gl, 3, 0)) && !ctxt.extensions.gl_arb_framebuffer_srgb &&
+       !ctxt.extensions.gl_ext_framebuffer_srgb
+    {
+        return;
+    }
+
+    unsafe {
+        if enable && !ctxt.state.enabled_framebuffer_srgb {
+            ctxt.gl.Enable(gl::FRAMEBUFFER_SRGB);
+            ctxt.state.enabled_framebuffer_srgb = true;
+        } else if !enable && ctxt.state.enabled_framebuffer_srgb {
+            ctxt.gl.Disable(gl::FRAMEBUFFER_SRGB);
+            ctxt.state.enabled_framebuffer_srgb = false;
+        }
+    }
+}
+
 unsafe fn sync_vertices_per_patch(ctxt: &mut context::CommandContext, vertices_per_patch: Option<u16>) {
     if let Some(vertices_per_patch) = vertices_per_patch {
         let vertices_per_patch = vertices_per_patch as gl::types::GLint;
@@ -1062,3 +1589,41 @@ unsafe fn sync_vertices_per_patch(ctxt: &mut context::CommandContext, vertices_p
         }
     }
 }
+
+fn sync_primitive_restart(ctxt: &mut context::CommandContext,
+                          primitive_restart: Option<PrimitiveRestart>)
+{
+    let (restart, fixed_index) = match primitive_restart {
+        Some(PrimitiveRestart::Index(index)) => (Some(index), false),
+        Some(PrimitiveRestart::FixedIndex) => (None, true),
+        None => (None, false),
+    };
+
+    unsafe {
+        if let Some(index) = restart {
+            if !ctxt.state.enabled_primitive_restart {
+                ctxt.gl.Enable(gl::PRIMITIVE_RESTART);
+                ctxt.state.enabled_primitive_restart = true;
+            }
+
+            if ctxt.state.primitive_restart_index != index {
+                ctxt.gl.PrimitiveRestartIndex(index);
+                ctxt.state.primitive_restart_index = index;
+            }
+
+        } else if ctxt.state.enabled_primitive_restart {
+            ctxt.gl.Disable(gl::PRIMITIVE_RESTART);
+            ctxt.state.enabled_primitive_restart = false;
+        }
+
+        if fixed_index != ctxt.state.enabled_primitive_restart_fixed_index {
+            if fixed_index {
+                ctxt.gl.Enable(gl::PRIMITIVE_RESTART_FIXED_INDEX);
+            } else {
+                ctxt.gl.Disable(gl::PRIMITIVE_RESTART_FIXED_INDEX);
+            }
+
+            ctxt.state.enabled_primitive_restart_fixed_index = fixed_index;
+        }
+    }
+}
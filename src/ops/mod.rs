@@ -1,10 +1,15 @@
 pub use self::blit::blit;
-pub use self::clear::clear;
+pub use self::clear::{clear, clear_color_buffer};
+pub use self::compute::dispatch_compute;
 pub use self::draw::draw;
 pub use self::read::{read_attachment, read_from_default_fb};
 pub use self::read::{read_attachment_to_pb, read_from_default_fb_to_pb};
+pub use self::read::{read_attachment_into, read_from_default_fb_into};
+pub use self::read::read_depth_stencil_attachment;
+pub use self::read::{read_rect, read_rect_into};
 
 mod blit;
 mod clear;
+mod compute;
 mod draw;
 mod read;
@@ -10,9 +10,12 @@ use fbo;
 use texture;
 
 use GlObject;
+use Rect;
 use libc;
 use context::CommandContext;
 use gl;
+use version::Version;
+use Api;
 
 pub fn read_attachment<P, T>(attachment: &fbo::Attachment, dimensions: (u32, u32),
                              context: &Context) -> T          // TODO: remove Clone for P
@@ -24,7 +27,7 @@ pub fn read_attachment<P, T>(attachment: &fbo::Attachment, dimensions: (u32, u32
     let (fbo, atch) = context.framebuffer_objects.as_ref().unwrap()
                             .get_framebuffer_for_reading(attachment, &mut ctxt);
 
-    read_impl(fbo, atch, dimensions, None, &mut ctxt).unwrap()
+    read_impl(fbo, atch, &full_rect(dimensions), None, &mut ctxt).unwrap()
 }
 
 /// Panics if the pixel buffer is not big enough.
@@ -33,12 +36,21 @@ pub fn read_attachment_to_pb<P, T>(attachment: &fbo::Attachment, dimensions: (u3
                                    where P: texture::PixelValue + Clone + Send,
                                    T: texture::Texture2dDataSink<Data = P>
 {
-    let mut ctxt = context.make_current();
+    let data = {
+        let mut ctxt = context.make_current();
 
-    let (fbo, atch) = context.framebuffer_objects.as_ref().unwrap()
-                            .get_framebuffer_for_reading(attachment, &mut ctxt);
+        let (fbo, atch) = context.framebuffer_objects.as_ref().unwrap()
+                                .get_framebuffer_for_reading(attachment, &mut ctxt);
+
+        if is_pixel_buffer_supported(&ctxt) {
+            read_impl(fbo, atch, &full_rect(dimensions), Some(dest), &mut ctxt);
+            return;
+        }
+
+        read_raw::<P, T>(fbo, atch, &full_rect(dimensions), &mut ctxt)
+    };
 
-    read_impl(fbo, atch, dimensions, Some(dest), &mut ctxt);
+    store_in_pb(dest, dimensions, data);
 }
 
 pub fn read_from_default_fb<P, T>(attachment: gl::types::GLenum, context: &Context) -> T          // TODO: remove Clone for P
@@ -49,7 +61,7 @@ pub fn read_from_default_fb<P, T>(attachment: gl::types::GLenum, context: &Conte
 
     let (w, h) = context.get_framebuffer_dimensions();
     let (w, h) = (w as u32, h as u32);      // TODO: remove this conversion
-    read_impl(0, attachment, (w, h), None, &mut ctxt).unwrap()
+    read_impl(0, attachment, &full_rect((w, h)), None, &mut ctxt).unwrap()
 }
 
 /// Panics if the pixel buffer is not big enough.
@@ -58,84 +70,285 @@ pub fn read_from_default_fb_to_pb<P, T>(attachment: gl::types::GLenum,
                                         where P: texture::PixelValue + Clone + Send,
                                         T: texture::Texture2dDataSink<Data = P>
 {
-    let mut ctxt = context.make_current();
     let (w, h) = context.get_framebuffer_dimensions();
-    read_impl(0, attachment, (w, h), Some(dest), &mut ctxt);
+
+    let data = {
+        let mut ctxt = context.make_current();
+
+        if is_pixel_buffer_supported(&ctxt) {
+            read_impl(0, attachment, &full_rect((w, h)), Some(dest), &mut ctxt);
+            return;
+        }
+
+        read_raw::<P, T>(0, attachment, &full_rect((w, h)), &mut ctxt)
+    };
+
+    store_in_pb(dest, (w, h), data);
 }
 
-fn read_impl<P, T>(fbo: gl::types::GLuint, readbuffer: gl::types::GLenum,
-                   dimensions: (u32, u32), target: Option<&mut PixelBuffer<T>>,
-                   mut ctxt: &mut CommandContext) -> Option<T>          // TODO: remove Clone for P
-                   where P: texture::PixelValue + Clone + Send,
-                   T: texture::Texture2dDataSink<Data = P>
+/// Panics if `dest` is not big enough.
+pub fn read_attachment_into<P>(attachment: &fbo::Attachment, dimensions: (u32, u32),
+                               dest: &mut [P], context: &Context)
+                               where P: texture::PixelValue
 {
-    use std::mem;
+    let mut ctxt = context.make_current();
 
-    let pixels_count = dimensions.0 * dimensions.1;
+    let (fbo, atch) = context.framebuffer_objects.as_ref().unwrap()
+                            .get_framebuffer_for_reading(attachment, &mut ctxt);
 
-    let chosen_format = <T as texture::Texture2dDataSink>::get_preferred_formats()[0];
-    let pixels_size = chosen_format.get_size();
-    let (format, gltype) = client_format_to_gl_enum(&chosen_format);
+    read_into_impl(fbo, atch, &full_rect(dimensions), dest, &mut ctxt);
+}
 
-    let total_data_size = pixels_count as usize * pixels_size;
+/// Panics if `dest` is not big enough.
+pub fn read_from_default_fb_into<P>(attachment: gl::types::GLenum, dest: &mut [P],
+                                    context: &Context)
+                                    where P: texture::PixelValue
+{
+    let mut ctxt = context.make_current();
+    let (w, h) = context.get_framebuffer_dimensions();
+    read_into_impl(0, attachment, &full_rect((w, h)), dest, &mut ctxt);
+}
 
-    let pixel_buffer = target.as_ref().map(|buf| buf.get_id()).unwrap_or(0);
+/// Reads the content of a packed depth-stencil attachment.
+///
+/// Returns a list of rows of `(depth, stencil)` pairs. The depth is between `0.0` and `1.0`.
+pub fn read_depth_stencil_attachment(attachment: &fbo::Attachment, dimensions: (u32, u32),
+                                     context: &Context) -> Vec<Vec<(f32, u8)>>
+{
+    let mut ctxt = context.make_current();
 
-    if let Some(pixel_buffer) = target {
-        assert!(pixel_buffer.get_size() >= total_data_size);
-        pixel_buffer::store_infos(pixel_buffer, dimensions, chosen_format);
-    }
+    let attachments = fbo::FramebufferAttachments {
+        colors: Vec::new(),
+        depth_stencil: fbo::FramebufferDepthStencilAttachments::DepthStencilAttachment(
+                                                                                *attachment),
+    };
+
+    let fbo = context.framebuffer_objects.as_ref().unwrap()
+                     .get_framebuffer_for_drawing(Some(&attachments), &mut ctxt);
+
+    let pixels_count = dimensions.0 as usize * dimensions.1 as usize;
 
     let data = unsafe {
-        // binding framebuffer
         fbo::bind_framebuffer(&mut ctxt, fbo, false, true);
 
-        // adjusting glReadBuffer
-        ctxt.gl.ReadBuffer(readbuffer);
+        // the framebuffer doesn't have any color attachment
+        ctxt.gl.ReadBuffer(gl::NONE);
 
-        // adjusting data alignement
         if ctxt.state.pixel_store_pack_alignment != 1 {
             ctxt.state.pixel_store_pack_alignment = 1;
             ctxt.gl.PixelStorei(gl::PACK_ALIGNMENT, 1);
         }
 
-        // binding buffer
-        if ctxt.state.pixel_pack_buffer_binding != pixel_buffer {
-            ctxt.gl.BindBuffer(gl::PIXEL_PACK_BUFFER, pixel_buffer);
-            ctxt.state.pixel_pack_buffer_binding = pixel_buffer;
+        if ctxt.state.pixel_pack_buffer_binding != 0 {
+            ctxt.gl.BindBuffer(gl::PIXEL_PACK_BUFFER, 0);
+            ctxt.state.pixel_pack_buffer_binding = 0;
         }
 
-        // reading
-        if pixel_buffer == 0 {
-            let data_size = pixels_count as usize * pixels_size / mem::size_of::<P>();
-            let mut data: Vec<P> = Vec::with_capacity(data_size);
-            ctxt.gl.ReadPixels(0, 0, dimensions.0 as gl::types::GLint,
-                               dimensions.1 as gl::types::GLint, format, gltype,
-                               data.as_mut_ptr() as *mut libc::c_void);
-            data.set_len(data_size);
-            Some(data)
-
-        } else {
-            ctxt.gl.ReadPixels(0, 0, dimensions.0 as gl::types::GLint,
-                               dimensions.1 as gl::types::GLint, format, gltype,
+        // each pixel is made of 24 bits of depth followed by 8 bits of stencil
+        let mut data: Vec<u32> = Vec::with_capacity(pixels_count);
+        ctxt.gl.ReadPixels(0, 0, dimensions.0 as gl::types::GLint,
+                           dimensions.1 as gl::types::GLint, gl::DEPTH_STENCIL,
+                           gl::UNSIGNED_INT_24_8, data.as_mut_ptr() as *mut libc::c_void);
+        data.set_len(pixels_count);
+        data
+    };
+
+    data.chunks(dimensions.0 as usize).map(|row| {
+        row.iter().map(|&value| {
+            ((value >> 8) as f32 / 16777215.0, (value & 0xff) as u8)
+        }).collect()
+    }).collect()
+}
+
+/// Reads a rectangle of the first color attachment of a framebuffer, or of the back buffer
+/// of the default framebuffer if `attachments` is `None`.
+///
+/// Panics if `rect` is outside of a surface of the given dimensions.
+pub fn read_rect<P, T>(attachments: Option<&fbo::FramebufferAttachments>, dimensions: (u32, u32),
+                       rect: &Rect, context: &Context) -> T          // TODO: remove Clone for P
+                       where P: texture::PixelValue + Clone + Send,
+                       T: texture::Texture2dDataSink<Data = P>
+{
+    check_rect(rect, dimensions);
+
+    let mut ctxt = context.make_current();
+    let (fbo, readbuffer) = get_read_framebuffer(attachments, context, &mut ctxt);
+    read_impl(fbo, readbuffer, rect, None, &mut ctxt).unwrap()
+}
+
+/// Same as `read_rect`, but writes the pixels in `dest`.
+///
+/// Panics if `dest` is not big enough.
+pub fn read_rect_into<P>(attachments: Option<&fbo::FramebufferAttachments>,
+                         dimensions: (u32, u32), rect: &Rect, dest: &mut [P], context: &Context)
+                         where P: texture::PixelValue
+{
+    check_rect(rect, dimensions);
+
+    let mut ctxt = context.make_current();
+    let (fbo, readbuffer) = get_read_framebuffer(attachments, context, &mut ctxt);
+    read_into_impl(fbo, readbuffer, rect, dest, &mut ctxt);
+}
+
+/// Returns the framebuffer and the value of `glReadBuffer` to use to read the first color
+/// attachment of `attachments`.
+fn get_read_framebuffer(attachments: Option<&fbo::FramebufferAttachments>, context: &Context,
+                        ctxt: &mut CommandContext) -> (gl::types::GLuint, gl::types::GLenum)
+{
+    let attachments = match attachments {
+        Some(a) => a,
+        None => return (0, gl::BACK_LEFT),
+    };
+
+    let key = match attachments.colors.first() {
+        Some(&(key, _)) => key,
+        None => panic!("The surface doesn't have any color attachment"),
+    };
+
+    let fbo = context.framebuffer_objects.as_ref().unwrap()
+                     .get_framebuffer_for_drawing(Some(attachments), ctxt);
+    (fbo, gl::COLOR_ATTACHMENT0 + key)
+}
+
+/// Panics if `rect` is outside of a surface of the given dimensions.
+fn check_rect(rect: &Rect, (width, height): (u32, u32)) {
+    assert!(rect.left + rect.width <= width && rect.bottom + rect.height <= height,
+            "The rectangle is outside of the surface");
+}
+
+/// Returns a rect that covers a whole surface of the given dimensions.
+fn full_rect((width, height): (u32, u32)) -> Rect {
+    Rect { left: 0, bottom: 0, width: width, height: height }
+}
+
+/// Returns true if `glReadPixels` can write into a buffer bound to `GL_PIXEL_PACK_BUFFER`.
+fn is_pixel_buffer_supported(ctxt: &CommandContext) -> bool {
+    ctxt.version >= &Version(Api::Gl, 2, 1) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+    ctxt.extensions.gl_arb_pixel_buffer_object
+}
+
+/// Reads the pixels synchronously in the format preferred by `T`.
+fn read_raw<P, T>(fbo: gl::types::GLuint, readbuffer: gl::types::GLenum,
+                  rect: &Rect, mut ctxt: &mut CommandContext)
+                  -> (Vec<P>, ClientFormat)
+                  where P: texture::PixelValue + Clone + Send,
+                  T: texture::Texture2dDataSink<Data = P>
+{
+    use std::mem;
+
+    let pixels_count = rect.width as usize * rect.height as usize;
+
+    let chosen_format = <T as texture::Texture2dDataSink>::get_preferred_formats()[0];
+    let pixels_size = chosen_format.get_size();
+    let (format, gltype) = client_format_to_gl_enum(&chosen_format);
+
+    let data = unsafe {
+        prepare_read(&mut ctxt, fbo, readbuffer, 0);
+
+        let data_size = pixels_count * pixels_size / mem::size_of::<P>();
+        let mut data: Vec<P> = Vec::with_capacity(data_size);
+        ctxt.gl.ReadPixels(rect.left as gl::types::GLint, rect.bottom as gl::types::GLint,
+                           rect.width as gl::types::GLsizei,
+                           rect.height as gl::types::GLsizei, format, gltype,
+                           data.as_mut_ptr() as *mut libc::c_void);
+        data.set_len(data_size);
+        data
+    };
+
+    (data, chosen_format)
+}
+
+/// Fallback when pixel buffers are not supported: stores pixels that have been read
+/// synchronously in the client memory of the buffer.
+fn store_in_pb<P, T>(dest: &mut PixelBuffer<T>, dimensions: (u32, u32),
+                     (data, format): (Vec<P>, ClientFormat))
+                     where P: texture::PixelValue + Clone + Send,
+                     T: texture::Texture2dDataSink<Data = P>
+{
+    pixel_buffer::store_infos(dest, dimensions, format);
+    pixel_buffer::store_fallback_data(dest, data, format);
+}
+
+/// Binds the framebuffer and the pixel pack buffer, and adjusts the pixel store parameters
+/// before a call to `glReadPixels`.
+unsafe fn prepare_read(mut ctxt: &mut CommandContext, fbo: gl::types::GLuint,
+                       readbuffer: gl::types::GLenum, pixel_buffer: gl::types::GLuint)
+{
+    // binding framebuffer
+    fbo::bind_framebuffer(&mut ctxt, fbo, false, true);
+
+    // adjusting glReadBuffer
+    ctxt.gl.ReadBuffer(readbuffer);
+
+    // adjusting data alignement
+    if ctxt.state.pixel_store_pack_alignment != 1 {
+        ctxt.state.pixel_store_pack_alignment = 1;
+        ctxt.gl.PixelStorei(gl::PACK_ALIGNMENT, 1);
+    }
+
+    // binding buffer
+    if ctxt.state.pixel_pack_buffer_binding != pixel_buffer {
+        ctxt.gl.BindBuffer(gl::PIXEL_PACK_BUFFER, pixel_buffer);
+        ctxt.state.pixel_pack_buffer_binding = pixel_buffer;
+    }
+}
+
+fn read_impl<P, T>(fbo: gl::types::GLuint, readbuffer: gl::types::GLenum,
+                   rect: &Rect, target: Option<&mut PixelBuffer<T>>,
+                   mut ctxt: &mut CommandContext) -> Option<T>          // TODO: remove Clone for P
+                   where P: texture::PixelValue + Clone + Send,
+                   T: texture::Texture2dDataSink<Data = P>
+{
+    if let Some(pixel_buffer) = target {
+        let pixels_count = rect.width as usize * rect.height as usize;
+
+        let chosen_format = <T as texture::Texture2dDataSink>::get_preferred_formats()[0];
+        let (format, gltype) = client_format_to_gl_enum(&chosen_format);
+
+        assert!(pixel_buffer.get_size() >= pixels_count * chosen_format.get_size());
+        pixel_buffer::store_infos(pixel_buffer, (rect.width, rect.height), chosen_format);
+
+        unsafe {
+            prepare_read(&mut ctxt, fbo, readbuffer, pixel_buffer.get_id());
+            ctxt.gl.ReadPixels(rect.left as gl::types::GLint, rect.bottom as gl::types::GLint,
+                               rect.width as gl::types::GLsizei,
+                               rect.height as gl::types::GLsizei, format, gltype,
                                ptr::null_mut());
-            None
         }
+
+        return None;
+    }
+
+    let (data, format) = read_raw::<P, T>(fbo, readbuffer, rect, ctxt);
+
+    let data = texture::RawImage2d {
+        data: ::std::borrow::Cow::Owned(data),
+        width: rect.width,
+        height: rect.height,
+        format: format,
     };
 
-    if let Some(data) = data {
-        let data = texture::RawImage2d {
-            data: ::std::borrow::Cow::Owned(data),
-            width: dimensions.0 as u32,
-            height: dimensions.1 as u32,
-            format: chosen_format,
-        };
+    Some(texture::Texture2dDataSink::from_raw(data))
+}
+
+fn read_into_impl<P>(fbo: gl::types::GLuint, readbuffer: gl::types::GLenum,
+                     rect: &Rect, dest: &mut [P], mut ctxt: &mut CommandContext)
+                     where P: texture::PixelValue
+{
+    let pixels_count = rect.width as usize * rect.height as usize;
+    assert!(dest.len() >= pixels_count, "The destination buffer is too small: {} pixels \
+                                         required, {} available", pixels_count, dest.len());
 
-        Some(texture::Texture2dDataSink::from_raw(data))
+    let (format, gltype) = client_format_to_gl_enum(&<P as texture::PixelValue>::get_format());
 
-    } else {
+    unsafe {
+        // rows are tightly packed in `dest`
+        prepare_read(&mut ctxt, fbo, readbuffer, 0);
 
-        None
+        ctxt.gl.ReadPixels(rect.left as gl::types::GLint, rect.bottom as gl::types::GLint,
+                           rect.width as gl::types::GLsizei,
+                           rect.height as gl::types::GLsizei, format, gltype,
+                           dest.as_mut_ptr() as *mut libc::c_void);
     }
 }
 
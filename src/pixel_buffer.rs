@@ -3,9 +3,26 @@ Pixel buffers are buffers that contain two-dimensional texture data.
 
 Contrary to textures, pixel buffers are stored in a client-defined format. They are used
 to transfer data to or from the video memory, before or after being turned into a texture.
+
+## Asynchronous reads
+
+Functions like `Texture2d::read_to_pixel_buffer` or `Display::read_front_buffer_to_pixel_buffer`
+only ask the GPU to copy the pixels into the buffer and return immediately, without waiting for
+the drawing commands that produce these pixels to be finished.
+
+The wait happens when you call `read` or `read_if_supported` on the pixel buffer. If you call
+them right after the copy, the CPU stalls until the GPU has caught up, which is no better than
+reading the pixels directly. To avoid this, keep the pixel buffer around and read it one or two
+frames later, for example by cycling between several pixel buffers.
+
+If the backend doesn't support pixel buffer objects (OpenGL 2.1, OpenGL ES 3.0 or
+`GL_ARB_pixel_buffer_object`), the pixels are read synchronously and kept in client memory
+by the pixel buffer, so the copy blocks like a regular read.
  */
 use std::borrow::Cow;
 use std::marker::PhantomData;
+use std::mem;
+use std::slice;
 
 use backend::Facade;
 
@@ -14,6 +31,7 @@ use texture::{RawImage2d, Texture2dDataSink, ClientFormat};
 use GlObject;
 use buffer::{Buffer, BufferFlags, BufferType};
 use gl;
+use version::{Api, Version};
 
 /// Buffer that stores the content of a texture.
 ///
@@ -22,17 +40,35 @@ pub struct PixelBuffer<T> {
     buffer: Buffer,
     dimensions: Option<(u32, u32)>,
     format: Option<ClientFormat>,
+    /// Pixels that have been read synchronously, if the backend doesn't support pixel buffer
+    /// objects, and their format.
+    fallback_data: Option<(Vec<u8>, ClientFormat)>,
     marker: PhantomData<T>,
 }
 
 impl<T> PixelBuffer<T> {
     /// Builds a new buffer with an uninitialized content.
     pub fn new_empty<F>(facade: &F, capacity: usize) -> PixelBuffer<T> where F: Facade {
+        // without pixel buffer objects, the buffer is never bound to `GL_PIXEL_PACK_BUFFER` and
+        // the pixels are kept in `fallback_data` instead
+        let ty = {
+            let context = facade.get_context();
+
+            if context.get_version() >= &Version(Api::Gl, 2, 1) ||
+               context.get_version() >= &Version(Api::GlEs, 3, 0) ||
+               context.get_extensions().gl_arb_pixel_buffer_object
+            {
+                BufferType::PixelPackBuffer
+            } else {
+                BufferType::ArrayBuffer
+            }
+        };
+
         PixelBuffer {
-            buffer: Buffer::new_empty(facade, BufferType::PixelPackBuffer, 1, capacity,
-                                      BufferFlags::simple()).unwrap(),
+            buffer: Buffer::new_empty(facade, ty, 1, capacity, BufferFlags::simple()).unwrap(),
             dimensions: None,
             format: None,
+            fallback_data: None,
             marker: PhantomData,
         }
     }
@@ -69,9 +105,12 @@ impl<T> PixelBuffer<T> where T: Texture2dDataSink {
     ///
     /// Panics if the pixel buffer is empty.
     pub fn read_if_supported(&self) -> Option<T> {
-        let data = match self.buffer.read_if_supported() {
-            Some(d) => d,
-            None => return None
+        let (data, format) = match self.fallback_data {
+            Some((ref data, format)) => (bytes_to_pixels(data), format),
+            None => match self.buffer.read_if_supported() {
+                Some(d) => (d, self.format.expect("The pixel buffer is empty")),
+                None => return None
+            },
         };
 
         let dimensions = self.dimensions.expect("The pixel buffer is empty");
@@ -80,7 +119,7 @@ impl<T> PixelBuffer<T> where T: Texture2dDataSink {
             data: Cow::Owned(data),
             width: dimensions.0,
             height: dimensions.1,
-            format: self.format.expect("The pixel buffer is empty"),
+            format: format,
         };
 
         Some(Texture2dDataSink::from_raw(data))
@@ -100,3 +139,34 @@ pub fn store_infos<T>(b: &mut PixelBuffer<T>, dimensions: (u32, u32), format: Cl
     b.dimensions = Some(dimensions);
     b.format = Some(format);
 }
+
+// TODO: remove this hack
+#[doc(hidden)]
+pub fn store_fallback_data<T, P>(b: &mut PixelBuffer<T>, data: Vec<P>, format: ClientFormat)
+                                 where T: Texture2dDataSink<Data = P>, P: Send + Copy + 'static
+{
+    let bytes = unsafe {
+        slice::from_raw_parts(data.as_ptr() as *const u8, data.len() * mem::size_of::<P>())
+    };
+
+    b.fallback_data = Some((bytes.to_vec(), format));
+}
+
+/// Copies raw bytes into a list of pixels.
+fn bytes_to_pixels<P>(bytes: &[u8]) -> Vec<P> where P: Copy {
+    let len = bytes.len() / mem::size_of::<P>();
+
+    let mut pixels: Vec<P> = Vec::with_capacity(len);
+
+    unsafe {
+        pixels.set_len(len);
+
+        let dest = slice::from_raw_parts_mut(pixels.as_mut_ptr() as *mut u8,
+                                             len * mem::size_of::<P>());
+        for (d, s) in dest.iter_mut().zip(bytes.iter()) {
+            *d = *s;
+        }
+    }
+
+    pixels
+}
@@ -1,5 +1,7 @@
 use std::sync::Mutex;
 
+use gl;
+
 pub use self::program::{Program, ProgramCreationError};
 pub use self::reflection::{Uniform, UniformBlock, UniformBlockMember};
 pub use self::reflection::{Attribute, TransformFeedbackVarying, TransformFeedbackBuffer, TransformFeedbackMode};
@@ -39,6 +41,14 @@ pub enum ProgramCreationInput<'a> {
         /// The information specified here will be passed to the OpenGL linker. If you pass
         /// `None`, then you won't be able to use transform feedback.
         transform_feedback_varyings: Option<(Vec<String>, TransformFeedbackMode)>,
+
+        /// Locations to assign to the outputs of the fragment shader.
+        ///
+        /// Each name is bound to its location with `glBindFragDataLocation` before linking.
+        /// This lets you choose which color attachment of a `MultiOutputFrameBuffer` each
+        /// output is written to, instead of relying on the locations picked by the driver.
+        /// Outputs that are not in the list are assigned a location by the driver.
+        frag_data_locations: Vec<(String, u32)>,
     },
 
     /// Use a precompiled binary.
@@ -90,6 +100,7 @@ impl<'a> IntoProgramCreationInput<'a> for SourceCode<'a> {
             geometry_shader: geometry_shader,
             fragment_shader: fragment_shader,
             transform_feedback_varyings: None,
+            frag_data_locations: Vec::new(),
         }
     }
 }
@@ -110,3 +121,47 @@ impl IntoProgramCreationInput<'static> for Binary {
         }
     }
 }
+
+/// Stage of the pipeline that a shader belongs to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ShaderType {
+    /// Vertex shader.
+    Vertex,
+    /// Tessellation control shader.
+    TessellationControl,
+    /// Tessellation evaluation shader.
+    TessellationEvaluation,
+    /// Geometry shader.
+    Geometry,
+    /// Fragment shader.
+    Fragment,
+    /// Compute shader.
+    Compute,
+}
+
+impl ShaderType {
+    /// Returns the name of the stage, as used in error messages.
+    fn get_name(&self) -> &'static str {
+        match self {
+            &ShaderType::Vertex => "vertex",
+            &ShaderType::TessellationControl => "tessellation control",
+            &ShaderType::TessellationEvaluation => "tessellation evaluation",
+            &ShaderType::Geometry => "geometry",
+            &ShaderType::Fragment => "fragment",
+            &ShaderType::Compute => "compute",
+        }
+    }
+
+    /// Builds the `ShaderType` corresponding to a `GL_*_SHADER` enum.
+    fn from_glenum(value: gl::types::GLenum) -> ShaderType {
+        match value {
+            gl::VERTEX_SHADER => ShaderType::Vertex,
+            gl::TESS_CONTROL_SHADER => ShaderType::TessellationControl,
+            gl::TESS_EVALUATION_SHADER => ShaderType::TessellationEvaluation,
+            gl::GEOMETRY_SHADER => ShaderType::Geometry,
+            gl::FRAGMENT_SHADER => ShaderType::Fragment,
+            gl::COMPUTE_SHADER => ShaderType::Compute,
+            _ => unreachable!()
+        }
+    }
+}
@@ -15,24 +15,35 @@ use std::collections::hash_map::{self, HashMap};
 use std::rc::Rc;
 use std::cell::RefCell;
 
+use DrawError;
 use GlObject;
 use Handle;
+use ProgramExt;
+
+use ops;
+use uniforms::Uniforms;
 
 use program::{COMPILER_GLOBAL_LOCK, IntoProgramCreationInput, ProgramCreationInput, Binary};
+use program::ShaderType;
 
 use program::reflection::{Uniform, UniformBlock};
 use program::reflection::{Attribute, TransformFeedbackMode, TransformFeedbackBuffer};
 use program::reflection::{reflect_uniforms, reflect_attributes, reflect_uniform_blocks};
 use program::reflection::{reflect_transform_feedback};
-use program::shader::build_shader;
+use program::shader::{Shader, build_shader, build_spirv_shader};
 
 /// Error that can be triggered when creating a `Program`.
 #[derive(Clone, Debug)]
 pub enum ProgramCreationError {
     /// Error while compiling one of the shaders.
-    CompilationError(String),
+    ///
+    /// Contains the info log of the compiler and the stage of the shader that failed to
+    /// compile.
+    CompilationError(String, ShaderType),
 
     /// Error while linking the program.
+    ///
+    /// Contains the info log of the linker.
     LinkingError(String),
 
     /// One of the requested shader types is not supported by the backend.
@@ -46,13 +57,32 @@ pub enum ProgramCreationError {
     /// You have requested transform feedback varyings, but transform feedback is not supported
     /// by the backend.
     TransformFeedbackNotSupported,
+
+    /// You have requested locations for the fragment shader outputs, but binding them is not
+    /// supported by the backend.
+    FragDataLocationNotSupported,
+
+    /// You have passed a `Binary`, but the backend doesn't support loading program binaries.
+    BinaryNotSupported,
+
+    /// The backend rejected the program binary.
+    ///
+    /// This usually happens when the binary was produced by another driver or by another
+    /// version of the same driver. You should compile the program from its source code
+    /// instead.
+    BinaryRejected,
+
+    /// You have passed SPIR-V modules, but the backend doesn't support OpenGL 4.6 or
+    /// `GL_ARB_gl_spirv`.
+    SpirvNotSupported,
 }
 
 impl fmt::Display for ProgramCreationError {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match self {
-            &ProgramCreationError::CompilationError(ref s) =>
-                formatter.write_fmt(format_args!("Compilation error in one of the shaders: {}", s)),
+            &ProgramCreationError::CompilationError(ref s, ty) =>
+                formatter.write_fmt(format_args!("Compilation error in the {} shader: {}",
+                                                 ty.get_name(), s)),
             &ProgramCreationError::LinkingError(ref s) =>
                 formatter.write_fmt(format_args!("Error while linking shaders together: {}", s)),
             &ProgramCreationError::ShaderTypeNotSupported =>
@@ -63,6 +93,15 @@ impl fmt::Display for ProgramCreationError {
             &ProgramCreationError::TransformFeedbackNotSupported => 
                 formatter.write_str("You requested transform feedback, but this feature is not \
                                      supported by the backend"),
+            &ProgramCreationError::FragDataLocationNotSupported =>
+                formatter.write_str("You requested locations for the fragment shader outputs, \
+                                     but this feature is not supported by the backend"),
+            &ProgramCreationError::BinaryNotSupported =>
+                formatter.write_str("The backend doesn't support loading program binaries"),
+            &ProgramCreationError::BinaryRejected =>
+                formatter.write_str("The backend rejected the program binary"),
+            &ProgramCreationError::SpirvNotSupported =>
+                formatter.write_str("The backend doesn't support SPIR-V shaders"),
         }
     }
 }
@@ -70,7 +109,7 @@ impl fmt::Display for ProgramCreationError {
 impl Error for ProgramCreationError {
     fn description(&self) -> &str {
         match self {
-            &ProgramCreationError::CompilationError(_) => "Compilation error in one of the \
+            &ProgramCreationError::CompilationError(_, _) => "Compilation error in one of the \
                                                            shaders",
             &ProgramCreationError::LinkingError(_) => "Error while linking shaders together",
             &ProgramCreationError::ShaderTypeNotSupported => "One of the request shader type is \
@@ -79,6 +118,14 @@ impl Error for ProgramCreationError {
                                                                shaders compilation",
             &ProgramCreationError::TransformFeedbackNotSupported => "Transform feedback is not \
                                                                      supported by the backend.",
+            &ProgramCreationError::FragDataLocationNotSupported => "Binding the locations of \
+                                                                    fragment outputs is not \
+                                                                    supported by the backend",
+            &ProgramCreationError::BinaryNotSupported => "The backend doesn't support loading \
+                                                          program binaries",
+            &ProgramCreationError::BinaryRejected => "The backend rejected the program binary",
+            &ProgramCreationError::SpirvNotSupported => "The backend doesn't support SPIR-V \
+                                                         shaders",
         }
     }
 
@@ -97,10 +144,35 @@ pub struct Program {
     frag_data_locations: RefCell<HashMap<String, Option<u32>>>,
     tf_buffers: Vec<TransformFeedbackBuffer>,
     has_tessellation_shaders: bool,
+    is_compute: bool,
+
+    /// Texture unit that was last assigned to each sampler uniform, indexed by location.
+    sampler_units: RefCell<HashMap<gl::types::GLint, gl::types::GLint>>,
 }
 
 impl Program {
     /// Builds a new program.
+    ///
+    /// `input` can be a `SourceCode`, which lets you pass tessellation shaders in addition to
+    /// the shaders accepted by `from_source`. Tessellation shaders require OpenGL 4.0 or
+    /// `GL_ARB_tessellation_shader`, otherwise `Err(ShaderTypeNotSupported)` is returned.
+    /// A program that contains tessellation shaders must be drawn with
+    /// `PrimitiveType::Patches`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let display: glium::Display = unsafe { std::mem::uninitialized() };
+    /// # let vertex_source = ""; let fragment_source = "";
+    /// # let tess_control_source = ""; let tess_eval_source = "";
+    /// let program = glium::Program::new(&display, glium::program::SourceCode {
+    ///     vertex_shader: vertex_source,
+    ///     tessellation_control_shader: Some(tess_control_source),
+    ///     tessellation_evaluation_shader: Some(tess_eval_source),
+    ///     geometry_shader: None,
+    ///     fragment_shader: fragment_source,
+    /// });
+    /// ```
     pub fn new<'a, F, I>(facade: &F, input: I) -> Result<Program, ProgramCreationError>
                          where I: IntoProgramCreationInput<'a>, F: Facade
     {
@@ -123,6 +195,10 @@ impl Program {
     /// - `fragment_shader`: Source code of the fragment shader.
     /// - `geometry_shader`: Source code of the geometry shader.
     ///
+    /// If one of the shaders fails to compile, `Err(CompilationError(log, stage))` is returned
+    /// with the content of `glGetShaderInfoLog` and the stage of the faulty shader. If linking
+    /// fails, `Err(LinkingError(log))` contains the content of `glGetProgramInfoLog`.
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -144,9 +220,180 @@ impl Program {
             tessellation_control_shader: None,
             tessellation_evaluation_shader: None,
             transform_feedback_varyings: None,
+            frag_data_locations: Vec::new(),
         })
     }
 
+    /// Builds a new program that contains a single compute shader.
+    ///
+    /// A compute program can't be used to draw. Use `dispatch_compute` instead.
+    ///
+    /// Compute shaders require OpenGL 4.3, OpenGL ES 3.1 or `GL_ARB_compute_shader`. If they
+    /// are not supported, `Err(ShaderTypeNotSupported)` is returned.
+    pub fn from_compute_source<F>(facade: &F, compute_shader: &str)
+                                  -> Result<Program, ProgramCreationError> where F: Facade
+    {
+        Program::from_shaders(facade, vec![(compute_shader, gl::COMPUTE_SHADER)], None,
+                              Vec::new(), false)
+    }
+
+    /// Builds a new program from precompiled SPIR-V modules.
+    ///
+    /// `vertex_shader` and `fragment_shader` are the SPIR-V binaries of each stage. Their
+    /// entry point must be named `main`. `specialization_constants` contains the index and
+    /// the value of each specialization constant to override, and is applied to both stages.
+    ///
+    /// SPIR-V shaders require OpenGL 4.6 or `GL_ARB_gl_spirv`, otherwise
+    /// `Err(SpirvNotSupported)` is returned. If the driver fails to specialize a module,
+    /// `Err(CompilationError(log, stage))` is returned.
+    pub fn from_spirv<F>(facade: &F, vertex_shader: &[u8], fragment_shader: &[u8],
+                         specialization_constants: &[(u32, u32)])
+                         -> Result<Program, ProgramCreationError> where F: Facade
+    {
+        let shaders = vec![
+            try!(build_spirv_shader(facade, gl::VERTEX_SHADER, vertex_shader, "main",
+                                    specialization_constants)),
+            try!(build_spirv_shader(facade, gl::FRAGMENT_SHADER, fragment_shader, "main",
+                                    specialization_constants)),
+        ];
+
+        Program::link_shaders(facade, shaders, None, Vec::new(), false, false)
+    }
+
+    /// Executes a compute program.
+    ///
+    /// `x`, `y` and `z` are the number of work groups to dispatch in each dimension. The
+    /// number of invocations per work group is defined in the shader with
+    /// `layout(local_size_x = ...) in;`.
+    ///
+    /// Returns `Err(DrawError::NotAComputeProgram)` if the program was not built with
+    /// `from_compute_source`, `Err(DrawError::ComputeShaderNotSupported)` if the backend
+    /// doesn't support compute shaders, and `Err(DrawError::WorkGroupCountNotSupported)` if
+    /// one of the dimensions is zero or exceeds `GL_MAX_COMPUTE_WORK_GROUP_COUNT`. Errors
+    /// related to the uniforms are the same as when drawing.
+    pub fn dispatch_compute<U>(&self, uniforms: U, x: u32, y: u32, z: u32)
+                               -> Result<(), DrawError> where U: Uniforms
+    {
+        ops::dispatch_compute(&self.context, self, uniforms, x, y, z)
+    }
+
+    /// Recompiles the program from GLSL source code, replacing the current one.
+    ///
+    /// The parameters are the same as `from_source`. This is useful when iterating on
+    /// shaders, as the `Program` object doesn't need to be recreated.
+    ///
+    /// The new shaders are linked into the same OpenGL program object, so the id of the
+    /// program doesn't change. The values of the uniforms are reset, like with any newly-linked
+    /// program.
+    ///
+    /// If compiling or linking fails, the error is returned and the program is left
+    /// untouched.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `facade` doesn't belong to the same context as the program.
+    #[unstable = "The list of shaders and the result error will probably change"]
+    pub fn reload_from_source<'a, F>(&mut self, facade: &F, vertex_shader: &'a str,
+                                     fragment_shader: &'a str, geometry_shader: Option<&'a str>)
+                                     -> Result<(), ProgramCreationError> where F: Facade
+    {
+        assert!(&*self.context as *const Context ==
+                &**facade.get_context() as *const Context);
+
+        // a failed link would make the current program unusable, so the sources are first
+        // linked into a separate program
+        try!(Program::from_source(facade, vertex_shader, fragment_shader, geometry_shader));
+
+        let mut shaders = vec![
+            (vertex_shader, gl::VERTEX_SHADER),
+            (fragment_shader, gl::FRAGMENT_SHADER)
+        ];
+
+        if let Some(gs) = geometry_shader {
+            shaders.push((gs, gl::GEOMETRY_SHADER));
+        }
+
+        let shaders_store = {
+            let mut shaders_store = Vec::new();
+            for (src, ty) in shaders.into_iter() {
+                shaders_store.push(try!(build_shader(facade, ty, src)));
+            }
+            shaders_store
+        };
+
+        let mut ctxt = facade.get_context().make_current();
+
+        // the VAOs that use this program may refer to attribute locations that are changing
+        self.context.vertex_array_objects.purge_program(&mut ctxt, self.id);
+
+        unsafe {
+            // replacing the shaders attached to the program
+            match self.id {
+                Handle::Id(id) => {
+                    let mut count: gl::types::GLint = mem::uninitialized();
+                    ctxt.gl.GetProgramiv(id, gl::ATTACHED_SHADERS, &mut count);
+
+                    let mut attached: Vec<gl::types::GLuint> = vec![0; count as usize];
+                    ctxt.gl.GetAttachedShaders(id, count, &mut count, attached.as_mut_ptr());
+
+                    for &sh in attached[..count as usize].iter() {
+                        ctxt.gl.DetachShader(id, sh);
+                    }
+                },
+                Handle::Handle(id) => {
+                    let mut count: gl::types::GLint = mem::uninitialized();
+                    ctxt.gl.GetObjectParameterivARB(id, gl::OBJECT_ATTACHED_OBJECTS_ARB,
+                                                    &mut count);
+
+                    let mut attached: Vec<gl::types::GLhandleARB> =
+                                            vec![0 as gl::types::GLhandleARB; count as usize];
+                    ctxt.gl.GetAttachedObjectsARB(id, count, &mut count,
+                                                  attached.as_mut_ptr());
+
+                    for &sh in attached[..count as usize].iter() {
+                        ctxt.gl.DetachObjectARB(id, sh);
+                    }
+                }
+            }
+
+            for sh in shaders_store.iter() {
+                match (self.id, sh.get_id()) {
+                    (Handle::Id(id), Handle::Id(sh)) => ctxt.gl.AttachShader(id, sh),
+                    (Handle::Handle(id), Handle::Handle(sh)) => ctxt.gl.AttachObjectARB(id, sh),
+                    _ => unreachable!()
+                }
+            }
+
+            // linking
+            {
+                let _lock = COMPILER_GLOBAL_LOCK.lock();
+
+                ctxt.report_debug_output_errors.set(false);
+
+                match self.id {
+                    Handle::Id(id) => ctxt.gl.LinkProgram(id),
+                    Handle::Handle(id) => ctxt.gl.LinkProgramARB(id),
+                }
+
+                ctxt.report_debug_output_errors.set(true);
+            }
+
+            try!(check_program_link_errors(&mut ctxt, self.id));
+
+            self.uniforms = reflect_uniforms(&mut ctxt, self.id);
+            self.attributes = reflect_attributes(&mut ctxt, self.id);
+            self.uniform_blocks = reflect_uniform_blocks(&mut ctxt, self.id);
+            self.tf_buffers = reflect_transform_feedback(&mut ctxt, self.id);
+        }
+
+        self.has_tessellation_shaders = false;
+        self.is_compute = false;
+        self.frag_data_locations.borrow_mut().clear();
+        self.sampler_units.borrow_mut().clear();
+
+        Ok(())
+    }
+
     /// Compiles a program from source.
     ///
     /// Must only be called if `input` is a `ProgramCreationInput::SourceCode`, will
@@ -158,19 +405,22 @@ impl Program {
         let mut has_tessellation_shaders = false;
 
         // getting an array of the source codes and their type
-        let (shaders, transform_feedback_varyings): (Vec<(&str, gl::types::GLenum)>, _) = {
+        let (shaders, transform_feedback_varyings, frag_data_locations):
+            (Vec<(&str, gl::types::GLenum)>, _, _) =
+        {
             let (vertex_shader, fragment_shader, geometry_shader,
                  tessellation_control_shader, tessellation_evaluation_shader,
-                 transform_feedback_varyings) = match input
+                 transform_feedback_varyings, frag_data_locations) = match input
             {
                 ProgramCreationInput::SourceCode { vertex_shader, fragment_shader,
                                                    geometry_shader, tessellation_control_shader,
                                                    tessellation_evaluation_shader,
-                                                   transform_feedback_varyings } =>
+                                                   transform_feedback_varyings,
+                                                   frag_data_locations } =>
                 {
                     (vertex_shader, fragment_shader, geometry_shader,
                      tessellation_control_shader, tessellation_evaluation_shader,
-                     transform_feedback_varyings)
+                     transform_feedback_varyings, frag_data_locations)
                 },
                 _ => unreachable!()     // the function shouldn't be called with anything else
             };
@@ -201,9 +451,29 @@ impl Program {
                 return Err(ProgramCreationError::TransformFeedbackNotSupported);
             }
 
-            (shaders, transform_feedback_varyings)
+            if !frag_data_locations.is_empty() &&
+                !(facade.get_context().get_version() >= &Version(Api::Gl, 3, 0)) &&
+                !facade.get_context().get_extensions().gl_ext_gpu_shader4
+            {
+                return Err(ProgramCreationError::FragDataLocationNotSupported);
+            }
+
+            (shaders, transform_feedback_varyings, frag_data_locations)
         };
 
+        Program::from_shaders(facade, shaders, transform_feedback_varyings,
+                              frag_data_locations, has_tessellation_shaders)
+    }
+
+    /// Compiles a list of shaders and links them together.
+    fn from_shaders<F>(facade: &F, shaders: Vec<(&str, gl::types::GLenum)>,
+                       transform_feedback_varyings: Option<(Vec<String>, TransformFeedbackMode)>,
+                       frag_data_locations: Vec<(String, u32)>,
+                       has_tessellation_shaders: bool)
+                       -> Result<Program, ProgramCreationError> where F: Facade
+    {
+        let is_compute = shaders.iter().any(|&(_, ty)| ty == gl::COMPUTE_SHADER);
+
         let shaders_store = {
             let mut shaders_store = Vec::new();
             for (src, ty) in shaders.into_iter() {
@@ -212,6 +482,17 @@ impl Program {
             shaders_store
         };
 
+        Program::link_shaders(facade, shaders_store, transform_feedback_varyings,
+                              frag_data_locations, has_tessellation_shaders, is_compute)
+    }
+
+    /// Links a list of compiled shaders together.
+    fn link_shaders<F>(facade: &F, shaders_store: Vec<Shader>,
+                       transform_feedback_varyings: Option<(Vec<String>, TransformFeedbackMode)>,
+                       frag_data_locations: Vec<(String, u32)>,
+                       has_tessellation_shaders: bool, is_compute: bool)
+                       -> Result<Program, ProgramCreationError> where F: Facade
+    {
         let mut shaders_ids = Vec::new();
         for sh in shaders_store.iter() {
             shaders_ids.push(sh.get_id());
@@ -275,6 +556,32 @@ impl Program {
                 }
             }
 
+            // fragment outputs locations
+            for (name, location) in frag_data_locations.into_iter() {
+                let id = match id {
+                    Handle::Id(id) => id,
+                    Handle::Handle(_) => unreachable!()     // handles are only used before GL 2.0
+                };
+
+                let name = ffi::CString::new(name.into_bytes()).unwrap();
+
+                if ctxt.version >= &Version(Api::Gl, 3, 0) {
+                    ctxt.gl.BindFragDataLocation(id, location, name.as_ptr());
+                } else if ctxt.extensions.gl_ext_gpu_shader4 {
+                    ctxt.gl.BindFragDataLocationEXT(id, location, name.as_ptr());
+                } else {
+                    unreachable!();     // has been checked in the frontend
+                }
+            }
+
+            // asking the driver to keep the binary around so that `get_binary` works
+            if let Handle::Id(id) = id {
+                if is_program_binary_supported(&ctxt) {
+                    ctxt.gl.ProgramParameteri(id, gl::PROGRAM_BINARY_RETRIEVABLE_HINT,
+                                              gl::TRUE as gl::types::GLint);
+                }
+            }
+
             // linking
             {
                 let _lock = COMPILER_GLOBAL_LOCK.lock();
@@ -322,6 +629,8 @@ impl Program {
             frag_data_locations: RefCell::new(HashMap::new()),
             tf_buffers: tf_buffers,
             has_tessellation_shaders: has_tessellation_shaders,
+            is_compute: is_compute,
+            sampler_units: RefCell::new(HashMap::new()),
         })
     }
 
@@ -339,21 +648,29 @@ impl Program {
 
         let mut ctxt = facade.get_context().make_current();
 
+        if !is_program_binary_supported(&ctxt) {
+            return Err(ProgramCreationError::BinaryNotSupported);
+        }
+
         let id = unsafe {
             let id = create_program(&mut ctxt);
 
-            match id {
-                Handle::Id(id) => {
-                    assert!(ctxt.version >= &Version(Api::Gl, 2, 0));
-                    ctxt.gl.ProgramBinary(id, binary.format,
-                                          binary.content.as_ptr() as *const _,
-                                          binary.content.len() as gl::types::GLsizei);
-                },
-                Handle::Handle(id) => unreachable!()
+            let raw_id = match id {
+                Handle::Id(id) => id,
+                Handle::Handle(_) => unreachable!()
             };
 
-            // checking for errors
-            try!(check_program_link_errors(&mut ctxt, id));
+            ctxt.gl.ProgramBinary(raw_id, binary.format, binary.content.as_ptr() as *const _,
+                                  binary.content.len() as gl::types::GLsizei);
+
+            // the driver is allowed to reject any binary, for example after an update
+            let mut link_success: gl::types::GLint = mem::uninitialized();
+            ctxt.gl.GetProgramiv(raw_id, gl::LINK_STATUS, &mut link_success);
+
+            if link_success == 0 {
+                ctxt.gl.DeleteProgram(raw_id);
+                return Err(ProgramCreationError::BinaryRejected);
+            }
 
             id
         };
@@ -376,6 +693,8 @@ impl Program {
             frag_data_locations: RefCell::new(HashMap::new()),
             tf_buffers: tf_buffers,
             has_tessellation_shaders: true,     // FIXME: 
+            is_compute: true,       // FIXME: the stages of a binary are unknown
+            sampler_units: RefCell::new(HashMap::new()),
         })
     }
 
@@ -384,6 +703,25 @@ impl Program {
     /// You can store the result in a file, then reload it later. This avoids having to compile
     /// the source code every time.
     ///
+    /// The driver is free to reject a binary that it produced earlier, for example after it
+    /// has been updated. In this situation, `Program::new` returns `Err(BinaryRejected)` and
+    /// you should compile the program from its source code again:
+    ///
+    /// ```no_run
+    /// # let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+    /// # let vertex_source = ""; let fragment_source = "";
+    /// # let cached_binary: glium::program::Binary = unsafe { ::std::mem::uninitialized() };
+    /// let program = match glium::Program::new(&display, cached_binary) {
+    ///     Ok(p) => p,
+    ///     Err(glium::program::ProgramCreationError::BinaryRejected) |
+    ///     Err(glium::program::ProgramCreationError::BinaryNotSupported) => {
+    ///         glium::Program::from_source(&display, vertex_source, fragment_source,
+    ///                                     None).unwrap()
+    ///     },
+    ///     Err(e) => panic!("{}", e)
+    /// };
+    /// ```
+    ///
     /// ## Features
     ///
     /// Only available if the `gl_program_binary` feature is enabled.
@@ -400,9 +738,7 @@ impl Program {
         unsafe {
             let ctxt = self.context.make_current();
 
-            if ctxt.version >= &Version(Api::Gl, 4, 1) ||
-               ctxt.extensions.gl_arb_get_programy_binary
-            {
+            if is_program_binary_supported(&ctxt) {
                 let id = match self.id {
                     Handle::Id(id) => id,
                     Handle::Handle(_) => unreachable!()
@@ -527,6 +863,35 @@ impl GlObject for Program {
     }
 }
 
+impl ProgramExt for Program {
+    fn is_compute(&self) -> bool {
+        self.is_compute
+    }
+
+    fn set_sampler_uniform(&self, ctxt: &mut CommandContext, location: gl::types::GLint,
+                           texture_unit: gl::types::GLint)
+    {
+        let mut sampler_units = self.sampler_units.borrow_mut();
+
+        if sampler_units.get(&location) == Some(&texture_unit) {
+            return;
+        }
+
+        unsafe {
+            if ctxt.version >= &Version(Api::Gl, 1, 5) ||
+               ctxt.version >= &Version(Api::GlEs, 2, 0)
+            {
+                ctxt.gl.Uniform1i(location, texture_unit);
+            } else {
+                assert!(ctxt.extensions.gl_arb_shader_objects);
+                ctxt.gl.Uniform1iARB(location, texture_unit);
+            }
+        }
+
+        sampler_units.insert(location, texture_unit);
+    }
+}
+
 impl Drop for Program {
     fn drop(&mut self) {
         let mut ctxt = self.context.make_current();
@@ -582,6 +947,12 @@ unsafe fn create_program(ctxt: &mut CommandContext) -> Handle {
     id
 }
 
+/// Returns true if the backend supports `glGetProgramBinary` and `glProgramBinary`.
+fn is_program_binary_supported(ctxt: &CommandContext) -> bool {
+    ctxt.version >= &Version(Api::Gl, 4, 1) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+    ctxt.extensions.gl_arb_get_programy_binary
+}
+
 unsafe fn check_program_link_errors(ctxt: &mut CommandContext, id: Handle)
                                     -> Result<(), ProgramCreationError>
 {
@@ -650,7 +1021,7 @@ unsafe fn check_program_link_errors(ctxt: &mut CommandContext, id: Handle)
 
         error_log.set_len(error_log_size as usize);
 
-        let msg = String::from_utf8(error_log).unwrap();
+        let msg = String::from_utf8_lossy(&error_log).into_owned();
         return Err(LinkingError(msg));
     }
 
@@ -170,7 +170,13 @@ pub unsafe fn reflect_uniforms(ctxt: &mut CommandContext, program: Handle)
 
         uniform_name_tmp.set_len(uniform_name_tmp_len as usize);
 
-        let uniform_name = String::from_utf8(uniform_name_tmp).unwrap();
+        let mut uniform_name = String::from_utf8(uniform_name_tmp).unwrap();
+
+        // arrays are reported as `name[0]`, but are looked up by `name`
+        if uniform_name.ends_with("[0]") {
+            let len = uniform_name.len();
+            uniform_name.truncate(len - 3);
+        }
         let location = match program {
             Handle::Id(program) => {
                 assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
@@ -15,6 +15,7 @@ use Handle;
 
 use program::COMPILER_GLOBAL_LOCK;
 use program::ProgramCreationError;
+use program::ShaderType;
 
 /// A single, compiled but unlinked, shader.
 pub struct Shader {
@@ -171,14 +172,94 @@ pub fn build_shader<F>(facade: &F, shader_type: gl::types::GLenum, source_code:
 
             error_log.set_len(error_log_size as usize);
 
-            match String::from_utf8(error_log) {
-                Ok(msg) => Err(ProgramCreationError::CompilationError(msg)),
-                Err(_) => Err(
-                    ProgramCreationError::CompilationError("Could not convert the log \
-                                                            message to UTF-8".to_string())
-                ),
+            // some drivers don't produce valid UTF-8, so the invalid characters are replaced
+            // instead of losing the whole log
+            let msg = String::from_utf8_lossy(&error_log).into_owned();
+            Err(ProgramCreationError::CompilationError(msg, ShaderType::from_glenum(shader_type)))
+        }
+    }
+}
+
+/// Builds an individual shader from a SPIR-V module.
+///
+/// `specialization_constants` contains the index and the value of each constant to specialize.
+pub fn build_spirv_shader<F>(facade: &F, shader_type: gl::types::GLenum, binary: &[u8],
+                             entry_point: &str, specialization_constants: &[(u32, u32)])
+                             -> Result<Shader, ProgramCreationError> where F: Facade
+{
+    unsafe {
+        let mut ctxt = facade.get_context().make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 4, 6)) && !ctxt.extensions.gl_arb_gl_spirv {
+            return Err(ProgramCreationError::SpirvNotSupported);
+        }
+
+        try!(check_shader_type_compatibility(&mut ctxt, shader_type));
+
+        // a SPIR-V module is a list of 32-bits words that starts with a magic number
+        if binary.len() % 4 != 0 || binary.len() < 4 ||
+           (&binary[..4] != &[0x03, 0x02, 0x23, 0x07] &&
+            &binary[..4] != &[0x07, 0x23, 0x02, 0x03])
+        {
+            return Err(ProgramCreationError::CompilationError("Invalid SPIR-V module".to_string(),
+                                                              ShaderType::from_glenum(shader_type)));
+        }
+
+        let id = ctxt.gl.CreateShader(shader_type);
+
+        if id == 0 {
+            return Err(ProgramCreationError::ShaderTypeNotSupported);
+        }
+
+        let entry_point = ffi::CString::new(entry_point.as_bytes()).unwrap();
+        let (indices, values): (Vec<_>, Vec<_>) = specialization_constants.iter().cloned()
+                                                                          .unzip();
+
+        {
+            let _lock = COMPILER_GLOBAL_LOCK.lock();
+
+            ctxt.report_debug_output_errors.set(false);
+
+            ctxt.gl.ShaderBinary(1, &id, gl::SHADER_BINARY_FORMAT_SPIR_V_ARB,
+                                 binary.as_ptr() as *const _,
+                                 binary.len() as gl::types::GLsizei);
+
+            if ctxt.version >= &Version(Api::Gl, 4, 6) {
+                ctxt.gl.SpecializeShader(id, entry_point.as_ptr(),
+                                         indices.len() as gl::types::GLuint,
+                                         indices.as_ptr(), values.as_ptr());
+            } else {
+                ctxt.gl.SpecializeShaderARB(id, entry_point.as_ptr(),
+                                            indices.len() as gl::types::GLuint,
+                                            indices.as_ptr(), values.as_ptr());
             }
+
+            ctxt.report_debug_output_errors.set(true);
         }
+
+        // specializing a SPIR-V module sets the compile status of the shader
+        let mut compilation_success: gl::types::GLint = mem::uninitialized();
+        ctxt.gl.GetShaderiv(id, gl::COMPILE_STATUS, &mut compilation_success);
+
+        if compilation_success == 1 {
+            return Ok(Shader {
+                context: facade.get_context().clone(),
+                id: Handle::Id(id),
+            });
+        }
+
+        let mut error_log_size: gl::types::GLint = mem::uninitialized();
+        ctxt.gl.GetShaderiv(id, gl::INFO_LOG_LENGTH, &mut error_log_size);
+
+        let mut error_log: Vec<u8> = Vec::with_capacity(error_log_size as usize);
+        ctxt.gl.GetShaderInfoLog(id, error_log_size, &mut error_log_size,
+                                 error_log.as_mut_ptr() as *mut gl::types::GLchar);
+        error_log.set_len(error_log_size as usize);
+
+        ctxt.gl.DeleteShader(id);
+
+        let msg = String::from_utf8_lossy(&error_log).into_owned();
+        Err(ProgramCreationError::CompilationError(msg, ShaderType::from_glenum(shader_type)))
     }
 }
 
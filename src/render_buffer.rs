@@ -26,6 +26,16 @@ use ContextExt;
 use version::Version;
 use version::Api;
 
+/// Error that can happen when creating a multisample render buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultisampleCreationError {
+    /// Multisample render buffers are not supported by the backend.
+    NotSupported,
+
+    /// The number of samples is zero or is superior to `GL_MAX_SAMPLES`.
+    SamplesCountNotSupported,
+}
+
 /// A render buffer is similar to a texture, but is optimized for usage as a draw target.
 ///
 /// Contrary to a texture, you can't sample or modify the content of the `RenderBuffer`.
@@ -43,10 +53,29 @@ impl RenderBuffer {
         let format = format.expect("Format not supported");
 
         RenderBuffer {
-            buffer: RenderBufferImpl::new(facade, format, width, height)
+            buffer: RenderBufferImpl::new(facade, format, width, height, None)
         }
     }
 
+    /// Builds a new multisample render buffer.
+    ///
+    /// Drawing on a framebuffer that uses this render buffer produces an antialiased image,
+    /// which can then be resolved into a regular texture with `blit_color`.
+    pub fn new_multisample<F>(facade: &F, format: UncompressedFloatFormat, width: u32,
+                              height: u32, samples: u32)
+                              -> Result<RenderBuffer, MultisampleCreationError> where F: Facade
+    {
+        try!(check_samples(facade, samples));
+
+        let format = image_format::TextureFormatRequest::Specific(image_format::TextureFormat::UncompressedFloat(format));
+        let (_, format) = image_format::format_request_to_glenum(&facade.get_context(), None, format).unwrap();
+        let format = format.expect("Format not supported");
+
+        Ok(RenderBuffer {
+            buffer: RenderBufferImpl::new(facade, format, width, height, Some(samples))
+        })
+    }
+
     /// Returns the dimensions of the render buffer.
     pub fn get_dimensions(&self) -> (u32, u32) {
         (self.buffer.width, self.buffer.height)
@@ -83,10 +112,29 @@ impl DepthRenderBuffer {
         let format = format.expect("Format not supported");
 
         DepthRenderBuffer {
-            buffer: RenderBufferImpl::new(facade, format, width, height)
+            buffer: RenderBufferImpl::new(facade, format, width, height, None)
         }
     }
 
+    /// Builds a new multisample render buffer.
+    ///
+    /// The number of samples must match the one of the color attachments of the framebuffer.
+    pub fn new_multisample<F>(facade: &F, format: DepthFormat, width: u32, height: u32,
+                              samples: u32)
+                              -> Result<DepthRenderBuffer, MultisampleCreationError>
+                              where F: Facade
+    {
+        try!(check_samples(facade, samples));
+
+        let format = image_format::TextureFormatRequest::Specific(image_format::TextureFormat::DepthFormat(format));
+        let (_, format) = image_format::format_request_to_glenum(&facade.get_context(), None, format).unwrap();
+        let format = format.expect("Format not supported");
+
+        Ok(DepthRenderBuffer {
+            buffer: RenderBufferImpl::new(facade, format, width, height, Some(samples))
+        })
+    }
+
     /// Returns the dimensions of the render buffer.
     pub fn get_dimensions(&self) -> (u32, u32) {
         (self.buffer.width, self.buffer.height)
@@ -123,7 +171,7 @@ impl StencilRenderBuffer {
         let format = format.expect("Format not supported");
 
         StencilRenderBuffer {
-            buffer: RenderBufferImpl::new(facade, format, width, height)
+            buffer: RenderBufferImpl::new(facade, format, width, height, None)
         }
     }
 
@@ -163,7 +211,7 @@ impl DepthStencilRenderBuffer {
         let format = format.expect("Format not supported");
 
         DepthStencilRenderBuffer {
-            buffer: RenderBufferImpl::new(facade, format, width, height)
+            buffer: RenderBufferImpl::new(facade, format, width, height, None)
         }
     }
 
@@ -186,6 +234,19 @@ impl GlObject for DepthStencilRenderBuffer {
     }
 }
 
+/// Checks that the backend supports multisample render buffers with this number of samples.
+fn check_samples<F>(facade: &F, samples: u32) -> Result<(), MultisampleCreationError>
+                    where F: Facade
+{
+    match facade.get_context().capabilities().max_samples {
+        None => Err(MultisampleCreationError::NotSupported),
+        Some(max) if samples == 0 || samples > max as u32 => {
+            Err(MultisampleCreationError::SamplesCountNotSupported)
+        },
+        Some(_) => Ok(())
+    }
+}
+
 /// The implementation
 struct RenderBufferImpl {
     context: Rc<Context>,
@@ -196,8 +257,10 @@ struct RenderBufferImpl {
 
 impl RenderBufferImpl {
     /// Builds a new render buffer.
-    fn new<F>(facade: &F, format: gl::types::GLenum, width: u32, height: u32)
-              -> RenderBufferImpl where F: Facade
+    ///
+    /// If `samples` is `Some`, the caller must have checked that multisampling is supported.
+    fn new<F>(facade: &F, format: gl::types::GLenum, width: u32, height: u32,
+              samples: Option<u32>) -> RenderBufferImpl where F: Facade
     {
         // TODO: check that dimensions don't exceed GL_MAX_RENDERBUFFER_SIZE
         let mut ctxt = facade.get_context().make_current();
@@ -209,8 +272,17 @@ impl RenderBufferImpl {
                 ctxt.extensions.gl_arb_direct_state_access
             {
                 ctxt.gl.CreateRenderbuffers(1, &mut id);
-                ctxt.gl.NamedRenderbufferStorage(id, format, width as gl::types::GLsizei,
-                                                 height as gl::types::GLsizei);
+
+                if let Some(samples) = samples {
+                    ctxt.gl.NamedRenderbufferStorageMultisample(id,
+                                                                samples as gl::types::GLsizei,
+                                                                format,
+                                                                width as gl::types::GLsizei,
+                                                                height as gl::types::GLsizei);
+                } else {
+                    ctxt.gl.NamedRenderbufferStorage(id, format, width as gl::types::GLsizei,
+                                                     height as gl::types::GLsizei);
+                }
 
             } else if ctxt.version >= &Version(Api::Gl, 3, 0) ||
                       ctxt.version >= &Version(Api::GlEs, 2, 0)
@@ -218,12 +290,22 @@ impl RenderBufferImpl {
                 ctxt.gl.GenRenderbuffers(1, &mut id);
                 ctxt.gl.BindRenderbuffer(gl::RENDERBUFFER, id);
                 ctxt.state.renderbuffer = id;
-                // FIXME: gles2 only supports very few formats
-                ctxt.gl.RenderbufferStorage(gl::RENDERBUFFER, format,
-                                            width as gl::types::GLsizei,
-                                            height as gl::types::GLsizei);
+
+                if let Some(samples) = samples {
+                    ctxt.gl.RenderbufferStorageMultisample(gl::RENDERBUFFER,
+                                                           samples as gl::types::GLsizei,
+                                                           format,
+                                                           width as gl::types::GLsizei,
+                                                           height as gl::types::GLsizei);
+                } else {
+                    // FIXME: gles2 only supports very few formats
+                    ctxt.gl.RenderbufferStorage(gl::RENDERBUFFER, format,
+                                                width as gl::types::GLsizei,
+                                                height as gl::types::GLsizei);
+                }
 
             } else if ctxt.extensions.gl_ext_framebuffer_object {
+                assert!(samples.is_none());
                 ctxt.gl.GenRenderbuffersEXT(1, &mut id);
                 ctxt.gl.BindRenderbufferEXT(gl::RENDERBUFFER_EXT, id);
                 ctxt.state.renderbuffer = id;
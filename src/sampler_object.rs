@@ -21,7 +21,8 @@ impl SamplerObject {
     /// Builds a new sampler object.
     pub fn new(ctxt: &mut CommandContext, behavior: &SamplerBehavior) -> SamplerObject {
         // making sure that the backend supports samplers
-        assert!(ctxt.version >= &Version(Api::Gl, 3, 2) ||
+        assert!(ctxt.version >= &Version(Api::Gl, 3, 3) ||
+                ctxt.version >= &Version(Api::GlEs, 3, 0) ||
                 ctxt.extensions.gl_arb_sampler_objects);
 
         let sampler = unsafe {
@@ -43,6 +44,13 @@ impl SamplerObject {
             ctxt.gl.SamplerParameteri(sampler, gl::TEXTURE_MAG_FILTER,
                                       behavior.magnify_filter.to_glenum() as gl::types::GLint);
 
+            if let Some(comparison) = behavior.depth_texture_comparison {
+                ctxt.gl.SamplerParameteri(sampler, gl::TEXTURE_COMPARE_MODE,
+                                          gl::COMPARE_REF_TO_TEXTURE as gl::types::GLint);
+                ctxt.gl.SamplerParameteri(sampler, gl::TEXTURE_COMPARE_FUNC,
+                                          comparison.to_glenum() as gl::types::GLint);
+            }
+
             if let Some(max_value) = ctxt.capabilities.max_texture_max_anisotropy {
                 let value = if behavior.max_anisotropy as f32 > max_value {
                     max_value
@@ -51,6 +59,11 @@ impl SamplerObject {
                 };
 
                 ctxt.gl.SamplerParameterf(sampler, gl::TEXTURE_MAX_ANISOTROPY_EXT, value);
+
+            } else if behavior.max_anisotropy > 1 {
+                // anisotropic filtering is not supported, so the value is clamped to 1
+                ::context::insert_debug_message(ctxt, "Anisotropic filtering is not supported \
+                                                       by the backend and has been disabled");
             }
         }
 
@@ -92,7 +105,9 @@ pub fn get_sampler(ctxt: &mut CommandContext,
                    -> Result<gl::types::GLuint, DrawError>
 {
     // checking for compatibility
-    if ctxt.version < &Version(Api::Gl, 3, 2) && !ctxt.extensions.gl_arb_sampler_objects {
+    if !(ctxt.version >= &Version(Api::Gl, 3, 3)) && !(ctxt.version >= &Version(Api::GlEs, 3, 0)) &&
+       !ctxt.extensions.gl_arb_sampler_objects
+    {
         return Err(DrawError::SamplersNotSupported);
     }
 
@@ -69,6 +69,27 @@ impl SyncFence {
             _ => panic!("Could not wait for the fence")
         };
     }
+
+    /// Blocks until the operation has finished on the server, or until `timeout_ns`
+    /// nanoseconds have elapsed.
+    ///
+    /// Returns `true` if the fence is signaled, and `false` if the timeout expired. A timeout
+    /// of `0` doesn't block and only checks whether the operation has finished.
+    pub fn wait_timeout(&self, timeout_ns: u64) -> bool {
+        let sync = self.id.unwrap();
+
+        let ctxt = self.context.make_current();
+
+        let result = unsafe {
+            ctxt.gl.ClientWaitSync(sync, gl::SYNC_FLUSH_COMMANDS_BIT, timeout_ns)
+        };
+
+        match result {
+            gl::ALREADY_SIGNALED | gl::CONDITION_SATISFIED => true,
+            gl::TIMEOUT_EXPIRED => false,
+            _ => panic!("Could not wait for the fence")
+        }
+    }
 }
 
 impl Drop for SyncFence {
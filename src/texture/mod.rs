@@ -29,6 +29,10 @@ Each combination of dimensions and format corresponds to a sampler type in GLSL.
 an `IntegralTexture3d` can only be bound to an `isampler3D` uniform in GLSL. Some combinations
 don't exist, like `DepthBufferTexture`.
 
+Integral and unsigned textures can't be filtered. They are created with `Nearest` filtering and
+without automatically-generated mipmaps, and you shouldn't pass a sampler with linear filtering
+when binding them.
+
 The difference between compressed textures and uncompressed textures is that you can't do
 render-to-texture on the former.
 
@@ -55,10 +59,12 @@ use {Surface, GlObject};
 use FboAttachments;
 use fbo::FramebufferAttachments;
 use Rect;
+use ContextExt;
 use BlitTarget;
+use BlitError;
 use uniforms;
 
-use self::tex_impl::TextureImplementation;
+use self::tex_impl::{TextureImplementation, MipmapsOption};
 use image_format::{TextureFormatRequest, FormatNotSupportedError};
 
 pub use image_format::{ClientFormat, TextureFormat};
@@ -87,6 +93,44 @@ pub trait Texture {
     fn get_array_size(&self) -> Option<u32>;
 }
 
+/// Represents a face of a cubemap.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CubeLayer {
+    /// Face pointing towards the positive X axis.
+    PositiveX,
+    /// Face pointing towards the negative X axis.
+    NegativeX,
+    /// Face pointing towards the positive Y axis.
+    PositiveY,
+    /// Face pointing towards the negative Y axis.
+    NegativeY,
+    /// Face pointing towards the positive Z axis.
+    PositiveZ,
+    /// Face pointing towards the negative Z axis.
+    NegativeZ,
+}
+
+impl CubeLayer {
+    /// Returns the index of the face, between `0` and `5`, in the order in which OpenGL
+    /// stores them.
+    pub fn get_layer_index(&self) -> usize {
+        match *self {
+            CubeLayer::PositiveX => 0,
+            CubeLayer::NegativeX => 1,
+            CubeLayer::PositiveY => 2,
+            CubeLayer::NegativeY => 3,
+            CubeLayer::PositiveZ => 4,
+            CubeLayer::NegativeZ => 5,
+        }
+    }
+}
+
+impl ::ToGlEnum for CubeLayer {
+    fn to_glenum(&self) -> gl::types::GLenum {
+        gl::TEXTURE_CUBE_MAP_POSITIVE_X + self.get_layer_index() as gl::types::GLenum
+    }
+}
+
 /// Trait that describes data for a one-dimensional texture.
 pub trait Texture1dDataSource<'a> {
     /// The type of each pixel.
@@ -395,10 +439,10 @@ impl<P> Texture3dDataSink for Vec<Vec<Vec<P>>> where P: PixelValue + Clone {
 pub struct TextureSurface<'a>(framebuffer::SimpleFrameBuffer<'a>);
 
 impl<'a> Surface for TextureSurface<'a> {
-    fn clear(&mut self, color: Option<(f32, f32, f32, f32)>, depth: Option<f32>,
-             stencil: Option<i32>)
+    fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>,
+             depth: Option<f32>, stencil: Option<i32>)
     {
-        self.0.clear(color, depth, stencil)
+        self.0.clear(rect, color, depth, stencil)
     }
 
     fn get_dimensions(&self) -> (u32, u32) {
@@ -422,13 +466,14 @@ impl<'a> Surface for TextureSurface<'a> {
     }
 
     fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
-                     filter: uniforms::MagnifySamplerFilter) where S: Surface
+                     filter: uniforms::MagnifySamplerFilter)
+                     -> Result<(), BlitError> where S: Surface
     {
         target.blit_from_simple_framebuffer(&self.0, source_rect, target_rect, filter)
     }
 
     fn blit_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget,
-                       filter: uniforms::MagnifySamplerFilter)
+                       filter: uniforms::MagnifySamplerFilter) -> Result<(), BlitError>
     {
         self.0.blit_from_frame(source_rect, target_rect, filter)
     }
@@ -436,6 +481,7 @@ impl<'a> Surface for TextureSurface<'a> {
     fn blit_from_simple_framebuffer(&self, source: &framebuffer::SimpleFrameBuffer,
                                     source_rect: &Rect, target_rect: &BlitTarget,
                                     filter: uniforms::MagnifySamplerFilter)
+                                    -> Result<(), BlitError>
     {
         self.0.blit_from_simple_framebuffer(source, source_rect, target_rect, filter)
     }
@@ -443,9 +489,20 @@ impl<'a> Surface for TextureSurface<'a> {
     fn blit_from_multioutput_framebuffer(&self, source: &framebuffer::MultiOutputFrameBuffer,
                                          source_rect: &Rect, target_rect: &BlitTarget,
                                          filter: uniforms::MagnifySamplerFilter)
+                                         -> Result<(), BlitError>
     {
         self.0.blit_from_multioutput_framebuffer(source, source_rect, target_rect, filter)
     }
+
+    fn read_rect<P, T>(&self, rect: &Rect) -> T
+                       where P: PixelValue + Clone + Send, T: Texture2dDataSink<Data = P>
+    {
+        self.0.read_rect(rect)
+    }
+
+    fn read_rect_into<P>(&self, rect: &Rect, dest: &mut [P]) where P: PixelValue {
+        self.0.read_rect_into(rect, dest)
+    }
 }
 
 impl<'a> FboAttachments for TextureSurface<'a> {
@@ -462,6 +519,58 @@ pub enum TextureCreationError {
 
     /// The requested texture dimensions are not supported.
     DimensionsNotSupported,
+
+    /// The requested number of mipmap levels is zero, or is larger than what the dimensions
+    /// of the texture allow.
+    InvalidMipmapsCount,
+
+    /// Not enough memory to create the texture.
+    OutOfMemory,
+
+    /// The length of the compressed data doesn't match the format and the dimensions of the
+    /// texture.
+    DataSizeMismatch,
+}
+
+/// Error that can happen when reading the content of a depth-stencil texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthStencilReadError {
+    /// The texture doesn't use the packed 24 bits depth and 8 bits stencil format.
+    NotPackedFormat,
+
+    /// Reading depth-stencil data is not supported by the backend.
+    NotSupported,
+}
+
+/// Error that can happen when generating the mipmaps of a texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MipmapsGenerationError {
+    /// The texture only has one mipmap level. Create it with `with_mipmaps` or
+    /// `empty_with_mipmaps` instead.
+    NoMipmaps,
+
+    /// Mipmaps generation is undefined for the format of the texture, which is the case of
+    /// integral, unsigned, compressed, depth and stencil formats.
+    UnsupportedFormat,
+}
+
+/// Error that can happen when copying a region of a texture to another texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureCopyError {
+    /// The source or the destination region is outside of its texture.
+    OutOfRange,
+
+    /// The source and the destination are the same texture and the regions overlap.
+    Overlap,
+
+    /// The copy was done with a blit, which failed.
+    BlitError(BlitError),
+}
+
+impl From<BlitError> for TextureCopyError {
+    fn from(err: BlitError) -> TextureCopyError {
+        TextureCopyError::BlitError(err)
+    }
 }
 
 /// Error that can happen when creating a texture which we don't know whether it is supported.
@@ -3,15 +3,17 @@ use GlObject;
 
 use backend::Facade;
 use version::Version;
-use context::Context;
+use context::{Context, CommandContext};
 use ContextExt;
 use version::Api;
 
 use pixel_buffer::PixelBuffer;
 use image_format::{self, TextureFormatRequest};
 use texture::{Texture2dDataSink, PixelValue};
-use texture::{TextureFormat, ClientFormat};
+use texture::{TextureFormat, ClientFormat, CompressedFormat};
 use texture::{TextureCreationError, TextureMaybeSupportedCreationError};
+use texture::{DepthStencilFormat, DepthStencilReadError, MipmapsGenerationError};
+use texture::TextureCopyError;
 
 use libc;
 use std::fmt;
@@ -22,6 +24,33 @@ use std::rc::Rc;
 
 use ops;
 use fbo;
+use Rect;
+use BlitTarget;
+
+/// Describes what to do about mipmaps during texture creation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MipmapsOption {
+    /// No mipmap will be allocated or generated.
+    NoMipmap,
+
+    /// Allocates all the mipmap levels and generates them from the main level, if the format
+    /// allows it.
+    AutoGeneratedMipmaps,
+
+    /// Allocates exactly this number of levels, including the main level. Mipmaps are not
+    /// generated and contain undefined data.
+    EmptyMipmaps(u32),
+}
+
+impl From<bool> for MipmapsOption {
+    fn from(mipmaps: bool) -> MipmapsOption {
+        if mipmaps {
+            MipmapsOption::AutoGeneratedMipmaps
+        } else {
+            MipmapsOption::NoMipmap
+        }
+    }
+}
 
 pub struct TextureImplementation {
     context: Rc<Context>,
@@ -39,10 +68,13 @@ pub struct TextureImplementation {
 
 impl TextureImplementation {
     /// Builds a new texture.
+    ///
+    /// If `cubemap` is true, the texture is a cubemap whose faces are `width * height` and
+    /// `data` must be `None`.
     pub fn new<'a, F, P>(facade: &F, format: TextureFormatRequest,
-                         data: Option<(ClientFormat, Cow<'a, [P]>)>, generate_mipmaps: bool,
+                         data: Option<(ClientFormat, Cow<'a, [P]>)>, mipmaps: MipmapsOption,
                          width: u32, height: Option<u32>, depth: Option<u32>,
-                         array_size: Option<u32>, samples: Option<u32>)
+                         array_size: Option<u32>, samples: Option<u32>, cubemap: bool)
                          -> Result<TextureImplementation, TextureMaybeSupportedCreationError>
                          where P: Send + Clone + 'a, F: Facade
     {
@@ -67,7 +99,12 @@ impl TextureImplementation {
             }
         }
 
-        let texture_type = if height.is_none() && depth.is_none() {
+        let texture_type = if cubemap {
+            assert!(data.is_none());
+            assert!(depth.is_none() && array_size.is_none() && samples.is_none());
+            gl::TEXTURE_CUBE_MAP
+
+        } else if height.is_none() && depth.is_none() {
             assert!(samples.is_none());
             if array_size.is_none() { gl::TEXTURE_1D } else { gl::TEXTURE_1D_ARRAY }
 
@@ -84,9 +121,8 @@ impl TextureImplementation {
             gl::TEXTURE_3D
         };
 
-        let generate_mipmaps = generate_mipmaps && match format {
-            TextureFormatRequest::AnyFloatingPoint |
-            TextureFormatRequest::Specific(TextureFormat::UncompressedFloat(_)) |
+        // integral textures can't be filtered, and glGenerateMipmap rejects them
+        let is_integral = match format {
             TextureFormatRequest::AnyIntegral |
             TextureFormatRequest::Specific(TextureFormat::UncompressedIntegral(_)) |
             TextureFormatRequest::AnyUnsigned |
@@ -94,11 +130,28 @@ impl TextureImplementation {
             _ => false,
         };
 
-        let texture_levels = if generate_mipmaps {
-            1 + (::std::cmp::max(width, ::std::cmp::max(height.unwrap_or(1),
-                                 depth.unwrap_or(1))) as f32).log2() as gl::types::GLsizei
-        } else {
-            1
+        let generate_mipmaps = mipmaps == MipmapsOption::AutoGeneratedMipmaps && match format {
+            TextureFormatRequest::AnyFloatingPoint |
+            TextureFormatRequest::Specific(TextureFormat::UncompressedFloat(_)) => true,
+            _ => false,
+        };
+
+        let max_levels = 1 + (::std::cmp::max(width, ::std::cmp::max(height.unwrap_or(1),
+                              depth.unwrap_or(1))) as f32).log2() as gl::types::GLsizei;
+
+        let texture_levels = match mipmaps {
+            MipmapsOption::EmptyMipmaps(levels) => {
+                if levels == 0 || levels as gl::types::GLsizei > max_levels ||
+                    (levels > 1 && samples.is_some())
+                {
+                    let ce = TextureCreationError::InvalidMipmapsCount;
+                    return Err(TextureMaybeSupportedCreationError::CreationError(ce));
+                }
+
+                levels as gl::types::GLsizei
+            },
+            _ if generate_mipmaps => max_levels,
+            _ => 1,
         };
 
         let (teximg_internal_format, storage_internal_format) =
@@ -138,6 +191,13 @@ impl TextureImplementation {
 
             ctxt.gl.BindTexture(texture_type, id);
 
+            // keeping track of the texture that is now bound to the active unit
+            let unit = (ctxt.state.active_texture - gl::TEXTURE0) as usize;
+            if let Some(entry) = ctxt.state.texture_units.get_mut(unit) {
+                entry.0 = texture_type;
+                entry.1 = id;
+            }
+
             ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_WRAP_S, gl::REPEAT as i32);
             if height.is_some() || depth.is_some() || array_size.is_some() {
                 ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_WRAP_T, gl::REPEAT as i32);
@@ -145,18 +205,22 @@ impl TextureImplementation {
             if depth.is_some() || array_size.is_some() {
                 ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_WRAP_R, gl::REPEAT as i32);
             }
-            ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
-            if generate_mipmaps {
+            if is_integral {
+                ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MAG_FILTER, gl::NEAREST as i32);
+                ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MIN_FILTER, gl::NEAREST as i32);
+            } else if texture_levels > 1 {
+                ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
                 ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MIN_FILTER,
                                       gl::LINEAR_MIPMAP_LINEAR as i32);
             } else {
+                ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
                 ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MIN_FILTER,
                                       gl::LINEAR as i32);
             }
 
             if !generate_mipmaps {
                 ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_BASE_LEVEL, 0);
-                ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MAX_LEVEL, 0);
+                ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MAX_LEVEL, texture_levels - 1);
             }
 
             if texture_type == gl::TEXTURE_3D || texture_type == gl::TEXTURE_2D_ARRAY {
@@ -180,6 +244,41 @@ impl TextureImplementation {
                                        height.unwrap() as i32,
                                        depth.or(array_size).unwrap() as i32, 0,
                                        client_format as u32, client_type, data_raw);
+
+                    if !generate_mipmaps {
+                        for level in 1 .. texture_levels {
+                            let depth = match depth {
+                                Some(d) => mipmap_dimension(d, level),
+                                None => array_size.unwrap() as i32,
+                            };
+
+                            ctxt.gl.TexImage3D(texture_type, level, teximg_internal_format as i32,
+                                               mipmap_dimension(width, level),
+                                               mipmap_dimension(height.unwrap(), level),
+                                               depth, 0, client_format as u32, client_type,
+                                               ptr::null());
+                        }
+                    }
+                }
+
+            } else if texture_type == gl::TEXTURE_CUBE_MAP {
+                if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
+                    ctxt.gl.TexStorage2D(texture_type, texture_levels,
+                                         storage_internal_format.unwrap() as gl::types::GLenum,
+                                         width as gl::types::GLsizei,
+                                         height.unwrap() as gl::types::GLsizei);
+
+                } else {
+                    // each face must be allocated separately
+                    for face in 0 .. 6 {
+                        for level in 0 .. texture_levels {
+                            ctxt.gl.TexImage2D(gl::TEXTURE_CUBE_MAP_POSITIVE_X + face, level,
+                                               teximg_internal_format as i32,
+                                               mipmap_dimension(width, level),
+                                               mipmap_dimension(height.unwrap(), level), 0,
+                                               client_format as u32, client_type, ptr::null());
+                        }
+                    }
                 }
 
             } else if texture_type == gl::TEXTURE_2D || texture_type == gl::TEXTURE_1D_ARRAY {
@@ -199,6 +298,19 @@ impl TextureImplementation {
                     ctxt.gl.TexImage2D(texture_type, 0, teximg_internal_format as i32, width as i32,
                                        height.or(array_size).unwrap() as i32, 0,
                                        client_format as u32, client_type, data_raw);
+
+                    if !generate_mipmaps {
+                        for level in 1 .. texture_levels {
+                            let height = match height {
+                                Some(h) => mipmap_dimension(h, level),
+                                None => array_size.unwrap() as i32,
+                            };
+
+                            ctxt.gl.TexImage2D(texture_type, level, teximg_internal_format as i32,
+                                               mipmap_dimension(width, level), height, 0,
+                                               client_format as u32, client_type, ptr::null());
+                        }
+                    }
                 }
 
             } else if texture_type == gl::TEXTURE_2D_MULTISAMPLE {
@@ -261,6 +373,14 @@ impl TextureImplementation {
                 } else {
                     ctxt.gl.TexImage1D(texture_type, 0, teximg_internal_format as i32, width as i32,
                                        0, client_format as u32, client_type, data_raw);
+
+                    if !generate_mipmaps {
+                        for level in 1 .. texture_levels {
+                            ctxt.gl.TexImage1D(texture_type, level, teximg_internal_format as i32,
+                                               mipmap_dimension(width, level), 0,
+                                               client_format as u32, client_type, ptr::null());
+                        }
+                    }
                 }
 
             } else {
@@ -292,52 +412,270 @@ impl TextureImplementation {
         })
     }
 
+    /// Builds a new two-dimensional texture from data that is already compressed.
+    pub fn new_compressed_2d<F>(facade: &F, format: CompressedFormat, data: &[u8],
+                                width: u32, height: u32)
+                                -> Result<TextureImplementation, TextureMaybeSupportedCreationError>
+                                where F: Facade
+    {
+        if format.get_data_size(width, height) != data.len() {
+            let ce = TextureCreationError::DataSizeMismatch;
+            return Err(TextureMaybeSupportedCreationError::CreationError(ce));
+        }
+
+        let request = TextureFormatRequest::Specific(format.to_texture_format());
+        let (internal_format, _) = try!(image_format::format_request_to_glenum(facade.get_context(),
+                                                                               None, request));
+
+        let mut ctxt = facade.get_context().make_current();
+
+        let id = unsafe {
+            if ctxt.state.pixel_store_unpack_alignment != 1 {
+                ctxt.state.pixel_store_unpack_alignment = 1;
+                ctxt.gl.PixelStorei(gl::UNPACK_ALIGNMENT, 1);
+            }
+
+            if ctxt.state.pixel_unpack_buffer_binding != 0 {
+                ctxt.state.pixel_unpack_buffer_binding = 0;
+                ctxt.gl.BindBuffer(gl::PIXEL_UNPACK_BUFFER, 0);
+            }
+
+            let id: gl::types::GLuint = mem::uninitialized();
+            ctxt.gl.GenTextures(1, mem::transmute(&id));
+
+            ctxt.gl.BindTexture(gl::TEXTURE_2D, id);
+
+            // keeping track of the texture that is now bound to the active unit
+            let unit = (ctxt.state.active_texture - gl::TEXTURE0) as usize;
+            if let Some(entry) = ctxt.state.texture_units.get_mut(unit) {
+                entry.0 = gl::TEXTURE_2D;
+                entry.1 = id;
+            }
+
+            ctxt.gl.TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::REPEAT as i32);
+            ctxt.gl.TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::REPEAT as i32);
+            ctxt.gl.TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+            ctxt.gl.TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
+            ctxt.gl.TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_BASE_LEVEL, 0);
+            ctxt.gl.TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAX_LEVEL, 0);
+
+            ctxt.gl.CompressedTexImage2D(gl::TEXTURE_2D, 0, internal_format,
+                                         width as gl::types::GLsizei,
+                                         height as gl::types::GLsizei, 0,
+                                         data.len() as gl::types::GLsizei,
+                                         data.as_ptr() as *const libc::c_void);
+
+            id
+        };
+
+        Ok(TextureImplementation {
+            context: facade.get_context().clone(),
+            id: id,
+            requested_format: request,
+            bind_point: gl::TEXTURE_2D,
+            width: width,
+            height: Some(height),
+            depth: None,
+            array_size: None,
+            levels: 1,
+        })
+    }
+
     /// Reads the content of a mipmap level of the texture.
-    // TODO: this function only works for level 0 right now
-    //       width/height need adjustements
+    ///
+    /// The returned data has the dimensions of the mipmap level.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `level` is out of range or can't be read by the backend.
+    /// See `is_mipmap_level_readable`.
     pub fn read<P, T>(&self, level: u32) -> T
                       where P: PixelValue + Clone + Send,
                       T: Texture2dDataSink<Data = P>
             // TODO: remove Clone for P
     {
-        assert_eq!(level, 0);   // TODO:
+        assert!(self.is_mipmap_level_readable(level));
 
         let attachment = fbo::Attachment::Texture {
             id: self.id,
             bind_point: self.bind_point,
             layer: 0,
+            level: level
+        };
+
+        ops::read_attachment(&attachment, self.get_level_dimensions(level), &self.context)
+    }
+
+    /// Reads the content of a mipmap level of the texture into a flat buffer.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `level` is out of range or can't be read by the backend, or if `dest`
+    /// contains less than `width * height` elements, where `width` and `height` are the
+    /// dimensions of the mipmap level.
+    pub fn read_into<P>(&self, level: u32, dest: &mut [P]) where P: PixelValue {
+        assert!(self.is_mipmap_level_readable(level));
+
+        let attachment = fbo::Attachment::Texture {
+            id: self.id,
+            bind_point: self.bind_point,
+            layer: 0,
+            level: level
+        };
+
+        ops::read_attachment_into(&attachment, self.get_level_dimensions(level), dest,
+                                  &self.context)
+    }
+
+    /// Reads the content of a layer of an array texture.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the texture is not an array texture or if `layer` is out of range.
+    pub fn read_layer<P, T>(&self, layer: u32) -> T
+                            where P: PixelValue + Clone + Send,
+                            T: Texture2dDataSink<Data = P>
+            // TODO: remove Clone for P
+    {
+        let array_size = self.array_size.expect("The texture is not an array texture");
+        assert!(layer < array_size, "Layer {} is out of range, the texture only has {} layers",
+                layer, array_size);
+
+        let attachment = fbo::Attachment::Texture {
+            id: self.id,
+            bind_point: self.bind_point,
+            layer: layer,
             level: 0
         };
 
         ops::read_attachment(&attachment, (self.width, self.height.unwrap_or(1)), &self.context)
     }
 
+    /// Reads the content of a depth-stencil texture as `(depth, stencil)` pairs.
+    pub fn read_depth_stencil(&self) -> Result<Vec<Vec<(f32, u8)>>, DepthStencilReadError> {
+        match self.requested_format {
+            TextureFormatRequest::AnyDepthStencil => (),
+            TextureFormatRequest::Specific(TextureFormat::DepthStencilFormat(
+                                                                DepthStencilFormat::I24I8)) => (),
+            _ => return Err(DepthStencilReadError::NotPackedFormat)
+        };
+
+        if !(self.context.get_version() >= &Version(Api::Gl, 3, 0)) &&
+           !self.context.get_extensions().gl_ext_packed_depth_stencil
+        {
+            return Err(DepthStencilReadError::NotSupported);
+        }
+
+        let attachment = fbo::Attachment::Texture {
+            id: self.id,
+            bind_point: self.bind_point,
+            layer: 0,
+            level: 0
+        };
+
+        Ok(ops::read_depth_stencil_attachment(&attachment,
+                                              (self.width, self.height.unwrap_or(1)),
+                                              &self.context))
+    }
+
     /// Reads the content of a mipmap level of the texture to a pixel buffer.
-    // TODO: this function only works for level 0 right now
-    //       width/height need adjustements
+    ///
+    /// # Panic
+    ///
+    /// Panics if `level` is out of range or can't be read by the backend.
     pub fn read_to_pixel_buffer<P, T>(&self, level: u32) -> PixelBuffer<T>
                                       where P: PixelValue + Clone + Send,
                                       T: Texture2dDataSink<Data = P>
             // TODO: remove Clone for P
     {
-        assert_eq!(level, 0);   // TODO:
+        assert!(self.is_mipmap_level_readable(level));
 
-        let size = self.width as usize * self.height.unwrap_or(1) as usize *
+        let dimensions = self.get_level_dimensions(level);
+        let size = dimensions.0 as usize * dimensions.1 as usize *
                    <T as Texture2dDataSink>::get_preferred_formats()[0].get_size();
 
         let attachment = fbo::Attachment::Texture {
             id: self.id,
             bind_point: self.bind_point,
             layer: 0,
-            level: 0
+            level: level
         };
 
         let mut pb = PixelBuffer::new_empty(&self.context, size);
-        ops::read_attachment_to_pb(&attachment, (self.width, self.height.unwrap_or(1)),
-                                   &mut pb, &self.context);
+        ops::read_attachment_to_pb(&attachment, dimensions, &mut pb, &self.context);
         pb
     }
 
+    /// Copies a rectangle of the main level of this texture to the main level of `target`,
+    /// with the bottom-left corner of the rectangle ending at `dest`.
+    ///
+    /// Uses `glCopyImageSubData` if it is supported, and blits between two framebuffers
+    /// otherwise.
+    pub fn copy_to(&self, target: &TextureImplementation, source_rect: &Rect, dest: (u32, u32))
+                   -> Result<(), TextureCopyError>
+    {
+        if source_rect.left + source_rect.width > self.width ||
+           source_rect.bottom + source_rect.height > self.height.unwrap_or(1) ||
+           dest.0 + source_rect.width > target.width ||
+           dest.1 + source_rect.height > target.height.unwrap_or(1)
+        {
+            return Err(TextureCopyError::OutOfRange);
+        }
+
+        if self.id == target.id &&
+           source_rect.left < dest.0 + source_rect.width &&
+           dest.0 < source_rect.left + source_rect.width &&
+           source_rect.bottom < dest.1 + source_rect.height &&
+           dest.1 < source_rect.bottom + source_rect.height
+        {
+            return Err(TextureCopyError::Overlap);
+        }
+
+        {
+            let ctxt = self.context.make_current();
+
+            if ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.extensions.gl_arb_copy_image {
+                unsafe {
+                    ctxt.gl.CopyImageSubData(self.id, self.bind_point, 0,
+                                             source_rect.left as gl::types::GLint,
+                                             source_rect.bottom as gl::types::GLint, 0,
+                                             target.id, target.bind_point, 0,
+                                             dest.0 as gl::types::GLint,
+                                             dest.1 as gl::types::GLint, 0,
+                                             source_rect.width as gl::types::GLsizei,
+                                             source_rect.height as gl::types::GLsizei, 1);
+                }
+
+                return Ok(());
+            }
+        }
+
+        let source = fbo::FramebufferAttachments {
+            colors: vec![(0, fbo::Attachment::Texture {
+                id: self.id, bind_point: self.bind_point, level: 0, layer: 0
+            })],
+            depth_stencil: fbo::FramebufferDepthStencilAttachments::None,
+        };
+
+        let target_attachments = fbo::FramebufferAttachments {
+            colors: vec![(0, fbo::Attachment::Texture {
+                id: target.id, bind_point: target.bind_point, level: 0, layer: 0
+            })],
+            depth_stencil: fbo::FramebufferDepthStencilAttachments::None,
+        };
+
+        let target_rect = BlitTarget {
+            left: dest.0,
+            bottom: dest.1,
+            width: source_rect.width as i32,
+            height: source_rect.height as i32,
+        };
+
+        try!(ops::blit(&self.context, Some(&source), Some(&target_attachments),
+                       gl::COLOR_BUFFER_BIT, source_rect, &target_rect, gl::NEAREST));
+        Ok(())
+    }
+
     /// Changes some parts of the texture.
     pub fn upload<'a, P>(&self, x_offset: u32, y_offset: u32, z_offset: u32,
                          (format, data): (ClientFormat, Cow<'a, [P]>), width: u32,
@@ -348,12 +686,20 @@ impl TextureImplementation {
         let bind_point = self.bind_point;
         let regen_mipmaps = regen_mipmaps && self.levels >= 2;
 
+        // for cubemaps, `z_offset` is the index of the face
+        let max_depth = if bind_point == gl::TEXTURE_CUBE_MAP { 6 } else { self.depth.unwrap_or(1) };
+
         assert!(x_offset <= self.width);
         assert!(y_offset <= self.height.unwrap_or(1));
-        assert!(z_offset <= self.depth.unwrap_or(1));
+        assert!(z_offset <= max_depth);
         assert!(x_offset + width <= self.width);
         assert!(y_offset + height.unwrap_or(1) <= self.height.unwrap_or(1));
-        assert!(z_offset + depth.unwrap_or(1) <= self.depth.unwrap_or(1));
+        assert!(z_offset + depth.unwrap_or(1) <= max_depth);
+
+        // the data must be large enough for the region, otherwise OpenGL would read out of bounds
+        assert!(data.len() * mem::size_of::<P>() >= width as usize * height.unwrap_or(1) as usize *
+                                                   depth.unwrap_or(1) as usize * format.get_size(),
+                "The data is too small for the region of the texture being written");
 
         let (client_format, client_type) = image_format::client_format_to_glenum(&self.context, format,
                                                                                  self.requested_format);
@@ -373,9 +719,26 @@ impl TextureImplementation {
 
             ctxt.gl.BindTexture(bind_point, id);
 
+            // keeping track of the texture that is now bound to the active unit
+            let unit = (ctxt.state.active_texture - gl::TEXTURE0) as usize;
+            if let Some(entry) = ctxt.state.texture_units.get_mut(unit) {
+                entry.0 = bind_point;
+                entry.1 = id;
+            }
+
             if bind_point == gl::TEXTURE_3D || bind_point == gl::TEXTURE_2D_ARRAY {
                 unimplemented!();
 
+            } else if bind_point == gl::TEXTURE_CUBE_MAP {
+                ctxt.gl.TexSubImage2D(gl::TEXTURE_CUBE_MAP_POSITIVE_X + z_offset,
+                                      level as gl::types::GLint,
+                                      x_offset as gl::types::GLint,
+                                      y_offset as gl::types::GLint,
+                                      width as gl::types::GLsizei,
+                                      height.unwrap_or(1) as gl::types::GLsizei,
+                                      client_format, client_type,
+                                      data.as_ptr() as *const libc::c_void);
+
             } else if bind_point == gl::TEXTURE_2D || bind_point == gl::TEXTURE_1D_ARRAY {
                 assert!(z_offset == 0);
                 ctxt.gl.TexSubImage2D(bind_point, level as gl::types::GLint,
@@ -404,6 +767,44 @@ impl TextureImplementation {
         }
     }
 
+    /// Regenerates the content of all the mipmap levels from the main level.
+    pub fn generate_mipmaps(&self) -> Result<(), MipmapsGenerationError> {
+        match self.requested_format {
+            TextureFormatRequest::AnyFloatingPoint |
+            TextureFormatRequest::Specific(TextureFormat::UncompressedFloat(_)) |
+            TextureFormatRequest::AnySrgb |
+            TextureFormatRequest::Specific(TextureFormat::Srgb(_)) => (),
+            _ => return Err(MipmapsGenerationError::UnsupportedFormat)
+        };
+
+        if self.levels < 2 {
+            return Err(MipmapsGenerationError::NoMipmaps);
+        }
+
+        let mut ctxt = self.context.make_current();
+
+        unsafe {
+            ctxt.gl.BindTexture(self.bind_point, self.id);
+
+            // keeping track of the texture that is now bound to the active unit
+            let unit = (ctxt.state.active_texture - gl::TEXTURE0) as usize;
+            if let Some(entry) = ctxt.state.texture_units.get_mut(unit) {
+                entry.0 = self.bind_point;
+                entry.1 = self.id;
+            }
+
+            if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+               ctxt.version >= &Version(Api::GlEs, 2, 0)
+            {
+                ctxt.gl.GenerateMipmap(self.bind_point);
+            } else {
+                ctxt.gl.GenerateMipmapEXT(self.bind_point);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Returns the `Context` associated with this texture.
     pub fn get_context(&self) -> &Rc<Context> {
         &self.context
@@ -433,6 +834,29 @@ impl TextureImplementation {
     pub fn get_mipmap_levels(&self) -> u32 {
         self.levels
     }
+
+    /// Returns true if the mipmap level exists and can be attached to a framebuffer in order
+    /// to be read.
+    ///
+    /// OpenGL ES 2.0 only allows attaching the level 0, unless `GL_OES_fbo_render_mipmap` is
+    /// supported.
+    pub fn is_mipmap_level_readable(&self, level: u32) -> bool {
+        if level >= self.levels {
+            return false;
+        }
+
+        let version = self.context.get_version();
+
+        level == 0 || version >= &Version(Api::Gl, 1, 0) ||
+            version >= &Version(Api::GlEs, 3, 0) ||
+            self.context.get_extensions().gl_oes_fbo_render_mipmap
+    }
+
+    /// Returns the width and height of a mipmap level of the texture.
+    fn get_level_dimensions(&self, level: u32) -> (u32, u32) {
+        (mipmap_dimension(self.width, level as gl::types::GLint) as u32,
+         mipmap_dimension(self.height.unwrap_or(1), level as gl::types::GLint) as u32)
+    }
 }
 
 impl GlObject for TextureImplementation {
@@ -459,5 +883,21 @@ impl Drop for TextureImplementation {
                     .purge_texture(self.id, &mut ctxt);
 
         unsafe { ctxt.gl.DeleteTextures(1, [ self.id ].as_ptr()); }
+        forget_texture_bindings(&mut ctxt, self.id);
+    }
+}
+
+/// Deleting a texture unbinds it from all the texture units. The id may later be reused by
+/// a new texture, so the units that contained it must no longer be considered as such.
+fn forget_texture_bindings(ctxt: &mut CommandContext, id: gl::types::GLuint) {
+    for entry in ctxt.state.texture_units.iter_mut() {
+        if entry.1 == id {
+            entry.1 = 0;
+        }
     }
 }
+
+/// Returns the dimension of a mipmap level, given the dimension of the main level.
+fn mipmap_dimension(dimension: u32, level: gl::types::GLint) -> gl::types::GLint {
+    ::std::cmp::max(1, dimension >> level as u32) as gl::types::GLint
+}
@@ -101,7 +101,7 @@ impl<T> UniformBuffer<T> where T: Copy + Send + 'static {
 
     /// Modifies the content of the buffer.
     pub fn upload(&mut self, data: T) {
-        self.buffer.buffer.upload(0, vec![data])
+        self.buffer.buffer.upload(0, &[data])
     }
 
     /// Maps the buffer to allow write access to it.
@@ -44,6 +44,54 @@ let uniforms = uniform! {
 # }
 ```
 
+Textures also provide a `sampled()` method that does the same thing:
+
+```no_run
+#[macro_use]
+extern crate glium;
+
+# fn main() {
+# let texture: glium::texture::Texture2d = unsafe { std::mem::uninitialized() };
+use glium::uniforms::{MagnifySamplerFilter, SamplerWrapFunction};
+
+let uniforms = uniform! {
+    texture: texture.sampled()
+                    .magnify_filter(MagnifySamplerFilter::Nearest)
+                    .wrap_function(SamplerWrapFunction::Clamp)
+};
+# }
+```
+
+Sampler parameters are stored in sampler objects when the backend supports them (OpenGL 3.3,
+OpenGL ES 3.0 or `GL_ARB_sampler_objects`), so the same texture can be sampled differently in
+two draw calls.
+
+## Arrays and structs
+
+Uniform arrays like `uniform vec4 lights[8];` can be set by passing a slice. The slice may be
+shorter than the array, in which case only the first elements are modified.
+
+Members of uniform structs are set individually by using their full name, as reported by
+the program.
+
+```no_run
+#[macro_use]
+extern crate glium;
+
+# fn main() {
+# let bones: Vec<[[f32; 4]; 4]> = unsafe { std::mem::uninitialized() };
+let weights = [0.5f32, 0.25, 0.125, 0.125];
+
+let uniforms = uniform! {
+    weights: &weights[..],
+    bones: &bones[..]
+};
+
+let uniforms = uniforms.add("light.color", [1.0, 1.0, 1.0f32])
+                       .add("light.intensity", 0.8f32);
+# }
+```
+
 ## Blocks
 
 In GLSL, you can choose to use a uniform *block*. When you use a block, you first need to
@@ -90,17 +138,41 @@ let uniforms = uniform! {
 # }
 ```
 
+If the content of the block is a struct, you can use the `implement_uniform_block!` macro.
+The offsets of the fields of the struct are then checked against the offsets of the members
+of the block when drawing, and `UniformBlockLayoutError::OffsetMismatch` is returned if
+they don't match. This usually means that you forgot to add padding between the fields of
+your struct in order to follow the `std140` layout. The struct must also be at least as large
+as the block, including the padding at the end of the block, otherwise
+`UniformBlockLayoutError::SizeMismatch` is returned.
+
+Binding a buffer to a name that doesn't correspond to any active block of the program returns
+`DrawError::UniformBlockNotFound`.
+
+## Shader storage blocks
+
+Shader storage blocks (`buffer MyBlock { ... };` in GLSL) work the same way, except that you
+need a `ShaderStorageBuffer` and that the shader can write to the buffer. This is usually
+used with compute shaders to read back the results of the computation.
+
+Shader storage buffers require OpenGL 4.3 or OpenGL ES 3.1.
+
 */
 pub use self::buffer::UniformBuffer;
+pub use self::storage_buffer::ShaderStorageBuffer;
 pub use self::sampler::{SamplerWrapFunction, MagnifySamplerFilter, MinifySamplerFilter};
-pub use self::sampler::{Sampler, SamplerBehavior};
+pub use self::sampler::{Sampler, SamplerBehavior, DepthTextureComparison};
 pub use self::uniforms::{EmptyUniforms, UniformsStorage};
 pub use self::value::{UniformValue, IntoUniformValue, UniformType};
 
 use program;
 
+use std::fmt;
+use std::error::Error;
+
 mod buffer;
 mod sampler;
+mod storage_buffer;
 mod uniforms;
 mod value;
 
@@ -113,7 +185,82 @@ pub trait Uniforms {
 /// Objects that are suitable for being binded to a uniform block.
 pub trait UniformBlock: Copy {
     /// Checks whether the uniforms' layout matches the given block.
-    fn matches(&program::UniformBlock) -> bool;
+    fn matches(&program::UniformBlock) -> Result<(), UniformBlockLayoutError>;
+}
+
+/// Error that can happen when the layout of a type doesn't match the layout of a uniform block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UniformBlockLayoutError {
+    /// The type doesn't match the content of the block.
+    LayoutMismatch,
+
+    /// A member of the block doesn't have any corresponding field in the type.
+    MissingField {
+        /// Name of the member of the block.
+        field: String,
+    },
+
+    /// A field of the type is not at the same offset as the corresponding member of the block.
+    OffsetMismatch {
+        /// Name of the field.
+        field: String,
+        /// Offset of the member in the block, as reported by `GL_UNIFORM_OFFSET`.
+        expected_offset: usize,
+        /// Offset of the field in the type.
+        actual_offset: usize,
+    },
+
+    /// The type is smaller than the block. The size of the block includes the padding
+    /// required by its layout.
+    SizeMismatch {
+        /// Size of the block, as reported by `GL_UNIFORM_BLOCK_DATA_SIZE`.
+        block_size: usize,
+        /// Size of the type.
+        type_size: usize,
+    },
+}
+
+impl fmt::Display for UniformBlockLayoutError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            &UniformBlockLayoutError::LayoutMismatch => {
+                write!(formatter, "The type doesn't match the content of the block")
+            },
+            &UniformBlockLayoutError::MissingField { ref field } => {
+                write!(formatter, "The member `{}` of the block doesn't have any corresponding \
+                                   field", field)
+            },
+            &UniformBlockLayoutError::OffsetMismatch { ref field, expected_offset,
+                                                       actual_offset } =>
+            {
+                write!(formatter, "The field `{}` is at offset {}, but the block expects it at \
+                                   offset {}", field, actual_offset, expected_offset)
+            },
+            &UniformBlockLayoutError::SizeMismatch { block_size, type_size } => {
+                write!(formatter, "The type is {} bytes long, but the block requires {} bytes",
+                       type_size, block_size)
+            },
+        }
+    }
+}
+
+impl Error for UniformBlockLayoutError {
+    fn description(&self) -> &str {
+        match self {
+            &UniformBlockLayoutError::LayoutMismatch => {
+                "The type doesn't match the content of the block"
+            },
+            &UniformBlockLayoutError::MissingField { .. } => {
+                "A member of the block doesn't have any corresponding field"
+            },
+            &UniformBlockLayoutError::OffsetMismatch { .. } => {
+                "A field is not at the same offset as the corresponding member of the block"
+            },
+            &UniformBlockLayoutError::SizeMismatch { .. } => {
+                "The type is smaller than the block"
+            },
+        }
+    }
 }
 
 // TODO: hacky (see #189)
@@ -85,6 +85,54 @@ impl ToGlEnum for MinifySamplerFilter {
     }
 }
 
+/// The function that the GPU will use to compare the reference value with the content of a
+/// depth texture.
+///
+/// The comparison is only performed when the texture is sampled with a shadow sampler
+/// (for example `sampler2DShadow` in GLSL). The result of the sampling is then `1.0` if
+/// the comparison passes and `0.0` if it fails.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum DepthTextureComparison {
+    /// The comparison never passes.
+    Never,
+
+    /// The comparison always passes.
+    Always,
+
+    /// Passes if the reference value is equal to the value in the texture.
+    IfEqual,
+
+    /// Passes if the reference value is different from the value in the texture.
+    IfNotEqual,
+
+    /// Passes if the reference value is more than the value in the texture.
+    IfMore,
+
+    /// Passes if the reference value is more than, or equal to the value in the texture.
+    IfMoreOrEqual,
+
+    /// Passes if the reference value is less than the value in the texture.
+    IfLess,
+
+    /// Passes if the reference value is less than, or equal to the value in the texture.
+    IfLessOrEqual,
+}
+
+impl ToGlEnum for DepthTextureComparison {
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            DepthTextureComparison::Never => gl::NEVER,
+            DepthTextureComparison::Always => gl::ALWAYS,
+            DepthTextureComparison::IfEqual => gl::EQUAL,
+            DepthTextureComparison::IfNotEqual => gl::NOTEQUAL,
+            DepthTextureComparison::IfMore => gl::GREATER,
+            DepthTextureComparison::IfMoreOrEqual => gl::GEQUAL,
+            DepthTextureComparison::IfLess => gl::LESS,
+            DepthTextureComparison::IfLessOrEqual => gl::LEQUAL,
+        }
+    }
+}
+
 /// A sampler.
 pub struct Sampler<'t, T: 't>(pub &'t T, pub SamplerBehavior);
 
@@ -112,16 +160,27 @@ impl<'t, T: 't> Sampler<'t, T> {
         self
     }
 
-    /// Changes the magnifying filter of the sampler.
+    /// Changes the maximum level of anisotropy of the sampler.
+    ///
+    /// The value is clamped to what the hardware supports. See
+    /// `Display::get_max_anisotropy_support`.
     pub fn anisotropy(mut self, level: u16) -> Sampler<'t, T> {
         self.1.max_anisotropy = level;
         self
     }
+
+    /// Changes the comparison function used when sampling a depth texture with a shadow
+    /// sampler. `None` disables the comparison.
+    pub fn depth_texture_comparison(mut self, comparison: Option<DepthTextureComparison>)
+                                    -> Sampler<'t, T>
+    {
+        self.1.depth_texture_comparison = comparison;
+        self
+    }
 }
 
 /// Behavior of a sampler.
-// TODO: GL_TEXTURE_BORDER_COLOR, GL_TEXTURE_MIN_LOD, GL_TEXTURE_MAX_LOD, GL_TEXTURE_LOD_BIAS,
-//       GL_TEXTURE_COMPARE_MODE, GL_TEXTURE_COMPARE_FUNC
+// TODO: GL_TEXTURE_BORDER_COLOR, GL_TEXTURE_MIN_LOD, GL_TEXTURE_MAX_LOD, GL_TEXTURE_LOD_BIAS
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub struct SamplerBehavior {
     /// Functions to use for the X, Y, and Z coordinates.
@@ -138,11 +197,17 @@ pub struct SamplerBehavior {
     /// ## Compatibility
     ///
     /// This parameter is always available. However it is ignored on hardware that does
-    /// not support anisotropic filtering.
+    /// not support anisotropic filtering (ie. without `GL_EXT_texture_filter_anisotropic`),
+    /// in which case a low-severity message is sent to the debug output.
     ///
     /// If you set the value to a value higher than what the hardware supports, it will
     /// be clamped.
     pub max_anisotropy: u16,
+
+    /// If `Some`, the comparison to perform when sampling a depth texture with a shadow sampler.
+    ///
+    /// This is required in order to use `sampler2DShadow` and similar types.
+    pub depth_texture_comparison: Option<DepthTextureComparison>,
 }
 
 impl Default for SamplerBehavior {
@@ -156,6 +221,7 @@ impl Default for SamplerBehavior {
             minify_filter: MinifySamplerFilter::LinearMipmapLinear,
             magnify_filter: MagnifySamplerFilter::Linear,
             max_anisotropy: 1,
+            depth_texture_comparison: None,
         }
     }
 }
@@ -0,0 +1,123 @@
+use buffer::{self, Buffer, BufferFlags, BufferType};
+use uniforms::{IntoUniformValue, UniformValue};
+
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::sync::mpsc::Sender;
+
+use backend::Facade;
+
+use GlObject;
+use BufferExt;
+use gl;
+use sync;
+use version::Version;
+use version::Api;
+
+/// Buffer that can be bound to a shader storage block (`buffer { ... }` in GLSL).
+///
+/// Contrary to uniform buffers, shader storage blocks can be written by the shader. The
+/// content of the buffer can then be read back with `map` or `read_if_supported`.
+#[derive(Debug)]
+pub struct ShaderStorageBuffer<T> {
+    buffer: TypelessShaderStorageBuffer,
+    marker: PhantomData<T>,
+}
+
+/// Same as `ShaderStorageBuffer` but doesn't contain any information about the type.
+#[derive(Debug)]
+pub struct TypelessShaderStorageBuffer {
+    buffer: Buffer,
+}
+
+impl<T> ShaderStorageBuffer<T> where T: Copy + Send + 'static {
+    /// Uploads data in the shader storage buffer, or returns `None` if shader storage
+    /// buffers are not supported.
+    ///
+    /// Shader storage buffers require OpenGL 4.3 or OpenGL ES 3.1.
+    pub fn new_if_supported<F>(facade: &F, data: T) -> Option<ShaderStorageBuffer<T>>
+                               where F: Facade
+    {
+        if !(facade.get_context().get_version() >= &Version(Api::Gl, 4, 3) ||
+             facade.get_context().get_version() >= &Version(Api::GlEs, 3, 1))
+        {
+            return None;
+        }
+
+        let buffer = Buffer::new(facade, &[data], BufferType::ShaderStorageBuffer,
+                                 BufferFlags::simple()).unwrap();
+
+        Some(ShaderStorageBuffer {
+            buffer: TypelessShaderStorageBuffer {
+                buffer: buffer,
+            },
+            marker: PhantomData,
+        })
+    }
+
+    /// Modifies the content of the buffer.
+    pub fn upload(&mut self, data: T) {
+        self.buffer.buffer.upload(0, &[data])
+    }
+
+    /// Maps the buffer to allow read and write access to it.
+    ///
+    /// This function will block until the buffer stops being used by the backend.
+    pub fn map<'a>(&'a mut self) -> Mapping<'a, T> {
+        Mapping(self.buffer.buffer.map(0, 1))
+    }
+
+    /// Reads the content of the buffer.
+    pub fn read_if_supported(&self) -> Option<T> {
+        let res = self.buffer.buffer.read_if_supported();
+        res.map(|res| res.into_iter().next().unwrap())
+    }
+}
+
+impl<T> GlObject for ShaderStorageBuffer<T> {
+    type Id = gl::types::GLuint;
+    fn get_id(&self) -> gl::types::GLuint {
+        self.buffer.get_id()
+    }
+}
+
+impl GlObject for TypelessShaderStorageBuffer {
+    type Id = gl::types::GLuint;
+    fn get_id(&self) -> gl::types::GLuint {
+        self.buffer.get_id()
+    }
+}
+
+impl<T> BufferExt for ShaderStorageBuffer<T> {
+    fn add_fence(&self) -> Option<Sender<sync::LinearSyncFence>> {
+        self.buffer.add_fence()
+    }
+}
+
+impl BufferExt for TypelessShaderStorageBuffer {
+    fn add_fence(&self) -> Option<Sender<sync::LinearSyncFence>> {
+        self.buffer.add_fence()
+    }
+}
+
+/// A mapping of a shader storage buffer.
+pub struct Mapping<'a, T>(buffer::Mapping<'a, T>);
+
+impl<'a, T> Deref for Mapping<'a, T> {
+    type Target = T;
+    fn deref<'b>(&'b self) -> &'b T {
+        self.0.deref().get(0).unwrap()
+    }
+}
+
+impl<'a, T> DerefMut for Mapping<'a, T> {
+    fn deref_mut<'b>(&'b mut self) -> &'b mut T {
+        self.0.deref_mut().get_mut(0).unwrap()
+    }
+}
+
+impl<'a, T> IntoUniformValue<'a> for &'a ShaderStorageBuffer<T> {
+    fn into_uniform_value(self) -> UniformValue<'a> {
+        UniformValue::ShaderStorageBlock(&self.buffer)
+    }
+}
@@ -1,8 +1,9 @@
 use program;
 use texture;
-use uniforms::UniformBlock;
+use uniforms::{UniformBlock, UniformBlockLayoutError};
 use uniforms::SamplerBehavior;
 use uniforms::buffer::TypelessUniformBuffer;
+use uniforms::storage_buffer::TypelessShaderStorageBuffer;
 
 use std::default::Default;
 
@@ -140,7 +141,10 @@ pub enum UniformValue<'a> {
     /// can be binded on a block with the given layout.
     /// The last parameter is a sender which must be used to send a `SyncFence` that expires when
     /// the buffer has finished being used.
-    Block(&'a TypelessUniformBuffer, fn(&program::UniformBlock) -> bool),
+    Block(&'a TypelessUniformBuffer,
+          fn(&program::UniformBlock) -> Result<(), UniformBlockLayoutError>),
+    /// Contains a handle to a buffer that must be binded on a shader storage block.
+    ShaderStorageBlock(&'a TypelessShaderStorageBuffer),
     SignedInt(i32),
     UnsignedInt(u32),
     Float(f32),
@@ -153,6 +157,17 @@ pub enum UniformValue<'a> {
     Vec2([f32; 2]),
     Vec3([f32; 3]),
     Vec4([f32; 4]),
+    SignedIntArray(&'a [i32]),
+    FloatArray(&'a [f32]),
+    /// Array of 2x2 column-major matrices.
+    Mat2Array(&'a [[[f32; 2]; 2]]),
+    /// Array of 3x3 column-major matrices.
+    Mat3Array(&'a [[[f32; 3]; 3]]),
+    /// Array of 4x4 column-major matrices.
+    Mat4Array(&'a [[[f32; 4]; 4]]),
+    Vec2Array(&'a [[f32; 2]]),
+    Vec3Array(&'a [[f32; 3]]),
+    Vec4Array(&'a [[f32; 4]]),
     Texture1d(&'a texture::Texture1d, Option<SamplerBehavior>),
     CompressedTexture1d(&'a texture::CompressedTexture1d, Option<SamplerBehavior>),
     SrgbTexture1d(&'a texture::SrgbTexture1d, Option<SamplerBehavior>),
@@ -198,6 +213,8 @@ pub enum UniformValue<'a> {
     IntegralTexture2dMultisampleArray(&'a texture::IntegralTexture2dMultisampleArray, Option<SamplerBehavior>),
     UnsignedTexture2dMultisampleArray(&'a texture::UnsignedTexture2dMultisampleArray, Option<SamplerBehavior>),
     DepthTexture2dMultisampleArray(&'a texture::DepthTexture2dMultisampleArray, Option<SamplerBehavior>),
+    CubeMap(&'a texture::CubeMap, Option<SamplerBehavior>),
+    DepthCubeMap(&'a texture::DepthCubeMap, Option<SamplerBehavior>),
 }
 
 impl<'a> Clone for UniformValue<'a> {
@@ -219,6 +236,14 @@ impl<'a> UniformValue<'a> {
             (&UniformValue::Vec2(_), UniformType::FloatVec2) => true,
             (&UniformValue::Vec3(_), UniformType::FloatVec3) => true,
             (&UniformValue::Vec4(_), UniformType::FloatVec4) => true,
+            (&UniformValue::SignedIntArray(_), UniformType::Int) => true,
+            (&UniformValue::FloatArray(_), UniformType::Float) => true,
+            (&UniformValue::Mat2Array(_), UniformType::FloatMat2) => true,
+            (&UniformValue::Mat3Array(_), UniformType::FloatMat3) => true,
+            (&UniformValue::Mat4Array(_), UniformType::FloatMat4) => true,
+            (&UniformValue::Vec2Array(_), UniformType::FloatVec2) => true,
+            (&UniformValue::Vec3Array(_), UniformType::FloatVec3) => true,
+            (&UniformValue::Vec4Array(_), UniformType::FloatVec4) => true,
             (&UniformValue::Texture1d(_, _), UniformType::Sampler1d) => true,
             (&UniformValue::CompressedTexture1d(_, _), UniformType::Sampler1d) => true,
             (&UniformValue::SrgbTexture1d(_, _), UniformType::Sampler1d) => true,
@@ -226,6 +251,7 @@ impl<'a> UniformValue<'a> {
             (&UniformValue::IntegralTexture1d(_, _), UniformType::ISampler1d) => true,
             (&UniformValue::UnsignedTexture1d(_, _), UniformType::USampler1d) => true,
             (&UniformValue::DepthTexture1d(_, _), UniformType::Sampler1d) => true,
+            (&UniformValue::DepthTexture1d(_, _), UniformType::Sampler1dShadow) => true,
             (&UniformValue::Texture2d(_, _), UniformType::Sampler2d) => true,
             (&UniformValue::CompressedTexture2d(_, _), UniformType::Sampler2d) => true,
             (&UniformValue::SrgbTexture2d(_, _), UniformType::Sampler2d) => true,
@@ -233,6 +259,7 @@ impl<'a> UniformValue<'a> {
             (&UniformValue::IntegralTexture2d(_, _), UniformType::ISampler2d) => true,
             (&UniformValue::UnsignedTexture2d(_, _), UniformType::USampler2d) => true,
             (&UniformValue::DepthTexture2d(_, _), UniformType::Sampler2d) => true,
+            (&UniformValue::DepthTexture2d(_, _), UniformType::Sampler2dShadow) => true,
             (&UniformValue::Texture3d(_, _), UniformType::Sampler3d) => true,
             (&UniformValue::CompressedTexture3d(_, _), UniformType::Sampler3d) => true,
             (&UniformValue::SrgbTexture3d(_, _), UniformType::Sampler3d) => true,
@@ -247,6 +274,7 @@ impl<'a> UniformValue<'a> {
             (&UniformValue::IntegralTexture1dArray(_, _), UniformType::ISampler1dArray) => true,
             (&UniformValue::UnsignedTexture1dArray(_, _), UniformType::USampler1dArray) => true,
             (&UniformValue::DepthTexture1dArray(_, _), UniformType::Sampler1dArray) => true,
+            (&UniformValue::DepthTexture1dArray(_, _), UniformType::Sampler1dArrayShadow) => true,
             (&UniformValue::Texture2dArray(_, _), UniformType::Sampler2dArray) => true,
             (&UniformValue::CompressedTexture2dArray(_, _), UniformType::Sampler2dArray) => true,
             (&UniformValue::SrgbTexture2dArray(_, _), UniformType::Sampler2dArray) => true,
@@ -254,40 +282,134 @@ impl<'a> UniformValue<'a> {
             (&UniformValue::IntegralTexture2dArray(_, _), UniformType::ISampler2dArray) => true,
             (&UniformValue::UnsignedTexture2dArray(_, _), UniformType::USampler2dArray) => true,
             (&UniformValue::DepthTexture2dArray(_, _), UniformType::Sampler2dArray) => true,
+            (&UniformValue::DepthTexture2dArray(_, _), UniformType::Sampler2dArrayShadow) => true,
+            (&UniformValue::CubeMap(_, _), UniformType::SamplerCube) => true,
+            (&UniformValue::DepthCubeMap(_, _), UniformType::SamplerCube) => true,
+            (&UniformValue::DepthCubeMap(_, _), UniformType::SamplerCubeShadow) => true,
             _ => false,
         }
     }
+
+    /// Returns the type of uniform that this value is primarily meant for, or `None` if
+    /// this value is a buffer that must be bound to a block.
+    ///
+    /// Some values can be used with multiple types. See `is_usable_with`.
+    pub fn get_type(&self) -> Option<UniformType> {
+        match *self {
+            UniformValue::Block(_, _) => None,
+            UniformValue::ShaderStorageBlock(_) => None,
+            UniformValue::SignedInt(_) => Some(UniformType::Int),
+            UniformValue::UnsignedInt(_) => Some(UniformType::UnsignedInt),
+            UniformValue::Float(_) => Some(UniformType::Float),
+            UniformValue::Mat2(_) => Some(UniformType::FloatMat2),
+            UniformValue::Mat3(_) => Some(UniformType::FloatMat3),
+            UniformValue::Mat4(_) => Some(UniformType::FloatMat4),
+            UniformValue::Vec2(_) => Some(UniformType::FloatVec2),
+            UniformValue::Vec3(_) => Some(UniformType::FloatVec3),
+            UniformValue::Vec4(_) => Some(UniformType::FloatVec4),
+            UniformValue::SignedIntArray(_) => Some(UniformType::Int),
+            UniformValue::FloatArray(_) => Some(UniformType::Float),
+            UniformValue::Mat2Array(_) => Some(UniformType::FloatMat2),
+            UniformValue::Mat3Array(_) => Some(UniformType::FloatMat3),
+            UniformValue::Mat4Array(_) => Some(UniformType::FloatMat4),
+            UniformValue::Vec2Array(_) => Some(UniformType::FloatVec2),
+            UniformValue::Vec3Array(_) => Some(UniformType::FloatVec3),
+            UniformValue::Vec4Array(_) => Some(UniformType::FloatVec4),
+            UniformValue::Texture1d(_, _) => Some(UniformType::Sampler1d),
+            UniformValue::CompressedTexture1d(_, _) => Some(UniformType::Sampler1d),
+            UniformValue::SrgbTexture1d(_, _) => Some(UniformType::Sampler1d),
+            UniformValue::CompressedSrgbTexture1d(_, _) => Some(UniformType::Sampler1d),
+            UniformValue::IntegralTexture1d(_, _) => Some(UniformType::ISampler1d),
+            UniformValue::UnsignedTexture1d(_, _) => Some(UniformType::USampler1d),
+            UniformValue::DepthTexture1d(_, _) => Some(UniformType::Sampler1d),
+            UniformValue::Texture2d(_, _) => Some(UniformType::Sampler2d),
+            UniformValue::CompressedTexture2d(_, _) => Some(UniformType::Sampler2d),
+            UniformValue::SrgbTexture2d(_, _) => Some(UniformType::Sampler2d),
+            UniformValue::CompressedSrgbTexture2d(_, _) => Some(UniformType::Sampler2d),
+            UniformValue::IntegralTexture2d(_, _) => Some(UniformType::ISampler2d),
+            UniformValue::UnsignedTexture2d(_, _) => Some(UniformType::USampler2d),
+            UniformValue::DepthTexture2d(_, _) => Some(UniformType::Sampler2d),
+            UniformValue::Texture2dMultisample(_, _) => Some(UniformType::Sampler2dMultisample),
+            UniformValue::SrgbTexture2dMultisample(_, _) => Some(UniformType::Sampler2dMultisample),
+            UniformValue::IntegralTexture2dMultisample(_, _) => Some(UniformType::ISampler2dMultisample),
+            UniformValue::UnsignedTexture2dMultisample(_, _) => Some(UniformType::USampler2dMultisample),
+            UniformValue::DepthTexture2dMultisample(_, _) => Some(UniformType::Sampler2dMultisample),
+            UniformValue::Texture3d(_, _) => Some(UniformType::Sampler3d),
+            UniformValue::CompressedTexture3d(_, _) => Some(UniformType::Sampler3d),
+            UniformValue::SrgbTexture3d(_, _) => Some(UniformType::Sampler3d),
+            UniformValue::CompressedSrgbTexture3d(_, _) => Some(UniformType::Sampler3d),
+            UniformValue::IntegralTexture3d(_, _) => Some(UniformType::ISampler3d),
+            UniformValue::UnsignedTexture3d(_, _) => Some(UniformType::USampler3d),
+            UniformValue::DepthTexture3d(_, _) => Some(UniformType::Sampler3d),
+            UniformValue::Texture1dArray(_, _) => Some(UniformType::Sampler1dArray),
+            UniformValue::CompressedTexture1dArray(_, _) => Some(UniformType::Sampler1dArray),
+            UniformValue::SrgbTexture1dArray(_, _) => Some(UniformType::Sampler1dArray),
+            UniformValue::CompressedSrgbTexture1dArray(_, _) => Some(UniformType::Sampler1dArray),
+            UniformValue::IntegralTexture1dArray(_, _) => Some(UniformType::ISampler1dArray),
+            UniformValue::UnsignedTexture1dArray(_, _) => Some(UniformType::USampler1dArray),
+            UniformValue::DepthTexture1dArray(_, _) => Some(UniformType::Sampler1dArray),
+            UniformValue::Texture2dArray(_, _) => Some(UniformType::Sampler2dArray),
+            UniformValue::CompressedTexture2dArray(_, _) => Some(UniformType::Sampler2dArray),
+            UniformValue::SrgbTexture2dArray(_, _) => Some(UniformType::Sampler2dArray),
+            UniformValue::CompressedSrgbTexture2dArray(_, _) => Some(UniformType::Sampler2dArray),
+            UniformValue::IntegralTexture2dArray(_, _) => Some(UniformType::ISampler2dArray),
+            UniformValue::UnsignedTexture2dArray(_, _) => Some(UniformType::USampler2dArray),
+            UniformValue::DepthTexture2dArray(_, _) => Some(UniformType::Sampler2dArray),
+            UniformValue::Texture2dMultisampleArray(_, _) => Some(UniformType::Sampler2dMultisampleArray),
+            UniformValue::SrgbTexture2dMultisampleArray(_, _) => Some(UniformType::Sampler2dMultisampleArray),
+            UniformValue::IntegralTexture2dMultisampleArray(_, _) => Some(UniformType::ISampler2dMultisampleArray),
+            UniformValue::UnsignedTexture2dMultisampleArray(_, _) => Some(UniformType::USampler2dMultisampleArray),
+            UniformValue::DepthTexture2dMultisampleArray(_, _) => Some(UniformType::Sampler2dMultisampleArray),
+            UniformValue::CubeMap(_, _) => Some(UniformType::SamplerCube),
+            UniformValue::DepthCubeMap(_, _) => Some(UniformType::SamplerCube),
+        }
+    }
+
+    /// Returns the number of elements if this value is an array.
+    pub fn get_array_len(&self) -> Option<usize> {
+        match *self {
+            UniformValue::SignedIntArray(val) => Some(val.len()),
+            UniformValue::FloatArray(val) => Some(val.len()),
+            UniformValue::Mat2Array(val) => Some(val.len()),
+            UniformValue::Mat3Array(val) => Some(val.len()),
+            UniformValue::Mat4Array(val) => Some(val.len()),
+            UniformValue::Vec2Array(val) => Some(val.len()),
+            UniformValue::Vec3Array(val) => Some(val.len()),
+            UniformValue::Vec4Array(val) => Some(val.len()),
+            _ => None,
+        }
+    }
 }
 
 // TODO: implement for each type individually instead
 impl<'a, T> UniformBlock for T where T: IntoUniformValue<'a> + Copy + Send + Default {
-    fn matches(block: &program::UniformBlock) -> bool {
+    fn matches(block: &program::UniformBlock) -> Result<(), UniformBlockLayoutError> {
         use std::mem;
 
         if block.members.len() != 1 {
-            return false;
+            return Err(UniformBlockLayoutError::LayoutMismatch);
         }
 
         if block.size != mem::size_of::<T>() {
-            return false;
+            return Err(UniformBlockLayoutError::LayoutMismatch);
         }
 
         let ref member = block.members[0];
 
         if member.offset != 0 {
-            return false;
+            return Err(UniformBlockLayoutError::LayoutMismatch);
         }
 
         let me: T = Default::default();
         if !me.into_uniform_value().is_usable_with(&member.ty) {
-            return false;
+            return Err(UniformBlockLayoutError::LayoutMismatch);
         }
 
         if member.size.is_some() {
-            return false;
+            return Err(UniformBlockLayoutError::LayoutMismatch);
         }
 
-        true
+        Ok(())
     }
 }
 
@@ -387,6 +509,54 @@ impl IntoUniformValue<'static> for [f32; 4] {
     }
 }
 
+impl<'a> IntoUniformValue<'a> for &'a [i32] {
+    fn into_uniform_value(self) -> UniformValue<'a> {
+        UniformValue::SignedIntArray(self)
+    }
+}
+
+impl<'a> IntoUniformValue<'a> for &'a [f32] {
+    fn into_uniform_value(self) -> UniformValue<'a> {
+        UniformValue::FloatArray(self)
+    }
+}
+
+impl<'a> IntoUniformValue<'a> for &'a [[[f32; 2]; 2]] {
+    fn into_uniform_value(self) -> UniformValue<'a> {
+        UniformValue::Mat2Array(self)
+    }
+}
+
+impl<'a> IntoUniformValue<'a> for &'a [[[f32; 3]; 3]] {
+    fn into_uniform_value(self) -> UniformValue<'a> {
+        UniformValue::Mat3Array(self)
+    }
+}
+
+impl<'a> IntoUniformValue<'a> for &'a [[[f32; 4]; 4]] {
+    fn into_uniform_value(self) -> UniformValue<'a> {
+        UniformValue::Mat4Array(self)
+    }
+}
+
+impl<'a> IntoUniformValue<'a> for &'a [[f32; 2]] {
+    fn into_uniform_value(self) -> UniformValue<'a> {
+        UniformValue::Vec2Array(self)
+    }
+}
+
+impl<'a> IntoUniformValue<'a> for &'a [[f32; 3]] {
+    fn into_uniform_value(self) -> UniformValue<'a> {
+        UniformValue::Vec3Array(self)
+    }
+}
+
+impl<'a> IntoUniformValue<'a> for &'a [[f32; 4]] {
+    fn into_uniform_value(self) -> UniformValue<'a> {
+        UniformValue::Vec4Array(self)
+    }
+}
+
 #[cfg(feature = "nalgebra")]
 impl IntoUniformValue<'static> for nalgebra::Mat2<f32> {
     fn into_uniform_value(self) -> UniformValue<'static> {
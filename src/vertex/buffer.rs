@@ -1,13 +1,15 @@
 use std::marker::PhantomData;
 use std::ops::{Range, Deref, DerefMut};
 use std::sync::mpsc::Sender;
-use std::mem;
+use std::{fmt, mem, slice};
+use std::error::Error;
 
-use buffer::{self, Buffer, BufferFlags, BufferType, BufferCreationError};
+use buffer::{self, Buffer, BufferFlags, BufferType, BufferCreationError, CopyError};
 use vertex::{Vertex, VerticesSource, IntoVerticesSource, PerInstance};
 use vertex::format::VertexFormat;
 
 use BufferExt;
+use ContextExt;
 use GlObject;
 
 use backend::Facade;
@@ -30,6 +32,57 @@ pub struct VertexBufferSlice<'b, T: 'b> {
     length: usize,
 }
 
+/// Iterator over consecutive slices of a `VertexBuffer`.
+///
+/// Returned by `VertexBuffer::chunks`.
+pub struct Chunks<'b, T: 'b> {
+    buffer: &'b VertexBuffer<T>,
+    offset: usize,
+    chunk_size: usize,
+}
+
+/// Error that can happen when accessing a slice of a buffer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SliceError {
+    /// The end of the range is before its start.
+    InvalidRange {
+        /// Start of the requested range.
+        start: usize,
+        /// End of the requested range.
+        end: usize,
+    },
+
+    /// The range goes beyond the end of the buffer.
+    OutOfRange {
+        /// End of the requested range.
+        end: usize,
+        /// Number of elements in the buffer.
+        len: usize,
+    },
+}
+
+impl fmt::Display for SliceError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            &SliceError::InvalidRange { start, end } =>
+                write!(formatter, "Invalid slice range: the end ({}) is before the start ({})",
+                       end, start),
+            &SliceError::OutOfRange { end, len } =>
+                write!(formatter, "Slice out of range: the range ends at {} but the buffer \
+                                   only contains {} elements", end, len),
+        }
+    }
+}
+
+impl Error for SliceError {
+    fn description(&self) -> &str {
+        match self {
+            &SliceError::InvalidRange { .. } => "The end of the range is before its start",
+            &SliceError::OutOfRange { .. } => "The range goes beyond the end of the buffer",
+        }
+    }
+}
+
 impl<T: Vertex + 'static + Send> VertexBuffer<T> {
     /// Builds a new vertex buffer.
     ///
@@ -57,10 +110,97 @@ impl<T: Vertex + 'static + Send> VertexBuffer<T> {
     /// ```
     ///
     pub fn new<F, D>(facade: &F, data: D) -> VertexBuffer<T> where F: Facade, D: AsRef<[T]> {
+        VertexBuffer::new_impl(facade, data).unwrap()
+    }
+
+    /// Builds a new vertex buffer, or returns an error if the allocation failed.
+    ///
+    /// Contrary to `new`, this function doesn't panic if the implementation doesn't have
+    /// enough memory to hold the data. Instead `BufferCreationError::OutOfMemory` is returned.
+    pub fn try_new<F, D>(facade: &F, data: D) -> Result<VertexBuffer<T>, BufferCreationError>
+                         where F: Facade, D: AsRef<[T]>
+    {
+        let buffer = try!(VertexBuffer::new_impl(facade, data));
+
+        // the buffer is destroyed when it is dropped
+        if facade.get_context().check_out_of_memory() {
+            return Err(BufferCreationError::OutOfMemory);
+        }
+
+        Ok(buffer)
+    }
+
+    fn new_impl<F, D>(facade: &F, data: D) -> Result<VertexBuffer<T>, BufferCreationError>
+                      where F: Facade, D: AsRef<[T]>
+    {
+        let bindings = <T as Vertex>::build_bindings();
+
+        let buffer = try!(Buffer::new(facade, data.as_ref(), BufferType::ArrayBuffer,
+                                      BufferFlags::simple()));
+        let elements_size = buffer.get_elements_size();
+
+        Ok(VertexBuffer {
+            buffer: VertexBufferAny {
+                buffer: buffer,
+                bindings: bindings,
+                elements_size: elements_size,
+            },
+            marker: PhantomData,
+        })
+    }
+
+    /// Builds a new vertex buffer whose content is meant to be rewritten every frame.
+    ///
+    /// If the backend supports `GL_ARB_buffer_storage` (or OpenGL 4.4), the buffer is
+    /// persistently mapped in a coherent way. Calling `map` then writes directly into the
+    /// buffer's memory, and the mapping pointer stays valid for the lifetime of the buffer.
+    ///
+    /// ## Orphaning fallback
+    ///
+    /// On older drivers the buffer is allocated with `GL_DYNAMIC_DRAW` instead. Mapping it
+    /// will block until the backend has finished using it. To avoid this stall, replace the
+    /// whole content of the buffer with `write(0, &data)` instead. Since the entire buffer is
+    /// rewritten, its previous storage is invalidated and the driver can orphan it and hand
+    /// out fresh memory while the old content is still being drawn.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # #[macro_use]
+    /// # extern crate glium;
+    /// # extern crate glutin;
+    /// # fn main() {
+    /// #[derive(Copy, Clone)]
+    /// struct Particle {
+    ///     position: [f32; 3],
+    /// }
+    ///
+    /// implement_vertex!(Particle, position);
+    ///
+    /// # let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+    /// let mut particles = glium::VertexBuffer::dynamic(&display, vec![
+    ///     Particle { position: [0.0, 0.0, 0.0] }; 1024
+    /// ]);
+    ///
+    /// for particle in particles.map().iter_mut() {
+    ///     particle.position[1] += 0.1;
+    /// }
+    /// # }
+    /// ```
+    ///
+    pub fn dynamic<F, D>(facade: &F, data: D) -> VertexBuffer<T> where F: Facade, D: AsRef<[T]> {
         let bindings = <T as Vertex>::build_bindings();
 
-        let buffer = Buffer::new(facade, data.as_ref(), BufferType::ArrayBuffer,
-                                 BufferFlags::simple()).unwrap();
+        let buffer = match Buffer::new(facade, data.as_ref(), BufferType::ArrayBuffer,
+                                       BufferFlags::persistent())
+        {
+            Err(BufferCreationError::PersistentMappingNotSupported) => {
+                Buffer::new(facade, data.as_ref(), BufferType::ArrayBuffer,
+                            BufferFlags::dynamic_draw()).unwrap()
+            },
+            b => b.unwrap()
+        };
+
         let elements_size = buffer.get_elements_size();
 
         VertexBuffer {
@@ -80,7 +220,7 @@ impl<T: Vertex + 'static + Send> VertexBuffer<T> {
         let bindings = <T as Vertex>::build_bindings();
 
         let buffer = Buffer::new(facade, &data, BufferType::ArrayBuffer,
-                                 BufferFlags::simple()).unwrap();
+                                 BufferFlags::dynamic_draw()).unwrap();
         let elements_size = buffer.get_elements_size();
 
         VertexBuffer {
@@ -131,12 +271,29 @@ impl<T: Vertex + 'static + Send> VertexBuffer<T> {
 
     /// Builds an empty vertex buffer.
     ///
-    /// The parameter indicates the number of elements.
+    /// The parameter indicates the number of elements. The memory is allocated but not
+    /// initialized, so the content of the buffer is undefined until you write to it, for
+    /// example with `write` or `map`.
     pub fn empty<F>(facade: &F, elements: usize) -> VertexBuffer<T> where F: Facade {
+        VertexBuffer::empty_impl(facade, elements, BufferFlags::simple())
+    }
+
+    /// Builds an empty vertex buffer.
+    ///
+    /// Same as `empty`, except that the buffer has better performance when it is modified
+    /// frequently.
+    pub fn empty_dynamic<F>(facade: &F, elements: usize) -> VertexBuffer<T> where F: Facade {
+        VertexBuffer::empty_impl(facade, elements, BufferFlags::dynamic_draw())
+    }
+
+    /// Implementation of `empty` and `empty_dynamic`.
+    fn empty_impl<F>(facade: &F, elements: usize, flags: BufferFlags) -> VertexBuffer<T>
+                     where F: Facade
+    {
         let bindings = <T as Vertex>::build_bindings();
 
         let buffer = Buffer::new_empty(facade, BufferType::ArrayBuffer, mem::size_of::<T>(),
-                                       elements, BufferFlags::simple()).unwrap();
+                                       elements, flags).unwrap();
         let elements_size = buffer.get_elements_size();
 
         VertexBuffer {
@@ -163,10 +320,10 @@ impl<T: Send + Copy + 'static> VertexBuffer<T> {
     ///
     /// let bindings = vec![(
     ///         Cow::Borrowed("position"), 0,
-    ///         glium::vertex::AttributeType::F32F32,
+    ///         glium::vertex::AttributeType::F32F32, false,
     ///     ), (
     ///         Cow::Borrowed("color"), 2 * ::std::mem::size_of::<f32>(),
-    ///         glium::vertex::AttributeType::F32,
+    ///         glium::vertex::AttributeType::F32, false,
     ///     ),
     /// ];
     ///
@@ -200,20 +357,50 @@ impl<T: Send + Copy + 'static> VertexBuffer<T> {
     /// Accesses a slice of the buffer.
     ///
     /// Returns `None` if the slice is out of range.
-    pub fn slice(&self, Range { start, end }: Range<usize>) -> Option<VertexBufferSlice<T>> {
-        let len = end - start;
+    pub fn slice(&self, range: Range<usize>) -> Option<VertexBufferSlice<T>> {
+        self.slice_checked(range).ok()
+    }
 
-        if start > self.len() || start + len > self.len() {
-            return None;
+    /// Accesses a slice of the buffer.
+    ///
+    /// Same as `slice`, but returns an error describing why the range is invalid.
+    pub fn slice_checked(&self, Range { start, end }: Range<usize>)
+                         -> Result<VertexBufferSlice<T>, SliceError>
+    {
+        if end < start {
+            return Err(SliceError::InvalidRange { start: start, end: end });
         }
 
-        Some(VertexBufferSlice {
+        if end > self.len() {
+            return Err(SliceError::OutOfRange { end: end, len: self.len() });
+        }
+
+        Ok(VertexBufferSlice {
             buffer: self,
             offset: start,
-            length: len
+            length: end - start,
         })
     }
 
+    /// Returns an iterator over consecutive slices of `chunk_size` vertices.
+    ///
+    /// The last slice is shorter if the length of the buffer is not a multiple
+    /// of `chunk_size`.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `chunk_size` is 0.
+    ///
+    pub fn chunks(&self, chunk_size: usize) -> Chunks<T> {
+        assert!(chunk_size != 0);
+
+        Chunks {
+            buffer: self,
+            offset: 0,
+            chunk_size: chunk_size,
+        }
+    }
+
     /// Maps the buffer to allow write access to it.
     ///
     /// This function will block until the buffer stops being used by the backend.
@@ -224,6 +411,21 @@ impl<T: Send + Copy + 'static> VertexBuffer<T> {
         Mapping(mapping)
     }
 
+    /// Maps the buffer to allow write access to it, with explicit flushing.
+    ///
+    /// Contrary to `map`, the backend doesn't assume that the whole buffer has been modified.
+    /// Instead you must call `flush_range` on the mapping for each range that you wrote to.
+    /// The content of the ranges that you modified but didn't flush is undefined once the
+    /// mapping is destroyed.
+    ///
+    /// This has no effect if the buffer is persistent, as persistent buffers are always
+    /// coherent.
+    pub fn map_explicit_flush<'a>(&'a mut self) -> Mapping<'a, T> {
+        let len = self.buffer.buffer.get_elements_count();
+        let mapping = self.buffer.buffer.map_explicit_flush(0, len);
+        Mapping(mapping)
+    }
+
     /// Reads the content of the buffer.
     ///
     /// This function is usually better if are just doing one punctual read, while `map`
@@ -245,14 +447,32 @@ impl<T: Send + Copy + 'static> VertexBuffer<T> {
         self.buffer.buffer.read_if_supported()
     }
 
-    /// Replaces the content of the buffer.
+    /// Writes some vertices to the buffer, starting at the element `offset`.
     ///
-    /// ## Panic
+    /// This uses `glBufferSubData`, which means that contrary to `map` it doesn't wait for
+    /// the backend to stop using the buffer. Writing the whole buffer at once allows the
+    /// driver to discard the previous content.
+    ///
+    /// Returns an error if `offset + data.len()` is greater than the length of the buffer.
+    pub fn write(&self, offset: usize, data: &[T]) -> Result<(), SliceError> {
+        if offset + data.len() > self.len() {
+            return Err(SliceError::OutOfRange { end: offset + data.len(), len: self.len() });
+        }
+
+        self.buffer.buffer.upload(offset, data);
+        Ok(())
+    }
+
+    /// Copies `len` vertices starting at `src_offset` to `target`, starting at `dest_offset`.
     ///
-    /// Panics if the length of `data` is different from the length of this buffer.
-    pub fn write(&self, data: Vec<T>) {
-        assert!(data.len() == self.len());
-        self.buffer.buffer.upload(0, data)
+    /// The copy is done by the GPU with `glCopyBufferSubData`, without going through the
+    /// CPU. `target` can be the same buffer as long as the ranges don't overlap.
+    pub fn copy_to(&self, target: &VertexBuffer<T>, src_offset: usize, dest_offset: usize,
+                   len: usize) -> Result<(), CopyError>
+    {
+        let size = self.get_elements_size();
+        self.buffer.buffer.copy_to(&target.buffer.buffer, src_offset * size, dest_offset * size,
+                                   len * size)
     }
 }
 
@@ -357,14 +577,43 @@ impl<'b, T> VertexBufferSlice<'b, T> where T: Send + Copy + 'static {
         self.buffer.buffer.buffer.read_slice_if_supported(self.offset, self.length)
     }
 
-    /// Writes some vertices to the buffer.
-    ///
-    /// ## Panic
+    /// Writes some vertices to the slice, starting at the element `offset` of the slice.
     ///
-    /// Panics if the length of `data` is different from the length of this slice.
-    pub fn write(&self, data: Vec<T>) {
-        assert!(data.len() == self.length);
-        self.buffer.buffer.buffer.upload(self.offset, data)
+    /// Returns an error if `offset + data.len()` is greater than the length of the slice.
+    pub fn write(&self, offset: usize, data: &[T]) -> Result<(), SliceError> {
+        if offset + data.len() > self.length {
+            return Err(SliceError::OutOfRange { end: offset + data.len(), len: self.length });
+        }
+
+        self.buffer.buffer.buffer.upload(self.offset + offset, data);
+        Ok(())
+    }
+}
+
+impl<'b, T> Iterator for Chunks<'b, T> {
+    type Item = VertexBufferSlice<'b, T>;
+
+    fn next(&mut self) -> Option<VertexBufferSlice<'b, T>> {
+        let len = self.buffer.len();
+
+        if self.offset >= len {
+            return None;
+        }
+
+        let length = if self.offset + self.chunk_size > len {
+            len - self.offset
+        } else {
+            self.chunk_size
+        };
+
+        let slice = VertexBufferSlice {
+            buffer: self.buffer,
+            offset: self.offset,
+            length: length,
+        };
+
+        self.offset += length;
+        Some(slice)
     }
 }
 
@@ -380,6 +629,271 @@ impl<'a, T> IntoVerticesSource<'a> for VertexBufferSlice<'a, T> {
     }
 }
 
+/// Number of regions in a `StreamBuffer`.
+const STREAM_BUFFER_REGIONS: usize = 3;
+
+/// A vertex buffer whose content is meant to be streamed, for example rewritten every frame.
+///
+/// The buffer is split in three regions that are used one after the other. Each call to
+/// `alloc` returns the next region, and `slice` then returns the part of the buffer that
+/// has been written, ready to be drawn. Writing to a region while the GPU is still drawing
+/// from another one doesn't block.
+///
+/// If the backend supports `GL_ARB_buffer_storage` (or OpenGL 4.4), the buffer is
+/// persistently mapped in a coherent way and `alloc` returns a pointer directly into the
+/// buffer's memory. The draw commands that use a region are fenced, and `alloc` only
+/// waits if the region it is about to return is still being used.
+///
+/// Otherwise, the data is written to a temporary buffer which is uploaded with
+/// `glBufferSubData` when the mapping is destroyed, and the storage of the buffer is
+/// orphaned every time the first region is reused.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use]
+/// # extern crate glium;
+/// # extern crate glutin;
+/// # fn main() {
+/// #[derive(Copy, Clone)]
+/// struct Vertex {
+///     position: [f32; 2],
+/// }
+///
+/// implement_vertex!(Vertex, position);
+///
+/// # let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+/// let mut stream = glium::vertex::StreamBuffer::<Vertex>::new(&display, 1024);
+///
+/// // every frame
+/// {
+///     let mut vertices = stream.alloc(3);
+///     vertices[0] = Vertex { position: [-0.5, -0.5] };
+///     vertices[1] = Vertex { position: [ 0.0,  0.5] };
+///     vertices[2] = Vertex { position: [ 0.5, -0.5] };
+/// }
+///
+/// let vertices = stream.slice();
+/// // target.draw(vertices, ...);
+/// # }
+/// ```
+///
+pub struct StreamBuffer<T> {
+    buffer: VertexBuffer<T>,
+    region_len: usize,
+    // the region returned by the latest call to `alloc` and the number of elements allocated
+    current: Option<(usize, usize)>,
+    // fences of the draw commands that used each region
+    fences: Vec<Option<sync::SyncFence>>,
+}
+
+impl<T: Vertex + 'static + Send> StreamBuffer<T> {
+    /// Builds a new stream buffer.
+    ///
+    /// The parameter is the maximal number of elements that can be allocated at once with
+    /// `alloc`. The buffer itself is three times larger.
+    pub fn new<F>(facade: &F, region_len: usize) -> StreamBuffer<T> where F: Facade {
+        let bindings = <T as Vertex>::build_bindings();
+        let elements = region_len * STREAM_BUFFER_REGIONS;
+
+        let buffer = match Buffer::new_empty(facade, BufferType::ArrayBuffer, mem::size_of::<T>(),
+                                             elements, BufferFlags::persistent())
+        {
+            Err(BufferCreationError::PersistentMappingNotSupported) => {
+                Buffer::new_empty(facade, BufferType::ArrayBuffer, mem::size_of::<T>(),
+                                  elements, BufferFlags::dynamic_draw()).unwrap()
+            },
+            b => b.unwrap()
+        };
+
+        let elements_size = buffer.get_elements_size();
+
+        StreamBuffer {
+            buffer: VertexBuffer {
+                buffer: VertexBufferAny {
+                    buffer: buffer,
+                    bindings: bindings,
+                    elements_size: elements_size,
+                },
+                marker: PhantomData,
+            },
+            region_len: region_len,
+            current: None,
+            fences: (0 .. STREAM_BUFFER_REGIONS).map(|_| None).collect(),
+        }
+    }
+}
+
+impl<T: Send + Copy + 'static> StreamBuffer<T> {
+    /// Switches to the next region of the buffer and returns `len` elements of it.
+    ///
+    /// The content of the returned elements is undefined until you write to it. Once the
+    /// mapping is destroyed, the elements can be drawn with `slice`.
+    ///
+    /// This function only blocks if the region is still being used by the backend, which
+    /// means that the backend is more than two allocations behind.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `len` is greater than the length passed to `new`.
+    ///
+    pub fn alloc<'a>(&'a mut self, len: usize) -> StreamMapping<'a, T> {
+        assert!(len <= self.region_len, "Trying to allocate {} elements in a stream buffer \
+                                         whose regions contain {} elements", len,
+                                         self.region_len);
+
+        let region = match self.current {
+            Some((region, _)) => {
+                // every fence that has been registered since the last call to `alloc`
+                // belongs to a draw command that used the previous region
+                let buffer = &self.buffer.buffer.buffer;
+                let mut last = None;
+                for fence in buffer.take_fences() {
+                    if let Ok(fence) = fence.recv() {
+                        last = Some(fence.into_sync_fence(buffer.get_context()));
+                    }
+                }
+                if last.is_some() {
+                    self.fences[region] = last;
+                }
+
+                (region + 1) % STREAM_BUFFER_REGIONS
+            },
+            None => 0,
+        };
+
+        if let Some(fence) = self.fences[region].take() {
+            fence.wait();
+        }
+
+        let buffer = &self.buffer.buffer.buffer;
+        let offset = region * self.region_len;
+        self.current = Some((region, len));
+
+        match buffer.get_persistent_mapping() {
+            Some(ptr) => {
+                StreamMapping {
+                    buffer: buffer,
+                    offset: offset,
+                    data: unsafe { (ptr as *mut T).offset(offset as isize) },
+                    len: len,
+                    staging: None,
+                }
+            },
+
+            None => {
+                if region == 0 {
+                    buffer.invalidate();
+                }
+
+                let mut staging = Vec::with_capacity(len);
+                unsafe { staging.set_len(len); }
+
+                StreamMapping {
+                    buffer: buffer,
+                    offset: offset,
+                    data: staging.as_mut_ptr(),
+                    len: len,
+                    staging: Some(staging),
+                }
+            },
+        }
+    }
+}
+
+impl<T> StreamBuffer<T> {
+    /// Returns the offset, in number of elements, of the latest allocation in the buffer.
+    ///
+    /// This can be used to pass the base vertex of a draw command, for example when
+    /// manipulating the buffer with `as_vertex_buffer`.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `alloc` has never been called.
+    ///
+    pub fn offset(&self) -> usize {
+        let (region, _) = self.current.expect("Nothing has been allocated in the stream buffer");
+        region * self.region_len
+    }
+
+    /// Returns the elements returned by the latest call to `alloc`.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `alloc` has never been called.
+    ///
+    pub fn slice(&self) -> VertexBufferSlice<T> {
+        let (_, len) = self.current.expect("Nothing has been allocated in the stream buffer");
+
+        VertexBufferSlice {
+            buffer: &self.buffer,
+            offset: self.offset(),
+            length: len,
+        }
+    }
+
+    /// Returns the maximal number of elements that can be allocated at once.
+    pub fn region_len(&self) -> usize {
+        self.region_len
+    }
+
+    /// Returns true if the buffer is mapped in a permanent way in memory.
+    pub fn is_persistent(&self) -> bool {
+        self.buffer.is_persistent()
+    }
+
+    /// Returns the whole underlying vertex buffer.
+    pub fn as_vertex_buffer(&self) -> &VertexBuffer<T> {
+        &self.buffer
+    }
+}
+
+impl<T> GlObject for StreamBuffer<T> {
+    type Id = gl::types::GLuint;
+    fn get_id(&self) -> gl::types::GLuint {
+        self.buffer.get_id()
+    }
+}
+
+impl<'a, T> IntoVerticesSource<'a> for &'a StreamBuffer<T> {
+    fn into_vertices_source(self) -> VerticesSource<'a> {
+        self.slice().into_vertices_source()
+    }
+}
+
+/// A region of a `StreamBuffer`, returned by `alloc`.
+pub struct StreamMapping<'a, T> where T: Copy + Send + 'static {
+    buffer: &'a Buffer,
+    offset: usize,
+    data: *mut T,
+    len: usize,
+    // if the buffer is not persistent, the data is written here and uploaded on drop
+    staging: Option<Vec<T>>,
+}
+
+impl<'a, T> Deref for StreamMapping<'a, T> where T: Copy + Send + 'static {
+    type Target = [T];
+    fn deref<'b>(&'b self) -> &'b [T] {
+        unsafe { slice::from_raw_parts(self.data, self.len) }
+    }
+}
+
+impl<'a, T> DerefMut for StreamMapping<'a, T> where T: Copy + Send + 'static {
+    fn deref_mut<'b>(&'b mut self) -> &'b mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.data, self.len) }
+    }
+}
+
+impl<'a, T> Drop for StreamMapping<'a, T> where T: Copy + Send + 'static {
+    fn drop(&mut self) {
+        if let Some(staging) = self.staging.take() {
+            if !staging.is_empty() {
+                self.buffer.upload(self.offset, &staging);
+            }
+        }
+    }
+}
+
 /// A list of vertices loaded in the graphics card's memory.
 ///
 /// Contrary to `VertexBuffer`, this struct doesn't know about the type of data
@@ -471,6 +985,19 @@ impl<'a> IntoVerticesSource<'a> for VertexBufferAnySlice<'a> {
 /// A mapping of a buffer.
 pub struct Mapping<'a, T>(buffer::Mapping<'a, T>);
 
+impl<'a, T> Mapping<'a, T> {
+    /// Indicates that the elements between `offset` and `offset + len` have been modified.
+    ///
+    /// Only has an effect if the mapping was created with `map_explicit_flush`.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the range is out of the bounds of the mapping.
+    pub fn flush_range(&self, offset: usize, len: usize) {
+        self.0.flush_range(offset, len)
+    }
+}
+
 impl<'a, T> Deref for Mapping<'a, T> {
     type Target = [T];
     fn deref<'b>(&'b self) -> &'b [T] {
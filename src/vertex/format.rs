@@ -85,6 +85,15 @@ pub enum AttributeType {
     /// 4x4 matrix of `f64`s
     /// Warning: using `f64`s can be very slow.
     F64x4x4,
+    /// Four normalized signed values packed in 32 bits: 10 bits for each of the first three
+    /// components and 2 bits for the last one. Corresponds to `GL_INT_2_10_10_10_REV`.
+    #[allow(non_camel_case_types)]
+    I2_10_10_10_Rev,
+    /// Four normalized unsigned values packed in 32 bits: 10 bits for each of the first three
+    /// components and 2 bits for the last one. Corresponds to
+    /// `GL_UNSIGNED_INT_2_10_10_10_REV`.
+    #[allow(non_camel_case_types)]
+    U2_10_10_10_Rev,
 }
 
 impl AttributeType {
@@ -141,6 +150,16 @@ impl AttributeType {
             AttributeType::F64x4x2 => 8,
             AttributeType::F64x4x3 => 12,
             AttributeType::F64x4x4 => 16,
+            AttributeType::I2_10_10_10_Rev => 4,
+            AttributeType::U2_10_10_10_Rev => 4,
+        }
+    }
+
+    /// Returns true if the components of this type are packed together.
+    pub fn is_packed(&self) -> bool {
+        match *self {
+            AttributeType::I2_10_10_10_Rev | AttributeType::U2_10_10_10_Rev => true,
+            _ => false,
         }
     }
 }
@@ -149,7 +168,12 @@ impl AttributeType {
 ///
 /// The first element is the name of the binding, the second element is the offset
 /// from the start of each vertex to this element, and the third element is the type.
-pub type VertexFormat = Vec<(Cow<'static, str>, usize, AttributeType)>;
+///
+/// The fourth element indicates whether integer data should be normalized when the shader
+/// reads it as floating-point, for example `[u8; 4]` colors that the shader sees as values
+/// between `0.0` and `1.0`. It is ignored for floating-point data and for attributes that are
+/// integers in the shader.
+pub type VertexFormat = Vec<(Cow<'static, str>, usize, AttributeType, bool)>;
 
 unsafe impl Attribute for i8 {
     fn get_type() -> AttributeType {
@@ -522,3 +546,102 @@ unsafe impl Attribute for [[f64; 4]; 4] {
         AttributeType::F64x4x4
     }
 }
+
+/// Four signed values packed in a `u32`, in the `GL_INT_2_10_10_10_REV` format.
+///
+/// The first three components are stored on 10 bits and the last one on 2 bits. The values
+/// are normalized, which means that the shader will see them as floats between `-1.0`
+/// and `1.0`. This is commonly used to store normals in 4 bytes instead of 12.
+///
+/// Requires OpenGL 3.3 or OpenGL ES 3.0.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PackedI10I10I10I2(pub u32);
+
+impl PackedI10I10I10I2 {
+    /// Packs four values. The first three must be between `-512` and `511`, and the last one
+    /// between `-2` and `1`.
+    pub fn new(x: i16, y: i16, z: i16, w: i8) -> PackedI10I10I10I2 {
+        assert!(x >= -512 && x <= 511 && y >= -512 && y <= 511 && z >= -512 && z <= 511);
+        assert!(w >= -2 && w <= 1);
+
+        PackedI10I10I10I2((x as u32 & 0x3ff) | ((y as u32 & 0x3ff) << 10) |
+                          ((z as u32 & 0x3ff) << 20) | ((w as u32 & 0x3) << 30))
+    }
+
+    /// Packs four floats between `-1.0` and `1.0`, for example the components of a normal.
+    /// Values outside of this range are clamped.
+    ///
+    /// This is the inverse of `to_f32`, give or take the precision of 10 and 2 bits.
+    pub fn from_f32(x: f32, y: f32, z: f32, w: f32) -> PackedI10I10I10I2 {
+        fn encode(value: f32, max: f32) -> i16 {
+            (value.max(-1.0).min(1.0) * max).round() as i16
+        }
+
+        PackedI10I10I10I2::new(encode(x, 511.0), encode(y, 511.0), encode(z, 511.0),
+                               encode(w, 1.0) as i8)
+    }
+
+    /// Returns the four values as the shader sees them, between `-1.0` and `1.0`.
+    pub fn to_f32(&self) -> (f32, f32, f32, f32) {
+        // sign-extends the value stored in the `bits` lowest bits of `value`
+        fn decode(value: u32, bits: u32, max: f32) -> f32 {
+            let shift = 32 - bits;
+            let value = ((value << shift) as i32) >> shift;
+            (value as f32 / max).max(-1.0)
+        }
+
+        (decode(self.0, 10, 511.0), decode(self.0 >> 10, 10, 511.0),
+         decode(self.0 >> 20, 10, 511.0), decode(self.0 >> 30, 2, 1.0))
+    }
+}
+
+unsafe impl Attribute for PackedI10I10I10I2 {
+    fn get_type() -> AttributeType {
+        AttributeType::I2_10_10_10_Rev
+    }
+}
+
+/// Four unsigned values packed in a `u32`, in the `GL_UNSIGNED_INT_2_10_10_10_REV` format.
+///
+/// The first three components are stored on 10 bits and the last one on 2 bits. The values
+/// are normalized, which means that the shader will see them as floats between `0.0`
+/// and `1.0`.
+///
+/// Requires OpenGL 3.3 or OpenGL ES 3.0.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PackedU10U10U10U2(pub u32);
+
+impl PackedU10U10U10U2 {
+    /// Packs four values. The first three must be inferior to `1024`, and the last one
+    /// inferior to `4`.
+    pub fn new(x: u16, y: u16, z: u16, w: u8) -> PackedU10U10U10U2 {
+        assert!(x < 1024 && y < 1024 && z < 1024);
+        assert!(w < 4);
+
+        PackedU10U10U10U2(x as u32 | ((y as u32) << 10) | ((z as u32) << 20) | ((w as u32) << 30))
+    }
+
+    /// Packs four floats between `0.0` and `1.0`. Values outside of this range are clamped.
+    ///
+    /// This is the inverse of `to_f32`, give or take the precision of 10 and 2 bits.
+    pub fn from_f32(x: f32, y: f32, z: f32, w: f32) -> PackedU10U10U10U2 {
+        fn encode(value: f32, max: f32) -> u16 {
+            (value.max(0.0).min(1.0) * max).round() as u16
+        }
+
+        PackedU10U10U10U2::new(encode(x, 1023.0), encode(y, 1023.0), encode(z, 1023.0),
+                               encode(w, 3.0) as u8)
+    }
+
+    /// Returns the four values as the shader sees them, between `0.0` and `1.0`.
+    pub fn to_f32(&self) -> (f32, f32, f32, f32) {
+        ((self.0 & 0x3ff) as f32 / 1023.0, ((self.0 >> 10) & 0x3ff) as f32 / 1023.0,
+         ((self.0 >> 20) & 0x3ff) as f32 / 1023.0, (self.0 >> 30) as f32 / 3.0)
+    }
+}
+
+unsafe impl Attribute for PackedU10U10U10U2 {
+    fn get_type() -> AttributeType {
+        AttributeType::U2_10_10_10_Rev
+    }
+}
@@ -55,13 +55,96 @@ let data = vec![
 let vertex_buffer = glium::vertex::VertexBuffer::new(&display, data);
 ```
 
+# Instancing
+
+Instancing draws the same geometry multiple times in a single draw call. To do so, pass a tuple
+containing your per-vertex buffer and a per-instance buffer to `draw`. The per-instance buffer
+is obtained with `VertexBuffer::per_instance` or `VertexBuffer::per_instance_if_supported`.
+
+```no_run
+# #[macro_use]
+# extern crate glium;
+# extern crate glutin;
+# fn main() {
+# use glium::Surface;
+# #[derive(Copy, Clone)]
+# struct Vertex { position: [f32; 2] }
+# implement_vertex!(Vertex, position);
+#[derive(Copy, Clone)]
+struct Instance {
+    world_position: [f32; 3],
+}
+
+implement_vertex!(Instance, world_position);
+
+# let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+# let program: glium::Program = unsafe { ::std::mem::uninitialized() };
+# let vertex_buffer: glium::VertexBuffer<Vertex> = unsafe { ::std::mem::uninitialized() };
+# let indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+# let mut frame = display.draw();
+let instances = glium::VertexBuffer::new(&display, vec![
+    Instance { world_position: [0.0, 0.0, 0.0] },
+    Instance { world_position: [1.0, 0.0, 0.0] },
+]);
+
+frame.draw((&vertex_buffer, instances.per_instance_if_supported().unwrap()), &indices,
+           &program, &glium::uniforms::EmptyUniforms, &Default::default()).unwrap();
+# }
+```
+
+The number of instances is the length of the per-instance buffer, and the attributes of the
+per-instance buffer advance once per instance instead of once per vertex. If multiple
+per-instance sources are passed, they must all have the same length or
+`DrawError::InstancesCountMismatch` is returned. If the backend doesn't support per-instance
+attributes, `DrawError::InstancingNotSupported` is returned.
+
+# Multiple vertex sources
+
+The same mechanism lets you store the attributes of your vertices in separate buffers, for
+example if your positions and normals are stored in separate arrays. Pass a tuple of vertex
+buffers to `draw`, and each attribute is read from the buffer whose format contains it.
+
+```no_run
+# #[macro_use]
+# extern crate glium;
+# extern crate glutin;
+# fn main() {
+# use glium::Surface;
+#[derive(Copy, Clone)]
+struct Position { position: [f32; 3] }
+implement_vertex!(Position, position);
+
+#[derive(Copy, Clone)]
+struct Normal { normal: [f32; 3] }
+implement_vertex!(Normal, normal);
+
+# let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+# let program: glium::Program = unsafe { ::std::mem::uninitialized() };
+# let indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+# let mut frame = display.draw();
+# let positions_data: Vec<Position> = Vec::new();
+# let normals_data: Vec<Normal> = Vec::new();
+let positions = glium::VertexBuffer::new(&display, positions_data);
+let normals = glium::VertexBuffer::new(&display, normals_data);
+
+frame.draw((&positions, &normals), &indices, &program, &glium::uniforms::EmptyUniforms,
+           &Default::default()).unwrap();
+# }
+```
+
+All the per-vertex sources must have the same length, otherwise drawing without indices
+returns `DrawError::VerticesSourcesLengthMismatch`. An attribute can only be provided by one
+of the sources, otherwise `DrawError::AttributeDefinedMultipleTimes` is returned.
+
 */
 use std::iter::Chain;
 use std::option::IntoIter;
 
 pub use self::buffer::{VertexBuffer, VertexBufferAny, Mapping};
-pub use self::buffer::{VertexBufferSlice, VertexBufferAnySlice};
+pub use self::buffer::{VertexBufferSlice, VertexBufferAnySlice, Chunks, SliceError};
+pub use self::buffer::{StreamBuffer, StreamMapping};
 pub use self::format::{AttributeType, VertexFormat};
+pub use self::format::{PackedI10I10I10I2, PackedU10U10U10U2};
 
 mod buffer;
 mod format;
@@ -75,6 +158,15 @@ pub enum VerticesSource<'a> {
     /// The fourth parameter tells whether or not this buffer is "per instance" (true) or
     // "per vertex" (false)
     VertexBuffer(&'a VertexBufferAny, usize, usize, bool),
+
+    /// A marker indicating a "phantom list of attributes".
+    Marker {
+        /// Number of attributes.
+        len: usize,
+
+        /// Whether or not this buffer is "per instance" (true) or "per vertex" (false).
+        per_instance: bool,
+    },
 }
 
 /// Objects that can be used as vertex sources.
@@ -103,6 +195,31 @@ impl<'a> IntoVerticesSource<'a> for PerInstance<'a> {
     }
 }
 
+/// Marker that can be passed instead of a buffer to indicate an empty list of buffers.
+///
+/// This is useful when the data of each instance doesn't come from a vertex buffer, for example
+/// when the vertex shader reads it from a uniform buffer thanks to `gl_InstanceID`.
+///
+/// ## Example
+///
+/// ```no_run
+/// # let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+/// # let vertex_buffer: glium::vertex::VertexBufferAny = unsafe { ::std::mem::uninitialized() };
+/// // draws the geometry 16 times
+/// let vertices = (&vertex_buffer, glium::vertex::EmptyInstanceAttributes { len: 16 });
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct EmptyInstanceAttributes {
+    /// Number of instances to draw.
+    pub len: usize,
+}
+
+impl<'a> IntoVerticesSource<'a> for EmptyInstanceAttributes {
+    fn into_vertices_source(self) -> VerticesSource<'a> {
+        VerticesSource::Marker { len: self.len, per_instance: true }
+    }
+}
+
 /// Objects that describe multiple vertex sources.
 pub trait MultiVerticesSource<'a> {
     /// Iterator that enumerates each source.
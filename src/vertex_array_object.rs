@@ -8,6 +8,7 @@ use program::Program;
 use vertex::AttributeType;
 use vertex::VertexBufferAny;
 use vertex::VertexFormat;
+use DrawError;
 use GlObject;
 
 use {libc, gl};
@@ -19,7 +20,8 @@ use version::Version;
 pub struct VertexAttributesSystem {
     // we maintain a list of VAOs for each vertexbuffer-indexbuffer-program association
     // the key is a (buffers-list-with-offset, program) ; the buffers list must be sorted
-    vaos: RefCell<HashMap<(Vec<(gl::types::GLuint, usize)>, Handle), VertexArrayObject>>,
+    vaos: RefCell<HashMap<(Vec<(gl::types::GLuint, usize, Option<u32>)>, Handle),
+                           VertexArrayObject>>,
 }
 
 /// Object allowing one to bind vertex attributes to the current context.
@@ -56,7 +58,7 @@ impl VertexAttributesSystem {
     /// purge its VAOs cache.
     pub fn purge_buffer(&self, ctxt: &mut CommandContext, id: gl::types::GLuint) {
         self.purge_if(ctxt, |&(ref buffers, _)| {
-            buffers.iter().find(|&&(b, _)| b == id).is_some()
+            buffers.iter().find(|&&(b, _, _)| b == id).is_some()
         })
     }
 
@@ -89,7 +91,7 @@ impl VertexAttributesSystem {
 
     /// Purges VAOs that match a certain condition.
     fn purge_if<F>(&self, ctxt: &mut CommandContext, mut condition: F)
-                   where F: FnMut(&(Vec<(gl::types::GLuint, usize)>, Handle)) -> bool
+                   where F: FnMut(&(Vec<(gl::types::GLuint, usize, Option<u32>)>, Handle)) -> bool
     {
         let mut vaos = self.vaos.borrow_mut();
 
@@ -134,9 +136,9 @@ impl<'a, 'c, 'd: 'c> Binder<'a, 'c, 'd> {
         {
             // VAOs are supported
             let mut buffers_list: Vec<_> = self.vertex_buffers.iter()
-                                                              .map(|&(v, _, o, _, _)| (v, o))
+                                                              .map(|&(v, _, o, _, d)| (v, o, d))
                                                               .collect();
-            buffers_list.push((self.element_array_buffer, 0));
+            buffers_list.push((self.element_array_buffer, 0, None));
             buffers_list.sort();
 
             let program_id = self.program.get_id();
@@ -179,6 +181,28 @@ impl<'a, 'c, 'd: 'c> Binder<'a, 'c, 'd> {
                 }
             }
 
+            // disabling the attribute arrays that a previous draw call has enabled but that
+            // aren't used by this one, otherwise they would still be fetched by the GPU
+            let mut enabled = Vec::new();
+            for &(_, ref bindings, _, _, _) in &self.vertex_buffers {
+                for &(ref name, _, _, _) in bindings {
+                    match self.program.get_attribute(Borrow::<str>::borrow(name)) {
+                        Some(a) if a.location != -1 => enabled.push(a.location as u32),
+                        _ => ()
+                    }
+                }
+            }
+
+            for location in mem::replace(&mut ctxt.state.enabled_vertex_attrib_arrays,
+                                         Vec::new())
+            {
+                if !enabled.contains(&location) {
+                    unsafe { ctxt.gl.DisableVertexAttribArray(location); }
+                }
+            }
+
+            ctxt.state.enabled_vertex_attrib_arrays = enabled;
+
             for (vertex_buffer, bindings, offset, stride, divisor) in self.vertex_buffers {
                 unsafe {
                     bind_attribute(ctxt, self.program, vertex_buffer, &bindings, offset, stride,
@@ -189,6 +213,54 @@ impl<'a, 'c, 'd: 'c> Binder<'a, 'c, 'd> {
     }
 }
 
+/// Checks that the vertex formats provide all the attributes of the program, and that their
+/// types match.
+///
+/// Attributes of the vertex formats that are not used by the program are ignored.
+pub fn check_attributes(program: &Program, formats: &[&VertexFormat]) -> Result<(), DrawError> {
+    // checking the attributes types
+    for bindings in formats.iter() {
+        for &(ref name, _, ty, _) in bindings.iter() {
+            let attribute = match program.get_attribute(Borrow::<str>::borrow(name)) {
+                Some(a) => a,
+                None => continue
+            };
+
+            if ty.get_num_components() != attribute.ty.get_num_components() ||
+                attribute.size != 1 || !is_attribute_type_compatible(ty, attribute.ty)
+            {
+                return Err(DrawError::AttributeTypeMismatch { name: name.to_string() });
+            }
+        }
+    }
+
+    // checking for attributes that are provided by multiple sources
+    for (index, bindings) in formats.iter().enumerate() {
+        for &(ref name, _, _, _) in bindings.iter() {
+            let duplicate = formats[index + 1 ..].iter().any(|other| {
+                other.iter().any(|&(ref n, _, _, _)| n == name)
+            });
+
+            if duplicate {
+                return Err(DrawError::AttributeDefinedMultipleTimes { name: name.to_string() });
+            }
+        }
+    }
+
+    // checking for missing attributes
+    for (&ref name, _) in program.attributes() {
+        let found = formats.iter().any(|bindings| {
+            bindings.iter().any(|&(ref n, _, _, _)| n == name)
+        });
+
+        if !found {
+            return Err(DrawError::AttributeMissing { name: name.clone() });
+        }
+    }
+
+    Ok(())
+}
+
 /// Stores informations about how to bind a vertex buffer, an index buffer and a program.
 struct VertexArrayObject {
     id: gl::types::GLuint,
@@ -204,39 +276,6 @@ impl VertexArrayObject {
                   vertex_buffers: &[(gl::types::GLuint, VertexFormat, usize, usize, Option<u32>)],
                   ib_id: gl::types::GLuint, program: &Program) -> VertexArrayObject
     {
-        // checking the attributes types
-        for &(_, ref bindings, _, _, _) in vertex_buffers {
-            for &(ref name, _, ty) in bindings {
-                let attribute = match program.get_attribute(Borrow::<str>::borrow(name)) {
-                    Some(a) => a,
-                    None => continue
-                };
-
-                if ty.get_num_components() != attribute.ty.get_num_components() ||
-                    attribute.size != 1
-                {
-                    panic!("The program attribute `{}` does not match the vertex format. \
-                            Program expected {:?}, got {:?}.", name, attribute.ty, ty);
-                }
-            }
-        }
-
-        // checking for missing attributes
-        for (&ref name, _) in program.attributes() {
-            let mut found = false;
-            for &(_, ref bindings, _, _, _) in vertex_buffers {
-                if bindings.iter().find(|&&(ref n, _, _)| n == name).is_some() {
-                    found = true;
-                    break;
-                }
-            }
-            if !found {
-                panic!("The program attribute `{}` is missing in the vertex bindings", name);
-            }
-        };
-
-        // TODO: check for collisions between the vertices sources
-
         // building the VAO
         let id = {
             let mut id = mem::uninitialized();
@@ -377,6 +416,8 @@ fn vertex_binding_type_to_gl(ty: AttributeType) -> (gl::types::GLenum, gl::types
         AttributeType::F64x4x2 => (gl::DOUBLE_MAT4x2, 1),
         AttributeType::F64x4x3 => (gl::DOUBLE_MAT4x3, 1),
         AttributeType::F64x4x4 => (gl::DOUBLE_MAT4, 1),
+        AttributeType::I2_10_10_10_Rev => (gl::INT_2_10_10_10_REV, 4),
+        AttributeType::U2_10_10_10_Rev => (gl::UNSIGNED_INT_2_10_10_10_REV, 4),
     }
 }
 
@@ -404,6 +445,30 @@ fn bind_vao(ctxt: &mut CommandContext, vao_id: gl::types::GLuint) {
     }
 }
 
+/// Returns true if vertex data of type `data` can be used for a program attribute of
+/// type `attribute`.
+///
+/// Integer attributes (`ivec`, `uvec`) must be fed with integers, and double attributes
+/// (`double`, `dvec`) with doubles. Float attributes accept everything, as the data is
+/// converted.
+fn is_attribute_type_compatible(data: AttributeType, attribute: AttributeType) -> bool {
+    let (data, _) = vertex_binding_type_to_gl(data);
+    let (attribute, _) = vertex_binding_type_to_gl(attribute);
+
+    match attribute {
+        gl::BYTE | gl::UNSIGNED_BYTE | gl::SHORT | gl::UNSIGNED_SHORT |
+        gl::INT | gl::UNSIGNED_INT => match data {
+            gl::BYTE | gl::UNSIGNED_BYTE | gl::SHORT | gl::UNSIGNED_SHORT |
+            gl::INT | gl::UNSIGNED_INT => true,
+            _ => false
+        },
+
+        gl::DOUBLE => data == gl::DOUBLE,
+
+        _ => true
+    }
+}
+
 /// Binds an individual attribute to the current VAO.
 unsafe fn bind_attribute(ctxt: &mut CommandContext, program: &Program,
                          vertex_buffer: gl::types::GLuint, bindings: &VertexFormat,
@@ -425,7 +490,7 @@ unsafe fn bind_attribute(ctxt: &mut CommandContext, program: &Program,
     }
 
     // binding attributes
-    for &(ref name, offset, ty) in bindings {
+    for &(ref name, offset, ty, normalize) in bindings {
         let (data_type, elements_count) = vertex_binding_type_to_gl(ty);
 
         let attribute = match program.get_attribute(Borrow::<str>::borrow(name)) {
@@ -453,14 +518,27 @@ unsafe fn bind_attribute(ctxt: &mut CommandContext, program: &Program,
                                                  stride as i32,
                                                  (buffer_offset + offset) as *const libc::c_void),
 
-                _ => ctxt.gl.VertexAttribPointer(attribute.location as u32,
-                                                 elements_count as gl::types::GLint, data_type, 0,
-                                                 stride as i32,
-                                                 (buffer_offset + offset) as *const libc::c_void)
+                _ => {
+                    // packed formats are always normalized
+                    let normalized = if ty.is_packed() || normalize { gl::TRUE } else { gl::FALSE };
+
+                    ctxt.gl.VertexAttribPointer(attribute.location as u32,
+                                                elements_count as gl::types::GLint, data_type,
+                                                normalized, stride as i32,
+                                                (buffer_offset + offset) as *const libc::c_void)
+                }
             }
 
-            if let Some(divisor) = divisor {
-                ctxt.gl.VertexAttribDivisor(attribute.location as u32, divisor);
+            // the divisor must also be reset to 0 for per-vertex buffers, in case the
+            // attribute was previously used with a per-instance buffer
+            if ctxt.version >= &Version(Api::Gl, 3, 3) ||
+               ctxt.version >= &Version(Api::GlEs, 3, 0)
+            {
+                ctxt.gl.VertexAttribDivisor(attribute.location as u32, divisor.unwrap_or(0));
+            } else if ctxt.extensions.gl_arb_instanced_arrays {
+                ctxt.gl.VertexAttribDivisorARB(attribute.location as u32, divisor.unwrap_or(0));
+            } else {
+                assert!(divisor.is_none());
             }
 
             ctxt.gl.EnableVertexAttribArray(attribute.location as u32);
@@ -7,7 +7,6 @@ use glium::Surface;
 mod support;
 
 #[test]
-#[should_panic(expected = "The program attribute `field1` does not match the vertex format")]
 fn attribute_types_mismatch() {
     let display = support::build_display();
 
@@ -46,15 +45,18 @@ fn attribute_types_mismatch() {
 
     // drawing a frame
     let mut target = display.draw();
-    target.draw(&vertex_buffer, &index_buffer, &program, &glium::uniforms::EmptyUniforms,
-                &std::default::Default::default()).unwrap();
+    match target.draw(&vertex_buffer, &index_buffer, &program,
+                      &glium::uniforms::EmptyUniforms, &std::default::Default::default())
+    {
+        Err(glium::DrawError::AttributeTypeMismatch { ref name }) if name == "field1" => (),
+        _ => panic!()
+    };
     target.finish();
     
     display.assert_no_error();
 }
 
 #[test]
-#[should_panic(expected = "The program attribute `field2` is missing in the vertex bindings")]
 fn missing_attribute() {
     let display = support::build_display();
 
@@ -93,8 +95,12 @@ fn missing_attribute() {
 
     // drawing a frame
     let mut target = display.draw();
-    target.draw(&vertex_buffer, &index_buffer, &program, &glium::uniforms::EmptyUniforms,
-                &std::default::Default::default()).unwrap();
+    match target.draw(&vertex_buffer, &index_buffer, &program,
+                      &glium::uniforms::EmptyUniforms, &std::default::Default::default())
+    {
+        Err(glium::DrawError::AttributeMissing { ref name }) if name == "field2" => (),
+        _ => panic!()
+    };
     target.finish();
     
     display.assert_no_error();
@@ -205,3 +211,463 @@ attribute_test!(attribute_vec3_i32, [i32; 3], "vec3", [0, 0, 0], "vec4(field1, 1
 attribute_test!(attribute_vec3_tuple_i32, (i32, i32, i32), "vec3", (0, 0, 0), "vec4(field1, 1.0)");
 attribute_test!(attribute_vec4_i32, [i32; 4], "vec4", [0, 0, 0, 0], "field1");
 attribute_test!(attribute_vec4_tuple_i32, (i32, i32, i32, i32), "vec4", (0, 0, 0, 0), "field1");
+
+#[test]
+fn attribute_packed_u10u10u10u2() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+        color: glium::vertex::PackedU10U10U10U2,
+    }
+
+    implement_vertex!(Vertex, position, color);
+
+    let color = glium::vertex::PackedU10U10U10U2::new(1023, 0, 1023, 3);
+
+    let vertex_buffer = glium::VertexBuffer::new(&display, vec![
+            Vertex { position: [-1.0,  1.0], color: color },
+            Vertex { position: [ 1.0,  1.0], color: color },
+            Vertex { position: [-1.0, -1.0], color: color },
+            Vertex { position: [ 1.0, -1.0], color: color },
+        ]);
+    let index_buffer = glium::IndexBuffer::new(&display,
+                            glium::index::TriangleStrip(vec![0u16, 1, 2, 3]));
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+            attribute vec4 color;
+            varying vec4 v_color;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+                v_color = color;
+            }
+        ",
+        "
+            #version 110
+            varying vec4 v_color;
+
+            void main() {
+                gl_FragColor = v_color;
+            }
+        ",
+        None)
+        .unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    match texture.as_surface().draw(&vertex_buffer, &index_buffer, &program,
+                                    &glium::uniforms::EmptyUniforms,
+                                    &std::default::Default::default())
+    {
+        Err(glium::DrawError::AttributeTypeNotSupported) => return,
+        r => r.unwrap()
+    };
+
+    let data: Vec<Vec<(f32, f32, f32, f32)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(1.0, 0.0, 1.0, 1.0));
+        }
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn attribute_packed_i10i10i10i2() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+        color: glium::vertex::PackedI10I10I10I2,
+    }
+
+    implement_vertex!(Vertex, position, color);
+
+    // negative values are clamped to 0 when written to the framebuffer
+    let color = glium::vertex::PackedI10I10I10I2::from_f32(1.0, -1.0, 1.0, 1.0);
+
+    let vertex_buffer = glium::VertexBuffer::new(&display, vec![
+            Vertex { position: [-1.0,  1.0], color: color },
+            Vertex { position: [ 1.0,  1.0], color: color },
+            Vertex { position: [-1.0, -1.0], color: color },
+            Vertex { position: [ 1.0, -1.0], color: color },
+        ]);
+    let index_buffer = glium::IndexBuffer::new(&display,
+                            glium::index::TriangleStrip(vec![0u16, 1, 2, 3]));
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+            attribute vec4 color;
+            varying vec4 v_color;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+                v_color = color;
+            }
+        ",
+        "
+            #version 110
+            varying vec4 v_color;
+
+            void main() {
+                gl_FragColor = v_color;
+            }
+        ",
+        None)
+        .unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    match texture.as_surface().draw(&vertex_buffer, &index_buffer, &program,
+                                    &glium::uniforms::EmptyUniforms,
+                                    &std::default::Default::default())
+    {
+        Err(glium::DrawError::AttributeTypeNotSupported) => return,
+        r => r.unwrap()
+    };
+
+    let data: Vec<Vec<(f32, f32, f32, f32)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(1.0, 0.0, 1.0, 1.0));
+        }
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn packed_attributes_round_trip() {
+    let packed = glium::vertex::PackedI10I10I10I2::from_f32(1.0, -1.0, 0.0, -1.0);
+    assert_eq!(packed.to_f32(), (1.0, -1.0, 0.0, -1.0));
+    assert_eq!(glium::vertex::PackedI10I10I10I2::from_f32(2.0, -5.0, 0.0, 1.0).to_f32(),
+               (1.0, -1.0, 0.0, 1.0));
+
+    let packed = glium::vertex::PackedU10U10U10U2::from_f32(1.0, 0.0, 1.0, 1.0);
+    assert_eq!(packed, glium::vertex::PackedU10U10U10U2::new(1023, 0, 1023, 3));
+    assert_eq!(packed.to_f32(), (1.0, 0.0, 1.0, 1.0));
+}
+
+#[test]
+fn attribute_normalized_u8() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+        color: [u8; 4],
+    }
+
+    implement_vertex!(Vertex, position normalize(false), color normalize(true));
+
+    let color = [255, 0, 255, 255];
+
+    let vertex_buffer = glium::VertexBuffer::new(&display, vec![
+            Vertex { position: [-1.0,  1.0], color: color },
+            Vertex { position: [ 1.0,  1.0], color: color },
+            Vertex { position: [-1.0, -1.0], color: color },
+            Vertex { position: [ 1.0, -1.0], color: color },
+        ]);
+    let index_buffer = glium::IndexBuffer::new(&display,
+                            glium::index::TriangleStrip(vec![0u16, 1, 2, 3]));
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+            attribute vec4 color;
+            varying vec4 v_color;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+                v_color = color;
+            }
+        ",
+        "
+            #version 110
+            varying vec4 v_color;
+
+            void main() {
+                gl_FragColor = v_color;
+            }
+        ",
+        None)
+        .unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vertex_buffer, &index_buffer, &program,
+                              &glium::uniforms::EmptyUniforms,
+                              &std::default::Default::default()).unwrap();
+
+    let data: Vec<Vec<(f32, f32, f32, f32)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(1.0, 0.0, 1.0, 1.0));
+        }
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn unused_attributes_after_previous_draw() {
+    let display = support::build_display();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    // first draw with three attributes
+    {
+        #[derive(Copy, Clone)]
+        struct Vertex {
+            position: [f32; 2],
+            color: [f32; 3],
+            weight: f32,
+        }
+
+        implement_vertex!(Vertex, position, color, weight);
+
+        let vertex_buffer = glium::VertexBuffer::new(&display, vec![
+                Vertex { position: [-1.0,  1.0], color: [0.0, 1.0, 0.0], weight: 1.0 },
+                Vertex { position: [ 1.0,  1.0], color: [0.0, 1.0, 0.0], weight: 1.0 },
+                Vertex { position: [-1.0, -1.0], color: [0.0, 1.0, 0.0], weight: 1.0 },
+                Vertex { position: [ 1.0, -1.0], color: [0.0, 1.0, 0.0], weight: 1.0 },
+            ]);
+        let index_buffer = glium::IndexBuffer::new(&display,
+                                glium::index::TriangleStrip(vec![0u16, 1, 2, 3]));
+
+        let program = glium::Program::from_source(&display,
+            "
+                #version 110
+
+                attribute vec2 position;
+                attribute vec3 color;
+                attribute float weight;
+                varying vec3 v_color;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                    v_color = color * weight;
+                }
+            ",
+            "
+                #version 110
+                varying vec3 v_color;
+
+                void main() {
+                    gl_FragColor = vec4(v_color, 1.0);
+                }
+            ",
+            None)
+            .unwrap();
+
+        texture.as_surface().draw(&vertex_buffer, &index_buffer, &program,
+                                  &glium::uniforms::EmptyUniforms,
+                                  &std::default::Default::default()).unwrap();
+    }
+
+    // then with only one attribute, after the first buffer has been destroyed
+    let (vertex_buffer, index_buffer, program) = support::build_fullscreen_red_pipeline(&display);
+    texture.as_surface().draw(&vertex_buffer, &index_buffer, &program,
+                              &glium::uniforms::EmptyUniforms,
+                              &std::default::Default::default()).unwrap();
+
+    let data: Vec<Vec<(f32, f32, f32, f32)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(1.0, 0.0, 0.0, 1.0));
+        }
+    }
+
+    display.assert_no_error();
+}
+
+macro_rules! modern_attribute_test(
+    ($name:ident, $attr_ty:ty, $version:expr, $glsl_ty:expr, $value:expr, $check:expr) => (
+        #[test]
+        fn $name() {
+            let display = support::build_display();
+
+            #[derive(Copy, Clone)]
+            struct Vertex {
+                position: [f32; 2],
+                field1: $attr_ty,
+            }
+
+            implement_vertex!(Vertex, position, field1);
+
+            let vertex_buffer = glium::VertexBuffer::new(&display, vec![
+                    Vertex { position: [-1.0,  1.0], field1: $value },
+                    Vertex { position: [ 1.0,  1.0], field1: $value },
+                    Vertex { position: [-1.0, -1.0], field1: $value },
+                    Vertex { position: [ 1.0, -1.0], field1: $value },
+                ]);
+            let index_buffer = glium::IndexBuffer::new(&display,
+                                    glium::index::TriangleStrip(vec![0u16, 1, 2, 3]));
+
+            // the fragment is red if the attribute has been read correctly
+            let program = match glium::Program::from_source(&display,
+                &format!("
+                    #version {version}
+
+                    in vec2 position;
+                    in {ty} field1;
+                    flat out int v_ok;
+
+                    void main() {{
+                        gl_Position = vec4(position, 0.0, 1.0);
+                        v_ok = ({check}) ? 1 : 0;
+                    }}
+                ", version = $version, ty = $glsl_ty, check = $check),
+                &format!("
+                    #version {version}
+
+                    flat in int v_ok;
+                    out vec4 color;
+
+                    void main() {{
+                        color = vec4(float(v_ok), 0.0, 0.0, 1.0);
+                    }}
+                ", version = $version),
+                None)
+            {
+                Err(glium::CompilationError(..)) => return,
+                Ok(p) => p,
+                e => e.unwrap()
+            };
+
+            let texture = support::build_renderable_texture(&display);
+            texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+            texture.as_surface().draw(&vertex_buffer, &index_buffer, &program,
+                                      &glium::uniforms::EmptyUniforms,
+                                      &std::default::Default::default()).unwrap();
+
+            let data: Vec<Vec<(f32, f32, f32, f32)>> = texture.read();
+            assert_eq!(data[512][512], (1.0, 0.0, 0.0, 1.0));
+
+            display.assert_no_error();
+        }
+    )
+);
+
+modern_attribute_test!(attribute_ivec4_i32, [i32; 4], 130, "ivec4", [16777217, -3, 0, 7],
+                       "field1 == ivec4(16777217, -3, 0, 7)");
+modern_attribute_test!(attribute_uvec2_u32, [u32; 2], 130, "uvec2", [4294967295, 12],
+                       "field1 == uvec2(4294967295u, 12u)");
+modern_attribute_test!(attribute_dvec3_f64, [f64; 3], 410, "dvec3", [0.1, 1.0e-300, -2.5],
+                       "field1 == dvec3(0.1LF, 1.0e-300LF, -2.5LF)");
+
+#[test]
+fn attribute_float_data_for_integer_input() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        field1: [f32; 4],
+    }
+
+    implement_vertex!(Vertex, field1);
+
+    let vertex_buffer = glium::VertexBuffer::new(&display, vec![Vertex { field1: [0.0; 4] }]);
+    let index_buffer = glium::IndexBuffer::new(&display,
+                            glium::index::PointsList(vec![0u16]));
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 130
+
+            in ivec4 field1;
+
+            void main() {
+                gl_Position = vec4(field1);
+            }
+        ",
+        "
+            #version 130
+            out vec4 color;
+
+            void main() {
+                color = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(..)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let mut target = display.draw();
+    match target.draw(&vertex_buffer, &index_buffer, &program,
+                      &glium::uniforms::EmptyUniforms, &std::default::Default::default())
+    {
+        Err(glium::DrawError::AttributeTypeMismatch { ref name }) if name == "field1" => (),
+        _ => panic!()
+    };
+    target.finish();
+
+    display.assert_no_error();
+}
+
+#[test]
+fn missing_attribute_validation_disabled() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        field1: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, field1);
+
+    let vertex_buffer = glium::VertexBuffer::new(&display, vec![Vertex { field1: [0.0; 2] }]);
+    let index_buffer = glium::IndexBuffer::new(&display,
+                            glium::index::PointsList(vec![0u16]));
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 field1;
+            attribute vec2 field2;
+
+            void main() {
+                gl_Position = vec4(field1 + field2, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+            void main() {
+                gl_FragColor = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None)
+        .unwrap();
+
+    let params = glium::DrawParameters {
+        validate_attributes: false,
+        .. std::default::Default::default()
+    };
+
+    let mut target = display.draw();
+    target.draw(&vertex_buffer, &index_buffer, &program, &glium::uniforms::EmptyUniforms,
+                &params).unwrap();
+    target.finish();
+
+    display.assert_no_error();
+}
@@ -243,3 +243,29 @@ fn cull_counterclockwise_trianglestrip() {
     
     display.assert_no_error();
 }
+
+#[test]
+fn polygon_mode_line() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+        &glium::DrawParameters {
+            polygon_mode: glium::PolygonMode::Line,
+            .. std::default::Default::default()
+        })
+    {
+        Ok(_) => (),
+        Err(glium::DrawError::PolygonModeNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    // this pixel is neither on the border nor on the diagonal of the rectangle
+    let read_back: Vec<Vec<(f32, f32, f32, f32)>> = texture.read();
+    assert_eq!(read_back[100][500], (0.0, 0.0, 0.0, 0.0));
+
+    display.assert_no_error();
+}
@@ -42,7 +42,7 @@ macro_rules! blending_test {
                 None).unwrap();
 
             let texture = support::build_renderable_texture(&display);
-            texture.as_surface().clear(Some($source), None, None);
+            texture.as_surface().clear(None, Some($source), None, None);
             texture.as_surface().draw(&vb, &ib, &program, &uniform!{ color: $dest },
                                       &params).unwrap();
 
@@ -70,3 +70,150 @@ blending_test!(one_plus_one, glium::BlendingFunction::Addition {
                    destination: glium::LinearBlendingFactor::One,
                },
                (0.0, 1.0, 1.0, 0.0), (1.0, 0.0, 0.0, 1.0), (1.0, 1.0, 1.0, 1.0));
+
+blending_test!(source_alpha, glium::BlendingFunction::Addition {
+                   source: glium::LinearBlendingFactor::SourceAlpha,
+                   destination: glium::LinearBlendingFactor::OneMinusSourceAlpha,
+               },
+               (0.0, 0.0, 1.0, 1.0), (1.0, 0.0, 0.0, 0.0), (0.0, 0.0, 1.0, 1.0));
+
+#[test]
+fn no_blending_after_blending() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform vec4 color;
+
+            void main() {
+                gl_FragColor = color;
+            }
+        ",
+        None).unwrap();
+
+    let blending = glium::DrawParameters {
+        blending_function: Some(glium::BlendingFunction::Max),
+        .. std::default::Default::default()
+    };
+
+    let no_blending = glium::DrawParameters {
+        blending_function: None,
+        .. std::default::Default::default()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear(None, Some((0.0, 1.0, 0.0, 1.0)), None, None);
+    texture.as_surface().draw(&vb, &ib, &program, &uniform!{ color: (1.0, 0.0, 0.0, 1.0f32) },
+                              &blending).unwrap();
+
+    // `None` must disable the blending that was enabled by the previous draw
+    texture.as_surface().draw(&vb, &ib, &program, &uniform!{ color: (0.0, 0.0, 1.0, 1.0f32) },
+                              &no_blending).unwrap();
+
+    let data: Vec<Vec<(f32, f32, f32, f32)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0.0, 0.0, 1.0, 1.0));
+        }
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn advanced_multiply_blending() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        blending_function: Some(glium::BlendingFunction::Advanced(
+                                        glium::AdvancedBlendingEquation::Multiply)),
+        .. std::default::Default::default()
+    };
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 310 es
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 310 es
+            #extension GL_KHR_blend_equation_advanced : require
+
+            layout(blend_support_multiply) out;
+            layout(location = 0) out mediump vec4 color;
+
+            void main() {
+                color = vec4(0.5, 1.0, 0.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear(None, Some((1.0, 0.5, 1.0, 1.0)), None, None);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &uniform!{}, &params) {
+        Ok(_) => (),
+        Err(glium::DrawError::AdvancedBlendingNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let data: Vec<Vec<(f32, f32, f32, f32)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0.5, 0.5, 0.0, 1.0));
+        }
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn smooth_with_alpha_blending() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let params = glium::DrawParameters {
+        blending_function: Some(glium::BlendingFunction::Addition {
+            source: glium::LinearBlendingFactor::SourceAlpha,
+            destination: glium::LinearBlendingFactor::OneMinusSourceAlpha,
+        }),
+        smooth: Some(glium::Smooth::Nicest),
+        .. std::default::Default::default()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Ok(_) => (),
+        Err(glium::DrawError::SmoothingNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    display.assert_no_error();
+}
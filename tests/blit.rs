@@ -36,7 +36,7 @@ fn blit_texture_to_window() {
     target.clear_color(0.0, 0.0, 0.0, 0.0);
 
     texture.as_surface().blit_color(&src_rect, &target, &dest_rect,
-                                    glium::uniforms::MagnifySamplerFilter::Nearest);
+                                    glium::uniforms::MagnifySamplerFilter::Nearest).unwrap();
 
     target.finish();
 
@@ -65,3 +65,165 @@ fn blit_texture_to_window() {
     
     display.assert_no_error();
 }
+
+#[test]
+fn blit_texture_to_texture_flipped() {
+    let display = support::build_display();
+
+    let src_texture = glium::Texture2d::new(&display, vec![
+        vec![(255, 0, 0), (255, 0, 0)],
+        vec![(0, 255, 0), (0, 255, 0)],
+    ]);
+
+    let dest_texture = glium::Texture2d::empty(&display, 2, 2);
+    dest_texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let src_rect = Rect {
+        left: 0,
+        bottom: 0,
+        width: 2,
+        height: 2,
+    };
+
+    let dest_rect = BlitTarget {
+        left: 0,
+        bottom: 2,
+        width: 2,
+        height: -2,
+    };
+
+    src_texture.as_surface().blit_color(&src_rect, &dest_texture.as_surface(), &dest_rect,
+                                        glium::uniforms::MagnifySamplerFilter::Nearest).unwrap();
+
+    let data: Vec<Vec<(f32, f32, f32)>> = dest_texture.read();
+
+    assert_eq!(data[0][0], (0.0, 1.0, 0.0));
+    assert_eq!(data[0][1], (0.0, 1.0, 0.0));
+    assert_eq!(data[1][0], (1.0, 0.0, 0.0));
+    assert_eq!(data[1][1], (1.0, 0.0, 0.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn blit_frame_to_texture() {
+    // ignoring test on travis
+    // TODO: find out why they are failing
+    if ::std::env::var("TRAVIS").is_ok() {
+        return;
+    }
+
+    let display = support::build_display();
+
+    let texture = glium::Texture2d::new_empty(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              2, 2);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let mut target = display.draw();
+    target.clear_color(1.0, 0.0, 0.0, 1.0);
+
+    let src_rect = Rect { left: 0, bottom: 0, width: 2, height: 2 };
+    let dest_rect = BlitTarget { left: 0, bottom: 0, width: 2, height: 2 };
+
+    target.blit_to(&src_rect, &texture.as_surface(), &dest_rect,
+                   glium::uniforms::MagnifySamplerFilter::Nearest).unwrap();
+    target.finish();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn blit_simple_framebuffer_to_multioutput_framebuffer() {
+    let display = support::build_display();
+
+    let src_texture = glium::Texture2d::new(&display, vec![
+        vec![(255, 0, 0), (255, 0, 0)],
+        vec![(255, 0, 0), (255, 0, 0)],
+    ]);
+    let src = glium::framebuffer::SimpleFrameBuffer::new(&display, &src_texture);
+
+    let dest_texture = glium::Texture2d::empty(&display, 2, 2);
+    dest_texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    let dest = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                                                               &[("color", &dest_texture)]);
+
+    let rect = Rect { left: 0, bottom: 0, width: 2, height: 2 };
+    let target = BlitTarget { left: 0, bottom: 0, width: 2, height: 2 };
+
+    src.blit_color(&rect, &dest, &target,
+                   glium::uniforms::MagnifySamplerFilter::Nearest).unwrap();
+
+    let data: Vec<Vec<(f32, f32, f32)>> = dest_texture.read();
+    assert_eq!(data[0][0], (1.0, 0.0, 0.0));
+    assert_eq!(data[1][1], (1.0, 0.0, 0.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn blit_multisample_resolve() {
+    let display = support::build_display();
+
+    let multisampled = match glium::texture::Texture2dMultisample::empty_if_supported(&display,
+                                                                                      4, 4, 4)
+    {
+        Some(t) => t,
+        None => return
+    };
+
+    // left half red, right half blue
+    {
+        let mut surface = multisampled.as_surface();
+        surface.clear_color(0.0, 0.0, 1.0, 1.0);
+        surface.clear(Some(&Rect { left: 0, bottom: 0, width: 2, height: 4 }),
+                      Some((1.0, 0.0, 0.0, 1.0)), None, None);
+    }
+
+    let resolved = glium::Texture2d::empty(&display, 4, 4);
+    let target = BlitTarget { left: 0, bottom: 0, width: 4, height: 4 };
+    multisampled.as_surface().blit_whole_color_to(&resolved.as_surface(), &target,
+                                                  glium::uniforms::MagnifySamplerFilter::Nearest)
+                             .unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = resolved.read();
+    for row in data.iter() {
+        assert_eq!(row[0], (255, 0, 0, 255));
+        assert_eq!(row[1], (255, 0, 0, 255));
+        assert_eq!(row[2], (0, 0, 255, 255));
+        assert_eq!(row[3], (0, 0, 255, 255));
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn blit_multisample_resolve_dimensions_mismatch() {
+    let display = support::build_display();
+
+    let multisampled = match glium::texture::Texture2dMultisample::empty_if_supported(&display,
+                                                                                      4, 4, 4)
+    {
+        Some(t) => t,
+        None => return
+    };
+
+    let resolved = glium::Texture2d::empty(&display, 8, 8);
+    let target = BlitTarget { left: 0, bottom: 0, width: 8, height: 8 };
+
+    match multisampled.as_surface().blit_whole_color_to(&resolved.as_surface(), &target,
+                                                    glium::uniforms::MagnifySamplerFilter::Linear)
+    {
+        Err(glium::BlitError::MultisampleDimensionsMismatch) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error();
+}
@@ -16,6 +16,35 @@ fn get_opengl_version() {
     assert!(version.1 >= 1);
 }
 
+#[test]
+fn capability_limits() {
+    let display = support::build_display();
+
+    // minimum values guaranteed by OpenGL ES 2.0
+    assert!(display.get_max_texture_size() >= 64);
+    assert!(display.get_max_texture_image_units() >= 8);
+    assert!(display.get_max_combined_texture_image_units() >= 8);
+    assert!(display.get_max_vertex_attributes() >= 8);
+    assert!(display.get_max_color_attachments() >= 1);
+
+    display.assert_no_error();
+}
+
+#[test]
+fn extensions_query() {
+    let display = support::build_display();
+
+    for name in display.get_extensions_names().iter() {
+        assert!(display.is_extension_supported(name));
+    }
+
+    assert!(!display.is_extension_supported("GL_GLIUM_this_extension_does_not_exist"));
+    assert_eq!(display.get_extensions().gl_arb_buffer_storage,
+               display.is_extension_supported("GL_ARB_buffer_storage"));
+
+    display.assert_no_error();
+}
+
 #[test]
 fn clear_color() {
     let display = support::build_display();
@@ -65,6 +94,45 @@ fn viewport_too_large() {
     display.assert_no_error();
 }
 
+#[test]
+fn line_width_clamped() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        line_width: Some(1000000.0),
+        .. Default::default()
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &params).unwrap();
+
+    display.assert_no_error();
+}
+
+#[test]
+fn program_point_size() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        program_point_size: true,
+        .. Default::default()
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::ProgramPointSizeNotSupported) => return,
+        a => a.unwrap()
+    };
+
+    display.assert_no_error();
+}
+
 #[test]
 fn timestamp_query() {
     let display = support::build_display();
@@ -180,6 +248,36 @@ fn scissor_followed_by_clear() {
     display.assert_no_error();
 }
 
+#[test]
+fn clear_with_rect() {
+    let display = support::build_display();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let rect = glium::Rect {
+        left: 2,
+        bottom: 2,
+        width: 2,
+        height: 2,
+    };
+
+    texture.as_surface().clear(Some(&rect), Some((1.0, 0.0, 1.0, 1.0)), None, None);
+
+    let data: Vec<Vec<(f32, f32, f32, f32)>> = texture.read();
+    for (y, row) in data.iter().enumerate() {
+        for (x, pixel) in row.iter().enumerate() {
+            if x >= 2 && x < 4 && y >= 2 && y < 4 {
+                assert_eq!(pixel, &(1.0, 0.0, 1.0, 1.0));
+            } else {
+                assert_eq!(pixel, &(0.0, 0.0, 0.0, 0.0));
+            }
+        }
+    }
+
+    display.assert_no_error();
+}
+
 #[test]
 fn viewport_followed_by_clear() {
     let display = support::build_display();
@@ -345,3 +443,497 @@ fn multiple_displays() {
     display1.assert_no_error();
     display2.assert_no_error();
 }
+
+#[test]
+fn get_error() {
+    let display = support::build_display();
+    assert_eq!(display.get_error(), None);
+}
+
+#[test]
+fn read_back_buffer() {
+    // headless contexts don't have a back buffer
+    if support::is_headless() {
+        return;
+    }
+
+    let display = support::build_display();
+
+    let mut target = display.draw();
+    target.clear_color(1.0, 0.0, 0.0, 1.0);
+
+    let data: Vec<Vec<(u8, u8, u8)>> = display.read_buffer(glium::ReadBuffer::Back);
+    assert_eq!(data[0][0], (255, 0, 0));
+
+    target.finish();
+    display.assert_no_error();
+}
+
+#[test]
+fn time_elapsed_query() {
+    let display = support::build_display();
+
+    let mut query = match glium::debug::TimeElapsedQuery::new_if_supported(&display) {
+        Some(q) => q,
+        None => return
+    };
+
+    assert_eq!(query.get(), None);
+
+    let texture = support::build_renderable_texture(&display);
+
+    query.begin();
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    query.end();
+
+    let elapsed = query.get_blocking();
+    assert_eq!(query.get(), Some(elapsed));
+
+    display.assert_no_error();
+}
+
+#[test]
+#[should_panic(expected = "A TimeElapsedQuery is already active")]
+fn time_elapsed_query_nested() {
+    let display = support::build_display();
+
+    let mut query1 = glium::debug::TimeElapsedQuery::new_if_supported(&display).unwrap();
+    let mut query2 = glium::debug::TimeElapsedQuery::new_if_supported(&display).unwrap();
+
+    query1.begin();
+    query2.begin();
+}
+
+#[test]
+fn samples_passed_query() {
+    let display = support::build_display();
+
+    let mut query = match glium::debug::SamplesPassedQuery::new_if_supported(&display) {
+        Some(q) => q,
+        None => return
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+
+    query.begin();
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+    query.end();
+
+    let (width, height) = texture.as_surface().get_dimensions();
+    assert_eq!(query.get_blocking(), width * height);
+
+    display.assert_no_error();
+}
+
+#[test]
+fn conditional_rendering() {
+    let display = support::build_display();
+
+    let (mut visible, mut hidden) = match (glium::debug::SamplesPassedQuery::new_if_supported(&display),
+                                           glium::debug::SamplesPassedQuery::new_if_supported(&display))
+    {
+        (Some(q1), Some(q2)) => (q1, q2),
+        _ => return
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+
+    visible.begin();
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+    visible.end();
+
+    // nothing is drawn while `hidden` is active
+    hidden.begin();
+    hidden.end();
+
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let params = glium::DrawParameters {
+        condition: Some(&hidden),
+        .. Default::default()
+    };
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::ConditionalRenderingNotSupported) => return,
+        a => a.unwrap()
+    };
+
+    let data: Vec<Vec<(u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 0, 0));
+
+    let params = glium::DrawParameters {
+        condition: Some(&visible),
+        .. Default::default()
+    };
+
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn conditional_rendering_invalid_query() {
+    let display = support::build_display();
+
+    let mut query = match glium::debug::SamplesPassedQuery::new_if_supported(&display) {
+        Some(q) => q,
+        None => return
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+
+    {
+        let params = glium::DrawParameters {
+            condition: Some(&query),
+            .. Default::default()
+        };
+
+        match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                        &params)
+        {
+            Err(glium::DrawError::ConditionalRenderingNotSupported) => return,
+            Err(glium::DrawError::ConditionQueryNeverBegun) => (),
+            e => panic!("{:?}", e)
+        };
+    }
+
+    query.begin();
+
+    {
+        let params = glium::DrawParameters {
+            condition: Some(&query),
+            .. Default::default()
+        };
+
+        match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                        &params)
+        {
+            Err(glium::DrawError::ConditionQueryActive) => (),
+            e => panic!("{:?}", e)
+        };
+    }
+
+    query.end();
+
+    display.assert_no_error();
+}
+
+#[test]
+fn default_framebuffer_srgb() {
+    let display = support::build_display();
+    display.is_default_framebuffer_srgb();
+    display.assert_no_error();
+}
+
+#[test]
+fn srgb_disabled_linear_target() {
+    let display = support::build_display();
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let params = glium::DrawParameters {
+        srgb: false,
+        .. Default::default()
+    };
+
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &params).unwrap();
+
+    // the conversion never applies to attachments that are not in sRGB
+    let data: Vec<Vec<(u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn color_mask() {
+    let display = support::build_display();
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 1.0, 1.0, 1.0);
+
+    let params = glium::DrawParameters {
+        color_mask: (false, true, true, true),
+        .. Default::default()
+    };
+
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0, 0, 0));
+        }
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn color_mask_partial() {
+    let display = support::build_display();
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 1.0, 1.0, 1.0);
+
+    let params = glium::DrawParameters {
+        color_mask: (true, false, false, false),
+        .. Default::default()
+    };
+
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 255, 255));
+        }
+    }
+
+    // clearing ignores the mask of the previous draw
+    texture.as_surface().clear_color(0.0, 0.0, 1.0, 1.0);
+
+    let data: Vec<Vec<(u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 0, 255));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn color_mask_clear_color_buffer() {
+    let display = support::build_display();
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture);
+    framebuffer.clear_color(0.0, 1.0, 1.0, 1.0);
+
+    let params = glium::DrawParameters {
+        color_mask: (true, false, false, false),
+        .. Default::default()
+    };
+
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    // clearing a single attachment ignores the mask of the previous draw as well
+    framebuffer.clear_color_buffer(0, (0.0, 0.0, 1.0, 1.0));
+
+    let data: Vec<Vec<(u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 0, 255));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn depth_clamp() {
+    let display = support::build_display();
+
+    let (vb, ib, _) = support::build_fullscreen_red_pipeline(&display);
+
+    // all the vertices are beyond the far plane
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 2.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 0, 0));
+
+    let params = glium::DrawParameters {
+        depth_clamp: true,
+        .. Default::default()
+    };
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::DepthClampNotSupported) => return,
+        a => a.unwrap()
+    };
+
+    let data: Vec<Vec<(u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn polygon_offset() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform vec4 color;
+
+            void main() {
+                gl_FragColor = color;
+            }
+        ",
+        None).unwrap();
+
+    let texture = glium::Texture2d::new_empty(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              16, 16);
+    let depth = glium::render_buffer::DepthRenderBuffer::new(&display,
+                                                glium::texture::DepthFormat::I24, 16, 16);
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
+                                                                                  &texture, &depth);
+    framebuffer.clear_color(0.0, 0.0, 0.0, 1.0);
+    framebuffer.clear_depth(1.0);
+
+    let params = glium::DrawParameters {
+        depth_test: glium::DepthTest::IfLess,
+        depth_write: true,
+        .. Default::default()
+    };
+
+    framebuffer.draw(&vb, &ib, &program, &uniform!{ color: (1.0f32, 0.0f32, 0.0f32, 1.0f32) },
+                     &params).unwrap();
+
+    // coplanar with the first draw, so it only passes the depth test thanks to the offset
+    let params = glium::DrawParameters {
+        polygon_offset: glium::PolygonOffset {
+            factor: -1.0,
+            units: -1.0,
+            fill: true,
+            .. Default::default()
+        },
+        .. params
+    };
+
+    framebuffer.draw(&vb, &ib, &program, &uniform!{ color: (0.0f32, 1.0f32, 0.0f32, 1.0f32) },
+                     &params).unwrap();
+
+    let read_back: Vec<Vec<(f32, f32, f32, f32)>> = texture.read();
+    assert_eq!(read_back[0][0], (0.0, 1.0, 0.0, 1.0));
+    assert_eq!(read_back[15][15], (0.0, 1.0, 0.0, 1.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn alpha_to_coverage_not_multisampled() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 0.0, 0.0, 0.0);
+            }
+        ",
+        None).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let params = glium::DrawParameters {
+        alpha_to_coverage: true,
+        .. Default::default()
+    };
+
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &params).unwrap();
+
+    // the target is not multisampled, so the alpha of 0.0 doesn't discard anything
+    let data: Vec<Vec<(u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn flush_and_synchronize() {
+    let display = support::build_display();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(1.0, 0.0, 0.0, 1.0);
+
+    display.flush();
+    display.synchronize();
+
+    display.assert_no_error();
+}
+
+#[test]
+fn sync_fence_wait_timeout() {
+    let display = support::build_display();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(1.0, 0.0, 0.0, 1.0);
+
+    let fence = match glium::SyncFence::new_if_supported(&display) {
+        Some(f) => f,
+        None => return
+    };
+
+    display.synchronize();
+
+    // all the commands have finished, so the fence is signaled without waiting
+    assert!(fence.wait_timeout(0));
+    fence.wait();
+
+    display.assert_no_error();
+}
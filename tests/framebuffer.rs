@@ -54,6 +54,54 @@ fn no_depth_buffer_depth_write() {
     display.assert_no_error();
 }
 
+#[test]
+fn no_stencil_buffer_stencil_test() {
+    let display = support::build_display();
+    let (vertex_buffer, index_buffer, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = glium::texture::Texture2d::new_empty(&display,
+                            glium::texture::UncompressedFloatFormat::U8U8U8U8, 128, 128);
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture);
+
+    let parameters = glium::DrawParameters {
+        stencil_test_clockwise: glium::StencilTest::IfEqual { mask: 0xff },
+        .. std::default::Default::default()
+    };
+
+    match framebuffer.draw(&vertex_buffer, &index_buffer, &program,
+                           &glium::uniforms::EmptyUniforms, &parameters)
+    {
+        Err(glium::DrawError::NoStencilBuffer) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error();
+}
+
+#[test]
+fn no_stencil_buffer_stencil_operation() {
+    let display = support::build_display();
+    let (vertex_buffer, index_buffer, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = glium::texture::Texture2d::new_empty(&display,
+                            glium::texture::UncompressedFloatFormat::U8U8U8U8, 128, 128);
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture);
+
+    let parameters = glium::DrawParameters {
+        stencil_depth_pass_operation_counter_clockwise: glium::StencilOperation::Replace,
+        .. std::default::Default::default()
+    };
+
+    match framebuffer.draw(&vertex_buffer, &index_buffer, &program,
+                           &glium::uniforms::EmptyUniforms, &parameters)
+    {
+        Err(glium::DrawError::NoStencilBuffer) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error();
+}
+
 #[test]
 fn simple_dimensions() {
     let display = support::build_display();
@@ -179,7 +227,7 @@ fn multioutput() {
         ",
         None)
     {
-        Err(glium::CompilationError(_)) => return,
+        Err(glium::CompilationError(..)) => return,
         Ok(p) => p,
         e => e.unwrap()
     };
@@ -221,3 +269,1037 @@ fn multioutput() {
 
     display.assert_no_error();
 }
+
+#[test]
+fn multioutput_clear_single_buffer() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            out vec4 color1;
+            out vec4 color2;
+
+            void main() {
+                color1 = vec4(0.0, 0.0, 1.0, 1.0);
+                color2 = vec4(0.0, 0.0, 1.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(..)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let color1 = glium::Texture2d::new_empty(&display,
+                                             glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                             128, 128);
+    let color2 = glium::Texture2d::new_empty(&display,
+                                             glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                             128, 128);
+
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                                             &[("color1", &color1), ("color2", &color2)]);
+
+    // clearing everything, then only the second attachment
+    framebuffer.clear_color(1.0, 0.0, 0.0, 1.0);
+    framebuffer.clear_color_buffer(1, (0.0, 1.0, 0.0, 1.0));
+
+    let read_back1: Vec<Vec<(f32, f32, f32, f32)>> = color1.read();
+    assert_eq!(read_back1[0][0], (1.0, 0.0, 0.0, 1.0));
+    let read_back2: Vec<Vec<(f32, f32, f32, f32)>> = color2.read();
+    assert_eq!(read_back2[0][0], (0.0, 1.0, 0.0, 1.0));
+
+    // drawing, then clearing the second attachment in the middle of the frame
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &std::default::Default::default()).unwrap();
+    framebuffer.clear_color_buffer(1, (0.0, 1.0, 0.0, 1.0));
+
+    let read_back1: Vec<Vec<(f32, f32, f32, f32)>> = color1.read();
+    for row in read_back1.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0.0, 0.0, 1.0, 1.0));
+        }
+    }
+
+    let read_back2: Vec<Vec<(f32, f32, f32, f32)>> = color2.read();
+    for row in read_back2.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0.0, 1.0, 0.0, 1.0));
+        }
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn simple_clear_color_buffer() {
+    let display = support::build_display();
+
+    let texture = glium::Texture2d::new_empty(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              16, 16);
+    let depth = glium::render_buffer::DepthRenderBuffer::new(&display,
+                                                glium::texture::DepthFormat::I24, 16, 16);
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
+                                                                                  &texture, &depth);
+    framebuffer.clear_color(1.0, 0.0, 0.0, 1.0);
+    framebuffer.clear_depth(1.0);
+    framebuffer.clear_color_buffer(0, (0.0, 1.0, 0.0, 1.0));
+
+    let read_back: Vec<Vec<(f32, f32, f32, f32)>> = texture.read();
+    assert_eq!(read_back[0][0], (0.0, 1.0, 0.0, 1.0));
+    assert_eq!(read_back[15][15], (0.0, 1.0, 0.0, 1.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+#[should_panic(expected = "The surface only has one color attachment")]
+fn simple_clear_color_buffer_out_of_range() {
+    let display = support::build_display();
+
+    let texture = glium::Texture2d::new_empty(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              16, 16);
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture);
+    framebuffer.clear_color_buffer(1, (0.0, 1.0, 0.0, 1.0));
+}
+
+#[test]
+fn simple_depth_renderbuffer_bits() {
+    let display = support::build_display();
+
+    let texture = glium::Texture2d::new_empty(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              16, 16);
+    let depth = glium::render_buffer::DepthRenderBuffer::new(&display,
+                                                glium::texture::DepthFormat::I24, 16, 16);
+
+    let framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
+                                                                              &texture, &depth);
+    assert!(framebuffer.get_depth_buffer_bits().unwrap() >= 24);
+    assert_eq!(framebuffer.get_stencil_buffer_bits(), None);
+
+    display.assert_no_error();
+}
+
+#[test]
+#[should_panic(expected = "The depth attachment must have the same dimensions")]
+fn simple_depth_renderbuffer_wrong_dimensions() {
+    let display = support::build_display();
+
+    let texture = glium::Texture2d::new_empty(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              16, 16);
+    let depth = glium::render_buffer::DepthRenderBuffer::new(&display,
+                                                glium::texture::DepthFormat::I24, 32, 32);
+
+    glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display, &texture, &depth);
+}
+
+#[test]
+fn depth_write_with_overwrite() {
+    use std::iter;
+
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+            uniform float depth;
+
+            void main() {
+                gl_Position = vec4(position, depth, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform vec4 color;
+
+            void main() {
+                gl_FragColor = color;
+            }
+        ",
+        None).unwrap();
+
+    let color = glium::Texture2d::new_empty(&display,
+                                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                            128, 128);
+
+    // depth texture with a value of 0.9 everywhere
+    let depth_data = iter::repeat(iter::repeat(0.9f32).take(128).collect::<Vec<_>>())
+                                  .take(128).collect::<Vec<_>>();
+    let depth = match glium::texture::DepthTexture2d::new_if_supported(&display, depth_data) {
+        None => return,
+        Some(t) => t
+    };
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
+                                                                                   &color, &depth);
+    framebuffer.clear_color(0.0, 0.0, 0.0, 1.0);
+
+    // priming the depth buffer with 0.25 (a Z of -0.5) and a test that always passes
+    let params = glium::DrawParameters {
+        depth_test: glium::DepthTest::Overwrite,
+        depth_write: true,
+        .. std::default::Default::default()
+    };
+
+    let uniforms = uniform!{ depth: -0.5f32, color: (1.0f32, 0.0f32, 0.0f32, 1.0f32) };
+    framebuffer.draw(&vb, &ib, &program, &uniforms, &params).unwrap();
+
+    // drawing at 0.5 (a Z of 0.0) must fail if the depth has been written
+    let params = glium::DrawParameters {
+        depth_test: glium::DepthTest::IfLess,
+        .. std::default::Default::default()
+    };
+
+    let uniforms = uniform!{ depth: 0.0f32, color: (0.0f32, 1.0f32, 0.0f32, 1.0f32) };
+    framebuffer.draw(&vb, &ib, &program, &uniforms, &params).unwrap();
+
+    let read_back: Vec<Vec<(f32, f32, f32, f32)>> = color.read();
+    assert_eq!(read_back[0][0], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(read_back[127][127], (1.0, 0.0, 0.0, 1.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn framebuffer_then_frame() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform vec4 color;
+
+            void main() {
+                gl_FragColor = color;
+            }
+        ",
+        None).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+
+    let mut target = display.draw();
+    target.clear_color(0.0, 0.0, 0.0, 1.0);
+
+    // drawing red on the texture while the frame is alive
+    {
+        let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture);
+        framebuffer.clear_color(0.0, 0.0, 0.0, 1.0);
+        framebuffer.draw(&vb, &ib, &program, &uniform!{ color: (1.0f32, 0.0f32, 0.0f32, 1.0f32) },
+                         &std::default::Default::default()).unwrap();
+    }
+
+    // drawing blue on the frame
+    target.draw(&vb, &ib, &program, &uniform!{ color: (0.0f32, 0.0f32, 1.0f32, 1.0f32) },
+                &std::default::Default::default()).unwrap();
+    target.finish();
+
+    let read_back: Vec<Vec<(f32, f32, f32, f32)>> = texture.read();
+    for row in read_back.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(1.0, 0.0, 0.0, 1.0));
+        }
+    }
+
+    let data: Vec<Vec<(u8, u8, u8)>> = display.read_front_buffer();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0, 0, 255));
+        }
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn sample_mask_reduces_coverage() {
+    let display = support::build_display();
+    let (vertex_buffer, index_buffer, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let multisampled = match glium::texture::Texture2dMultisample::empty_if_supported(&display,
+                                                                                      4, 4, 8)
+    {
+        Some(t) => t,
+        None => return
+    };
+
+    let parameters = glium::DrawParameters {
+        sample_mask: Some(0x0f),
+        .. Default::default()
+    };
+
+    multisampled.as_surface().clear_color(0.0, 0.0, 0.0, 1.0);
+    match multisampled.as_surface().draw(&vertex_buffer, &index_buffer, &program,
+                                         &glium::uniforms::EmptyUniforms, &parameters)
+    {
+        Err(glium::DrawError::SampleMaskNotSupported) => return,
+        r => r.unwrap()
+    };
+
+    let resolved = glium::texture::Texture2d::new_empty(&display,
+                            glium::texture::UncompressedFloatFormat::U8U8U8U8, 4, 4);
+    let rect = glium::Rect { left: 0, bottom: 0, width: 4, height: 4 };
+    let target = glium::BlitTarget { left: 0, bottom: 0, width: 4, height: 4 };
+    multisampled.as_surface().blit_color(&rect, &resolved.as_surface(), &target,
+                                         glium::uniforms::MagnifySamplerFilter::Nearest).unwrap();
+
+    let read_back: Vec<Vec<(f32, f32, f32, f32)>> = resolved.read();
+    for row in read_back.iter() {
+        for pixel in row.iter() {
+            assert!(pixel.0 > 0.4 && pixel.0 < 0.6);
+        }
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn clear_float_depth_buffer_precise() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+            uniform float z;
+
+            void main() {
+                gl_Position = vec4(position, z, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    let color = glium::Texture2d::new_empty(&display,
+                                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                            4, 4);
+    let depth = match glium::texture::DepthTexture2d::empty_with_format_if_supported(&display,
+                                                glium::texture::DepthFormat::F32, false, 4, 4)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
+                                                                                   &color, &depth);
+    let params = glium::DrawParameters {
+        depth_test: glium::DepthTest::IfLess,
+        .. std::default::Default::default()
+    };
+
+    // a window depth of `0.3001` is just behind the cleared value and must be rejected
+    framebuffer.clear(None, Some((0.0, 0.0, 0.0, 1.0)), Some(0.3), None);
+    framebuffer.draw(&vb, &ib, &program, &uniform!{ z: -0.3998f32 }, &params).unwrap();
+
+    let read_back: Vec<Vec<(f32, f32, f32, f32)>> = color.read();
+    assert_eq!(read_back[0][0], (0.0, 0.0, 0.0, 1.0));
+
+    // a window depth of `0.2999` is just in front of the cleared value and must pass
+    framebuffer.draw(&vb, &ib, &program, &uniform!{ z: -0.4002f32 }, &params).unwrap();
+
+    let read_back: Vec<Vec<(f32, f32, f32, f32)>> = color.read();
+    assert_eq!(read_back[0][0], (1.0, 1.0, 1.0, 1.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn simple_set_color_attachment() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture1 = glium::Texture2d::new_empty(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               128, 128);
+    let texture2 = glium::Texture2d::new_empty(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               64, 32);
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture1);
+    framebuffer.clear_color(0.0, 0.0, 1.0, 1.0);
+
+    framebuffer.set_color_attachment(&texture2).unwrap();
+    assert_eq!(framebuffer.get_dimensions(), (64, 32));
+    framebuffer.clear_color(0.0, 0.0, 0.0, 1.0);
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &Default::default()).unwrap();
+
+    let read_back: Vec<Vec<(f32, f32, f32, f32)>> = texture1.read();
+    assert_eq!(read_back[0][0], (0.0, 0.0, 1.0, 1.0));
+    assert_eq!(read_back[127][127], (0.0, 0.0, 1.0, 1.0));
+
+    let read_back: Vec<Vec<(f32, f32, f32, f32)>> = texture2.read();
+    assert_eq!(read_back[0][0], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(read_back[31][63], (1.0, 0.0, 0.0, 1.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn simple_set_color_attachment_dimensions_mismatch() {
+    let display = support::build_display();
+
+    let texture1 = glium::Texture2d::new_empty(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               128, 128);
+    let texture2 = glium::Texture2d::new_empty(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               64, 32);
+    let depth = glium::render_buffer::DepthRenderBuffer::new(&display,
+                                                glium::texture::DepthFormat::I24, 128, 128);
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
+                                                                                   &texture1,
+                                                                                   &depth);
+
+    assert_eq!(framebuffer.set_color_attachment(&texture2),
+               Err(glium::framebuffer::ValidationError::DimensionsMismatch));
+    assert_eq!(framebuffer.get_dimensions(), (128, 128));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn depth_only_shadow_map() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let shadow_map = match glium::texture::DepthTexture2d::empty_with_format_if_supported(
+                                &display, glium::texture::DepthFormat::I24, false, 64, 64)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    // first pass: rendering the depth of an occluder that covers the left half of the map
+    let occluder = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position.x * 0.5 - 0.5, position.y, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            void main() {
+            }
+        ",
+        None).unwrap();
+
+    let mut light_pass = glium::framebuffer::SimpleFrameBuffer::depth_only(&display, &shadow_map);
+    assert_eq!(light_pass.get_dimensions(), (64, 64));
+    light_pass.clear_depth(1.0);
+    light_pass.draw(&vb, &ib, &occluder, &glium::uniforms::EmptyUniforms,
+                    &glium::DrawParameters {
+                        depth_test: glium::DepthTest::IfLess,
+                        depth_write: true,
+                        .. Default::default()
+                    }).unwrap();
+
+    // second pass: every fragment at a depth of 0.75 is in the shadow of the occluder, whose
+    // depth is 0.5
+    let receiver = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+            varying vec2 v_tex_coords;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+                v_tex_coords = position * 0.5 + 0.5;
+            }
+        ",
+        "
+            #version 110
+
+            uniform sampler2DShadow shadow_map;
+            varying vec2 v_tex_coords;
+
+            void main() {
+                float lit = shadow2D(shadow_map, vec3(v_tex_coords, 0.75)).r;
+                gl_FragColor = vec4(lit, 0.0, 0.0, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    let sampler = glium::uniforms::Sampler::new(&shadow_map)
+                        .minify_filter(glium::uniforms::MinifySamplerFilter::Nearest)
+                        .magnify_filter(glium::uniforms::MagnifySamplerFilter::Nearest)
+                        .depth_texture_comparison(
+                            Some(glium::uniforms::DepthTextureComparison::IfLessOrEqual));
+
+    let output = glium::Texture2d::new_empty(&display,
+                                             glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                             64, 64);
+    output.as_surface().clear_color(0.0, 0.0, 1.0, 1.0);
+
+    match output.as_surface().draw(&vb, &ib, &receiver, &uniform!{ shadow_map: sampler },
+                                   &Default::default())
+    {
+        Err(glium::DrawError::SamplersNotSupported) => return,
+        r => r.unwrap()
+    };
+
+    let read_back: Vec<Vec<(f32, f32, f32, f32)>> = output.read();
+    assert_eq!(read_back[32][8], (0.0, 0.0, 0.0, 1.0));
+    assert_eq!(read_back[32][56], (1.0, 0.0, 0.0, 1.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn simple_depth_stencil_texture() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let color = glium::Texture2d::new_empty(&display,
+                                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                            64, 64);
+    let depth_stencil = match glium::texture::DepthStencilTexture2d::empty_if_supported(&display,
+                                                                                        64, 64)
+    {
+        Some(t) => t,
+        None => return
+    };
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_stencil_buffer(
+                                                            &display, &color, &depth_stencil);
+    assert!(framebuffer.has_depth_buffer());
+    assert!(framebuffer.has_stencil_buffer());
+
+    framebuffer.clear_all((0.0, 0.0, 0.0, 1.0), 1.0, 0);
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &glium::DrawParameters {
+                         depth_test: glium::DepthTest::IfLess,
+                         depth_write: true,
+                         .. Default::default()
+                     }).unwrap();
+
+    let read_back: Vec<Vec<(f32, f32, f32, f32)>> = color.read();
+    assert_eq!(read_back[32][32], (1.0, 0.0, 0.0, 1.0));
+
+    let depth = match depth_stencil.read_depth_stencil() {
+        Ok(d) => d,
+        Err(_) => return
+    };
+
+    assert!((depth[32][32].0 - 0.5).abs() < 0.001);
+    assert_eq!(depth[32][32].1, 0);
+
+    display.assert_no_error();
+}
+
+#[test]
+fn stencil_separate_clockwise_counter_clockwise() {
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, position);
+
+    let display = support::build_display();
+    let (cw_vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    // same quad as `cw_vb`, but with the opposite winding
+    let ccw_vb = glium::VertexBuffer::new(&display, vec![
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [-1.0, -1.0] },
+        Vertex { position: [ 1.0,  1.0] }, Vertex { position: [ 1.0, -1.0] },
+    ]);
+
+    let color = glium::Texture2d::new_empty(&display,
+                                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                            64, 64);
+    let depth_stencil = match glium::texture::DepthStencilTexture2d::empty_if_supported(&display,
+                                                                                        64, 64)
+    {
+        Some(t) => t,
+        None => return
+    };
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_stencil_buffer(
+                                                            &display, &color, &depth_stencil);
+    framebuffer.clear_all((0.0, 0.0, 0.0, 1.0), 1.0, 0);
+
+    // the tests always pass, so only the operations enable the stencil test
+    let params = glium::DrawParameters {
+        stencil_depth_pass_operation_clockwise: glium::StencilOperation::Replace,
+        stencil_reference_value_clockwise: 0x21,
+        stencil_write_mask_clockwise: 0xf0,
+        stencil_depth_pass_operation_counter_clockwise: glium::StencilOperation::Replace,
+        stencil_reference_value_counter_clockwise: 0x12,
+        stencil_write_mask_counter_clockwise: 0x0f,
+        .. Default::default()
+    };
+
+    framebuffer.draw(&ccw_vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &params).unwrap();
+
+    let stencil = match depth_stencil.read_depth_stencil() {
+        Ok(d) => d,
+        Err(_) => return
+    };
+    assert_eq!(stencil[32][32].1, 0x02);
+
+    framebuffer.draw(&cw_vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &params).unwrap();
+
+    let stencil = depth_stencil.read_depth_stencil().unwrap();
+    assert_eq!(stencil[32][32].1, 0x22);
+
+    display.assert_no_error();
+}
+
+#[test]
+fn clear_stencil_buffer_ignores_write_mask() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let color = glium::Texture2d::new_empty(&display,
+                                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                            64, 64);
+    let depth_stencil = match glium::texture::DepthStencilTexture2d::empty_if_supported(&display,
+                                                                                        64, 64)
+    {
+        Some(t) => t,
+        None => return
+    };
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_stencil_buffer(
+                                                            &display, &color, &depth_stencil);
+    framebuffer.clear_all((0.0, 0.0, 0.0, 1.0), 1.0, 0);
+
+    // leaves a stencil write mask of 0 in the state
+    let params = glium::DrawParameters {
+        stencil_depth_pass_operation_clockwise: glium::StencilOperation::Replace,
+        stencil_write_mask_clockwise: 0x00,
+        stencil_depth_pass_operation_counter_clockwise: glium::StencilOperation::Replace,
+        stencil_write_mask_counter_clockwise: 0x00,
+        .. Default::default()
+    };
+
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    framebuffer.clear_stencil_buffer(0x42);
+    framebuffer.clear_depth_buffer(0.5);
+
+    let data = match depth_stencil.read_depth_stencil() {
+        Ok(d) => d,
+        Err(_) => return
+    };
+    assert!((data[32][32].0 - 0.5).abs() < 0.01);
+    assert_eq!(data[32][32].1, 0x42);
+
+    display.assert_no_error();
+}
+
+#[test]
+fn simple_stencil_buffer() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let color = glium::Texture2d::new_empty(&display,
+                                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                            64, 64);
+    let stencil = glium::render_buffer::StencilRenderBuffer::new(&display,
+                                                    glium::texture::StencilFormat::I8, 64, 64);
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_stencil_buffer(&display,
+                                                                          &color, &stencil);
+    assert!(!framebuffer.has_depth_buffer());
+    assert!(framebuffer.has_stencil_buffer());
+
+    framebuffer.clear_color_and_stencil((0.0, 0.0, 0.0, 1.0), 1);
+
+    let params = |reference| glium::DrawParameters {
+        stencil_test_clockwise: glium::StencilTest::IfEqual { mask: 0xff },
+        stencil_reference_value_clockwise: reference,
+        stencil_test_counter_clockwise: glium::StencilTest::IfEqual { mask: 0xff },
+        stencil_reference_value_counter_clockwise: reference,
+        .. Default::default()
+    };
+
+    // the stencil buffer contains 1, so this must be discarded
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &params(2)).unwrap();
+
+    let read_back: Vec<Vec<(f32, f32, f32, f32)>> = color.read();
+    assert_eq!(read_back[32][32], (0.0, 0.0, 0.0, 1.0));
+
+    // and this must pass
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &params(1)).unwrap();
+
+    let read_back: Vec<Vec<(f32, f32, f32, f32)>> = color.read();
+    assert_eq!(read_back[32][32], (1.0, 0.0, 0.0, 1.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn multioutput_stencil_buffer() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            out vec4 color1;
+            out vec4 color2;
+
+            void main() {
+                color1 = vec4(1.0, 0.0, 0.0, 1.0);
+                color2 = vec4(0.0, 1.0, 0.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(..)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let color1 = glium::Texture2d::new_empty(&display,
+                                             glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                             128, 128);
+    let color2 = glium::Texture2d::new_empty(&display,
+                                             glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                             128, 128);
+    let depth = glium::render_buffer::DepthRenderBuffer::new(&display,
+                                                glium::texture::DepthFormat::I24, 128, 128);
+    let stencil = glium::render_buffer::StencilRenderBuffer::new(&display,
+                                                glium::texture::StencilFormat::I8, 128, 128);
+
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::with_depth_and_stencil_buffer(
+                                &display, &[("color1", &color1), ("color2", &color2)],
+                                &depth, &stencil);
+    assert!(framebuffer.has_depth_buffer());
+    assert!(framebuffer.has_stencil_buffer());
+
+    framebuffer.clear_all((0.0, 0.0, 0.0, 1.0), 1.0, 1);
+
+    // the stencil buffer contains 1, so nothing must be drawn
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &glium::DrawParameters {
+                         stencil_test_clockwise: glium::StencilTest::IfEqual { mask: 0xff },
+                         stencil_reference_value_clockwise: 2,
+                         stencil_test_counter_clockwise: glium::StencilTest::IfEqual { mask: 0xff },
+                         stencil_reference_value_counter_clockwise: 2,
+                         .. Default::default()
+                     }).unwrap();
+
+    let read_back1: Vec<Vec<(f32, f32, f32, f32)>> = color1.read();
+    assert_eq!(read_back1[64][64], (0.0, 0.0, 0.0, 1.0));
+    let read_back2: Vec<Vec<(f32, f32, f32, f32)>> = color2.read();
+    assert_eq!(read_back2[64][64], (0.0, 0.0, 0.0, 1.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+#[should_panic(expected = "The stencil attachment must have the same dimensions")]
+fn multioutput_stencil_buffer_wrong_dimensions() {
+    let display = support::build_display();
+
+    let color = glium::Texture2d::new_empty(&display,
+                                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                            128, 128);
+    let depth = glium::render_buffer::DepthRenderBuffer::new(&display,
+                                                glium::texture::DepthFormat::I24, 128, 128);
+    let stencil = glium::render_buffer::StencilRenderBuffer::new(&display,
+                                                glium::texture::StencilFormat::I8, 64, 64);
+
+    glium::framebuffer::MultiOutputFrameBuffer::with_depth_and_stencil_buffer(
+                                &display, &[("color", &color)], &depth, &stencil);
+}
+
+#[test]
+fn texture2darray_layer_attachment() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::Texture2dArray::empty_if_supported(&display, 2, 2, 2) {
+        Some(t) => t,
+        None => return
+    };
+
+    glium::framebuffer::SimpleFrameBuffer::new(&display,
+            &glium::framebuffer::ColorAttachment::Texture2dArray(
+                texture.layer(0).unwrap().main_level())).clear_color(0.0, 0.0, 1.0, 1.0);
+    glium::framebuffer::SimpleFrameBuffer::new(&display,
+            &glium::framebuffer::ColorAttachment::Texture2dArray(
+                texture.layer(1).unwrap().main_level())).clear_color(1.0, 0.0, 0.0, 1.0);
+
+    let layer0: Vec<Vec<(u8, u8, u8, u8)>> = texture.read_layer(0);
+    assert_eq!(layer0[0][0], (0, 0, 255, 255));
+    assert_eq!(layer0[1][1], (0, 0, 255, 255));
+
+    let layer1: Vec<Vec<(u8, u8, u8, u8)>> = texture.read_layer(1);
+    assert_eq!(layer1[0][0], (255, 0, 0, 255));
+    assert_eq!(layer1[1][1], (255, 0, 0, 255));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn texture2darray_layered_attachment() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::Texture2dArray::empty_if_supported(&display, 2, 2, 3) {
+        Some(t) => t,
+        None => return
+    };
+
+    let attachment = glium::framebuffer::ColorAttachment::Layered(
+                        glium::framebuffer::LayeredColorAttachment::Texture2dArray(&texture));
+    let mut framebuffer = match glium::framebuffer::SimpleFrameBuffer::try_new(&display,
+                                                                              &attachment)
+    {
+        Ok(f) => f,
+        Err(glium::framebuffer::ValidationError::LayeredRenderingNotSupported) => return,
+    };
+    assert_eq!(framebuffer.get_dimensions(), (2, 2));
+
+    // clearing a layered framebuffer clears all of its layers
+    framebuffer.clear_color(0.0, 1.0, 0.0, 1.0);
+
+    for layer in 0 .. 3 {
+        let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read_layer(layer);
+        assert_eq!(data[0][0], (0, 255, 0, 255));
+        assert_eq!(data[1][1], (0, 255, 0, 255));
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn multisample_renderbuffer_resolve() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let color = match glium::render_buffer::RenderBuffer::new_multisample(&display,
+                                glium::texture::UncompressedFloatFormat::U8U8U8U8, 16, 16, 4)
+    {
+        Ok(b) => b,
+        Err(glium::render_buffer::MultisampleCreationError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+    let depth = glium::render_buffer::DepthRenderBuffer::new_multisample(&display,
+                                glium::texture::DepthFormat::I24, 16, 16, 4).unwrap();
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
+                                                                                  &color, &depth);
+    framebuffer.clear_color_and_depth((0.0, 0.0, 0.0, 1.0), 1.0);
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &glium::DrawParameters {
+                         depth_test: glium::DepthTest::IfLess,
+                         .. Default::default()
+                     }).unwrap();
+
+    let resolved = glium::Texture2d::new_empty(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               16, 16);
+    let target = glium::BlitTarget { left: 0, bottom: 0, width: 16, height: 16 };
+    framebuffer.blit_whole_color_to(&resolved.as_surface(), &target,
+                                    glium::uniforms::MagnifySamplerFilter::Nearest).unwrap();
+
+    let read_back: Vec<Vec<(f32, f32, f32, f32)>> = resolved.read();
+    assert_eq!(read_back[0][0], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(read_back[15][15], (1.0, 0.0, 0.0, 1.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn multisample_renderbuffer_too_many_samples() {
+    let display = support::build_display();
+
+    match glium::render_buffer::RenderBuffer::new_multisample(&display,
+                                glium::texture::UncompressedFloatFormat::U8U8U8U8, 16, 16, 4096)
+    {
+        Err(glium::render_buffer::MultisampleCreationError::SamplesCountNotSupported) => (),
+        Err(glium::render_buffer::MultisampleCreationError::NotSupported) => (),
+        Ok(_) => panic!()
+    };
+
+    display.assert_no_error();
+}
+
+#[test]
+fn multioutput_missing_fragment_output() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            out vec4 color1;
+
+            void main() {
+                color1 = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(..)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let color1 = glium::Texture2d::new_empty(&display,
+                                             glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                             128, 128);
+    let color2 = glium::Texture2d::new_empty(&display,
+                                             glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                             128, 128);
+
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                                             &[("color1", &color1), ("color2", &color2)]);
+
+    match framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                           &std::default::Default::default())
+    {
+        Err(glium::DrawError::FragmentShaderOutputNotFound(ref name)) if name == "color2" => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error();
+}
+
+#[test]
+fn multioutput_read_and_blit_attachment() {
+    let display = support::build_display();
+
+    let color1 = glium::Texture2d::new_empty(&display,
+                                             glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                             64, 64);
+    let color2 = glium::Texture2d::new_empty(&display,
+                                             glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                             64, 64);
+
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                                             &[("color1", &color1), ("color2", &color2)]);
+    framebuffer.clear_color_buffer(0, (1.0, 0.0, 0.0, 1.0));
+    framebuffer.clear_color_buffer(1, (0.0, 1.0, 0.0, 1.0));
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = framebuffer.read_attachment(1);
+    assert_eq!(read_back[10][10], (0, 255, 0, 255));
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let rect = glium::Rect { left: 0, bottom: 0, width: 64, height: 64 };
+    let target = glium::BlitTarget { left: 0, bottom: 0, width: 64, height: 64 };
+    framebuffer.blit_attachment_color(1, &rect, &output.as_surface(), &target,
+                                      glium::uniforms::MagnifySamplerFilter::Nearest).unwrap();
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    assert_eq!(read_back[10][10], (0, 255, 0, 255));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn read_rect() {
+    let display = support::build_display();
+
+    let texture = glium::Texture2d::new_empty(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              64, 64);
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture);
+    framebuffer.clear_color(0.0, 0.0, 1.0, 1.0);
+
+    let rect = glium::Rect { left: 10, bottom: 20, width: 2, height: 1 };
+    framebuffer.clear(Some(&rect), Some((1.0, 0.0, 0.0, 1.0)), None, None);
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> =
+                framebuffer.read_rect(&glium::Rect { left: 10, bottom: 20, width: 3, height: 1 });
+    assert_eq!(read_back.len(), 1);
+    assert_eq!(read_back[0], vec![(255, 0, 0, 255), (255, 0, 0, 255), (0, 0, 255, 255)]);
+
+    let mut pixel = [(0u8, 0u8, 0u8, 0u8)];
+    framebuffer.read_rect_into(&glium::Rect { left: 11, bottom: 20, width: 1, height: 1 },
+                               &mut pixel);
+    assert_eq!(pixel[0], (255, 0, 0, 255));
+
+    display.assert_no_error();
+}
+
+#[test]
+#[should_panic]
+fn read_rect_out_of_bounds() {
+    let display = support::build_display();
+
+    let texture = glium::Texture2d::new_empty(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              64, 64);
+
+    let _: Vec<Vec<(u8, u8, u8, u8)>> =
+                texture.as_surface().read_rect(&glium::Rect { left: 60, bottom: 0, width: 8,
+                                                              height: 1 });
+}
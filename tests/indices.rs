@@ -379,3 +379,376 @@ fn indexbuffer_slice_draw() {
 
     display.assert_no_error();
 }
+
+#[test]
+fn packed_meshes_slices_draw() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    // two meshes packed in the same buffers, with indices relative to each mesh
+    let vb = glium::VertexBuffer::new(&display, vec![
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0, -1.0] },
+        Vertex { position: [-1.0, -1.0] },
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [ 1.0, -1.0] },
+    ]);
+
+    let indices = glium::index::TrianglesList(vec![0u16, 1, 2, 0, 1, 2]);
+    let indices = glium::IndexBuffer::new(&display, indices);
+
+    // first mesh
+    let texture1 = support::build_renderable_texture(&display);
+    texture1.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture1.as_surface().draw(vb.slice(0 .. 3).unwrap(), &indices.slice(0 .. 3).unwrap(),
+                               &program, &glium::uniforms::EmptyUniforms,
+                               &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8)>> = texture1.read();
+    assert_eq!(data[0][0], (255, 0, 0));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(0, 0, 0));
+
+    // second mesh
+    let texture2 = support::build_renderable_texture(&display);
+    texture2.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture2.as_surface().draw(vb.slice(3 .. 6).unwrap(), &indices.slice(3 .. 6).unwrap(),
+                               &program, &glium::uniforms::EmptyUniforms,
+                               &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8)>> = texture2.read();
+    assert_eq!(data[0][0], (0, 0, 0));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn base_vertex_gpu() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    // the first four vertices are degenerate and must not be used
+    let vb = glium::VertexBuffer::new(&display, vec![
+        Vertex { position: [0.0, 0.0] }, Vertex { position: [0.0, 0.0] },
+        Vertex { position: [0.0, 0.0] }, Vertex { position: [0.0, 0.0] },
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]);
+
+    let indices = glium::index::TrianglesList(vec![0u16, 1, 2, 2, 1, 3]);
+    let indices = glium::IndexBuffer::new(&display, indices);
+
+    let params = glium::DrawParameters {
+        base_vertex: 4,
+        .. Default::default()
+    };
+
+    let mut target = display.draw();
+    target.clear_color(0.0, 0.0, 0.0, 0.0);
+    match target.draw(&vb, &indices, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Ok(_) => (),
+        Err(glium::DrawError::BaseVertexNotSupported) => {
+            target.finish();
+            return;
+        },
+        Err(e) => panic!("{:?}", e)
+    };
+    target.finish();
+
+    let data: Vec<Vec<(u8, u8, u8)>> = display.read_front_buffer();
+
+    assert_eq!(data[0][0], (255, 0, 0));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn triangle_strip_primitive_restart_gpu() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let vb = glium::VertexBuffer::new(&display, vec![
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]);
+
+    // two strips of one triangle each, separated by the restart index
+    let indices = glium::index::TriangleStrip(vec![0u16, 1, 2, 0xffff, 1, 2, 3]);
+    let indices = glium::IndexBuffer::new(&display, indices);
+
+    let params = glium::DrawParameters {
+        primitive_restart_index: Some(0xffff),
+        .. Default::default()
+    };
+
+    let mut target = display.draw();
+    target.clear_color(0.0, 0.0, 0.0, 0.0);
+    match target.draw(&vb, &indices, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Ok(_) => (),
+        Err(glium::DrawError::PrimitiveRestartNotSupported) => {
+            target.finish();
+            return;
+        },
+        Err(e) => panic!("{:?}", e)
+    };
+    target.finish();
+
+    let data: Vec<Vec<(u8, u8, u8)>> = display.read_front_buffer();
+
+    assert_eq!(data[0][0], (255, 0, 0));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn primitive_restart_index_too_large() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let vb = glium::VertexBuffer::new(&display, vec![
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]);
+
+    let indices = glium::index::TriangleStrip(vec![0u16, 1, 2, 3]);
+    let indices = glium::IndexBuffer::new(&display, indices);
+
+    let params = glium::DrawParameters {
+        primitive_restart_index: Some(0x10000),
+        .. Default::default()
+    };
+
+    let mut target = display.draw();
+    match target.draw(&vb, &indices, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Err(glium::DrawError::InvalidPrimitiveRestartIndex) => (),
+        a => panic!("{:?}", a)
+    };
+    target.finish();
+
+    display.assert_no_error();
+}
+
+// coordinates of the centers of the pixels 256 and 768 of a 1024x1024 texture
+const P256: f32 = -0.4990234375;
+const P768: f32 = 0.5009765625;
+
+fn draw_lines_and_points<I>(display: &glium::Display, vertices: Vec<Vertex>, indices: &I)
+                            -> Vec<Vec<(f32, f32, f32, f32)>>
+                            where I: glium::index::ToIndicesSource
+{
+    let program = build_program(display);
+    let vb = glium::VertexBuffer::new(display, vertices);
+
+    let params = glium::DrawParameters {
+        line_width: Some(1.0),
+        point_size: Some(1.0),
+        .. Default::default()
+    };
+
+    let texture = support::build_renderable_texture(display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, indices, &program, &glium::uniforms::EmptyUniforms,
+                              &params).unwrap();
+
+    texture.read()
+}
+
+#[test]
+fn points_list_cpu() {
+    let display = support::build_display();
+
+    let data = draw_lines_and_points(&display, vec![
+        Vertex { position: [P256, P256] }, Vertex { position: [P768, P768] },
+    ], &glium::index::PointsList(vec![0u16, 1]));
+
+    assert_eq!(data[256][256], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[768][768], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[512][512], (0.0, 0.0, 0.0, 0.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn points_list_gpu() {
+    let display = support::build_display();
+
+    let indices = glium::index::PointsList(vec![0u16, 1]);
+    let indices = glium::IndexBuffer::new(&display, indices);
+
+    let data = draw_lines_and_points(&display, vec![
+        Vertex { position: [P256, P256] }, Vertex { position: [P768, P768] },
+    ], &indices);
+
+    assert_eq!(data[256][256], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[768][768], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[512][512], (0.0, 0.0, 0.0, 0.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn points_noindices() {
+    let display = support::build_display();
+
+    let data = draw_lines_and_points(&display, vec![
+        Vertex { position: [P256, P256] }, Vertex { position: [P768, P768] },
+    ], &index::NoIndices(index::PrimitiveType::Points));
+
+    assert_eq!(data[256][256], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[768][768], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[512][512], (0.0, 0.0, 0.0, 0.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn lines_list_cpu() {
+    let display = support::build_display();
+
+    // an horizontal line and a vertical line
+    let data = draw_lines_and_points(&display, vec![
+        Vertex { position: [-1.0, P256] }, Vertex { position: [1.0, P256] },
+        Vertex { position: [P768, -1.0] }, Vertex { position: [P768, 1.0] },
+    ], &glium::index::LinesList(vec![0u16, 1, 2, 3]));
+
+    assert_eq!(data[256][512], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[512][768], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[512][512], (0.0, 0.0, 0.0, 0.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn lines_list_gpu() {
+    let display = support::build_display();
+
+    let indices = glium::index::LinesList(vec![0u16, 1, 2, 3]);
+    let indices = glium::IndexBuffer::new(&display, indices);
+
+    let data = draw_lines_and_points(&display, vec![
+        Vertex { position: [-1.0, P256] }, Vertex { position: [1.0, P256] },
+        Vertex { position: [P768, -1.0] }, Vertex { position: [P768, 1.0] },
+    ], &indices);
+
+    assert_eq!(data[256][512], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[512][768], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[512][512], (0.0, 0.0, 0.0, 0.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn line_strip_gpu() {
+    let display = support::build_display();
+
+    let indices = glium::index::LineStrip(vec![0u16, 1, 2, 3]);
+    let indices = glium::IndexBuffer::new(&display, indices);
+
+    // three sides of a square, the left side is missing
+    let data = draw_lines_and_points(&display, vec![
+        Vertex { position: [P256, P256] }, Vertex { position: [P768, P256] },
+        Vertex { position: [P768, P768] }, Vertex { position: [P256, P768] },
+    ], &indices);
+
+    assert_eq!(data[256][512], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[512][768], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[768][512], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[512][256], (0.0, 0.0, 0.0, 0.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn line_loop_cpu() {
+    let display = support::build_display();
+
+    // the loop closes the left side of the square
+    let data = draw_lines_and_points(&display, vec![
+        Vertex { position: [P256, P256] }, Vertex { position: [P768, P256] },
+        Vertex { position: [P768, P768] }, Vertex { position: [P256, P768] },
+    ], &glium::index::LineLoop(vec![0u16, 1, 2, 3]));
+
+    assert_eq!(data[256][512], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[512][768], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[768][512], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[512][256], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[512][512], (0.0, 0.0, 0.0, 0.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn line_loop_noindices() {
+    let display = support::build_display();
+
+    let data = draw_lines_and_points(&display, vec![
+        Vertex { position: [P256, P256] }, Vertex { position: [P768, P256] },
+        Vertex { position: [P768, P768] }, Vertex { position: [P256, P768] },
+    ], &index::NoIndices(index::PrimitiveType::LineLoop));
+
+    assert_eq!(data[256][512], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[512][768], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[768][512], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[512][256], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[512][512], (0.0, 0.0, 0.0, 0.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn empty_index_buffer_write_draw() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let vb = glium::VertexBuffer::new(&display, vec![
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]);
+
+    let indices = glium::IndexBuffer::empty(&display, index::IndexType::U16,
+                                            index::PrimitiveType::TrianglesList, 6);
+    assert_eq!(indices.len(), 6);
+    assert!(indices.slice(0 .. 7).is_none());
+
+    indices.write(0, &[0u16, 1, 2]).unwrap();
+    indices.write(3, &[2u16, 1, 3]).unwrap();
+
+    let mut target = display.draw();
+    target.clear_color(0.0, 0.0, 0.0, 0.0);
+    target.draw(&vb, &indices, &program, &glium::uniforms::EmptyUniforms, &Default::default()).unwrap();
+    target.finish();
+
+    let data: Vec<Vec<(u8, u8, u8)>> = display.read_front_buffer();
+
+    assert_eq!(data[0][0], (255, 0, 0));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn index_buffer_write_out_of_range() {
+    let display = support::build_display();
+
+    let indices = glium::IndexBuffer::empty(&display, index::IndexType::U16,
+                                            index::PrimitiveType::TrianglesList, 3);
+
+    match indices.write(2, &[0u16, 1]) {
+        Err(glium::vertex::SliceError::OutOfRange { end: 4, len: 3 }) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error();
+}
+
+#[test]
+#[should_panic(expected = "The type of the indices doesn't match the type of the buffer")]
+fn index_buffer_write_wrong_type() {
+    let display = support::build_display();
+
+    let indices = glium::IndexBuffer::empty(&display, index::IndexType::U16,
+                                            index::PrimitiveType::TrianglesList, 3);
+
+    indices.write(0, &[0u32, 1, 2]).unwrap();
+}
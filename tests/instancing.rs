@@ -200,3 +200,172 @@ fn per_instance_length_mismatch() {
 
     display.assert_no_error();
 }
+
+#[test]
+fn draw_instanced_without_per_instance_buffer() {
+    let display = support::build_display();
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 140
+
+            in vec2 position;
+
+            flat out int instance;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+                instance = gl_InstanceID;
+            }
+        ",
+        "
+            #version 140
+
+            flat in int instance;
+            out vec4 color;
+
+            void main() {
+                if (instance != 3) {
+                    discard;
+                }
+
+                color = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None) {
+        Ok(p) => p,
+        _ => return
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    match texture.as_surface().draw_instanced(&vb, &ib, &program, &uniform!{}, 4,
+                                              &std::default::Default::default())
+    {
+        Ok(_) => (),
+        Err(glium::DrawError::InstancingNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let data: Vec<Vec<(f32, f32, f32, f32)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(1.0, 0.0, 0.0, 1.0));
+        }
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn multiple_per_instance_buffers() {
+    let display = support::build_display();
+
+    let buffer1 = {
+        #[derive(Copy, Clone)]
+        struct Vertex {
+            position: [f32; 2],
+        }
+
+        implement_vertex!(Vertex, position);
+
+        glium::VertexBuffer::new(&display, 
+            vec![
+                Vertex { position: [-1.0,  1.0] },
+                Vertex { position: [ 1.0,  1.0] },
+                Vertex { position: [-1.0, -1.0] },
+                Vertex { position: [ 1.0, -1.0] },
+            ]
+        )
+    };
+
+    let buffer2 = {
+        #[derive(Copy, Clone)]
+        struct Vertex {
+            offset: f32,
+        }
+
+        implement_vertex!(Vertex, offset);
+
+        glium::vertex::VertexBuffer::new(&display, 
+            vec![
+                Vertex { offset: 3.0 },
+                Vertex { offset: 3.0 },
+                Vertex { offset: 0.0 },
+            ]
+        )
+    };
+
+    let buffer3 = {
+        #[derive(Copy, Clone)]
+        struct Vertex {
+            color: [f32; 3],
+        }
+
+        implement_vertex!(Vertex, color);
+
+        glium::vertex::VertexBuffer::new(&display, 
+            vec![
+                Vertex { color: [0.0, 0.0, 1.0] },
+                Vertex { color: [0.0, 1.0, 0.0] },
+                Vertex { color: [1.0, 0.0, 0.0] },
+            ]
+        )
+    };
+
+    let (buffer2, buffer3) = match (buffer2.per_instance_if_supported(),
+                                    buffer3.per_instance_if_supported())
+    {
+        (Some(b2), Some(b3)) => (b2, b3),
+        _ => return
+    };
+
+    let index_buffer = glium::IndexBuffer::new(&display,
+        glium::index::TriangleStrip(vec![0u16, 1, 2, 3]));
+
+    // the first two instances are moved outside of the screen, only the third one is visible
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 330
+
+            in vec2 position;
+            in float offset;
+            in vec3 color;
+
+            out vec3 v_color;
+
+            void main() {
+                gl_Position = vec4(position.x + offset, position.y, 0.0, 1.0);
+                v_color = color;
+            }
+        ",
+        "
+            #version 330
+            in vec3 v_color;
+
+            void main() {
+                gl_FragColor = vec4(v_color, 1.0);
+            }
+        ",
+        None) {
+        Ok(p) => p,
+        _ => return
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw((&buffer1, buffer2, buffer3), &index_buffer, &program, &uniform!{},
+                              &std::default::Default::default()).unwrap();
+
+    let data: Vec<Vec<(f32, f32, f32, f32)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(1.0, 0.0, 0.0, 1.0));
+        }
+    }
+
+    display.assert_no_error();
+}
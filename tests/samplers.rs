@@ -61,3 +61,252 @@ fn magnify_nearest_filtering() {
 
     display.assert_no_error();
 }
+
+#[test]
+fn same_texture_different_samplers() {
+    // ignoring test on travis
+    // TODO: find out why they are failing
+    if ::std::env::var("TRAVIS").is_ok() {
+        return;
+    }
+
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform sampler2D texture;
+
+            void main() {
+                gl_FragColor = texture2D(texture, vec2(0.51, 0.0));
+            }
+        ",
+        None).unwrap();
+
+    let texture_data = vec![vec![(0u8, 0, 0), (255, 255, 255)]];
+    let texture = glium::texture::Texture2d::new(&display, texture_data);
+
+    let output = support::build_renderable_texture(&display);
+
+    // first draw with linear filtering
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    let uniforms = uniform! {
+        texture: texture.sampled()
+                        .magnify_filter(glium::uniforms::MagnifySamplerFilter::Linear)
+                        .wrap_function(glium::uniforms::SamplerWrapFunction::Clamp)
+    };
+
+    match output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Ok(_) => (),
+        Err(glium::DrawError::SamplersNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let data: Vec<Vec<(u8, u8, u8)>> = output.read();
+    assert!(data[0][0] != (255, 255, 255));
+    assert!(data[0][0] != (0, 0, 0));
+
+    // then draw the same texture with nearest filtering
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    let uniforms = uniform! {
+        texture: texture.sampled()
+                        .magnify_filter(glium::uniforms::MagnifySamplerFilter::Nearest)
+                        .wrap_function(glium::uniforms::SamplerWrapFunction::Clamp)
+    };
+
+    output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8)>> = output.read();
+    assert_eq!(data[0][0], (255, 255, 255));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn anisotropic_filtering_clamped() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform sampler2D texture;
+
+            void main() {
+                gl_FragColor = texture2D(texture, vec2(0.5, 0.5));
+            }
+        ",
+        None).unwrap();
+
+    let texture_data = vec![vec![(255u8, 255, 255), (255, 255, 255)]];
+    let texture = glium::texture::Texture2d::new(&display, texture_data);
+
+    // a value way above what any hardware supports must be clamped instead of
+    // producing an error
+    let uniforms = uniform! {
+        texture: texture.sampled().anisotropy(65535)
+    };
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    match output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Ok(_) => (),
+        Err(glium::DrawError::SamplersNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let data: Vec<Vec<(u8, u8, u8)>> = output.read();
+    assert_eq!(data[0][0], (255, 255, 255));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn anisotropic_filtering_not_supported_debug_message() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    // the message is only sent if anisotropic filtering is not supported and if the debug
+    // output is available
+    if display.get_max_anisotropy_support().is_some() {
+        return;
+    }
+
+    if std::env::var("GLIUM_DISABLE_DEBUG_OUTPUT").is_ok() {
+        return;
+    }
+
+    if !(display.get_opengl_version() >= glium::Version(glium::Api::Gl, 4, 5)) &&
+       !display.get_extensions().gl_khr_debug && !display.get_extensions().gl_arb_debug_output
+    {
+        return;
+    }
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform sampler2D tex;
+
+            void main() {
+                gl_FragColor = texture2D(tex, vec2(0.5, 0.5));
+            }
+        ",
+        None).unwrap();
+
+    let texture_data = vec![vec![(255u8, 255, 255), (255, 255, 255)]];
+    let texture = glium::texture::Texture2d::new(&display, texture_data);
+
+    let uniforms = uniform! {
+        tex: texture.sampled().anisotropy(16)
+    };
+
+    let output = support::build_renderable_texture(&display);
+
+    match output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Ok(_) => (),
+        Err(glium::DrawError::SamplersNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let message = display.get_debug_message().unwrap();
+    assert!(message.contains("Anisotropic filtering is not supported"));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn depth_texture_2d_array_shadow_sampler() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let texture = match glium::texture::DepthTexture2dArray::empty_if_supported(&display,
+                                                                                 16, 16, 2)
+    {
+        Some(t) => t,
+        None => return
+    };
+
+    if !display.is_glsl_version_supported(&glium::Version(glium::Api::Gl, 1, 3)) {
+        return;
+    }
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 130
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 130
+
+            uniform sampler2DArrayShadow shadow_map;
+            out vec4 color;
+
+            void main() {
+                color = vec4(texture(shadow_map, vec4(0.5, 0.5, 1.0, 0.5)), 0.0, 0.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Ok(p) => p,
+        Err(glium::program::ProgramCreationError::CompilationNotSupported) => return,
+        Err(e) => panic!("{}", e)
+    };
+
+    let uniforms = uniform! {
+        shadow_map: texture.sampled()
+                        .minify_filter(glium::uniforms::MinifySamplerFilter::Nearest)
+                        .depth_texture_comparison(
+                            Some(glium::uniforms::DepthTextureComparison::Always))
+    };
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    match output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Ok(_) => (),
+        Err(glium::DrawError::SamplersNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let data: Vec<Vec<(u8, u8, u8)>> = output.read();
+    assert_eq!(data[0][0], (255, 0, 0));
+
+    display.assert_no_error();
+}
@@ -7,7 +7,7 @@ extern crate glutin;
 extern crate glium;
 
 use std::default::Default;
-use glium::Surface;
+use glium::{GlObject, Surface};
 
 mod support;
 
@@ -73,7 +73,64 @@ fn program_compilation_error() {
         None);
 
     match program {
-        Err(glium::CompilationError(_)) => (),
+        Err(glium::CompilationError(..)) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error();
+}
+
+#[test]
+fn program_compilation_error_stage() {
+    let display = support::build_display();
+
+    let program = glium::Program::from_source(&display,
+        // vertex shader
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+
+        // fragment shader
+        "
+            #version 110
+
+            void main() {
+                gl_FragColor = undefined_variable;
+            }
+        ",
+
+        // geometry shader
+        None);
+
+    match program {
+        Err(glium::CompilationError(log, stage)) => {
+            assert_eq!(stage, glium::program::ShaderType::Fragment);
+            assert!(!log.is_empty());
+        },
+        _ => panic!()
+    };
+
+    display.assert_no_error();
+}
+
+#[test]
+fn program_from_spirv_invalid_module() {
+    let display = support::build_display();
+
+    // not a SPIR-V module, as the magic number is missing
+    let garbage = [0u8; 16];
+
+    match glium::Program::from_spirv(&display, &garbage, &garbage, &[]) {
+        Err(glium::program::ProgramCreationError::SpirvNotSupported) => return,
+        Err(glium::CompilationError(_, stage)) => {
+            assert_eq!(stage, glium::program::ShaderType::Vertex);
+        },
         _ => panic!()
     };
 
@@ -380,6 +437,7 @@ fn get_transform_feedback_varyings() {
             vec!["normal".to_string(), "color".to_string()],
             glium::program::TransformFeedbackMode::Separate
         )),
+        frag_data_locations: Vec::new(),
     };
 
     let program = match glium::Program::new(&display, source) {
@@ -416,3 +474,412 @@ fn get_transform_feedback_varyings() {
 
     display.assert_no_error();
 }
+
+#[test]
+fn reload_from_source() {
+    let display = support::build_display();
+    let (vb, ib, mut program) = support::build_fullscreen_red_pipeline(&display);
+
+    let vertex_shader = "
+        #version 110
+
+        attribute vec2 position;
+
+        void main() {
+            gl_Position = vec4(position, 0.0, 1.0);
+        }
+    ";
+
+    let id = program.get_id();
+
+    program.reload_from_source(&display, vertex_shader,
+        "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(0.0, 1.0, 0.0, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    // the program is relinked in place
+    assert_eq!(program.get_id(), id);
+
+    // a failed reload must keep the previous program
+    match program.reload_from_source(&display, vertex_shader, "invalid glsl code", None) {
+        Err(glium::CompilationError(..)) => (),
+        _ => panic!()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(f32, f32, f32, f32)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0.0, 1.0, 0.0, 1.0));
+        }
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn tessellation_draw_patches() {
+    let display = support::build_display();
+    let (vb, _) = support::build_rectangle_vb_ib(&display);
+
+    let source = glium::program::SourceCode {
+        vertex_shader: "
+            #version 400
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        tessellation_control_shader: Some("
+            #version 400
+
+            layout(vertices = 3) out;
+
+            void main() {
+                gl_out[gl_InvocationID].gl_Position = gl_in[gl_InvocationID].gl_Position;
+                gl_TessLevelOuter[0] = 2.0;
+                gl_TessLevelOuter[1] = 2.0;
+                gl_TessLevelOuter[2] = 2.0;
+                gl_TessLevelInner[0] = 2.0;
+            }
+        "),
+        tessellation_evaluation_shader: Some("
+            #version 400
+
+            layout(triangles, equal_spacing) in;
+
+            void main() {
+                gl_Position = gl_TessCoord.x * gl_in[0].gl_Position +
+                              gl_TessCoord.y * gl_in[1].gl_Position +
+                              gl_TessCoord.z * gl_in[2].gl_Position;
+            }
+        "),
+        geometry_shader: None,
+        fragment_shader: "
+            #version 400
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+    };
+
+    let program = match glium::Program::new(&display, source) {
+        Err(glium::ShaderTypeNotSupported) => return,
+        Err(glium::CompilationError(..)) => return,
+        p => p.unwrap()
+    };
+
+    // two patches of three vertices covering the whole surface
+    let indices = glium::index::Patches(vec![0u16, 1, 2, 1, 3, 2], 3);
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    output.as_surface().draw(&vb, &indices, &program, &glium::uniforms::EmptyUniforms,
+                             &Default::default()).unwrap();
+
+    let data: Vec<Vec<(f32, f32, f32, f32)>> = output.read();
+    assert_eq!(data[0][0], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[512][512], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[1023][1023], (1.0, 0.0, 0.0, 1.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn compute_shader_dispatch() {
+    let display = support::build_display();
+
+    let program = match glium::Program::from_compute_source(&display, "
+        #version 430
+
+        layout(local_size_x = 1) in;
+
+        buffer MyBlock {
+            float values[4];
+        };
+
+        void main() {
+            values[gl_WorkGroupID.x] = float(gl_WorkGroupID.x) + 1.0;
+        }
+    ") {
+        Err(glium::ShaderTypeNotSupported) => return,
+        Err(glium::CompilationError(..)) => return,
+        p => p.unwrap()
+    };
+
+    let buffer = match glium::uniforms::ShaderStorageBuffer::new_if_supported(&display,
+                                                                             [0.0f32; 4])
+    {
+        Some(b) => b,
+        None => return
+    };
+
+    // one work group per value
+    program.dispatch_compute(uniform!{ MyBlock: &buffer }, 4, 1, 1).unwrap();
+
+    let data = match buffer.read_if_supported() {
+        Some(d) => d,
+        None => return
+    };
+
+    assert_eq!(data, [1.0, 2.0, 3.0, 4.0]);
+
+    display.assert_no_error();
+}
+
+#[test]
+fn compute_dispatch_not_a_compute_program() {
+    let display = support::build_display();
+    let (_, _, program) = support::build_fullscreen_red_pipeline(&display);
+
+    match program.dispatch_compute(glium::uniforms::EmptyUniforms, 1, 1, 1) {
+        Err(glium::DrawError::NotAComputeProgram) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error();
+}
+
+#[test]
+fn compute_shader_zero_work_groups() {
+    let display = support::build_display();
+
+    let program = match glium::Program::from_compute_source(&display, "
+        #version 430
+
+        layout(local_size_x = 1) in;
+
+        void main() {
+        }
+    ") {
+        Err(glium::ShaderTypeNotSupported) => return,
+        Err(glium::CompilationError(..)) => return,
+        p => p.unwrap()
+    };
+
+    match program.dispatch_compute(glium::uniforms::EmptyUniforms, 0, 1, 1) {
+        Err(glium::DrawError::WorkGroupCountNotSupported) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error();
+}
+
+#[test]
+fn compute_shader_write_storage_buffer() {
+    let display = support::build_display();
+
+    let program = match glium::Program::from_compute_source(&display, "
+        #version 430
+
+        layout(local_size_x = 4) in;
+
+        buffer MyBlock {
+            float values[4];
+        };
+
+        void main() {
+            values[gl_GlobalInvocationID.x] = float(gl_GlobalInvocationID.x) * 2.0;
+        }
+    ") {
+        Err(glium::ShaderTypeNotSupported) => return,
+        Err(glium::CompilationError(..)) => return,
+        p => p.unwrap()
+    };
+
+    let buffer = match glium::uniforms::ShaderStorageBuffer::new_if_supported(&display,
+                                                                             [0.0f32; 4])
+    {
+        Some(b) => b,
+        None => return
+    };
+
+    program.dispatch_compute(uniform!{ MyBlock: &buffer }, 1, 1, 1).unwrap();
+
+    let data = match buffer.read_if_supported() {
+        Some(d) => d,
+        None => return
+    };
+
+    assert_eq!(data, [0.0, 2.0, 4.0, 6.0]);
+
+    display.assert_no_error();
+}
+
+#[test]
+fn storage_buffer_upload_after_dispatch() {
+    let display = support::build_display();
+
+    let program = match glium::Program::from_compute_source(&display, "
+        #version 430
+
+        layout(local_size_x = 4) in;
+
+        buffer MyBlock {
+            float values[4];
+        };
+
+        void main() {
+            values[gl_GlobalInvocationID.x] = float(gl_GlobalInvocationID.x) * 2.0;
+        }
+    ") {
+        Err(glium::ShaderTypeNotSupported) => return,
+        Err(glium::CompilationError(..)) => return,
+        p => p.unwrap()
+    };
+
+    let mut buffer_a = match glium::uniforms::ShaderStorageBuffer::new_if_supported(&display,
+                                                                                   [0.0f32; 4])
+    {
+        Some(b) => b,
+        None => return
+    };
+
+    let buffer_b = glium::uniforms::ShaderStorageBuffer::new_if_supported(&display, [0.0f32; 4])
+                                                        .unwrap();
+
+    buffer_a.upload([3.0, 3.0, 3.0, 3.0]);
+    program.dispatch_compute(uniform!{ MyBlock: &buffer_b }, 1, 1, 1).unwrap();
+    buffer_a.upload([1.0, 1.0, 1.0, 1.0]);
+
+    let (data_a, data_b) = match (buffer_a.read_if_supported(), buffer_b.read_if_supported()) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return
+    };
+
+    assert_eq!(data_a, [1.0, 1.0, 1.0, 1.0]);
+    assert_eq!(data_b, [0.0, 2.0, 4.0, 6.0]);
+
+    display.assert_no_error();
+}
+
+#[test]
+fn storage_block_not_found() {
+    let display = support::build_display();
+
+    let program = match glium::Program::from_compute_source(&display, "
+        #version 430
+
+        layout(local_size_x = 1) in;
+
+        void main() {
+        }
+    ") {
+        Err(glium::ShaderTypeNotSupported) => return,
+        Err(glium::CompilationError(..)) => return,
+        p => p.unwrap()
+    };
+
+    let buffer = match glium::uniforms::ShaderStorageBuffer::new_if_supported(&display, 0.0f32) {
+        Some(b) => b,
+        None => return
+    };
+
+    match program.dispatch_compute(uniform!{ MyBlock: &buffer }, 1, 1, 1) {
+        Err(glium::DrawError::UniformBlockNotFound { .. }) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error();
+}
+
+#[test]
+fn program_binary_rejected() {
+    let display = support::build_display();
+
+    let program_src = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    let mut binary = match program_src.get_binary_if_supported() {
+        None => return,
+        Some(bin) => bin
+    };
+
+    // corrupting the binary
+    for byte in binary.content.iter_mut() {
+        *byte = 0;
+    }
+
+    match glium::Program::new(&display, binary) {
+        Err(glium::program::ProgramCreationError::BinaryRejected) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error();
+}
+
+#[test]
+fn frag_data_locations_binding() {
+    let display = support::build_display();
+
+    let source = glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+
+        vertex_shader: "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 130
+
+            out vec4 color1;
+            out vec4 color2;
+
+            void main() {
+                color1 = vec4(1.0, 1.0, 1.0, 1.0);
+                color2 = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+
+        transform_feedback_varyings: None,
+        frag_data_locations: vec![("color1".to_string(), 1), ("color2".to_string(), 0)],
+    };
+
+    let program = match glium::Program::new(&display, source) {
+        Err(glium::program::ProgramCreationError::FragDataLocationNotSupported) => return,
+        Err(glium::CompilationError(..)) => return,
+        p => p.unwrap()
+    };
+
+    assert_eq!(program.get_frag_data_location("color1"), Some(1));
+    assert_eq!(program.get_frag_data_location("color2"), Some(0));
+
+    display.assert_no_error();
+}
@@ -3,6 +3,7 @@ extern crate glutin;
 extern crate glium;
 
 use glium::Texture;
+use glium::Surface;
 
 mod support;
 
@@ -36,6 +37,21 @@ fn depth_texture_1d_creation() {
     display.assert_no_error();
 }
 
+#[test]
+fn texture_2d_try_new() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::try_new(&display, vec![
+        vec![(0, 0, 0, 0), (0, 0, 0, 0)],
+        vec![(0, 0, 0, 0), (0, 0, 0, 0u8)],
+    ]).unwrap();
+
+    assert_eq!(texture.get_width(), 2);
+    assert_eq!(texture.get_height(), Some(2));
+
+    display.assert_no_error();
+}
+
 #[test]
 fn texture_2d_creation() {    
     let display = support::build_display();
@@ -68,6 +84,65 @@ fn empty_texture2d_u8u8u8u8() {
     display.assert_no_error();
 }
 
+#[test]
+fn empty_texture2d_with_mipmap_levels() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty_with_mipmap_levels(&display, 3, 128, 128)
+                                                                              .unwrap();
+
+    assert_eq!(texture.get_mipmap_levels(), 3);
+    assert!(texture.mipmap(2).is_some());
+    assert!(texture.mipmap(3).is_none());
+
+    display.assert_no_error();
+    drop(texture);
+    display.assert_no_error();
+}
+
+#[test]
+fn empty_texture2d_with_too_many_mipmap_levels() {
+    let display = support::build_display();
+
+    match glium::texture::Texture2d::empty_with_mipmap_levels(&display, 9, 128, 128) {
+        Err(glium::texture::TextureCreationError::InvalidMipmapsCount) => (),
+        _ => panic!()
+    };
+
+    match glium::texture::Texture2d::empty_with_mipmap_levels(&display, 0, 128, 128) {
+        Err(glium::texture::TextureCreationError::InvalidMipmapsCount) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error();
+}
+
+#[test]
+fn texture2d_generate_mipmaps() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty_with_mipmap_levels(&display, 3, 128, 128)
+                                                                              .unwrap();
+    texture.as_surface().clear_color(1.0, 0.0, 0.0, 1.0);
+    texture.generate_mipmaps().unwrap();
+
+    display.assert_no_error();
+}
+
+#[test]
+fn texture2d_generate_mipmaps_without_mipmaps() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty_with_mipmaps(&display, false, 128, 128);
+
+    match texture.generate_mipmaps() {
+        Err(glium::texture::MipmapsGenerationError::NoMipmaps) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error();
+}
+
 #[test]
 fn depth_texture_2d_creation() {    
     let display = support::build_display();
@@ -215,3 +290,57 @@ empty_texture_test!(empty_unsignedtexture1darray, maybe UnsignedTexture1dArray,
 empty_texture_test!(empty_unsignedtexture2d, maybe UnsignedTexture2d, [64, 32], 64, Some(32), None, None);
 empty_texture_test!(empty_unsignedtexture2darray, maybe UnsignedTexture2dArray, [64, 32, 16], 64, Some(32), None, Some(16));
 empty_texture_test!(empty_unsignedtexture3d, maybe UnsignedTexture3d, [64, 32, 16], 64, Some(32), Some(16), None);
+
+#[test]
+fn rgba8_format_is_renderable_and_filterable() {
+    let display = support::build_display();
+
+    let format = glium::texture::UncompressedFloatFormat::U8U8U8U8.to_texture_format();
+    // `None` means that the backend can't tell
+    assert!(display.is_format_renderable(format) != Some(false));
+    assert!(display.is_format_filterable(format) != Some(false));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn compressed_texture2d_dxt1() {
+    let display = support::build_display();
+
+    // two 4x4 blocks: white/black endpoints with all indices pointing to the first color
+    let data = [0xffu8, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+    let texture = match glium::texture::CompressedTexture2d::with_compressed_data(&display,
+                                &data, 8, 4, glium::texture::CompressedFormat::S3tcDxt1NoAlpha)
+    {
+        Ok(t) => t,
+        Err(glium::texture::TextureMaybeSupportedCreationError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    assert_eq!(texture.get_width(), 8);
+    assert_eq!(texture.get_height(), Some(4));
+
+    display.assert_no_error();
+    drop(texture);
+    display.assert_no_error();
+}
+
+#[test]
+fn compressed_texture2d_data_size_mismatch() {
+    let display = support::build_display();
+
+    // a DXT5 block is 16 bytes, but only one DXT1-sized block is given
+    let data = [0u8; 8];
+
+    match glium::texture::CompressedTexture2d::with_compressed_data(&display, &data, 4, 4,
+                                        glium::texture::CompressedFormat::S3tcDxt5Alpha)
+    {
+        Err(glium::texture::TextureMaybeSupportedCreationError::CreationError(
+            glium::texture::TextureCreationError::DataSizeMismatch)) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error();
+}
@@ -39,6 +39,85 @@ fn texture_2d_read_pixelbuffer() {
     display.assert_no_error();
 }
 
+#[test]
+fn front_buffer_read_pixelbuffer() {
+    let display = support::build_display();
+
+    let pixel_buffer = display.read_front_buffer_to_pixel_buffer();
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = match pixel_buffer.read_if_supported() {
+        Some(d) => d,
+        None => return
+    };
+
+    let (width, height) = display.get_framebuffer_dimensions();
+    assert_eq!(read_back.len(), height as usize);
+    assert!(read_back.iter().all(|row| row.len() == width as usize));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn texture_2d_read_into() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 1u8, 2u8), (4u8, 8u8, 16u8)],
+        vec![(32u8, 64u8, 128u8), (32u8, 16u8, 4u8)],
+    ]);
+
+    let mut read_back = [(0u8, 0u8, 0u8); 4];
+    texture.read_into(&mut read_back);
+
+    assert_eq!(read_back[0], (0, 1, 2));
+    assert_eq!(read_back[1], (4, 8, 16));
+    assert_eq!(read_back[2], (32, 64, 128));
+    assert_eq!(read_back[3], (32, 16, 4));
+
+    display.assert_no_error();
+}
+
+#[test]
+#[should_panic]
+fn texture_2d_read_into_too_small() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 1u8, 2u8), (4u8, 8u8, 16u8)],
+        vec![(32u8, 64u8, 128u8), (32u8, 16u8, 4u8)],
+    ]);
+
+    let mut read_back = [(0u8, 0u8, 0u8); 3];
+    texture.read_into(&mut read_back);
+}
+
+#[test]
+fn texture_2d_read_mipmap() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::with_mipmaps(&display, vec![
+        vec![(255u8, 0u8, 0u8); 4],
+        vec![(255u8, 0u8, 0u8); 4],
+        vec![(255u8, 0u8, 0u8); 4],
+        vec![(255u8, 0u8, 0u8); 4],
+    ], true);
+
+    if texture.get_mipmap_levels() < 2 {
+        return;
+    }
+
+    // OpenGL ES 2.0 can't read levels other than 0 without `GL_OES_fbo_render_mipmap`
+    let read_back: Vec<Vec<(u8, u8, u8)>> = match texture.read_mipmap(1) {
+        Some(data) => data,
+        None => return
+    };
+    assert_eq!(read_back, vec![vec![(255, 0, 0); 2]; 2]);
+
+    let levels = texture.get_mipmap_levels();
+    assert!(texture.read_mipmap::<(u8, u8, u8), Vec<Vec<(u8, u8, u8)>>>(levels).is_none());
+
+    display.assert_no_error();
+}
+
 macro_rules! read_texture_test {
     ($test_name:ident, $tex_ty:ident, $data_ty:ty, $data:expr) => (
         #[test]
@@ -148,3 +227,65 @@ read_texture_test!(read_unsignedtexture2darray, maybe UnsignedTexture2dArray, (u
 read_texture_test!(read_unsignedtexture3d, maybe UnsignedTexture3d, (u8, u8, u8, u8),
     vec![vec![vec![(0, 1, 2, 3), (4, 5, 6, 7)], vec![(8, 9, 10, 11), (12, 13, 14, 15)]]]);*/
 // TODO: srgb textures
+
+#[test]
+fn depth_stencil_texture_2d_read_depth_stencil() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::DepthStencilTexture2d::empty_if_supported(&display,
+                                                                                  128, 64)
+    {
+        Some(t) => t,
+        None => return
+    };
+
+    let read_back = match texture.read_depth_stencil() {
+        Ok(d) => d,
+        Err(glium::texture::DepthStencilReadError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    assert_eq!(read_back.len(), 64);
+    assert!(read_back.iter().all(|row| row.len() == 128));
+    assert!(read_back.iter().all(|row| row.iter().all(|&(d, _)| d >= 0.0 && d <= 1.0)));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn depth_stencil_texture_2d_read_depth_stencil_not_packed() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::DepthStencilTexture2d::empty_with_format_if_supported(
+                            &display, glium::texture::DepthStencilFormat::F32I8, false, 128, 64)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    assert_eq!(texture.read_depth_stencil(),
+               Err(glium::texture::DepthStencilReadError::NotPackedFormat));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn texture_2d_array_read_layer() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::Texture2dArray::new_if_supported(&display, vec![
+        vec![vec![(255u8, 0u8, 0u8, 255u8); 2]; 2],
+        vec![vec![(0u8, 255u8, 0u8, 255u8); 2]; 2],
+    ]) {
+        Some(t) => t,
+        None => return
+    };
+
+    let layer0: Vec<Vec<(u8, u8, u8, u8)>> = texture.read_layer(0);
+    let layer1: Vec<Vec<(u8, u8, u8, u8)>> = texture.read_layer(1);
+
+    assert_eq!(layer0, vec![vec![(255, 0, 0, 255); 2]; 2]);
+    assert_eq!(layer1, vec![vec![(0, 255, 0, 255); 2]; 2]);
+
+    display.assert_no_error();
+}
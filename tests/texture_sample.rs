@@ -70,3 +70,220 @@ texture_sample_test!(compressed_texture_2d_draw, CompressedTexture2d, "sampler2D
         vec![(255, 0, 0, 255), (255, 0, 0, 255)],
         vec![(255, 0, 0, 255), (255, 0, 0, 255u8)],
     ]);
+
+#[test]
+fn texture_units_reassigned_between_draws() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let red = support::build_unicolor_texture2d(&display, 1.0, 0.0, 0.0);
+    let green = support::build_unicolor_texture2d(&display, 0.0, 1.0, 0.0);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform sampler2D a;
+            uniform sampler2D b;
+
+            void main() {
+                gl_FragColor = vec4(texture2D(a, vec2(0.5, 0.5)).r,
+                                    texture2D(b, vec2(0.5, 0.5)).g, 0.0, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    let output = support::build_renderable_texture(&display);
+
+    // the same textures end up in different texture units depending on the order of the
+    // uniforms, and the sampler uniforms must follow them
+    let expected = [
+        (uniform!{ a: &red, b: &green }, (1.0, 1.0, 0.0, 1.0)),
+        (uniform!{ b: &green, a: &red }, (1.0, 1.0, 0.0, 1.0)),
+        (uniform!{ a: &green, b: &red }, (0.0, 0.0, 0.0, 1.0)),
+        (uniform!{ a: &red, b: &green }, (1.0, 1.0, 0.0, 1.0)),
+    ];
+
+    for &(ref uniforms, color) in expected.iter() {
+        output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+        output.as_surface().draw(&vb, &ib, &program, uniforms, &Default::default()).unwrap();
+
+        let data: Vec<Vec<(f32, f32, f32, f32)>> = output.read();
+        for row in data.iter() {
+            for pixel in row.iter() {
+                assert_eq!(pixel, &color);
+            }
+        }
+    }
+
+    display.assert_no_error();
+}
+
+fn build_cubemap_program(display: &glium::Display) -> glium::Program {
+    glium::Program::from_source(display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform samplerCube texture;
+            uniform vec3 direction;
+
+            void main() {
+                gl_FragColor = textureCube(texture, direction);
+            }
+        ",
+        None).unwrap()
+}
+
+fn cube_faces() -> Vec<(glium::texture::CubeLayer, [f32; 3], (u8, u8, u8, u8))> {
+    use glium::texture::CubeLayer;
+
+    vec![
+        (CubeLayer::PositiveX, [1.0, 0.0, 0.0], (255, 0, 0, 255)),
+        (CubeLayer::NegativeX, [-1.0, 0.0, 0.0], (0, 255, 0, 255)),
+        (CubeLayer::PositiveY, [0.0, 1.0, 0.0], (0, 0, 255, 255)),
+        (CubeLayer::NegativeY, [0.0, -1.0, 0.0], (255, 255, 0, 255)),
+        (CubeLayer::PositiveZ, [0.0, 0.0, 1.0], (255, 0, 255, 255)),
+        (CubeLayer::NegativeZ, [0.0, 0.0, -1.0], (0, 255, 255, 255)),
+    ]
+}
+
+#[test]
+fn cubemap_render_to_faces_and_sample() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+    let program = build_cubemap_program(&display);
+
+    let cubemap = glium::texture::CubeMap::empty(&display, 16);
+
+    for &(face, _, color) in cube_faces().iter() {
+        let attachment = glium::framebuffer::ColorAttachment::CubeMap(cubemap.main_level(), face);
+        let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &attachment);
+        framebuffer.clear_color(color.0 as f32 / 255.0, color.1 as f32 / 255.0,
+                                color.2 as f32 / 255.0, color.3 as f32 / 255.0);
+    }
+
+    let output = support::build_renderable_texture(&display);
+
+    for &(_, direction, color) in cube_faces().iter() {
+        output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+        let uniforms = uniform! {
+            texture: &cubemap,
+            direction: direction,
+        };
+
+        output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+        let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+        assert_eq!(data[0][0], color);
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn cubemap_write_face_and_sample() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+    let program = build_cubemap_program(&display);
+
+    let cubemap = glium::texture::CubeMap::empty(&display, 2);
+
+    for &(face, _, color) in cube_faces().iter() {
+        let rect = glium::Rect { left: 0, bottom: 0, width: 2, height: 2 };
+        cubemap.write_face(face, rect, vec![vec![color, color], vec![color, color]]);
+    }
+
+    let output = support::build_renderable_texture(&display);
+
+    for &(_, direction, color) in cube_faces().iter() {
+        output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+        let uniforms = uniform! {
+            texture: &cubemap,
+            direction: direction,
+        };
+
+        output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+        let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+        assert_eq!(data[0][0], color);
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn unsigned_texture_2d_sample() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    // integral textures are only complete with `NEAREST` filtering, and asking for mipmaps
+    // must not try to generate them
+    let texture = match glium::texture::UnsignedTexture2d::with_format_if_supported(&display,
+                                vec![vec![7u32, 7], vec![7, 7]],
+                                glium::texture::UncompressedUintFormat::U32, true)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    if !display.is_glsl_version_supported(&glium::Version(glium::Api::Gl, 1, 3)) {
+        return;
+    }
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 130
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 130
+
+            uniform usampler2D tex;
+            out vec4 color;
+
+            void main() {
+                color = vec4(texture(tex, vec2(0.5, 0.5)).r == 7u ? 1.0 : 0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Ok(p) => p,
+        Err(glium::program::ProgramCreationError::CompilationNotSupported) => return,
+        Err(e) => panic!("{}", e)
+    };
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{ tex: &texture },
+                             &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error();
+}
@@ -25,3 +25,90 @@ fn texture_2d_write() {
 
     display.assert_no_error();
 }
+
+#[test]
+#[should_panic]
+fn texture_2d_write_out_of_bounds() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 1u8, 2u8), (4u8, 8u8, 16u8)],
+        vec![(32u8, 64u8, 128u8), (32u8, 16u8, 4u8)],
+    ]);
+
+    texture.write(glium::Rect { bottom: 1, left: 1, width: 2, height: 1 },
+                  vec![vec![(128u8, 64u8, 2u8), (128u8, 64u8, 2u8)]]);
+}
+
+#[test]
+#[should_panic(expected = "The data is too small for the region of the texture being written")]
+fn texture_2d_write_not_enough_data() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 1u8, 2u8), (4u8, 8u8, 16u8)],
+        vec![(32u8, 64u8, 128u8), (32u8, 16u8, 4u8)],
+    ]);
+
+    // the rect is inside the texture, but the second row of the data is missing a pixel
+    texture.write(glium::Rect { bottom: 0, left: 0, width: 2, height: 2 },
+                  vec![vec![(128u8, 64u8, 2u8), (128u8, 64u8, 2u8)], vec![(128u8, 64u8, 2u8)]]);
+}
+
+#[test]
+fn texture_2d_copy_to() {
+    let display = support::build_display();
+
+    let source = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 1u8, 2u8, 255u8), (4u8, 8u8, 16u8, 255u8)],
+        vec![(32u8, 64u8, 128u8, 255u8), (32u8, 16u8, 4u8, 255u8)],
+    ]);
+
+    let target = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 0u8, 0u8, 0u8), (0u8, 0u8, 0u8, 0u8)],
+        vec![(0u8, 0u8, 0u8, 0u8), (0u8, 0u8, 0u8, 0u8)],
+    ]);
+
+    source.copy_to(&target, glium::Rect { bottom: 1, left: 0, width: 2, height: 1 }, (0, 0))
+          .unwrap();
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = target.read();
+    assert_eq!(read_back[0][0], (32, 64, 128, 255));
+    assert_eq!(read_back[0][1], (32, 16, 4, 255));
+    assert_eq!(read_back[1][0], (0, 0, 0, 0));
+    assert_eq!(read_back[1][1], (0, 0, 0, 0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn texture_2d_copy_to_out_of_bounds() {
+    let display = support::build_display();
+
+    let source = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 1u8, 2u8), (4u8, 8u8, 16u8)],
+        vec![(32u8, 64u8, 128u8), (32u8, 16u8, 4u8)],
+    ]);
+
+    assert_eq!(source.copy_to(&source, glium::Rect { bottom: 0, left: 0, width: 1, height: 1 },
+                              (2, 0)),
+               Err(glium::texture::TextureCopyError::OutOfRange));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn texture_2d_copy_to_overlap() {
+    let display = support::build_display();
+
+    let source = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 1u8, 2u8), (4u8, 8u8, 16u8)],
+        vec![(32u8, 64u8, 128u8), (32u8, 16u8, 4u8)],
+    ]);
+
+    assert_eq!(source.copy_to(&source, glium::Rect { bottom: 0, left: 0, width: 1, height: 2 },
+                              (0, 0)),
+               Err(glium::texture::TextureCopyError::Overlap));
+
+    display.assert_no_error();
+}
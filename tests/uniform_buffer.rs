@@ -200,7 +200,7 @@ fn block_wrong_type() {
     target.clear_color(0.0, 0.0, 0.0, 0.0);
     
     match target.draw(&vb, &ib, &program, &uniforms, &Default::default()) {
-        Err(glium::DrawError::UniformBlockLayoutMismatch { ref name })
+        Err(glium::DrawError::UniformBlockLayoutMismatch { ref name, .. })
             if name == &"MyBlock" => (),
         a => panic!("{:?}", a)
     }
@@ -322,3 +322,244 @@ fn empty_uniform_buffer() {
 
     display.assert_no_error();
 }
+
+#[test]
+fn block_struct_offset_mismatch() {
+    let display = support::build_display();
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+            uniform layout(std140);
+
+            uniform MyBlock {
+                vec3 color;
+                float intensity;
+                vec3 tint;
+            };
+
+            void main() {
+                gl_FragColor = vec4(color * tint * intensity, 1.0);
+            }
+        ",
+        None);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    // `tint` is at offset 16 in the block, but at offset 12 in the struct
+    #[derive(Copy, Clone)]
+    struct Data {
+        color: [f32; 3],
+        tint: [f32; 3],
+        intensity: f32,
+    }
+
+    implement_uniform_block!(Data, color, intensity, tint);
+
+    let data = Data { color: [1.0, 1.0, 1.0], tint: [1.0, 1.0, 1.0], intensity: 1.0 };
+    let buffer = match glium::uniforms::UniformBuffer::new_if_supported(&display, data) {
+        None => return,
+        Some(b) => b
+    };
+
+    let uniforms = uniform!{
+        MyBlock: &buffer
+    };
+
+    let texture = support::build_renderable_texture(&display);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::UniformBlockLayoutMismatch {
+            err: glium::uniforms::UniformBlockLayoutError::OffsetMismatch { ref field, .. }, ..
+        }) if field == &"tint" || field == &"intensity" => (),
+        a => panic!("{:?}", a)
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn block_struct() {
+    let display = support::build_display();
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+            uniform layout(std140);
+
+            uniform MyBlock {
+                vec3 color;
+                float intensity;
+                vec3 tint;
+            };
+
+            void main() {
+                gl_FragColor = vec4(color * tint * intensity, 1.0);
+            }
+        ",
+        None);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    // the padding at the end makes the struct as large as the block
+    #[derive(Copy, Clone)]
+    struct Data {
+        color: [f32; 3],
+        intensity: f32,
+        tint: [f32; 3],
+        _padding: f32,
+    }
+
+    implement_uniform_block!(Data, color, intensity, tint);
+
+    let data = Data { color: [1.0, 1.0, 0.0], intensity: 1.0, tint: [1.0, 1.0, 1.0],
+                      _padding: 0.0 };
+    let buffer = match glium::uniforms::UniformBuffer::new_if_supported(&display, data) {
+        None => return,
+        Some(b) => b
+    };
+
+    let uniforms = uniform!{
+        MyBlock: &buffer
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(f32, f32, f32)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(1.0, 1.0, 0.0));
+        }
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn block_struct_too_small() {
+    let display = support::build_display();
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+            uniform layout(std140);
+
+            uniform MyBlock {
+                vec3 color;
+                vec4 tint;
+            };
+
+            void main() {
+                gl_FragColor = vec4(color, 1.0) * tint;
+            }
+        ",
+        None);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    // the offsets match, but `tint` only has three components
+    #[derive(Copy, Clone)]
+    struct Data {
+        color: [f32; 3],
+        _padding: f32,
+        tint: [f32; 3],
+    }
+
+    implement_uniform_block!(Data, color, tint);
+
+    let data = Data { color: [1.0, 1.0, 1.0], _padding: 0.0, tint: [1.0, 1.0, 1.0] };
+    let buffer = match glium::uniforms::UniformBuffer::new_if_supported(&display, data) {
+        None => return,
+        Some(b) => b
+    };
+
+    let uniforms = uniform!{
+        MyBlock: &buffer
+    };
+
+    let texture = support::build_renderable_texture(&display);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::UniformBlockLayoutMismatch {
+            err: glium::uniforms::UniformBlockLayoutError::SizeMismatch {
+                block_size: 32, type_size: 28
+            }, ..
+        }) => (),
+        a => panic!("{:?}", a)
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+fn block_not_found() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let buffer = match glium::uniforms::UniformBuffer::new_if_supported(&display,
+                                                                        (0.5f32, 0.5f32, 0.5f32))
+    {
+        None => return,
+        Some(b) => b
+    };
+
+    let uniforms = uniform!{
+        MyBlock: &buffer
+    };
+
+    let texture = support::build_renderable_texture(&display);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::UniformBlockNotFound { ref name }) if name == "MyBlock" => (),
+        a => panic!("{:?}", a)
+    }
+
+    display.assert_no_error();
+}
@@ -166,10 +166,153 @@ fn uniform_wrong_type() {
     let mut target = display.draw();
     target.clear_color(0.0, 0.0, 0.0, 0.0);
     match target.draw(&vb, &ib, &program, &uniforms, &Default::default()) {
-        Err(glium::DrawError::UniformTypeMismatch { .. }) => (),
+        Err(glium::DrawError::UniformTypeMismatch { ref name, expected, got }) => {
+            assert_eq!(name, "color");
+            assert_eq!(expected, glium::uniforms::UniformType::FloatVec4);
+            assert_eq!(got, glium::uniforms::UniformType::Float);
+        },
         a => panic!("{:?}", a)
     };
     target.finish();
 
     display.assert_no_error();
 }
+
+#[test]
+fn uniform_wrong_type_matrix() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform vec4 color;
+
+            void main() {
+                gl_FragColor = color;
+            }
+        ",
+        None).unwrap();
+
+    let uniforms = uniform! {
+        color: [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0f32]
+        ]
+    };
+
+    let mut target = display.draw();
+    match target.draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::UniformTypeMismatch { expected, got, .. }) => {
+            assert_eq!(expected, glium::uniforms::UniformType::FloatVec4);
+            assert_eq!(got, glium::uniforms::UniformType::FloatMat4);
+        },
+        a => panic!("{:?}", a)
+    };
+    target.finish();
+
+    display.assert_no_error();
+}
+
+#[test]
+fn uniform_array() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform float weights[4];
+            uniform vec4 colors[2];
+
+            void main() {
+                gl_FragColor = colors[0] * (weights[0] + weights[3]) + colors[1] * weights[1];
+            }
+        ",
+        None).unwrap();
+
+    let weights = [0.5f32, 1.0, 0.0, 0.5];
+    let colors = [[1.0, 0.0, 0.0, 1.0f32], [0.0, 0.0, 1.0, 0.0]];
+
+    let uniforms = uniform! {
+        weights: &weights[..],
+        colors: &colors[..]
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 255));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn uniform_struct_member() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            struct Light {
+                vec3 color;
+                float intensity;
+            };
+
+            uniform Light light;
+
+            void main() {
+                gl_FragColor = vec4(light.color * light.intensity, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    let uniforms = glium::uniforms::UniformsStorage::new("light.color", [0.0, 1.0, 0.0f32])
+                                                     .add("light.intensity", 1.0f32);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 255, 0));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(0, 255, 0));
+
+    display.assert_no_error();
+}
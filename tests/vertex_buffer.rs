@@ -31,6 +31,27 @@ fn vertex_buffer_creation() {
     display.assert_no_error();
 }
 
+#[test]
+fn vertex_buffer_try_new() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        field1: [f32; 3],
+    }
+
+    implement_vertex!(Vertex, field1);
+
+    let vb = glium::VertexBuffer::try_new(&display, vec![
+        Vertex { field1: [-0.5, -0.5, 0.0] },
+        Vertex { field1: [ 0.0,  0.5, 1.0] },
+    ]).unwrap();
+
+    assert_eq!(vb.len(), 2);
+
+    display.assert_no_error();
+}
+
 #[test]
 fn vertex_buffer_empty() {
     let display = support::build_display();
@@ -49,6 +70,32 @@ fn vertex_buffer_empty() {
     display.assert_no_error();
 }
 
+#[test]
+fn vertex_buffer_empty_dynamic_write() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct Vertex {
+        field1: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, field1);
+
+    let vb: glium::VertexBuffer<Vertex> = glium::VertexBuffer::empty_dynamic(&display, 2);
+    assert_eq!(vb.len(), 2);
+
+    vb.write(0, &[Vertex { field1: [1.0, 2.0] }, Vertex { field1: [3.0, 4.0] }]).unwrap();
+
+    let data = match vb.read_if_supported() {
+        Some(d) => d,
+        None => return
+    };
+
+    assert_eq!(data, vec![Vertex { field1: [1.0, 2.0] }, Vertex { field1: [3.0, 4.0] }]);
+
+    display.assert_no_error();
+}
+
 #[test]
 fn vertex_buffer_mapping_read() {
     let display = support::build_display();
@@ -106,6 +153,73 @@ fn vertex_buffer_mapping_write() {
     display.assert_no_error();
 }
 
+#[test]
+fn vertex_buffer_mapping_explicit_flush() {
+    let display = support::build_display();
+    
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        field1: [u8; 2],
+        field2: [u8; 2],
+    }
+
+    implement_vertex!(Vertex, field1, field2);
+
+    let mut vb = glium::VertexBuffer::new(&display, 
+        vec![
+            Vertex { field1: [ 2,  3], field2: [ 5,  7] },
+            Vertex { field1: [12, 13], field2: [15, 17] },
+            Vertex { field1: [22, 23], field2: [25, 27] },
+        ]
+    );
+
+    {
+        let mut mapping = vb.map_explicit_flush();
+        mapping[1].field1 = [0, 1];
+        mapping.flush_range(1, 1);
+    }
+
+    let mapping = vb.map();
+    assert_eq!(mapping[0].field1, [2, 3]);
+    assert_eq!(mapping[1].field1, [0, 1]);
+    assert_eq!(mapping[2].field2, [25, 27]);
+
+    display.assert_no_error();
+}
+
+#[test]
+fn vertex_buffer_dynamic_mapping() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        field1: [u8; 2],
+    }
+
+    implement_vertex!(Vertex, field1);
+
+    let mut vb = glium::VertexBuffer::dynamic(&display,
+        vec![
+            Vertex { field1: [ 2,  3] },
+            Vertex { field1: [12, 13] },
+        ]
+    );
+
+    // the mapping must stay usable across multiple frames
+    for i in 0 .. 3 {
+        {
+            let mut mapping = vb.map();
+            mapping[1].field1 = [i, i + 1];
+        }
+
+        let mapping = vb.map();
+        assert_eq!(mapping[0].field1, [2, 3]);
+        assert_eq!(mapping[1].field1, [i, i + 1]);
+    }
+
+    display.assert_no_error();
+}
+
 #[test]
 fn vertex_buffer_read() {
     let display = support::build_display();
@@ -189,6 +303,75 @@ fn vertex_buffer_slice_out_of_bounds() {
     display.assert_no_error();
 }
 
+#[test]
+fn vertex_buffer_slice_checked() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        field1: [u8; 2],
+        field2: [u8; 2],
+    }
+
+    implement_vertex!(Vertex, field1, field2);
+
+    let vb = glium::VertexBuffer::new(&display, 
+        vec![
+            Vertex { field1: [ 2,  3], field2: [ 5,  7] },
+            Vertex { field1: [12, 13], field2: [15, 17] },
+        ]
+    );
+
+    assert!(vb.slice_checked(0 .. 2).is_ok());
+
+    match vb.slice_checked(1 .. 3) {
+        Err(glium::vertex::SliceError::OutOfRange { end: 3, len: 2 }) => (),
+        _ => panic!()
+    };
+
+    match vb.slice_checked(2 .. 1) {
+        Err(glium::vertex::SliceError::InvalidRange { start: 2, end: 1 }) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error();
+}
+
+#[test]
+fn vertex_buffer_chunks() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        field1: [u8; 2],
+    }
+
+    implement_vertex!(Vertex, field1);
+
+    let vb = glium::VertexBuffer::new(&display, 
+        vec![
+            Vertex { field1: [ 2,  3] },
+            Vertex { field1: [12, 13] },
+            Vertex { field1: [22, 23] },
+            Vertex { field1: [32, 33] },
+            Vertex { field1: [42, 43] },
+        ]
+    );
+
+    let chunks = vb.chunks(2).collect::<Vec<_>>();
+    assert_eq!(chunks.len(), 3);
+
+    let data = match chunks[2].read_if_supported() {
+        Some(d) => d,
+        None => return
+    };
+
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].field1, [42, 43]);
+
+    display.assert_no_error();
+}
+
 #[test]
 fn vertex_buffer_any() {
     let display = support::build_display();
@@ -231,10 +414,10 @@ fn vertex_buffer_write() {
         ]
     );
 
-    vb.write(vec![
+    vb.write(0, &[
         Vertex { field1: [ 2,  3], field2: [ 5,  7] },
         Vertex { field1: [12, 13], field2: [15, 17] }
-    ]);
+    ]).unwrap();
 
     let data = match vb.read_if_supported() {
         Some(d) => d,
@@ -268,7 +451,7 @@ fn vertex_buffer_write_slice() {
         ]
     );
 
-    vb.slice(1 .. 2).unwrap().write(vec![Vertex { field1: [12, 13], field2: [15, 17] }]);
+    vb.slice(1 .. 2).unwrap().write(0, &[Vertex { field1: [12, 13], field2: [15, 17] }]).unwrap();
 
     let data = match vb.read_if_supported() {
         Some(d) => d,
@@ -283,6 +466,89 @@ fn vertex_buffer_write_slice() {
     display.assert_no_error();
 }
 
+#[test]
+fn vertex_buffer_write_offset() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        field1: [u8; 2],
+    }
+
+    implement_vertex!(Vertex, field1);
+
+    let vb = glium::VertexBuffer::new(&display,
+        vec![
+            Vertex { field1: [ 2,  3] },
+            Vertex { field1: [ 0,  0] },
+            Vertex { field1: [ 0,  0] },
+            Vertex { field1: [ 7,  8] },
+        ]
+    );
+
+    vb.write(1, &[Vertex { field1: [4, 5] }, Vertex { field1: [5, 6] }]).unwrap();
+
+    assert_eq!(vb.write(3, &[Vertex { field1: [0, 0] }, Vertex { field1: [0, 0] }]),
+               Err(glium::vertex::SliceError::OutOfRange { end: 5, len: 4 }));
+    assert_eq!(vb.slice(1 .. 3).unwrap().write(1, &[Vertex { field1: [0, 0] }; 2]),
+               Err(glium::vertex::SliceError::OutOfRange { end: 3, len: 2 }));
+
+    let data = match vb.read_if_supported() {
+        Some(d) => d,
+        None => return
+    };
+
+    assert_eq!(data[0].field1, [2, 3]);
+    assert_eq!(data[1].field1, [4, 5]);
+    assert_eq!(data[2].field1, [5, 6]);
+    assert_eq!(data[3].field1, [7, 8]);
+
+    display.assert_no_error();
+}
+
+#[test]
+fn vertex_buffer_copy_to() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        field1: [u8; 2],
+    }
+
+    implement_vertex!(Vertex, field1);
+
+    let source = glium::VertexBuffer::new(&display,
+        vec![
+            Vertex { field1: [ 2,  3] },
+            Vertex { field1: [ 4,  5] },
+            Vertex { field1: [ 6,  7] },
+        ]
+    );
+
+    let target = glium::VertexBuffer::new(&display, vec![Vertex { field1: [0, 0] }; 3]);
+
+    match source.copy_to(&target, 1, 0, 2) {
+        Ok(_) => (),
+        Err(glium::CopyError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    assert_eq!(source.copy_to(&target, 2, 0, 2), Err(glium::CopyError::OutOfRange));
+    assert_eq!(source.copy_to(&target, 0, 2, 2), Err(glium::CopyError::OutOfRange));
+    assert_eq!(source.copy_to(&source, 0, 1, 2), Err(glium::CopyError::Overlap));
+
+    let data = match target.read_if_supported() {
+        Some(d) => d,
+        None => return
+    };
+
+    assert_eq!(data[0].field1, [4, 5]);
+    assert_eq!(data[1].field1, [6, 7]);
+    assert_eq!(data[2].field1, [0, 0]);
+
+    display.assert_no_error();
+}
+
 #[test]
 fn multiple_buffers_source() {
     let display = support::build_display();
@@ -550,3 +816,192 @@ fn slice_draw_multiple() {
 
     display.assert_no_error();
 }
+
+#[test]
+fn multiple_vertex_sources_draw() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Position {
+        position: [f32; 2],
+    }
+
+    implement_vertex!(Position, position);
+
+    #[derive(Copy, Clone)]
+    struct Color {
+        color: [f32; 3],
+    }
+
+    implement_vertex!(Color, color);
+
+    let positions = glium::VertexBuffer::new(&display, vec![
+        Position { position: [-1.0,  1.0] }, Position { position: [1.0,  1.0] },
+        Position { position: [-1.0, -1.0] }, Position { position: [1.0, -1.0] },
+    ]);
+
+    let colors = glium::VertexBuffer::new(&display, vec![
+        Color { color: [1.0, 0.0, 0.0] }, Color { color: [1.0, 0.0, 0.0] },
+        Color { color: [1.0, 0.0, 0.0] }, Color { color: [1.0, 0.0, 0.0] },
+    ]);
+
+    let indices = glium::IndexBuffer::new(&display,
+                            glium::index::TrianglesList(vec![0u16, 1, 2, 2, 1, 3]));
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+            attribute vec3 color;
+            varying vec3 v_color;
+
+            void main() {
+                v_color = color;
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            varying vec3 v_color;
+
+            void main() {
+                gl_FragColor = vec4(v_color, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw((&positions, &colors), &indices, &program,
+                              &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(f32, f32, f32, f32)>> = texture.read();
+    assert_eq!(data[0][0], (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(data[512][512], (1.0, 0.0, 0.0, 1.0));
+
+    display.assert_no_error();
+}
+
+#[test]
+fn multiple_vertex_sources_same_attribute() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, position);
+
+    let vb1 = glium::VertexBuffer::new(&display, vec![Vertex { position: [0.0, 0.0] }]);
+    let vb2 = glium::VertexBuffer::new(&display, vec![Vertex { position: [0.0, 0.0] }]);
+
+    let indices = glium::index::NoIndices(glium::index::PrimitiveType::Points);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw((&vb1, &vb2), &indices, &program,
+                                    &glium::uniforms::EmptyUniforms, &Default::default())
+    {
+        Err(glium::DrawError::AttributeDefinedMultipleTimes { ref name })
+            if name == "position" => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error();
+}
+
+#[test]
+fn stream_buffer_draw() {
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, position);
+
+    let display = support::build_display();
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    let mut stream = glium::vertex::StreamBuffer::new(&display, 4);
+    let indices = glium::index::NoIndices(glium::index::PrimitiveType::TriangleStrip);
+    let texture = support::build_renderable_texture(&display);
+
+    // going through the regions more than once
+    for frame in 0 .. 5 {
+        {
+            let mut mapping = stream.alloc(4);
+            mapping[0] = Vertex { position: [-1.0,  1.0] };
+            mapping[1] = Vertex { position: [ 1.0,  1.0] };
+            mapping[2] = Vertex { position: [-1.0, -1.0] };
+            mapping[3] = Vertex { position: [ 1.0, -1.0] };
+        }
+
+        assert_eq!(stream.offset(), (frame % 3) * 4);
+
+        texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+        texture.as_surface().draw(stream.slice(), &indices, &program,
+                    &glium::uniforms::EmptyUniforms, &Default::default()).unwrap();
+
+        let data: Vec<Vec<(u8, u8, u8)>> = texture.read();
+        assert_eq!(data[0][0], (255, 0, 0));
+        assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 0));
+    }
+
+    display.assert_no_error();
+}
+
+#[test]
+#[should_panic(expected = "Trying to allocate 5 elements in a stream buffer whose regions contain \
+                           4 elements")]
+fn stream_buffer_alloc_too_large() {
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, position);
+
+    let display = support::build_display();
+    let mut stream: glium::vertex::StreamBuffer<Vertex> =
+        glium::vertex::StreamBuffer::new(&display, 4);
+    stream.alloc(5);
+}